@@ -0,0 +1,143 @@
+use analyze::build::scope::Scope;
+use analyze::build::{class, enum_def, field_group, interface, modifier};
+use analyze::definition::{
+    AnnotationDef, AnnotationElement, Class, Decl, InheritedMethods, MethodDef, Modifier,
+};
+use parse;
+use parse::builder;
+use parse::tree::{Annotated, AnnotatedValue, AnnotationBodyItem, Expr};
+use std::cell::RefCell;
+
+// Every annotation type implements `java.lang.annotation.Annotation` (JLS 9.6).
+pub const ANNOTATION_CLASS_NAME: &str = "java.lang.annotation.Annotation";
+
+pub fn build<'def>(
+    annotation: &parse::tree::Annotation<'def>,
+    scope: &mut Scope,
+) -> AnnotationDef<'def> {
+    scope.wrap(annotation.name.fragment(), |scope| {
+        let mut def = Class {
+            id: annotation.id,
+            name: annotation.name.fragment(),
+            import_path: scope.get_import_path(),
+            package_path: scope.package_path.clone(),
+            span_opt: Some(annotation.name),
+            modifiers: modifier::build(&annotation.modifiers),
+            type_params: vec![],
+            extend_opt: RefCell::new(class::default_extend_opt(&scope.get_import_path())),
+            implements: RefCell::new(vec![builder::class_type(ANNOTATION_CLASS_NAME)]),
+            constructors: vec![],
+            methods: vec![],
+            field_groups: vec![],
+            decls: vec![],
+            inherited_methods: InheritedMethods::default(),
+        };
+        let mut elements = vec![];
+
+        for item in &annotation.body.items {
+            match item {
+                AnnotationBodyItem::Param(p) => {
+                    def.methods.push(build_element(p));
+                    elements.push(AnnotationElement {
+                        name: p.name.fragment(),
+                        default_opt: p.default_opt.clone(),
+                    });
+                }
+                AnnotationBodyItem::FieldDeclarators(f) => {
+                    def.field_groups.push(field_group::build(f))
+                }
+                AnnotationBodyItem::Class(c) => def.decls.push(Decl::Class(class::build(c, scope))),
+                AnnotationBodyItem::Interface(i) => {
+                    def.decls.push(Decl::Interface(interface::build(i, scope)))
+                }
+                AnnotationBodyItem::Enum(e) => {
+                    def.decls.push(Decl::Enum(enum_def::build(e, scope)))
+                }
+                AnnotationBodyItem::Annotation(a) => {
+                    def.decls.push(Decl::Annotation(build(a, scope)))
+                }
+            };
+        }
+
+        AnnotationDef {
+            class: def,
+            elements,
+            retention_opt: meta_values(&annotation.modifiers, "Retention")
+                .and_then(|values| values.first().cloned()),
+            targets: meta_values(&annotation.modifiers, "Target").unwrap_or_default(),
+        }
+    })
+}
+
+fn build_element<'def>(param: &parse::tree::AnnotationParam<'def>) -> MethodDef<'def> {
+    let mut modifiers = modifier::build(&param.modifiers);
+    modifiers.insert(Modifier::Public);
+    modifiers.insert(Modifier::Abstract);
+
+    MethodDef {
+        modifiers,
+        type_params: vec![],
+        return_type: RefCell::new(param.tpe.clone()),
+        name: param.name.fragment(),
+        params: vec![],
+        id: param.id,
+        span_opt: Some(param.name),
+    }
+}
+
+// The constant names in the value of a meta-annotation, e.g. `METHOD` and `FIELD` in
+// `@Target({ElementType.METHOD, ElementType.FIELD})`.
+fn meta_values<'def>(
+    modifiers: &[parse::tree::Modifier<'def>],
+    name: &str,
+) -> Option<Vec<&'def str>> {
+    for modifier in modifiers {
+        let (class, value_opt) = match modifier {
+            parse::tree::Modifier::Annotated(Annotated::Single(s)) => (&s.class, Some(&s.value)),
+            parse::tree::Modifier::Annotated(Annotated::Normal(n)) => (
+                &n.class,
+                n.params
+                    .iter()
+                    .find(|p| p.name.fragment() == "value")
+                    .map(|p| &p.value),
+            ),
+            parse::tree::Modifier::Annotated(Annotated::Marker(m)) => (&m.class, None),
+            _ => continue,
+        };
+
+        if class.name == name {
+            let mut names = vec![];
+            if let Some(value) = value_opt {
+                collect_names(value, &mut names);
+            }
+            return Some(names);
+        }
+    }
+
+    None
+}
+
+fn collect_names<'def>(value: &AnnotatedValue<'def>, names: &mut Vec<&'def str>) {
+    match value {
+        AnnotatedValue::Expr(expr) => collect_expr_names(expr, names),
+        AnnotatedValue::Array(array) => {
+            for item in &array.items {
+                collect_names(item, names);
+            }
+        }
+        AnnotatedValue::Annotated(_) => (),
+    }
+}
+
+fn collect_expr_names<'def>(expr: &Expr<'def>, names: &mut Vec<&'def str>) {
+    match expr {
+        Expr::FieldAccess(f) => names.push(f.name.fragment()),
+        Expr::Name(n) => names.push(n.name.fragment()),
+        Expr::ArrayInitializer(a) => {
+            for item in &a.items {
+                collect_expr_names(item, names);
+            }
+        }
+        _ => (),
+    }
+}
@@ -1,4 +1,9 @@
-use analyze::definition::{Class, FieldDef, FieldGroup, MethodDef, Modifier, TypeParam};
+use analyze::build::class::OBJECT_CLASS_NAME;
+use analyze::definition::{
+    Class, FieldDef, FieldGroup, InheritedMethods, MethodDef, Modifier, TypeParam,
+};
+use parse::builder;
+use parse::id_gen::IdGen;
 use parse::tree::{
     ArrayType, ClassType, ParameterizedType, PrimitiveType, PrimitiveTypeType, Type, TypeArg,
     NATIVE_ARRAY_CLASS_NAME,
@@ -9,26 +14,22 @@ use std::iter::FromIterator;
 use std::pin::Pin;
 
 pub fn apply<'def>() -> Class<'def> {
+    let mut id_gen = IdGen::new(NATIVE_ARRAY_CLASS_NAME);
     Class {
-        id: format!("class_{}", NATIVE_ARRAY_CLASS_NAME),
-        name: NATIVE_ARRAY_CLASS_NAME.to_string(),
+        id: id_gen.get_next("class", NATIVE_ARRAY_CLASS_NAME),
+        name: NATIVE_ARRAY_CLASS_NAME,
         import_path: NATIVE_ARRAY_CLASS_NAME.to_string(),
+        package_path: String::new(),
         span_opt: None,
+        modifiers: HashSet::from_iter(vec![Modifier::Public]),
         type_params: vec![TypeParam {
-            name: "T".to_owned(),
+            name: "T",
             extends: RefCell::new(vec![]),
             span_opt: None,
-            id: format!("{}_TypeParam_T", NATIVE_ARRAY_CLASS_NAME),
+            id: id_gen.get_next("TypeParam", "T"),
         }],
-        extend_opt: RefCell::new(None),
-        //        extend_opt: RefCell::new(Some(ClassType {
-        //            prefix_opt: None,
-        //            name: "Object".to_string(),
-        //            span_opt: None,
-        //            type_args_opt: None,
-        //            def_opt: None,
-        //        })),
-        implements: vec![],
+        extend_opt: RefCell::new(Some(builder::class_type(OBJECT_CLASS_NAME))),
+        implements: RefCell::new(vec![]),
         constructors: vec![],
         methods: vec![MethodDef {
             modifiers: HashSet::from_iter(vec![Modifier::Public]),
@@ -37,7 +38,7 @@ pub fn apply<'def>() -> Class<'def> {
                 // ClassType will be converted to ParameterizedType in analyze::resolve::assign_type.
                 tpe: Box::new(Type::Class(ClassType {
                     prefix_opt: None,
-                    name: "T".to_string(),
+                    name: "T",
                     span_opt: None,
                     type_args_opt: None,
                     def_opt: None,
@@ -45,12 +46,12 @@ pub fn apply<'def>() -> Class<'def> {
                 size_opt: None,
                 underlying: ClassType {
                     prefix_opt: None,
-                    name: NATIVE_ARRAY_CLASS_NAME.to_string(),
+                    name: NATIVE_ARRAY_CLASS_NAME,
                     span_opt: None,
                     // ClassType will be converted to ParameterizedType in analyze::resolve::assign_type.
                     type_args_opt: Some(vec![TypeArg::Class(ClassType {
                         prefix_opt: None,
-                        name: "T".to_string(),
+                        name: "T",
                         span_opt: None,
                         type_args_opt: None,
                         def_opt: None,
@@ -58,9 +59,9 @@ pub fn apply<'def>() -> Class<'def> {
                     def_opt: None,
                 },
             })),
-            name: "clone".to_owned(),
+            name: "clone",
             params: vec![],
-            id: format!("{}_method_clone", NATIVE_ARRAY_CLASS_NAME),
+            id: id_gen.get_next("method", "clone"),
             span_opt: None,
         }],
         field_groups: vec![FieldGroup {
@@ -70,12 +71,13 @@ pub fn apply<'def>() -> Class<'def> {
                     span_opt: None,
                     tpe: PrimitiveTypeType::Int,
                 })),
-                name: "length".to_owned(),
+                name: "length",
                 span_opt: None,
-                id: format!("{}_field_length", NATIVE_ARRAY_CLASS_NAME),
+                id: id_gen.get_next("var", "length"),
             }],
             parse_opt: None,
         }],
         decls: vec![],
+        inherited_methods: InheritedMethods::default(),
     }
 }
@@ -1,65 +1,93 @@
 use analyze::build::scope::Scope;
-use analyze::build::{constructor, field_group, interface, method, type_param};
-use analyze::definition::{Class, Decl};
-use analyze::resolve::scope::EnclosingTypeDef;
+use analyze::build::{
+    annotation, constructor, enum_def, field_group, interface, method, modifier, type_param,
+};
+use analyze::definition::{Class, Decl, InheritedMethods};
 use parse;
-use parse::tree::ClassBodyItem;
-use std::cell::{Cell, RefCell};
-use std::ops::Deref;
-use std::pin::Pin;
+use parse::builder;
+use parse::tree::{ClassBodyItem, ClassType};
+use std::cell::RefCell;
 
 pub fn build<'def, 'scope_ref, 'def_ref>(
     class: &'def_ref parse::tree::Class<'def>,
     scope: &'scope_ref mut Scope,
 ) -> Class<'def> {
-    scope.wrap(class.name.fragment, |scope| {
-        let mut constructors = vec![];
-        let mut decls = vec![];
-        let mut methods = vec![];
-        let mut field_groups = vec![];
+    scope.wrap(class.name.fragment(), |scope| {
         let mut type_params = vec![];
-        let mut implements = vec![];
-
-        for item in &class.body.items {
-            match item {
-                ClassBodyItem::Constructor(c) => constructors.push(constructor::build(c)),
-                ClassBodyItem::Method(m) => methods.push(method::build(m)),
-                ClassBodyItem::FieldDeclarators(f) => field_groups.push(field_group::build(f)),
-                ClassBodyItem::Class(c) => decls.push(Decl::Class(build(c, scope))),
-                ClassBodyItem::Interface(i) => {
-                    decls.push(Decl::Interface(interface::build(i, scope)))
-                }
-                _ => (),
-            };
-        }
 
         for t in &class.type_params {
             type_params.push(type_param::build(t))
         }
 
-        for i in &class.implements {
-            implements.push(i.clone())
-        }
-
-        Class {
-            id: class.id.to_owned(),
-            name: class.name.fragment.to_owned(),
-            import_path: format!("{}.{}", scope.paths.join("."), class.name.fragment),
+        let mut def = Class {
+            id: class.id,
+            name: class.name.fragment(),
+            import_path: scope.get_import_path(),
+            package_path: scope.package_path.clone(),
             span_opt: Some(class.name),
+            modifiers: modifier::build(&class.modifiers),
             type_params,
             extend_opt: RefCell::new(match &class.extend_opt {
                 Some(extend) => Some(extend.clone()),
-                None => None,
+                None => default_extend_opt(&scope.get_import_path()),
             }),
-            decls,
-            constructors,
-            methods,
-            field_groups,
-            implements,
+            decls: vec![],
+            constructors: vec![],
+            methods: vec![],
+            field_groups: vec![],
+            implements: RefCell::new(class.implements.clone()),
+            inherited_methods: InheritedMethods::default(),
+        };
+        build_body(&mut def, &class.body, scope);
+
+        if def.constructors.is_empty() {
+            def.constructors.push(constructor::build_default(
+                def.name,
+                &def.modifiers,
+                &def.import_path,
+            ));
         }
+        def
     })
 }
 
+pub const OBJECT_CLASS_NAME: &str = "java.lang.Object";
+
+// `Object` is the superclass of every class but itself. It's the superclass of interfaces too,
+// like javac models them, so their types have its methods.
+pub fn default_extend_opt<'def>(import_path: &str) -> Option<ClassType<'def>> {
+    if import_path == OBJECT_CLASS_NAME {
+        None
+    } else {
+        Some(builder::class_type(OBJECT_CLASS_NAME))
+    }
+}
+
+// Adds the members of `body` to `class`. Enums and the bodies of enum constants are built with
+// this too.
+pub fn build_body<'def>(
+    class: &mut Class<'def>,
+    body: &parse::tree::ClassBody<'def>,
+    scope: &mut Scope,
+) {
+    for item in &body.items {
+        match item {
+            ClassBodyItem::Constructor(c) => class.constructors.push(constructor::build(c)),
+            ClassBodyItem::Method(m) => class.methods.push(method::build(m)),
+            ClassBodyItem::FieldDeclarators(f) => class.field_groups.push(field_group::build(f)),
+            ClassBodyItem::Class(c) => class.decls.push(Decl::Class(build(c, scope))),
+            ClassBodyItem::Interface(i) => class
+                .decls
+                .push(Decl::Interface(interface::build(i, scope))),
+            ClassBodyItem::Enum(e) => class.decls.push(Decl::Enum(enum_def::build(e, scope))),
+            ClassBodyItem::Annotation(a) => class
+                .decls
+                .push(Decl::Annotation(annotation::build(a, scope))),
+            _ => (),
+        };
+    }
+}
+
 //#[cfg(test)]
 //mod tests {
 //    use analyze::build::apply;
@@ -176,3 +204,26 @@ pub fn build<'def, 'scope_ref, 'def_ref>(
 //        )
 //    }
 //}
+
+#[cfg(test)]
+mod tests {
+    use analyze;
+    use parse;
+    use SourceFile;
+
+    #[test]
+    fn test_import_path() {
+        let source = SourceFile::new(
+            "Test.java",
+            "package dev;\nclass Test {\n  class Inner {}\n}",
+        );
+        let file = parse::apply(&source).unwrap();
+
+        let root = analyze::build::apply(&file.unit);
+
+        // The scope already has the class's name.
+        let test = root.subpackages()[0].units()[0].main.class();
+        assert_eq!(test.import_path, "dev.Test");
+        assert_eq!(test.decls[0].class().import_path, "dev.Test.Inner");
+    }
+}
@@ -1,11 +1,14 @@
 use analyze::build::scope::Scope;
-use analyze::build::{array, class, interface, package};
+use analyze::build::{annotation, array, class, enum_def, interface, package};
 use analyze::definition::{Class, CompilationUnit, Decl, Interface, Package, PackageDecl, Root};
 use either::Either;
 use parse;
 
 pub fn build<'def, 'r>(unit: &'r parse::tree::CompilationUnit<'def>) -> Root<'def> {
-    let mut scope = Scope { paths: vec![] };
+    let mut scope = Scope {
+        paths: vec![],
+        package_path: String::new(),
+    };
 
     let (subpackages, mut units) = match &unit.package_opt {
         Some(package) => (vec![package::build(package, unit, &mut scope)], vec![]),
@@ -18,7 +21,7 @@ pub fn build<'def, 'r>(unit: &'r parse::tree::CompilationUnit<'def>) -> Root<'de
         others: vec![],
     });
 
-    Root { subpackages, units }
+    Root::new(subpackages, units, vec![])
 }
 
 pub fn build_imports<'def, 'r>(
@@ -37,6 +40,7 @@ pub fn build_unit<'def, 'scope_ref, 'def_ref>(
     unit: &'def_ref parse::tree::CompilationUnit<'def>,
     scope: &'scope_ref mut Scope,
 ) -> CompilationUnit<'def> {
+    scope.package_path = scope.get_import_path();
     let main = build_decl(&unit.items.first().unwrap(), scope);
     let mut others = vec![];
 
@@ -60,8 +64,10 @@ fn build_decl<'def, 'scope_ref, 'def_ref>(
         parse::tree::CompilationUnitItem::Interface(i) => {
             Decl::Interface(interface::build(i, scope))
         }
-        parse::tree::CompilationUnitItem::Annotation(annotation) => panic!(),
-        parse::tree::CompilationUnitItem::Enum(enum_def) => panic!(),
+        parse::tree::CompilationUnitItem::Annotation(a) => {
+            Decl::Annotation(annotation::build(a, scope))
+        }
+        parse::tree::CompilationUnitItem::Enum(e) => Decl::Enum(enum_def::build(e, scope)),
     }
 }
 
@@ -1,10 +1,44 @@
-use analyze::definition::Constructor;
+use analyze::build::{modifier, param, type_param};
+use analyze::definition::{ConstructorDef, Modifier};
 use parse;
+use parse::id_gen::IdGen;
+use std::cell::RefCell;
+use std::collections::HashSet;
 
-pub fn build<'def, 'def_ref>(
-    constructor: &'def_ref parse::tree::Constructor<'def>,
-) -> Constructor<'def> {
-    Constructor {
-        name: constructor.name.clone(),
+pub fn build<'def>(constructor: &parse::tree::Constructor<'def>) -> ConstructorDef<'def> {
+    ConstructorDef {
+        modifiers: modifier::build(&constructor.modifiers),
+        type_params: constructor
+            .type_params
+            .iter()
+            .map(type_param::build)
+            .collect(),
+        params: constructor.params.iter().map(param::build).collect(),
+        throws: RefCell::new(constructor.throws.clone()),
+        id: constructor.id,
+        span_opt: Some(constructor.name),
+    }
+}
+
+// The default constructor has the same access modifier as its class.
+pub fn build_default<'def>(
+    name: &str,
+    class_modifiers: &HashSet<Modifier>,
+    import_path: &str,
+) -> ConstructorDef<'def> {
+    let mut modifiers = HashSet::new();
+    for access in &[Modifier::Public, Modifier::Protected, Modifier::Private] {
+        if class_modifiers.contains(access) {
+            modifiers.insert(access.clone());
+        }
+    }
+
+    ConstructorDef {
+        modifiers,
+        type_params: vec![],
+        params: vec![],
+        throws: RefCell::new(vec![]),
+        id: IdGen::new(import_path).get_next("constructor", name),
+        span_opt: None,
     }
 }
@@ -0,0 +1,182 @@
+use analyze::build::scope::Scope;
+use analyze::build::{class, constructor, modifier};
+use analyze::definition::{
+    Class, Enum, EnumConstant, FieldDef, FieldGroup, InheritedMethods, MethodDef, Modifier, Param,
+};
+use parse;
+use parse::builder;
+use parse::id_gen::IdGen;
+use parse::tree::{ClassType, PrimitiveTypeType, Type, TypeArg};
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+pub fn build<'def>(enum_def: &parse::tree::Enum<'def>, scope: &mut Scope) -> Enum<'def> {
+    scope.wrap(enum_def.name.fragment(), |scope| {
+        let name = enum_def.name.fragment();
+        let import_path = scope.get_import_path();
+
+        let mut constant_fields = vec![];
+        let mut constants = vec![];
+        for constant in &enum_def.constants {
+            constant_fields.push(FieldDef {
+                tpe: RefCell::new(Type::Class(self_type(name))),
+                name: constant.name.fragment(),
+                span_opt: Some(constant.name),
+                id: constant.id,
+            });
+            constants.push(build_constant(constant, name, scope));
+        }
+
+        let mut def = Class {
+            id: enum_def.id,
+            name,
+            import_path: import_path.clone(),
+            package_path: scope.package_path.clone(),
+            span_opt: Some(enum_def.name),
+            modifiers: modifier::build(&enum_def.modifiers),
+            type_params: vec![],
+            extend_opt: RefCell::new(Some(ClassType {
+                prefix_opt: None,
+                name: "Enum",
+                span_opt: None,
+                type_args_opt: Some(vec![TypeArg::Class(self_type(name))]),
+                def_opt: None,
+            })),
+            implements: RefCell::new(enum_def.implements.clone()),
+            constructors: vec![],
+            methods: implicit_methods(name, &mut IdGen::new(&import_path)),
+            field_groups: vec![FieldGroup {
+                modifiers: HashSet::from_iter(vec![
+                    Modifier::Public,
+                    Modifier::Static,
+                    Modifier::Final,
+                ]),
+                items: constant_fields,
+                parse_opt: None,
+            }],
+            decls: vec![],
+            inherited_methods: InheritedMethods::default(),
+        };
+
+        if let Some(body) = &enum_def.body_opt {
+            class::build_body(&mut def, body, scope);
+        }
+
+        // The constructor of an enum is always private.
+        if def.constructors.is_empty() {
+            def.constructors.push(constructor::build_default(
+                name,
+                &HashSet::from_iter(vec![Modifier::Private]),
+                &import_path,
+            ));
+        }
+
+        Enum {
+            class: def,
+            constants,
+        }
+    })
+}
+
+fn build_constant<'def>(
+    constant: &parse::tree::EnumConstant<'def>,
+    enum_name: &'def str,
+    scope: &mut Scope,
+) -> EnumConstant<'def> {
+    let body_opt = constant.body_opt.as_ref().map(|body| {
+        scope.wrap(constant.name.fragment(), |scope| {
+            let mut def = Class {
+                id: constant.body_id,
+                name: constant.name.fragment(),
+                import_path: scope.get_import_path(),
+                package_path: scope.package_path.clone(),
+                span_opt: Some(constant.name),
+                modifiers: HashSet::new(),
+                type_params: vec![],
+                extend_opt: RefCell::new(Some(self_type(enum_name))),
+                implements: RefCell::new(vec![]),
+                constructors: vec![],
+                methods: vec![],
+                field_groups: vec![],
+                decls: vec![],
+                inherited_methods: InheritedMethods::default(),
+            };
+            class::build_body(&mut def, body, scope);
+            def
+        })
+    });
+
+    EnumConstant {
+        name: constant.name.fragment(),
+        span_opt: Some(constant.name),
+        body_opt,
+    }
+}
+
+// The type is resolved in analyze::resolve::assign_type.
+fn self_type(name: &str) -> ClassType<'_> {
+    ClassType {
+        prefix_opt: None,
+        name,
+        span_opt: None,
+        type_args_opt: None,
+        def_opt: None,
+    }
+}
+
+fn implicit_methods<'def>(name: &'def str, id_gen: &mut IdGen) -> Vec<MethodDef<'def>> {
+    vec![
+        implicit_method(
+            vec![Modifier::Public, Modifier::Static],
+            builder::array(Type::Class(self_type(name))),
+            "values",
+            vec![],
+            id_gen,
+        ),
+        implicit_method(
+            vec![Modifier::Public, Modifier::Static],
+            Type::Class(self_type(name)),
+            "valueOf",
+            vec![Param {
+                tpe: RefCell::new(builder::tpe("String")),
+                name: "name",
+                span_opt: None,
+                is_varargs: false,
+            }],
+            id_gen,
+        ),
+        implicit_method(
+            vec![Modifier::Public, Modifier::Final],
+            builder::tpe("String"),
+            "name",
+            vec![],
+            id_gen,
+        ),
+        implicit_method(
+            vec![Modifier::Public, Modifier::Final],
+            builder::primitive(PrimitiveTypeType::Int),
+            "ordinal",
+            vec![],
+            id_gen,
+        ),
+    ]
+}
+
+fn implicit_method<'def>(
+    modifiers: Vec<Modifier>,
+    return_type: Type<'def>,
+    name: &'def str,
+    params: Vec<Param<'def>>,
+    id_gen: &mut IdGen,
+) -> MethodDef<'def> {
+    MethodDef {
+        modifiers: HashSet::from_iter(modifiers),
+        type_params: vec![],
+        return_type: RefCell::new(return_type),
+        name,
+        params,
+        id: id_gen.get_next("method", name),
+        span_opt: None,
+    }
+}
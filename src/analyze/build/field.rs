@@ -8,9 +8,9 @@ pub fn build<'def, 'def_ref>(
 ) -> FieldDef<'def> {
     FieldDef {
         tpe: field.tpe.clone(),
-        name: field.name.fragment.to_owned(),
+        name: field.name.fragment(),
         span_opt: Some(field.name),
-        id: field.id.to_owned(),
+        id: field.id,
     }
 }
 
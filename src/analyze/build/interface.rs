@@ -1,35 +1,57 @@
 use analyze::build::scope::Scope;
-use analyze::build::{class, constructor, field_group, method};
-use analyze::definition::{Class, Decl, Interface};
+use analyze::build::{class, modifier, type_param};
+use analyze::definition::{Class, InheritedMethods, Interface, Modifier};
 use parse;
-use parse::tree::ClassBodyItem;
+use std::cell::RefCell;
 
 pub fn build<'def, 'scope_ref, 'def_ref>(
     interface: &'def_ref parse::tree::Interface<'def>,
     scope: &'scope_ref mut Scope,
 ) -> Interface<'def> {
-    scope.wrap(interface.name.fragment, |scope| {
-        let mut decls = vec![];
-        let mut methods = vec![];
-        let mut field_groups = vec![];
+    scope.wrap(interface.name.fragment(), |scope| {
+        let mut def = Class {
+            id: interface.id,
+            name: interface.name.fragment(),
+            import_path: scope.get_import_path(),
+            package_path: scope.package_path.clone(),
+            span_opt: Some(interface.name),
+            modifiers: modifier::build(&interface.modifiers),
+            type_params: interface
+                .type_params
+                .iter()
+                .map(type_param::build)
+                .collect(),
+            extend_opt: RefCell::new(class::default_extend_opt(&scope.get_import_path())),
+            implements: RefCell::new(interface.extends.clone()),
+            constructors: vec![],
+            methods: vec![],
+            field_groups: vec![],
+            decls: vec![],
+            inherited_methods: InheritedMethods::default(),
+        };
+        class::build_body(&mut def, &interface.body, scope);
 
-        for item in &interface.body.items {
-            match item {
-                ClassBodyItem::Method(m) => methods.push(method::build(m)),
-                ClassBodyItem::FieldDeclarators(f) => field_groups.push(field_group::build(f)),
-                ClassBodyItem::Class(c) => decls.push(Decl::Class(class::build(c, scope))),
-                ClassBodyItem::Interface(i) => decls.push(Decl::Interface(build(i, scope))),
-                _ => (),
-            };
+        // A method is abstract unless it has a body, i.e. it is default, static, or private.
+        for method in &mut def.methods {
+            if !method.modifiers.contains(&Modifier::Private) {
+                method.modifiers.insert(Modifier::Public);
+            }
+            if !method.modifiers.contains(&Modifier::Default)
+                && !method.modifiers.contains(&Modifier::Static)
+                && !method.modifiers.contains(&Modifier::Private)
+            {
+                method.modifiers.insert(Modifier::Abstract);
+            }
         }
 
-        Interface {
-            import_path: scope.get_import_path(),
-            name: interface.name.clone(),
-            decls,
-            methods,
-            field_groups,
+        // A field is a constant.
+        for field_group in &mut def.field_groups {
+            field_group.modifiers.insert(Modifier::Public);
+            field_group.modifiers.insert(Modifier::Static);
+            field_group.modifiers.insert(Modifier::Final);
         }
+
+        Interface { class: def }
     })
 }
 
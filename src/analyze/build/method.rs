@@ -1,5 +1,5 @@
 use analyze::build::{modifier, param, type_param};
-use analyze::definition::MethodDef;
+use analyze::definition::{MethodDef, Modifier};
 use parse;
 use std::cell::RefCell;
 use std::ops::Deref;
@@ -17,13 +17,19 @@ pub fn build<'def, 'def_ref>(method: &'def_ref parse::tree::Method<'def>) -> Met
         type_params.push(type_param::build(t));
     }
 
+    // A method without a body is abstract unless it is native.
+    let mut modifiers = modifier::build(&method.modifiers);
+    if method.block_opt.is_none() && !modifiers.contains(&Modifier::Native) {
+        modifiers.insert(Modifier::Abstract);
+    }
+
     MethodDef {
-        modifiers: modifier::build(&method.modifiers),
+        modifiers,
         type_params,
         return_type: RefCell::new(method.return_type.clone()),
-        name: method.name.fragment.to_owned(),
+        name: method.name.fragment(),
         params,
-        id: method.id.to_owned(),
+        id: method.id,
         span_opt: Some(method.name.clone()),
     }
 }
@@ -1,7 +1,9 @@
+pub mod annotation;
 pub mod array;
 pub mod class;
 pub mod compilation_unit;
 pub mod constructor;
+pub mod enum_def;
 pub mod field;
 pub mod field_group;
 pub mod interface;
@@ -21,7 +21,7 @@ pub fn build<'def, 'def_ref>(
 }
 
 fn build_keyword<'def, 'def_ref>(keyword: &'def_ref Keyword<'def>) -> Modifier {
-    match keyword.name.fragment {
+    match keyword.name.fragment() {
         "abstract" => Modifier::Abstract,
         "default" => Modifier::Default,
         "final" => Modifier::Final,
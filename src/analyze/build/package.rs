@@ -15,18 +15,18 @@ pub fn build<'def, 'scope_ref, 'def_ref>(
         None => None,
     };
 
-    scope.push(package.name.fragment);
+    scope.push(package.name.fragment());
 
-    let current = Package {
-        import_path: scope.get_import_path(),
-        name: package.name.fragment.to_owned(),
-        subpackages: vec![],
-        units: vec![compilation_unit::build_unit(unit, scope)],
-    };
+    let current = Package::new(
+        scope.get_import_path(),
+        package.name.fragment(),
+        vec![],
+        vec![compilation_unit::build_unit(unit, scope)],
+    );
 
     let result = match prefix_opt {
         Some(mut prefix) => {
-            prefix.subpackages.push(current);
+            innermost(&mut prefix).subpackages_mut().push(current);
             prefix
         }
         None => current,
@@ -37,12 +37,21 @@ pub fn build<'def, 'scope_ref, 'def_ref>(
     result
 }
 
+// A prefix is a chain of packages with one subpackage each, e.g. `a` > `b` for `a.b`.
+fn innermost<'a, 'def>(package: &'a mut Package<'def>) -> &'a mut Package<'def> {
+    if package.subpackages().is_empty() {
+        package
+    } else {
+        innermost(package.subpackages_mut().last_mut().unwrap())
+    }
+}
+
 fn pop_scope(package: &analyze::definition::Package, scope: &mut Scope) {
     scope.pop();
 
-    assert!(package.subpackages.len() <= 1);
+    assert!(package.subpackages().len() <= 1);
 
-    for subpackage in &package.subpackages {
+    for subpackage in package.subpackages() {
         pop_scope(subpackage, scope);
     }
 }
@@ -56,18 +65,18 @@ fn build_prefix<'def, 'scope_ref, 'def_ref>(
         None => None,
     };
 
-    scope.push(package.name.fragment);
+    scope.push(package.name.fragment());
 
-    let current = Package {
-        import_path: scope.get_import_path(),
-        name: package.name.fragment.to_owned(),
-        subpackages: vec![],
-        units: vec![],
-    };
+    let current = Package::new(
+        scope.get_import_path(),
+        package.name.fragment(),
+        vec![],
+        vec![],
+    );
 
     match prefix_opt {
         Some(mut prefix) => {
-            prefix.subpackages.push(current);
+            innermost(&mut prefix).subpackages_mut().push(current);
             prefix
         }
         None => current,
@@ -97,3 +106,29 @@ fn build_prefix<'def, 'scope_ref, 'def_ref>(
 //        }
 //    })
 //}
+
+#[cfg(test)]
+mod tests {
+    use analyze;
+    use parse;
+    use SourceFile;
+
+    #[test]
+    fn test_nested_prefix() {
+        let source = SourceFile::new("X.java", "package a.b.c.d;\nclass X {}");
+        let file = parse::apply(&source).unwrap();
+
+        let root = analyze::build::apply(&file.unit);
+
+        // Each package of the prefix is nested in the previous one.
+        let mut package = &root.subpackages()[0];
+        let mut names = vec![package.import_path.as_str()];
+        while let Some(subpackage) = package.subpackages().first() {
+            assert_eq!(package.subpackages().len(), 1);
+            package = subpackage;
+            names.push(package.import_path.as_str());
+        }
+        assert_eq!(names, vec!["a", "a.b", "a.b.c", "a.b.c.d"]);
+        assert_eq!(package.units().len(), 1);
+    }
+}
@@ -5,7 +5,8 @@ use std::cell::RefCell;
 pub fn build<'def, 'def_ref>(param: &'def_ref parse::tree::Param<'def>) -> Param<'def> {
     Param {
         tpe: RefCell::new(param.tpe.clone()),
-        name: param.name.clone(),
+        name: param.name.fragment(),
+        span_opt: Some(param.name),
         is_varargs: param.is_varargs,
     }
 }
@@ -1,5 +1,7 @@
 pub struct Scope {
     pub paths: Vec<String>,
+    // The import path of the package of the compilation unit that is being built.
+    pub package_path: String,
 }
 
 impl Scope {
@@ -13,9 +13,9 @@ pub fn build<'def, 'def_ref>(
     }
 
     TypeParam {
-        name: type_param.name.fragment.to_owned(),
+        name: type_param.name.fragment(),
         extends: RefCell::new(extends),
         span_opt: Some(type_param.name),
-        id: type_param.id.to_owned(),
+        id: type_param.id,
     }
 }
@@ -0,0 +1,229 @@
+use analyze::class_file::{read, stub, LoadError, LoadErrorKind, Loaded};
+use parse::dir::archive_entry_path;
+use std::fs::File;
+use std::io::Read;
+use std::path::{Path, PathBuf};
+
+// Reads the classes of a `.jar` archive into stubs. The versioned classes of a multi-release
+// archive, which are under `META-INF/`, are skipped. Only an archive that can't be opened is an
+// error. The entries that can't be read are in `errors`.
+pub fn load(path: &Path) -> Result<Loaded, LoadError> {
+    let error = |path: PathBuf, kind| LoadError { path, kind };
+    let file = File::open(path).map_err(|e| error(path.to_owned(), LoadErrorKind::Io(e)))?;
+    let mut archive =
+        zip::ZipArchive::new(file).map_err(|e| error(path.to_owned(), LoadErrorKind::Zip(e)))?;
+
+    let mut errors = vec![];
+    let mut classes = vec![];
+    for index in 0..archive.len() {
+        let mut entry = match archive.by_index(index) {
+            Ok(entry) => entry,
+            Err(e) => {
+                errors.push(error(path.to_owned(), LoadErrorKind::Zip(e)));
+                continue;
+            }
+        };
+        let name = entry.name().to_owned();
+        if entry.is_dir() || !name.ends_with(".class") || name.starts_with("META-INF/") {
+            continue;
+        }
+
+        let entry_path = archive_entry_path(path, &name);
+        let mut bytes = vec![];
+        let result = entry
+            .read_to_end(&mut bytes)
+            .map_err(LoadErrorKind::Io)
+            .and_then(|_| read(&bytes).map_err(LoadErrorKind::ClassFile));
+        match result {
+            Ok(class) => classes.push((entry_path, class)),
+            Err(kind) => errors.push(error(PathBuf::from(&entry_path), kind)),
+        }
+    }
+    classes.sort_by(|a, b| a.0.cmp(&b.0));
+
+    Ok(Loaded {
+        sources: stub::apply(classes),
+        errors,
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use analyze;
+    use analyze::class_file::jar::load;
+    use analyze::class_file::{self, ClassFileError, LoadErrorKind};
+    use analyze::definition::{Class, Decl};
+    use analyze::resolve::{assign_type, merge};
+    use analyze::test_common::find_class;
+    use parse;
+    use parse::tree::Type;
+    use std::fs;
+    use std::io::{Read, Write};
+    use std::path::{Path, PathBuf};
+    use SourceFile;
+
+    #[test]
+    fn test_load() {
+        // Built by `javac --release 8` from `library/src`.
+        let loaded = load(Path::new("./tests/fixtures/library/library.jar")).unwrap();
+        assert!(loaded.errors.is_empty());
+        let sources = loaded.sources;
+
+        assert_eq!(
+            sources.iter().map(|s| s.path.as_str()).collect::<Vec<_>>(),
+            vec![
+                "jar:./tests/fixtures/library/library.jar!/lib/Label.class#stub",
+                "jar:./tests/fixtures/library/library.jar!/lib/Shelf.class#stub",
+            ]
+        );
+        assert_eq!(
+            sources[1].content,
+            r#"package lib;
+
+@lib.Label(value = "shelf", tags = {"a", "b"}, kind = lib.Label.Kind.FIXED)
+public abstract class Shelf<T extends java.lang.Comparable<? super T>> implements java.lang.Iterable<T> {
+    public static final int SIZE = 10;
+    protected java.util.List<? extends T> items;
+    java.util.Map.Entry<java.lang.String, T> last;
+    public Shelf(java.util.List<? extends T> arg0) {}
+    public abstract T first();
+    public <R> R fold(R arg0, java.util.function.BiFunction<R, T, R> arg1) throws java.io.IOException {}
+    public static int sum(int... arg0) {}
+    public class Row {
+        public Row(int arg0) {}
+        public lib.Shelf.Slot slot() {}
+    }
+    public static class Slot {
+        public java.lang.String name;
+        public Slot() {}
+    }
+    public static interface Visitor<V> {
+        public abstract V visit(lib.Shelf<?> arg0);
+        public default boolean accepts(java.lang.Object arg0) {}
+        public static <V> lib.Shelf.Visitor<V> empty() {}
+    }
+}
+"#
+        );
+
+        let app = SourceFile::new(
+            "App.java",
+            "package app;\nimport lib.Shelf;\nclass App {\n  Shelf.Slot slot;\n}",
+        );
+        let files = sources
+            .iter()
+            .chain(vec![&app])
+            .map(|s| parse::apply(s).unwrap())
+            .collect::<Vec<_>>();
+        let mut root = merge::apply(
+            files
+                .iter()
+                .map(|f| analyze::build::apply(&f.unit))
+                .collect(),
+        );
+
+        assign_type::apply(&mut root);
+
+        let app = find_class(&root, "app.App");
+        let slot = app.find_field("slot").unwrap().tpe.borrow();
+        match &*slot {
+            Type::Class(class) => assert_eq!(
+                unsafe { &*class.def_opt.unwrap() } as *const Class,
+                find_class(&root, "lib.Shelf").decls[1].class() as *const Class
+            ),
+            _ => panic!(),
+        }
+
+        let shelf = find_class(&root, "lib.Shelf");
+        assert_eq!(shelf.type_params[0].name, "T");
+        assert_eq!(shelf.constructors[0].params[0].name, "arg0");
+        assert!(shelf.find_method("fold").is_some());
+        assert_eq!(
+            shelf
+                .decls
+                .iter()
+                .map(|d| d.class().name)
+                .collect::<Vec<_>>(),
+            vec!["Row", "Slot", "Visitor"]
+        );
+
+        let label = root
+            .find_package("lib")
+            .unwrap()
+            .units()
+            .iter()
+            .find(|u| u.main.class().name == "Label")
+            .unwrap();
+        match &label.main {
+            Decl::Annotation(annotation) => {
+                assert_eq!(annotation.retention_opt, Some("RUNTIME"));
+                assert_eq!(annotation.targets, vec!["TYPE", "METHOD"]);
+                assert_eq!(
+                    annotation
+                        .elements
+                        .iter()
+                        .map(|e| (e.name, e.default_opt.is_some()))
+                        .collect::<Vec<_>>(),
+                    vec![
+                        ("value", false),
+                        ("tags", true),
+                        ("kind", true),
+                        ("mark", true),
+                        ("weight", true),
+                        ("type", true)
+                    ]
+                );
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_errors() {
+        let mut library =
+            zip::ZipArchive::new(fs::File::open("./tests/fixtures/library/library.jar").unwrap())
+                .unwrap();
+        let mut label = vec![];
+        library
+            .by_name("lib/Label.class")
+            .unwrap()
+            .read_to_end(&mut label)
+            .unwrap();
+
+        let archive =
+            std::env::temp_dir().join(format!("javaparser-jar-errors-{}.jar", std::process::id()));
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        for (name, content) in &[("lib/Bad.class", &b"nope"[..]), ("lib/Label.class", &label)] {
+            zip.start_file(*name, Default::default()).unwrap();
+            zip.write_all(content).unwrap();
+        }
+        zip.finish().unwrap();
+
+        let loaded = class_file::load(&[archive.clone(), PathBuf::from("Missing.class")]);
+        fs::remove_file(&archive).unwrap();
+
+        let prefix = format!("jar:{}!/", archive.to_str().unwrap());
+        assert_eq!(
+            loaded
+                .sources
+                .iter()
+                .map(|s| s.path.replace(&prefix, ""))
+                .collect::<Vec<_>>(),
+            vec!["lib/Label.class#stub"]
+        );
+        assert_eq!(loaded.errors.len(), 2);
+        assert_eq!(
+            loaded.errors[0].path,
+            PathBuf::from(format!("{}lib/Bad.class", prefix))
+        );
+        match &loaded.errors[0].kind {
+            LoadErrorKind::ClassFile(e) => assert_eq!(e, &ClassFileError::BadMagic),
+            _ => panic!(),
+        }
+        assert_eq!(loaded.errors[1].path, PathBuf::from("Missing.class"));
+        match &loaded.errors[1].kind {
+            LoadErrorKind::Io(_) => (),
+            _ => panic!(),
+        }
+    }
+}
@@ -0,0 +1,478 @@
+use std::path::{Path, PathBuf};
+use std::{fs, io};
+use SourceFile;
+
+pub mod jar;
+pub mod signature;
+pub mod stub;
+
+// Reads the declarations of compiled classes, so sources can resolve against their classpath.
+// A class file becomes a Java stub with the same signatures and no bodies (see `stub`), which
+// goes through `parse`, `analyze::build`, and `analyze::resolve::merge` like any source.
+
+pub const ACC_PUBLIC: u16 = 0x0001;
+pub const ACC_PRIVATE: u16 = 0x0002;
+pub const ACC_PROTECTED: u16 = 0x0004;
+pub const ACC_STATIC: u16 = 0x0008;
+pub const ACC_FINAL: u16 = 0x0010;
+pub const ACC_SYNCHRONIZED: u16 = 0x0020;
+pub const ACC_VOLATILE: u16 = 0x0040;
+pub const ACC_BRIDGE: u16 = 0x0040;
+pub const ACC_TRANSIENT: u16 = 0x0080;
+pub const ACC_VARARGS: u16 = 0x0080;
+pub const ACC_NATIVE: u16 = 0x0100;
+pub const ACC_INTERFACE: u16 = 0x0200;
+pub const ACC_ABSTRACT: u16 = 0x0400;
+pub const ACC_STRICT: u16 = 0x0800;
+pub const ACC_SYNTHETIC: u16 = 0x1000;
+pub const ACC_ANNOTATION: u16 = 0x2000;
+pub const ACC_ENUM: u16 = 0x4000;
+pub const ACC_MODULE: u16 = 0x8000;
+
+// Class names are internal names, e.g. "java/util/Map$Entry". Types are descriptors or
+// signatures, which `signature` turns into Java types.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ClassFile {
+    pub access_flags: u16,
+    pub name: String,
+    pub super_opt: Option<String>,
+    pub interfaces: Vec<String>,
+    pub signature_opt: Option<String>,
+    pub fields: Vec<Member>,
+    pub methods: Vec<Member>,
+    pub inner_classes: Vec<InnerClass>,
+    pub annotations: Vec<Annotation>,
+}
+
+impl ClassFile {
+    // The entry of the class itself in `InnerClasses` if it's nested. A nested class has its
+    // modifiers there, e.g. `static` and `private`.
+    pub fn nested_opt(&self) -> Option<&InnerClass> {
+        self.inner_classes
+            .iter()
+            .find(|inner| inner.name == self.name)
+    }
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct Member {
+    pub access_flags: u16,
+    pub name: String,
+    pub descriptor: String,
+    pub signature_opt: Option<String>,
+    // From `MethodParameters`, which is only emitted with `javac -parameters`.
+    pub param_names: Vec<String>,
+    pub exceptions: Vec<String>,
+    // From `AnnotationDefault`.
+    pub default_opt: Option<ElementValue>,
+    // From `ConstantValue`, e.g. the value of a `static final int`.
+    pub constant_opt: Option<Constant>,
+}
+
+// `outer_opt` and `simple_name_opt` are None for local and anonymous classes.
+#[derive(Debug, PartialEq, Clone)]
+pub struct InnerClass {
+    pub name: String,
+    pub outer_opt: Option<String>,
+    pub simple_name_opt: Option<String>,
+    pub access_flags: u16,
+}
+
+// `tpe` is a descriptor, e.g. "Ljava/lang/annotation/Retention;".
+#[derive(Debug, PartialEq, Clone)]
+pub struct Annotation {
+    pub tpe: String,
+    pub values: Vec<(String, ElementValue)>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ElementValue {
+    // The tag is one of "BCDFIJSZs" like in the class file.
+    Const(u8, Constant),
+    Enum { tpe: String, name: String },
+    Class(String),
+    Annotation(Annotation),
+    Array(Vec<ElementValue>),
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum Constant {
+    Int(i32),
+    Long(i64),
+    Float(f32),
+    Double(f64),
+    String(String),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ClassFileError {
+    UnexpectedEnd,
+    BadMagic,
+    BadConstant(u16),
+    BadTag(u8),
+}
+
+#[derive(Debug)]
+pub enum LoadErrorKind {
+    Io(io::Error),
+    Zip(zip::result::ZipError),
+    ClassFile(ClassFileError),
+}
+
+#[derive(Debug)]
+pub struct LoadError {
+    pub path: PathBuf,
+    pub kind: LoadErrorKind,
+}
+
+// The stubs and the errors of the paths that couldn't be read. A bad class in an archive doesn't
+// stop the rest of the archive.
+#[derive(Debug, Default)]
+pub struct Loaded {
+    pub sources: Vec<SourceFile>,
+    pub errors: Vec<LoadError>,
+}
+
+// The suffix of the path of a stub. A stub is rendered Java, so its spans don't point into the
+// class file that its path names.
+pub const STUB_SUFFIX: &str = "#stub";
+
+// Reads `.class` files and `.jar` archives into stubs, one per top-level class. The stubs of a
+// class file are named by its path, and the stubs of an archive by
+// `parse::dir::archive_entry_path`, both followed by `STUB_SUFFIX`.
+pub fn load(paths: &[PathBuf]) -> Loaded {
+    let mut loaded = Loaded::default();
+    let mut classes = vec![];
+    for path in paths {
+        let error = |kind| LoadError {
+            path: path.clone(),
+            kind,
+        };
+        if path.extension().is_some_and(|e| e == "jar") {
+            match jar::load(path) {
+                Ok(jar) => {
+                    loaded.sources.extend(jar.sources);
+                    loaded.errors.extend(jar.errors);
+                }
+                Err(e) => loaded.errors.push(e),
+            }
+            continue;
+        }
+
+        let result = fs::read(path)
+            .map_err(|e| error(LoadErrorKind::Io(e)))
+            .and_then(|bytes| read(&bytes).map_err(|e| error(LoadErrorKind::ClassFile(e))));
+        match result {
+            Ok(class) => classes.push((path.to_string_lossy().to_string(), class)),
+            Err(e) => loaded.errors.push(e),
+        }
+    }
+    loaded.sources.extend(stub::apply(classes));
+    loaded
+}
+
+pub fn read(bytes: &[u8]) -> Result<ClassFile, ClassFileError> {
+    let mut reader = Reader { bytes, pos: 0 };
+    if reader.u4()? != 0xCAFE_BABE {
+        return Err(ClassFileError::BadMagic);
+    }
+    reader.u2()?;
+    reader.u2()?;
+
+    let pool = read_pool(&mut reader)?;
+    let access_flags = reader.u2()?;
+    let name = pool.class(reader.u2()?)?;
+    let super_index = reader.u2()?;
+    let super_opt = if super_index == 0 {
+        None
+    } else {
+        Some(pool.class(super_index)?)
+    };
+    let mut interfaces = vec![];
+    for _ in 0..reader.u2()? {
+        interfaces.push(pool.class(reader.u2()?)?);
+    }
+    let fields = read_members(&mut reader, &pool)?;
+    let methods = read_members(&mut reader, &pool)?;
+
+    let mut class = ClassFile {
+        access_flags,
+        name,
+        super_opt,
+        interfaces,
+        signature_opt: None,
+        fields,
+        methods,
+        inner_classes: vec![],
+        annotations: vec![],
+    };
+    for _ in 0..reader.u2()? {
+        let (attribute, mut data) = read_attribute(&mut reader, &pool)?;
+        match attribute {
+            "Signature" => class.signature_opt = Some(pool.utf8(data.u2()?)?.to_owned()),
+            "InnerClasses" => {
+                for _ in 0..data.u2()? {
+                    let name = pool.class(data.u2()?)?;
+                    let outer_opt = pool.class_opt(data.u2()?)?;
+                    let simple_name_opt = pool.utf8_opt(data.u2()?)?;
+                    let access_flags = data.u2()?;
+                    class.inner_classes.push(InnerClass {
+                        name,
+                        outer_opt,
+                        simple_name_opt,
+                        access_flags,
+                    });
+                }
+            }
+            "RuntimeVisibleAnnotations" => {
+                for _ in 0..data.u2()? {
+                    class.annotations.push(read_annotation(&mut data, &pool)?);
+                }
+            }
+            _ => (),
+        }
+    }
+
+    Ok(class)
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn take(&mut self, len: usize) -> Result<&'a [u8], ClassFileError> {
+        if self.pos + len > self.bytes.len() {
+            return Err(ClassFileError::UnexpectedEnd);
+        }
+        let taken = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(taken)
+    }
+
+    fn u1(&mut self) -> Result<u8, ClassFileError> {
+        Ok(self.take(1)?[0])
+    }
+
+    fn u2(&mut self) -> Result<u16, ClassFileError> {
+        let b = self.take(2)?;
+        Ok(u16::from_be_bytes([b[0], b[1]]))
+    }
+
+    fn u4(&mut self) -> Result<u32, ClassFileError> {
+        let b = self.take(4)?;
+        Ok(u32::from_be_bytes([b[0], b[1], b[2], b[3]]))
+    }
+
+    fn u8(&mut self) -> Result<u64, ClassFileError> {
+        Ok(((self.u4()? as u64) << 32) | self.u4()? as u64)
+    }
+}
+
+// Only the entries that declarations refer to are kept. The others are `Other`.
+enum PoolEntry {
+    Utf8(String),
+    Class(u16),
+    Constant(Constant),
+    // A string refers to a utf8 entry, which may come later in the pool.
+    String(u16),
+    Other,
+}
+
+struct Pool {
+    entries: Vec<PoolEntry>,
+}
+
+impl Pool {
+    fn utf8(&self, index: u16) -> Result<&str, ClassFileError> {
+        match self.entries.get(index as usize) {
+            Some(PoolEntry::Utf8(s)) => Ok(s),
+            _ => Err(ClassFileError::BadConstant(index)),
+        }
+    }
+
+    fn utf8_opt(&self, index: u16) -> Result<Option<String>, ClassFileError> {
+        if index == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(self.utf8(index)?.to_owned()))
+        }
+    }
+
+    fn class(&self, index: u16) -> Result<String, ClassFileError> {
+        match self.entries.get(index as usize) {
+            Some(PoolEntry::Class(name)) => Ok(self.utf8(*name)?.to_owned()),
+            _ => Err(ClassFileError::BadConstant(index)),
+        }
+    }
+
+    fn class_opt(&self, index: u16) -> Result<Option<String>, ClassFileError> {
+        if index == 0 {
+            Ok(None)
+        } else {
+            Ok(Some(self.class(index)?))
+        }
+    }
+
+    fn constant(&self, index: u16) -> Result<Constant, ClassFileError> {
+        match self.entries.get(index as usize) {
+            Some(PoolEntry::Constant(c)) => Ok(c.clone()),
+            Some(PoolEntry::String(s)) => Ok(Constant::String(self.utf8(*s)?.to_owned())),
+            // An element value of type `String` refers to a utf8 entry directly.
+            Some(PoolEntry::Utf8(s)) => Ok(Constant::String(s.clone())),
+            _ => Err(ClassFileError::BadConstant(index)),
+        }
+    }
+}
+
+fn read_pool(reader: &mut Reader) -> Result<Pool, ClassFileError> {
+    let count = reader.u2()?;
+    // The entries are 1-based.
+    let mut entries = vec![PoolEntry::Other];
+    while entries.len() < count as usize {
+        let tag = reader.u1()?;
+        let entry = match tag {
+            1 => {
+                let len = reader.u2()?;
+                PoolEntry::Utf8(decode_modified_utf8(reader.take(len as usize)?))
+            }
+            3 => PoolEntry::Constant(Constant::Int(reader.u4()? as i32)),
+            4 => PoolEntry::Constant(Constant::Float(f32::from_bits(reader.u4()?))),
+            5 => PoolEntry::Constant(Constant::Long(reader.u8()? as i64)),
+            6 => PoolEntry::Constant(Constant::Double(f64::from_bits(reader.u8()?))),
+            7 => PoolEntry::Class(reader.u2()?),
+            8 => PoolEntry::String(reader.u2()?),
+            // Method handles
+            15 => {
+                reader.take(3)?;
+                PoolEntry::Other
+            }
+            // Method types, modules, and packages
+            16 | 19 | 20 => {
+                reader.u2()?;
+                PoolEntry::Other
+            }
+            // Field, method, and interface method refs, name and types, and dynamic constants
+            9 | 10 | 11 | 12 | 17 | 18 => {
+                reader.u4()?;
+                PoolEntry::Other
+            }
+            _ => return Err(ClassFileError::BadTag(tag)),
+        };
+        // A long or a double takes two entries.
+        let is_wide = tag == 5 || tag == 6;
+        entries.push(entry);
+        if is_wide {
+            entries.push(PoolEntry::Other);
+        }
+    }
+
+    Ok(Pool { entries })
+}
+
+// Class files encode strings in a modified UTF-8: a null is 2 bytes, and a supplementary
+// character is a pair of 3-byte surrogates.
+fn decode_modified_utf8(bytes: &[u8]) -> String {
+    let mut units = vec![];
+    let mut i = 0;
+    while i < bytes.len() {
+        let b = bytes[i] as u16;
+        let byte = |offset: usize| bytes.get(i + offset).map_or(0, |b| *b as u16 & 0x3F);
+        if b & 0x80 == 0 {
+            units.push(b);
+            i += 1;
+        } else if b & 0xE0 == 0xC0 {
+            units.push(((b & 0x1F) << 6) | byte(1));
+            i += 2;
+        } else {
+            units.push(((b & 0x0F) << 12) | (byte(1) << 6) | byte(2));
+            i += 3;
+        }
+    }
+    String::from_utf16_lossy(&units)
+}
+
+fn read_attribute<'a, 'p>(
+    reader: &mut Reader<'a>,
+    pool: &'p Pool,
+) -> Result<(&'p str, Reader<'a>), ClassFileError> {
+    let name = pool.utf8(reader.u2()?)?;
+    let len = reader.u4()?;
+    let bytes = reader.take(len as usize)?;
+    Ok((name, Reader { bytes, pos: 0 }))
+}
+
+fn read_members(reader: &mut Reader, pool: &Pool) -> Result<Vec<Member>, ClassFileError> {
+    let mut members = vec![];
+    for _ in 0..reader.u2()? {
+        let mut member = Member {
+            access_flags: reader.u2()?,
+            name: pool.utf8(reader.u2()?)?.to_owned(),
+            descriptor: pool.utf8(reader.u2()?)?.to_owned(),
+            signature_opt: None,
+            param_names: vec![],
+            exceptions: vec![],
+            default_opt: None,
+            constant_opt: None,
+        };
+        for _ in 0..reader.u2()? {
+            let (attribute, mut data) = read_attribute(reader, pool)?;
+            match attribute {
+                "Signature" => member.signature_opt = Some(pool.utf8(data.u2()?)?.to_owned()),
+                "Exceptions" => {
+                    for _ in 0..data.u2()? {
+                        member.exceptions.push(pool.class(data.u2()?)?);
+                    }
+                }
+                "MethodParameters" => {
+                    for _ in 0..data.u1()? {
+                        let name_opt = pool.utf8_opt(data.u2()?)?;
+                        data.u2()?;
+                        member.param_names.push(name_opt.unwrap_or_default());
+                    }
+                }
+                "ConstantValue" => member.constant_opt = Some(pool.constant(data.u2()?)?),
+                "AnnotationDefault" => {
+                    member.default_opt = Some(read_element_value(&mut data, pool)?)
+                }
+                _ => (),
+            }
+        }
+        members.push(member);
+    }
+    Ok(members)
+}
+
+fn read_annotation(reader: &mut Reader, pool: &Pool) -> Result<Annotation, ClassFileError> {
+    let tpe = pool.utf8(reader.u2()?)?.to_owned();
+    let mut values = vec![];
+    for _ in 0..reader.u2()? {
+        let name = pool.utf8(reader.u2()?)?.to_owned();
+        values.push((name, read_element_value(reader, pool)?));
+    }
+    Ok(Annotation { tpe, values })
+}
+
+fn read_element_value(reader: &mut Reader, pool: &Pool) -> Result<ElementValue, ClassFileError> {
+    let tag = reader.u1()?;
+    Ok(match tag {
+        b'B' | b'C' | b'D' | b'F' | b'I' | b'J' | b'S' | b'Z' | b's' => {
+            ElementValue::Const(tag, pool.constant(reader.u2()?)?)
+        }
+        b'e' => ElementValue::Enum {
+            tpe: pool.utf8(reader.u2()?)?.to_owned(),
+            name: pool.utf8(reader.u2()?)?.to_owned(),
+        },
+        b'c' => ElementValue::Class(pool.utf8(reader.u2()?)?.to_owned()),
+        b'@' => ElementValue::Annotation(read_annotation(reader, pool)?),
+        b'[' => {
+            let mut values = vec![];
+            for _ in 0..reader.u2()? {
+                values.push(read_element_value(reader, pool)?);
+            }
+            ElementValue::Array(values)
+        }
+        _ => return Err(ClassFileError::BadTag(tag)),
+    })
+}
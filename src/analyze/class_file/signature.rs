@@ -0,0 +1,285 @@
+use std::collections::HashMap;
+
+// Turns descriptors (e.g. "(ILjava/lang/String;)V") and generic signatures (e.g.
+// "<T:Ljava/lang/Object;>(TT;)Ljava/util/List<TT;>;") into Java types, e.g.
+// `java.util.List<T>`. `names` maps the internal names of nested classes to their Java names,
+// e.g. "java/util/Map$Entry" to "java.util.Map.Entry". A malformed input gives None.
+
+#[derive(Debug, PartialEq)]
+pub struct ClassSignature {
+    // E.g. "T extends java.lang.Comparable<? super T>"
+    pub type_params: Vec<String>,
+    pub super_class: String,
+    pub interfaces: Vec<String>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct MethodSignature {
+    pub type_params: Vec<String>,
+    pub params: Vec<String>,
+    pub return_type: String,
+    pub throws: Vec<String>,
+}
+
+pub fn field(signature: &str, names: &HashMap<String, String>) -> Option<String> {
+    let mut parser = Parser::new(signature, names);
+    let tpe = parser.tpe()?;
+    parser.end(tpe)
+}
+
+pub fn class(signature: &str, names: &HashMap<String, String>) -> Option<ClassSignature> {
+    let mut parser = Parser::new(signature, names);
+    let type_params = parser.type_params()?;
+    let super_class = parser.tpe()?;
+    let mut interfaces = vec![];
+    while !parser.is_end() {
+        interfaces.push(parser.tpe()?);
+    }
+    Some(ClassSignature {
+        type_params,
+        super_class,
+        interfaces,
+    })
+}
+
+// Works for both descriptors and signatures.
+pub fn method(signature: &str, names: &HashMap<String, String>) -> Option<MethodSignature> {
+    let mut parser = Parser::new(signature, names);
+    let type_params = parser.type_params()?;
+    parser.expect(b'(')?;
+    let mut params = vec![];
+    while parser.peek()? != b')' {
+        params.push(parser.tpe()?);
+    }
+    parser.expect(b')')?;
+    let return_type = parser.tpe()?;
+    let mut throws = vec![];
+    while !parser.is_end() {
+        parser.expect(b'^')?;
+        throws.push(parser.tpe()?);
+    }
+    Some(MethodSignature {
+        type_params,
+        params,
+        return_type,
+        throws,
+    })
+}
+
+// E.g. "java/util/Map$Entry" is `java.util.Map.Entry` if it's in `names`, and
+// `java.util.Map$Entry` otherwise.
+pub fn class_name(internal: &str, names: &HashMap<String, String>) -> String {
+    names
+        .get(internal)
+        .cloned()
+        .unwrap_or_else(|| internal.replace('/', "."))
+}
+
+struct Parser<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    names: &'a HashMap<String, String>,
+}
+
+impl<'a> Parser<'a> {
+    fn new(signature: &'a str, names: &'a HashMap<String, String>) -> Parser<'a> {
+        Parser {
+            bytes: signature.as_bytes(),
+            pos: 0,
+            names,
+        }
+    }
+
+    fn is_end(&self) -> bool {
+        self.pos >= self.bytes.len()
+    }
+
+    fn end<T>(&self, result: T) -> Option<T> {
+        if self.is_end() {
+            Some(result)
+        } else {
+            None
+        }
+    }
+
+    fn peek(&self) -> Option<u8> {
+        self.bytes.get(self.pos).cloned()
+    }
+
+    fn next(&mut self) -> Option<u8> {
+        let b = self.peek()?;
+        self.pos += 1;
+        Some(b)
+    }
+
+    fn expect(&mut self, b: u8) -> Option<()> {
+        if self.next()? == b {
+            Some(())
+        } else {
+            None
+        }
+    }
+
+    // An identifier ends at any of `stops`. Internal names keep their '/'.
+    fn identifier(&mut self, stops: &[u8]) -> Option<&'a str> {
+        let start = self.pos;
+        while !stops.contains(&self.peek()?) {
+            self.pos += 1;
+        }
+        if start == self.pos {
+            return None;
+        }
+        std::str::from_utf8(&self.bytes[start..self.pos]).ok()
+    }
+
+    fn type_params(&mut self) -> Option<Vec<String>> {
+        let mut type_params = vec![];
+        if self.peek() != Some(b'<') {
+            return Some(type_params);
+        }
+        self.pos += 1;
+
+        while self.peek()? != b'>' {
+            let name = self.identifier(b":")?;
+            self.expect(b':')?;
+            let mut bounds = vec![];
+            // The class bound is empty when there are only interface bounds.
+            if self.peek()? != b':' {
+                bounds.push(self.tpe()?);
+            }
+            while self.peek()? == b':' {
+                self.pos += 1;
+                bounds.push(self.tpe()?);
+            }
+            // Kotlin allows an array bound, which Java doesn't.
+            bounds.retain(|bound| bound != "java.lang.Object" && !bound.ends_with("[]"));
+            if bounds.is_empty() {
+                type_params.push(name.to_owned());
+            } else {
+                type_params.push(format!("{} extends {}", name, bounds.join(" & ")));
+            }
+        }
+        self.pos += 1;
+
+        Some(type_params)
+    }
+
+    fn tpe(&mut self) -> Option<String> {
+        Some(
+            match self.next()? {
+                b'B' => "byte",
+                b'C' => "char",
+                b'D' => "double",
+                b'F' => "float",
+                b'I' => "int",
+                b'J' => "long",
+                b'S' => "short",
+                b'Z' => "boolean",
+                b'V' => "void",
+                b'[' => return Some(format!("{}[]", self.tpe()?)),
+                b'T' => {
+                    let name = self.identifier(b";")?;
+                    self.pos += 1;
+                    return Some(name.to_owned());
+                }
+                b'L' => return self.class_type(),
+                _ => return None,
+            }
+            .to_owned(),
+        )
+    }
+
+    // After the 'L', e.g. "java/util/Map<TK;TV;>.Entry;"
+    fn class_type(&mut self) -> Option<String> {
+        let mut result = class_name(self.identifier(b"<.;")?, self.names);
+        loop {
+            if self.peek()? == b'<' {
+                self.pos += 1;
+                let mut args = vec![];
+                while self.peek()? != b'>' {
+                    args.push(self.type_arg()?);
+                }
+                self.pos += 1;
+                result.push_str(&format!("<{}>", args.join(", ")));
+            }
+
+            match self.next()? {
+                b';' => return Some(result),
+                b'.' => {
+                    result.push('.');
+                    result.push_str(self.identifier(b"<.;")?);
+                }
+                _ => return None,
+            }
+        }
+    }
+
+    fn type_arg(&mut self) -> Option<String> {
+        match self.peek()? {
+            b'*' => {
+                self.pos += 1;
+                Some("?".to_owned())
+            }
+            b'+' => {
+                self.pos += 1;
+                Some(format!("? extends {}", self.tpe()?))
+            }
+            b'-' => {
+                self.pos += 1;
+                Some(format!("? super {}", self.tpe()?))
+            }
+            _ => self.tpe(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use analyze::class_file::signature::{class, field, method, ClassSignature, MethodSignature};
+    use std::collections::HashMap;
+
+    #[test]
+    fn test_signature() {
+        let mut names = HashMap::new();
+        names.insert(
+            "java/util/Map$Entry".to_owned(),
+            "java.util.Map.Entry".to_owned(),
+        );
+
+        assert_eq!(
+            field("[Ljava/util/Map$Entry;", &names),
+            Some("java.util.Map.Entry[]".to_owned())
+        );
+        assert_eq!(
+            field("Lp/Outer<TT;>.Inner<*>;", &names),
+            Some("p.Outer<T>.Inner<?>".to_owned())
+        );
+        assert_eq!(
+            class(
+                "<T::Ljava/lang/Comparable<-TT;>;U:Ljava/lang/Object;>Ljava/lang/Object;Ljava/util/List<+TU;>;",
+                &names
+            ),
+            Some(ClassSignature {
+                type_params: vec![
+                    "T extends java.lang.Comparable<? super T>".to_owned(),
+                    "U".to_owned()
+                ],
+                super_class: "java.lang.Object".to_owned(),
+                interfaces: vec!["java.util.List<? extends U>".to_owned()],
+            })
+        );
+        assert_eq!(
+            method(
+                "<E:Ljava/lang/Number;:Ljava/io/Serializable;>([IJTE;)TE;^Ljava/io/IOException;",
+                &names
+            ),
+            Some(MethodSignature {
+                type_params: vec!["E extends java.lang.Number & java.io.Serializable".to_owned()],
+                params: vec!["int[]".to_owned(), "long".to_owned(), "E".to_owned()],
+                return_type: "E".to_owned(),
+                throws: vec!["java.io.IOException".to_owned()],
+            })
+        );
+        assert_eq!(field("Ljava/lang/String", &names), None);
+    }
+}
@@ -0,0 +1,464 @@
+use analyze::class_file::signature::{self, ClassSignature, MethodSignature};
+use analyze::class_file::{
+    Annotation, ClassFile, Constant, ElementValue, Member, ACC_ABSTRACT, ACC_ANNOTATION,
+    ACC_BRIDGE, ACC_ENUM, ACC_FINAL, ACC_INTERFACE, ACC_MODULE, ACC_NATIVE, ACC_PRIVATE,
+    ACC_PROTECTED, ACC_PUBLIC, ACC_STATIC, ACC_SYNCHRONIZED, ACC_SYNTHETIC, ACC_TRANSIENT,
+    ACC_VARARGS, ACC_VOLATILE, STUB_SUFFIX,
+};
+use parse::builder;
+use std::collections::HashMap;
+use tokenize;
+use SourceFile;
+
+// Renders compiled classes as Java sources with their signatures and without bodies, e.g.
+// `public abstract java.lang.String name(int arg0);`. Types are fully qualified, so the stubs
+// need no imports.
+//
+// A member class is nested in its top-level class, so `classes` should contain the whole
+// package. Local and anonymous classes, synthetic members, and names that aren't Java
+// identifiers are skipped. The path of a stub is the one of its top-level class followed by
+// `STUB_SUFFIX`.
+pub fn apply(classes: Vec<(String, ClassFile)>) -> Vec<SourceFile> {
+    let mut members: HashMap<&str, Vec<&ClassFile>> = HashMap::new();
+    let mut tops = vec![];
+    for (path, class) in &classes {
+        if class.access_flags & (ACC_SYNTHETIC | ACC_MODULE) != 0
+            || !is_identifier(simple_name(class))
+        {
+            continue;
+        }
+        match class.nested_opt() {
+            Some(nested) => {
+                if let (Some(outer), Some(_)) = (&nested.outer_opt, &nested.simple_name_opt) {
+                    members.entry(outer).or_default().push(class);
+                }
+            }
+            None => tops.push((path, class)),
+        }
+    }
+
+    tops.into_iter()
+        .map(|(path, class)| {
+            let mut content = String::new();
+            if let Some(index) = class.name.rfind('/') {
+                content.push_str(&format!(
+                    "package {};\n\n",
+                    class.name[..index].replace('/', ".")
+                ));
+            }
+            render_class(class, &members, 0, &mut content);
+            SourceFile::new(&format!("{}{}", path, STUB_SUFFIX), &content)
+        })
+        .collect()
+}
+
+// The Java names of the nested classes that the class refers to, which are all in its
+// `InnerClasses`.
+fn names(class: &ClassFile) -> HashMap<String, String> {
+    fn resolve(
+        internal: &str,
+        outers: &HashMap<&str, (&str, &str)>,
+        names: &mut HashMap<String, String>,
+    ) -> String {
+        if let Some(name) = names.get(internal) {
+            return name.clone();
+        }
+        let name = match outers.get(internal) {
+            Some((outer, simple_name)) => {
+                format!("{}.{}", resolve(outer, outers, names), simple_name)
+            }
+            None => internal.replace('/', "."),
+        };
+        names.insert(internal.to_owned(), name.clone());
+        name
+    }
+
+    let mut outers = HashMap::new();
+    for inner in &class.inner_classes {
+        if let (Some(outer), Some(simple_name)) = (&inner.outer_opt, &inner.simple_name_opt) {
+            outers.insert(inner.name.as_str(), (outer.as_str(), simple_name.as_str()));
+        }
+    }
+    let mut names = HashMap::new();
+    for inner in outers.keys() {
+        resolve(inner, &outers, &mut names);
+    }
+    names
+}
+
+fn simple_name(class: &ClassFile) -> &str {
+    if let Some(simple_name) = class.nested_opt().and_then(|n| n.simple_name_opt.as_ref()) {
+        return simple_name;
+    }
+    class.name.rsplit('/').next().unwrap_or(&class.name)
+}
+
+fn is_identifier(name: &str) -> bool {
+    name.chars().next().is_some_and(|c| !c.is_ascii_digit())
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '_' || c == '$')
+        && !tokenize::is_keyword(name)
+}
+
+fn render_class(
+    class: &ClassFile,
+    members: &HashMap<&str, Vec<&ClassFile>>,
+    depth: usize,
+    out: &mut String,
+) {
+    let names = names(class);
+    let indent = "    ".repeat(depth);
+    let flags = class
+        .nested_opt()
+        .map_or(class.access_flags, |nested| nested.access_flags);
+    let is_annotation = flags & ACC_ANNOTATION != 0;
+    let is_interface = flags & ACC_INTERFACE != 0;
+    let is_enum = flags & ACC_ENUM != 0 && class.super_opt.as_deref() == Some("java/lang/Enum");
+    let name = simple_name(class);
+
+    for annotation in &class.annotations {
+        out.push_str(&format!(
+            "{}{}\n",
+            indent,
+            render_annotation(annotation, &names)
+        ));
+    }
+    out.push_str(&indent);
+    out.push_str(&access(flags));
+    if flags & ACC_ABSTRACT != 0 && !is_interface && !is_enum {
+        out.push_str("abstract ");
+    }
+    if flags & ACC_STATIC != 0 {
+        out.push_str("static ");
+    }
+    if flags & ACC_FINAL != 0 && !is_enum {
+        out.push_str("final ");
+    }
+
+    let signature = class
+        .signature_opt
+        .as_ref()
+        .and_then(|s| signature::class(s, &names))
+        .unwrap_or_else(|| ClassSignature {
+            type_params: vec![],
+            super_class: class
+                .super_opt
+                .as_ref()
+                .map_or("java.lang.Object".to_owned(), |s| {
+                    signature::class_name(s, &names)
+                }),
+            interfaces: class
+                .interfaces
+                .iter()
+                .map(|i| signature::class_name(i, &names))
+                .collect(),
+        });
+    if is_annotation {
+        out.push_str(&format!("@interface {}", name));
+    } else if is_interface {
+        out.push_str(&format!(
+            "interface {}{}",
+            name,
+            type_params(&signature.type_params)
+        ));
+        if !signature.interfaces.is_empty() {
+            out.push_str(&format!(" extends {}", signature.interfaces.join(", ")));
+        }
+    } else if is_enum {
+        out.push_str(&format!("enum {}", name));
+        if !signature.interfaces.is_empty() {
+            out.push_str(&format!(" implements {}", signature.interfaces.join(", ")));
+        }
+    } else {
+        out.push_str(&format!(
+            "class {}{}",
+            name,
+            type_params(&signature.type_params)
+        ));
+        if signature.super_class != "java.lang.Object" {
+            out.push_str(&format!(" extends {}", signature.super_class));
+        }
+        if !signature.interfaces.is_empty() {
+            out.push_str(&format!(" implements {}", signature.interfaces.join(", ")));
+        }
+    }
+    out.push_str(" {\n");
+
+    let member_indent = "    ".repeat(depth + 1);
+    if is_enum {
+        let constants = class
+            .fields
+            .iter()
+            .filter(|f| f.access_flags & ACC_ENUM != 0 && is_identifier(&f.name))
+            .map(|f| f.name.as_str())
+            .collect::<Vec<_>>();
+        out.push_str(&format!("{}{};\n", member_indent, constants.join(", ")));
+    }
+
+    for field in &class.fields {
+        if field.access_flags & (ACC_SYNTHETIC | ACC_ENUM) != 0 || !is_identifier(&field.name) {
+            continue;
+        }
+        let tpe = field
+            .signature_opt
+            .as_ref()
+            .and_then(|s| signature::field(s, &names))
+            .or_else(|| signature::field(&field.descriptor, &names));
+        if let Some(tpe) = tpe {
+            out.push_str(&format!(
+                "{}{}{} {}",
+                member_indent,
+                field_modifiers(field.access_flags),
+                tpe,
+                field.name
+            ));
+            if let Some(constant) = &field.constant_opt {
+                // The descriptor of a constant is a primitive or `String`.
+                let tag = match field.descriptor.as_bytes()[0] {
+                    b'L' => b's',
+                    tag => tag,
+                };
+                let value = ElementValue::Const(tag, constant.clone());
+                out.push_str(&format!(" = {}", render_value(&value, &names)));
+            }
+            out.push_str(";\n");
+        }
+    }
+
+    for method in &class.methods {
+        if method.access_flags & (ACC_SYNTHETIC | ACC_BRIDGE) != 0 {
+            continue;
+        }
+        let is_constructor = method.name == "<init>";
+        if (!is_constructor && !is_identifier(&method.name))
+            || (is_constructor && (is_interface || is_enum))
+            || (is_enum && is_implicit_enum_method(method))
+        {
+            continue;
+        }
+        let signature = match method
+            .signature_opt
+            .as_ref()
+            .and_then(|s| signature::method(s, &names))
+        {
+            Some(signature) => signature,
+            None => match signature::method(&method.descriptor, &names) {
+                Some(mut signature) => {
+                    // The constructor of an inner class takes the outer instance first.
+                    if is_constructor
+                        && class.nested_opt().is_some()
+                        && flags & ACC_STATIC == 0
+                        && !signature.params.is_empty()
+                    {
+                        signature.params.remove(0);
+                    }
+                    signature
+                }
+                None => continue,
+            },
+        };
+
+        out.push_str(&member_indent);
+        if is_annotation {
+            out.push_str(&format!("{} {}()", signature.return_type, method.name));
+            if let Some(default) = &method.default_opt {
+                out.push_str(&format!(" default {}", render_value(default, &names)));
+            }
+            out.push_str(";\n");
+            continue;
+        }
+
+        let flags = method.access_flags;
+        out.push_str(&method_modifiers(flags));
+        if is_interface && flags & (ACC_ABSTRACT | ACC_STATIC | ACC_PRIVATE) == 0 {
+            out.push_str("default ");
+        }
+        if !signature.type_params.is_empty() {
+            out.push_str(&format!("{} ", type_params(&signature.type_params)));
+        }
+        if is_constructor {
+            out.push_str(name);
+        } else {
+            out.push_str(&format!("{} {}", signature.return_type, method.name));
+        }
+        out.push_str(&format!("({})", params(method, &signature)));
+
+        let throws = if signature.throws.is_empty() {
+            method
+                .exceptions
+                .iter()
+                .map(|e| signature::class_name(e, &names))
+                .collect()
+        } else {
+            signature.throws
+        };
+        if !throws.is_empty() {
+            out.push_str(&format!(" throws {}", throws.join(", ")));
+        }
+
+        if flags & (ACC_ABSTRACT | ACC_NATIVE) != 0 {
+            out.push_str(";\n");
+        } else {
+            out.push_str(" {}\n");
+        }
+    }
+
+    if let Some(nested) = members.get(class.name.as_str()) {
+        for member in nested {
+            render_class(member, members, depth + 1, out);
+        }
+    }
+
+    out.push_str(&format!("{}}}\n", indent));
+}
+
+// `build::enum_def` adds `values()` and `valueOf(String)`.
+fn is_implicit_enum_method(method: &Member) -> bool {
+    (method.name == "values" && method.descriptor.starts_with("()"))
+        || (method.name == "valueOf" && method.descriptor.starts_with("(Ljava/lang/String;)"))
+}
+
+fn type_params(type_params: &[String]) -> String {
+    if type_params.is_empty() {
+        String::new()
+    } else {
+        format!("<{}>", type_params.join(", "))
+    }
+}
+
+// The names come from `MethodParameters` if it's there. Otherwise, they are `arg0`, `arg1`,
+// and so on.
+fn params(method: &Member, signature: &MethodSignature) -> String {
+    let has_names = method.param_names.len() == signature.params.len()
+        && method.param_names.iter().all(|name| is_identifier(name));
+    let mut params = vec![];
+    for (index, tpe) in signature.params.iter().enumerate() {
+        let tpe = if index == signature.params.len() - 1 && method.access_flags & ACC_VARARGS != 0 {
+            format!("{}...", tpe.trim_end_matches("[]"))
+        } else {
+            tpe.clone()
+        };
+        let name = if has_names {
+            method.param_names[index].clone()
+        } else {
+            format!("arg{}", index)
+        };
+        params.push(format!("{} {}", tpe, name));
+    }
+    params.join(", ")
+}
+
+fn access(flags: u16) -> String {
+    if flags & ACC_PUBLIC != 0 {
+        "public ".to_owned()
+    } else if flags & ACC_PROTECTED != 0 {
+        "protected ".to_owned()
+    } else if flags & ACC_PRIVATE != 0 {
+        "private ".to_owned()
+    } else {
+        String::new()
+    }
+}
+
+fn field_modifiers(flags: u16) -> String {
+    let mut modifiers = access(flags);
+    for (flag, keyword) in &[
+        (ACC_STATIC, "static "),
+        (ACC_FINAL, "final "),
+        (ACC_VOLATILE, "volatile "),
+        (ACC_TRANSIENT, "transient "),
+    ] {
+        if flags & flag != 0 {
+            modifiers.push_str(keyword);
+        }
+    }
+    modifiers
+}
+
+fn method_modifiers(flags: u16) -> String {
+    let mut modifiers = access(flags);
+    for (flag, keyword) in &[
+        (ACC_ABSTRACT, "abstract "),
+        (ACC_STATIC, "static "),
+        (ACC_FINAL, "final "),
+        (ACC_SYNCHRONIZED, "synchronized "),
+        (ACC_NATIVE, "native "),
+    ] {
+        if flags & flag != 0 {
+            modifiers.push_str(keyword);
+        }
+    }
+    modifiers
+}
+
+fn render_annotation(annotation: &Annotation, names: &HashMap<String, String>) -> String {
+    let tpe = signature::field(&annotation.tpe, names).unwrap_or_default();
+    if annotation.values.is_empty() {
+        return format!("@{}", tpe);
+    }
+    let values = annotation
+        .values
+        .iter()
+        .map(|(name, value)| format!("{} = {}", name, render_value(value, names)))
+        .collect::<Vec<_>>();
+    format!("@{}({})", tpe, values.join(", "))
+}
+
+fn render_value(value: &ElementValue, names: &HashMap<String, String>) -> String {
+    match value {
+        ElementValue::Const(b'Z', Constant::Int(i)) => (*i != 0).to_string(),
+        ElementValue::Const(b'C', Constant::Int(i)) => {
+            let c = std::char::from_u32(*i as u32).unwrap_or_default();
+            if c == '\'' {
+                "'\\''".to_owned()
+            } else {
+                format!("'{}'", builder::escape(&c.to_string()))
+            }
+        }
+        ElementValue::Const(_, Constant::Int(i)) => i.to_string(),
+        ElementValue::Const(_, Constant::Long(l)) => format!("{}L", l),
+        // The shortest representation of the float is kept.
+        ElementValue::Const(_, Constant::Float(f)) => {
+            render_floating(f.to_string().parse().unwrap_or_default(), "f")
+        }
+        ElementValue::Const(_, Constant::Double(d)) => render_floating(*d, ""),
+        ElementValue::Const(_, Constant::String(s)) => format!("\"{}\"", builder::escape(s)),
+        ElementValue::Enum { tpe, name } => {
+            format!(
+                "{}.{}",
+                signature::field(tpe, names).unwrap_or_default(),
+                name
+            )
+        }
+        ElementValue::Class(tpe) => {
+            format!("{}.class", signature::field(tpe, names).unwrap_or_default())
+        }
+        // The parser doesn't take an annotation as a value. Where there's a value matters more
+        // than what it is, e.g. an element with a default is optional.
+        ElementValue::Annotation(_) => "null".to_owned(),
+        ElementValue::Array(values) => format!(
+            "{{{}}}",
+            values
+                .iter()
+                .map(|v| render_value(v, names))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ),
+    }
+}
+
+fn render_floating(value: f64, suffix: &str) -> String {
+    if value.is_nan() {
+        format!("(0.0{} / 0.0{})", suffix, suffix)
+    } else if value.is_infinite() {
+        format!(
+            "({}1.0{} / 0.0{})",
+            if value < 0.0 { "-" } else { "" },
+            suffix,
+            suffix
+        )
+    } else {
+        format!("{:?}{}", value, suffix)
+    }
+}
@@ -1,32 +1,96 @@
 use analyze::resolve::scope::EnclosingTypeDef;
 use parse;
+use parse::id_gen::Id;
+#[cfg(feature = "serde")]
+use parse::serialize;
 use parse::tree::{
     ClassType, InvocationContext, ParameterizedType, Type, TypeArg, TypeParamExtend,
     VariableDeclarator,
 };
-use std::cell::{Cell, RefCell};
-use std::collections::HashSet;
+use std::cell::{Cell, OnceCell, RefCell};
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use std::pin::Pin;
 use tokenize::span::Span;
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct Root<'a> {
-    pub subpackages: Vec<Package<'a>>,
-    pub units: Vec<CompilationUnit<'a>>,
+    subpackages: Vec<Package<'a>>,
+    units: Vec<CompilationUnit<'a>>,
+    // The classes that are imported but not defined, e.g. from a library that isn't loaded (see
+    // `analyze::resolve::external`).
+    pub externals: Vec<Class<'a>>,
+    index: OnceCell<NameIndex>,
+}
+
+// The index is derived from the subpackages and the units, so it isn't compared.
+impl<'a> PartialEq for Root<'a> {
+    fn eq(&self, other: &Root<'a>) -> bool {
+        let Root {
+            subpackages,
+            units,
+            externals,
+            index: _,
+        } = self;
+        *subpackages == other.subpackages && *units == other.units && *externals == other.externals
+    }
 }
 
 impl<'a> Root<'a> {
+    pub fn new(
+        subpackages: Vec<Package<'a>>,
+        units: Vec<CompilationUnit<'a>>,
+        externals: Vec<Class<'a>>,
+    ) -> Root<'a> {
+        Root {
+            subpackages,
+            units,
+            externals,
+            index: OnceCell::new(),
+        }
+    }
+
+    pub fn subpackages(&self) -> &[Package<'a>] {
+        &self.subpackages
+    }
+
+    pub fn units(&self) -> &[CompilationUnit<'a>] {
+        &self.units
+    }
+
+    // Changing the subpackages or the units drops the index, so it's built again on the next
+    // lookup.
+    pub fn subpackages_mut(&mut self) -> &mut Vec<Package<'a>> {
+        self.index.take();
+        &mut self.subpackages
+    }
+
+    pub fn units_mut(&mut self) -> &mut Vec<CompilationUnit<'a>> {
+        self.index.take();
+        &mut self.units
+    }
+
+    pub fn into_parts(self) -> (Vec<Package<'a>>, Vec<CompilationUnit<'a>>, Vec<Class<'a>>) {
+        (self.subpackages, self.units, self.externals)
+    }
+
+    fn index(&self) -> &NameIndex {
+        self.index
+            .get_or_init(|| NameIndex::build(&self.subpackages, &self.units))
+    }
+
+    pub fn find_external(&self, import_path: &str) -> Option<&Class<'a>> {
+        self.externals
+            .iter()
+            .find(|class| class.import_path == import_path)
+    }
+
     pub fn find(&self, name: &str) -> Option<EnclosingTypeDef<'a>> {
-        for unit in &self.units {
-            if let Some(class) = unit.find(name) {
-                return Some(EnclosingTypeDef::Class(class));
-            }
+        if let Some(class) = self.index().find_class(&self.units, name) {
+            return Some(EnclosingTypeDef::Class(class));
         }
-        for package in &self.subpackages {
-            if package.name.as_str() == name {
-                return Some(EnclosingTypeDef::Package(package));
-            }
+        if let Some(package) = self.index().find_package(&self.subpackages, name) {
+            return Some(EnclosingTypeDef::Package(package));
         }
 
         None
@@ -55,20 +119,68 @@ pub struct CompilationUnit<'a> {
     pub main: Decl<'a>,
     pub others: Vec<Decl<'a>>,
 }
-unsafe impl<'a> Send for CompilationUnit<'a> {}
 
 impl<'a> CompilationUnit<'a> {
+    // The top-level classes, i.e. `main` and then `others`.
+    pub fn decls(&self) -> impl Iterator<Item = &Decl<'a>> {
+        std::iter::once(&self.main).chain(self.others.iter())
+    }
+
     pub fn find(&self, name: &str) -> Option<*const Class<'a>> {
-        match &self.main {
-            Decl::Class(class) => {
-                if class.name == name {
-                    return Some(class.deref() as *const Class<'a>);
-                }
+        self.decls()
+            .map(|decl| decl.class())
+            .find(|class| class.name == name)
+            .map(|class| class as *const Class<'a>)
+    }
+}
+
+// The top-level classes and the subpackages of a root or a package by name, so lookups don't scan
+// the units. It's built on the first lookup.
+#[derive(Debug, Default, Clone)]
+struct NameIndex {
+    // The index of the unit and the index of the class in `CompilationUnit::decls`.
+    classes: HashMap<String, (usize, usize)>,
+    packages: HashMap<String, usize>,
+}
+
+impl NameIndex {
+    fn build(subpackages: &[Package], units: &[CompilationUnit]) -> NameIndex {
+        let mut index = NameIndex::default();
+        // The first one wins, like a scan would find it.
+        for (unit_index, unit) in units.iter().enumerate() {
+            for (decl_index, decl) in unit.decls().enumerate() {
+                index
+                    .classes
+                    .entry(decl.class().name.to_owned())
+                    .or_insert((unit_index, decl_index));
             }
-            _ => (),
         }
+        for (package_index, package) in subpackages.iter().enumerate() {
+            index
+                .packages
+                .entry(package.name.to_owned())
+                .or_insert(package_index);
+        }
+        index
+    }
 
-        None
+    fn find_class<'r, 'a>(
+        &self,
+        units: &'r [CompilationUnit<'a>],
+        name: &str,
+    ) -> Option<&'r Class<'a>> {
+        self.classes
+            .get(name)
+            .and_then(|&(unit_index, decl_index)| units[unit_index].decls().nth(decl_index))
+            .map(|decl| decl.class())
+    }
+
+    fn find_package<'r, 'a>(
+        &self,
+        subpackages: &'r [Package<'a>],
+        name: &str,
+    ) -> Option<&'r Package<'a>> {
+        self.packages.get(name).map(|&index| &subpackages[index])
     }
 }
 
@@ -76,6 +188,20 @@ impl<'a> CompilationUnit<'a> {
 pub enum Decl<'a> {
     Class(Class<'a>),
     Interface(Interface<'a>),
+    Enum(Enum<'a>),
+    Annotation(AnnotationDef<'a>),
+}
+
+impl<'a> Decl<'a> {
+    // The class that a type refers to.
+    pub fn class(&self) -> &Class<'a> {
+        match self {
+            Decl::Class(class) => class,
+            Decl::Interface(interface) => &interface.class,
+            Decl::Enum(enum_def) => &enum_def.class,
+            Decl::Annotation(annotation) => &annotation.class,
+        }
+    }
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -83,15 +209,76 @@ pub struct PackageDecl {
     pub components: Vec<String>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct Package<'a> {
     pub import_path: String,
-    pub name: String,
-    pub subpackages: Vec<Package<'a>>,
-    pub units: Vec<CompilationUnit<'a>>,
+    pub name: &'a str,
+    subpackages: Vec<Package<'a>>,
+    units: Vec<CompilationUnit<'a>>,
+    index: OnceCell<NameIndex>,
+}
+
+// See `Root`.
+impl<'a> PartialEq for Package<'a> {
+    fn eq(&self, other: &Package<'a>) -> bool {
+        let Package {
+            import_path,
+            name,
+            subpackages,
+            units,
+            index: _,
+        } = self;
+        *import_path == other.import_path
+            && *name == other.name
+            && *subpackages == other.subpackages
+            && *units == other.units
+    }
 }
 
 impl<'a> Package<'a> {
+    pub fn new(
+        import_path: String,
+        name: &'a str,
+        subpackages: Vec<Package<'a>>,
+        units: Vec<CompilationUnit<'a>>,
+    ) -> Package<'a> {
+        Package {
+            import_path,
+            name,
+            subpackages,
+            units,
+            index: OnceCell::new(),
+        }
+    }
+
+    pub fn subpackages(&self) -> &[Package<'a>] {
+        &self.subpackages
+    }
+
+    pub fn units(&self) -> &[CompilationUnit<'a>] {
+        &self.units
+    }
+
+    // See `Root::subpackages_mut`.
+    pub fn subpackages_mut(&mut self) -> &mut Vec<Package<'a>> {
+        self.index.take();
+        &mut self.subpackages
+    }
+
+    pub fn units_mut(&mut self) -> &mut Vec<CompilationUnit<'a>> {
+        self.index.take();
+        &mut self.units
+    }
+
+    pub fn into_parts(self) -> (Vec<Package<'a>>, Vec<CompilationUnit<'a>>) {
+        (self.subpackages, self.units)
+    }
+
+    fn index(&self) -> &NameIndex {
+        self.index
+            .get_or_init(|| NameIndex::build(&self.subpackages, &self.units))
+    }
+
     pub fn find(&self, name: &str) -> Option<EnclosingTypeDef<'a>> {
         if let Some(class) = self.find_class(name) {
             return Some(EnclosingTypeDef::Class(class));
@@ -104,51 +291,107 @@ impl<'a> Package<'a> {
     }
 
     pub fn find_package<'b>(&self, name: &str) -> Option<&Package<'a>> {
-        for package in &self.subpackages {
-            if package.name.as_str() == name {
-                return Some(package);
-            }
-        }
-
-        None
+        self.index().find_package(&self.subpackages, name)
     }
 
+    // Any top-level class of the package. Only the public ones are accessible from other packages
+    // (see `Scope::is_accessible`).
     pub fn find_class<'b>(&self, name: &str) -> Option<&Class<'a>> {
-        for unit in &self.units {
-            if let Some(class) = unit.find(name) {
-                return Some(unsafe { &(*class) });
-            }
-        }
-
-        None
+        self.index().find_class(&self.units, name)
     }
 }
 
-#[derive(Debug, PartialEq, Clone)]
+#[derive(Debug, Clone)]
 pub struct Class<'def> {
-    pub id: String,
-    pub name: String,
+    pub id: Id,
+    pub name: &'def str,
     pub import_path: String,
+    // The import path of the package, e.g. `java.util` for `java.util.Map.Entry`. It is empty in
+    // the default package.
+    pub package_path: String,
     pub span_opt: Option<Span<'def>>,
+    // A top-level class that isn't public is only accessible from its package.
+    pub modifiers: HashSet<Modifier>,
     // TODO: Handle class that can only be accessed within a compilation unit
     pub type_params: Vec<TypeParam<'def>>,
     pub extend_opt: RefCell<Option<ClassType<'def>>>,
-    pub implements: Vec<ClassType<'def>>,
-    pub constructors: Vec<Constructor<'def>>,
+    pub implements: RefCell<Vec<ClassType<'def>>>,
+    pub constructors: Vec<ConstructorDef<'def>>,
     pub methods: Vec<MethodDef<'def>>,
     pub field_groups: Vec<FieldGroup<'def>>,
     pub decls: Vec<Decl<'def>>,
+    pub inherited_methods: InheritedMethods<'def>,
+}
+
+// The inherited methods are derived from the supertypes, so they aren't compared.
+impl<'a> PartialEq for Class<'a> {
+    fn eq(&self, other: &Class<'a>) -> bool {
+        let Class {
+            id,
+            name,
+            import_path,
+            package_path,
+            span_opt,
+            modifiers,
+            type_params,
+            extend_opt,
+            implements,
+            constructors,
+            methods,
+            field_groups,
+            decls,
+            inherited_methods: _,
+        } = self;
+        *id == other.id
+            && *name == other.name
+            && *import_path == other.import_path
+            && *package_path == other.package_path
+            && *span_opt == other.span_opt
+            && *modifiers == other.modifiers
+            && *type_params == other.type_params
+            && *extend_opt == other.extend_opt
+            && *implements == other.implements
+            && *constructors == other.constructors
+            && *methods == other.methods
+            && *field_groups == other.field_groups
+            && *decls == other.decls
+    }
+}
+
+// The methods that a class inherits from its supertypes, collected by `ClassType::find_methods` on
+// the first lookup. A clone starts empty because the methods point into the supertypes of the
+// original.
+#[derive(Debug, Default)]
+pub struct InheritedMethods<'a>(OnceCell<Vec<InheritedMethod<'a>>>);
+
+impl<'a> InheritedMethods<'a> {
+    pub fn get_or_init<F>(&self, init: F) -> &[InheritedMethod<'a>]
+    where
+        F: FnOnce() -> Vec<InheritedMethod<'a>>,
+    {
+        self.0.get_or_init(init)
+    }
+}
+
+impl<'a> Clone for InheritedMethods<'a> {
+    fn clone(&self) -> InheritedMethods<'a> {
+        InheritedMethods::default()
+    }
+}
+
+// The depth of the method counts from the inheriting class, i.e. it is 1 for a method of a direct
+// supertype.
+#[derive(Debug, Clone)]
+pub struct InheritedMethod<'a> {
+    pub method: Method<'a>,
+    pub declaring: *const Class<'a>,
 }
-unsafe impl<'a> Sync for Class<'a> {}
-unsafe impl<'a> Send for Class<'a> {}
 
 impl<'a> Class<'a> {
     pub fn find<'b>(&self, name: &str) -> Option<&Class<'a>> {
         for decl in &self.decls {
-            if let Decl::Class(class) = decl {
-                if class.name == name {
-                    return Some(class);
-                }
+            if decl.class().name == name {
+                return Some(decl.class());
             }
         }
 
@@ -158,7 +401,7 @@ impl<'a> Class<'a> {
     pub fn find_field(&self, name: &str) -> Option<&FieldDef<'a>> {
         for group in &self.field_groups {
             for field in &group.items {
-                if &field.name == name {
+                if field.name == name {
                     return Some(field);
                 }
             }
@@ -167,9 +410,10 @@ impl<'a> Class<'a> {
         None
     }
 
+    // The first method named `name`. Use `find_methods` when the method can be overloaded.
     pub fn find_method(&self, name: &str) -> Option<&MethodDef<'a>> {
         for method in &self.methods {
-            if &method.name == name {
+            if method.name == name {
                 return Some(method);
             }
         }
@@ -177,9 +421,26 @@ impl<'a> Class<'a> {
         None
     }
 
+    // The overloads named `name` that are declared in this class. Inherited methods are found
+    // with `ClassType::find_methods`.
+    pub fn find_methods(&self, name: &str) -> Vec<&MethodDef<'a>> {
+        self.methods
+            .iter()
+            .filter(|method| method.name == name)
+            .collect()
+    }
+
+    // The method declared in this class with the erased signature, e.g.
+    // `put(java.lang.Object,java.lang.Object)`.
+    pub fn find_method_by_signature(&self, signature: &str) -> Option<&MethodDef<'a>> {
+        self.methods
+            .iter()
+            .find(|method| method.erased_signature() == signature)
+    }
+
     pub fn find_type_param(&self, name: &str) -> Option<&TypeParam<'a>> {
         for type_param in &self.type_params {
-            if &type_param.name == name {
+            if type_param.name == name {
                 return Some(type_param);
             }
         }
@@ -190,7 +451,7 @@ impl<'a> Class<'a> {
     pub fn to_type(&self) -> ClassType<'a> {
         ClassType {
             prefix_opt: None,
-            name: self.name.to_owned(),
+            name: self.name,
             span_opt: None,
             type_args_opt: None,
             def_opt: Some(self as *const Class<'a>),
@@ -198,18 +459,68 @@ impl<'a> Class<'a> {
     }
 }
 
+// An interface is referred to through its class. Its superinterfaces are in `class.implements`,
+// and the implicit modifiers (e.g. `public abstract` on a method without a body) are added.
 #[derive(Debug, PartialEq, Clone)]
 pub struct Interface<'a> {
-    pub import_path: String,
-    pub name: Span<'a>,
-    pub methods: Vec<MethodDef<'a>>,
-    pub field_groups: Vec<FieldGroup<'a>>,
-    pub decls: Vec<Decl<'a>>,
+    pub class: Class<'a>,
+}
+
+// An enum is a class that extends `Enum<E>`. Its constants are the public static final fields in
+// the first field group, and `values()`, `valueOf(String)`, `name()`, and `ordinal()` are
+// implicit methods.
+#[derive(Debug, PartialEq, Clone)]
+pub struct Enum<'a> {
+    pub class: Class<'a>,
+    pub constants: Vec<EnumConstant<'a>>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct EnumConstant<'a> {
+    pub name: &'a str,
+    pub span_opt: Option<Span<'a>>,
+    // A constant with a body (e.g. `RED { ... }`) is an anonymous subclass of the enum. The
+    // class has the id in `parse::tree::EnumConstant::body_id`.
+    pub body_opt: Option<Class<'a>>,
+}
+
+// An annotation type (`@interface`) is referred to through its class. Each element is a method
+// without params in `class.methods`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct AnnotationDef<'a> {
+    pub class: Class<'a>,
+    pub elements: Vec<AnnotationElement<'a>>,
+    // The name of the `RetentionPolicy` (e.g. `RUNTIME`). Without `@Retention`, it is `CLASS`.
+    pub retention_opt: Option<&'a str>,
+    // The names of the `ElementType`s in `@Target` (e.g. `METHOD`). Empty without `@Target`.
+    pub targets: Vec<&'a str>,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct AnnotationElement<'a> {
+    pub name: &'a str,
+    pub default_opt: Option<parse::tree::Expr<'a>>,
+}
+
+// A class that declares no constructor gets a default one without params. Its `span_opt` is None.
+#[derive(Debug, PartialEq, Clone)]
+pub struct ConstructorDef<'a> {
+    pub modifiers: HashSet<Modifier>,
+    pub type_params: Vec<TypeParam<'a>>,
+    pub params: Vec<Param<'a>>,
+    pub throws: RefCell<Vec<ClassType<'a>>>,
+    pub id: Id,
+    pub span_opt: Option<Span<'a>>,
 }
 
+// A constructor of a class type. The type args of the class type are applied to its params.
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Constructor<'a> {
-    pub name: Span<'a>,
+    pub type_params: Vec<TypeParam<'a>>,
+    pub params: Vec<Param<'a>>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr"))]
+    pub def: *const ConstructorDef<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
@@ -217,35 +528,70 @@ pub struct MethodDef<'a> {
     pub modifiers: HashSet<Modifier>,
     pub type_params: Vec<TypeParam<'a>>,
     pub return_type: RefCell<Type<'a>>,
-    pub name: String,
+    pub name: &'a str,
     pub params: Vec<Param<'a>>,
-    pub id: String,
+    pub id: Id,
     pub span_opt: Option<Span<'a>>,
 }
-unsafe impl<'a> Sync for MethodDef<'a> {}
+
+impl<'a> MethodDef<'a> {
+    pub fn erased_signature(&self) -> String {
+        erased_signature(self.name, &self.params)
+    }
+}
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Method<'a> {
     pub type_params: Vec<TypeParam<'a>>,
     pub params: Vec<Param<'a>>,
     pub return_type: Type<'a>,
     pub depth: usize,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr"))]
     pub def: *const MethodDef<'a>,
 }
 
+impl<'a> Method<'a> {
+    // The signature with the params as seen from the class type, e.g. `get(java.lang.String)` for
+    // `Map<String, Integer>.get(K)`. Two methods with the same one override or hide each other.
+    pub fn erased_signature(&self) -> String {
+        erased_signature(unsafe { &*self.def }.name, &self.params)
+    }
+}
+
+// The name and the erased param types, e.g. `put(java.lang.Object,java.lang.Object)` for
+// `put(K key, V value)`. See `Type::erasure`.
+fn erased_signature(name: &str, params: &[Param]) -> String {
+    let params = params
+        .iter()
+        .map(|param| {
+            let erasure = param.tpe.borrow().erasure();
+            if param.is_varargs {
+                format!("{}[]", erasure)
+            } else {
+                erasure
+            }
+        })
+        .collect::<Vec<_>>();
+    format!("{}({})", name, params.join(","))
+}
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Param<'a> {
     pub tpe: RefCell<Type<'a>>,
-    pub name: Span<'a>,
+    pub name: &'a str,
+    pub span_opt: Option<Span<'a>>,
     pub is_varargs: bool,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct TypeParam<'a> {
-    pub name: String,
+    pub name: &'a str,
     pub span_opt: Option<Span<'a>>,
     pub extends: RefCell<Vec<TypeParamExtend<'a>>>,
-    pub id: String,
+    pub id: Id,
 }
 
 impl<'a> TypeParam<'a> {
@@ -284,14 +630,15 @@ pub struct FieldGroup<'a> {
 #[derive(Debug, PartialEq, Clone)]
 pub struct FieldDef<'a> {
     pub tpe: RefCell<Type<'a>>,
-    pub name: String,
+    pub name: &'a str,
     pub span_opt: Option<Span<'a>>,
-    pub id: String,
+    pub id: Id,
 }
-unsafe impl<'a> Sync for FieldDef<'a> {}
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Field<'a> {
     pub tpe: Type<'a>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr"))]
     pub def: *const FieldDef<'a>,
 }
@@ -0,0 +1,381 @@
+use analyze::definition::Root;
+use analyze::resolve::merge;
+use std::sync::LazyLock;
+use {analyze, parse, SourceFile};
+
+// A minimal `java.lang` for when no JDK is loaded (e.g. with `class_file::load`). It has the
+// members that every class inherits from `Object` and the core types that sources use without
+// importing them. The stubs have no bodies beyond `{}`, like the ones of `class_file`. The
+// `java.util.Iterator` of `Iterable.iterator()` and the `java.lang.annotation.Annotation` of every
+// annotation type come with them.
+
+pub const BUILTIN_PATH: &str = "<builtin>";
+
+// The package, the name, and the declaration of each stub.
+const SOURCES: &[(&str, &str, &str)] = &[
+    (
+        "java.lang",
+        "Object",
+        "public class Object {
+    public Object() {}
+    public final Class<?> getClass() {}
+    public int hashCode() {}
+    public boolean equals(Object obj) {}
+    protected Object clone() throws CloneNotSupportedException {}
+    public String toString() {}
+    public final void notify() {}
+    public final void notifyAll() {}
+    public final void wait() throws InterruptedException {}
+    public final void wait(long timeout) throws InterruptedException {}
+    protected void finalize() throws Throwable {}
+}",
+    ),
+    (
+        "java.lang",
+        "Class",
+        "public final class Class<T> {
+    public String getName() {}
+    public String getSimpleName() {}
+    public boolean isInstance(Object obj) {}
+    public T cast(Object obj) {}
+}",
+    ),
+    (
+        "java.lang",
+        "String",
+        "public final class String implements CharSequence, Comparable<String> {
+    public int length() {}
+    public char charAt(int index) {}
+    public boolean isEmpty() {}
+    public String substring(int beginIndex) {}
+    public String substring(int beginIndex, int endIndex) {}
+    public int indexOf(String str) {}
+    public boolean contains(CharSequence s) {}
+    public boolean startsWith(String prefix) {}
+    public boolean endsWith(String suffix) {}
+    public String trim() {}
+    public String toLowerCase() {}
+    public String toUpperCase() {}
+    public int compareTo(String anotherString) {}
+    public static String valueOf(Object obj) {}
+    public static String format(String format, Object... args) {}
+}",
+    ),
+    (
+        "java.lang",
+        "CharSequence",
+        "public interface CharSequence {
+    int length();
+    char charAt(int index);
+    CharSequence subSequence(int start, int end);
+}",
+    ),
+    (
+        "java.lang",
+        "StringBuilder",
+        "public final class StringBuilder implements CharSequence {
+    public StringBuilder() {}
+    public StringBuilder(String str) {}
+    public StringBuilder append(Object obj) {}
+    public StringBuilder append(String str) {}
+    public int length() {}
+    public char charAt(int index) {}
+    public CharSequence subSequence(int start, int end) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Comparable",
+        "public interface Comparable<T> {
+    int compareTo(T o);
+}",
+    ),
+    (
+        "java.lang",
+        "Iterable",
+        "public interface Iterable<T> {
+    java.util.Iterator<T> iterator();
+}",
+    ),
+    (
+        "java.util",
+        "Iterator",
+        "public interface Iterator<E> {
+    boolean hasNext();
+    E next();
+}",
+    ),
+    (
+        "java.lang",
+        "Runnable",
+        "public interface Runnable {
+    void run();
+}",
+    ),
+    (
+        "java.lang",
+        "AutoCloseable",
+        "public interface AutoCloseable {
+    void close() throws Exception;
+}",
+    ),
+    ("java.lang", "Cloneable", "public interface Cloneable {}"),
+    (
+        "java.lang",
+        "Enum",
+        "public abstract class Enum<E extends Enum<E>> implements Comparable<E> {
+    protected Enum(String name, int ordinal) {}
+    public final String name() {}
+    public final int ordinal() {}
+    public final int compareTo(E o) {}
+}",
+    ),
+    ("java.lang", "Void", "public final class Void {}"),
+    (
+        "java.lang",
+        "Number",
+        "public abstract class Number {
+    public abstract int intValue();
+    public abstract long longValue();
+    public abstract float floatValue();
+    public abstract double doubleValue();
+}",
+    ),
+    (
+        "java.lang",
+        "Boolean",
+        "public final class Boolean implements Comparable<Boolean> {
+    public Boolean(boolean value) {}
+    public boolean booleanValue() {}
+    public int compareTo(Boolean b) {}
+    public static Boolean valueOf(boolean b) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Character",
+        "public final class Character implements Comparable<Character> {
+    public Character(char value) {}
+    public char charValue() {}
+    public int compareTo(Character anotherCharacter) {}
+    public static Character valueOf(char c) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Byte",
+        "public final class Byte extends Number implements Comparable<Byte> {
+    public Byte(byte value) {}
+    public int compareTo(Byte anotherByte) {}
+    public static Byte valueOf(byte b) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Short",
+        "public final class Short extends Number implements Comparable<Short> {
+    public Short(short value) {}
+    public int compareTo(Short anotherShort) {}
+    public static Short valueOf(short s) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Integer",
+        "public final class Integer extends Number implements Comparable<Integer> {
+    public static final int MIN_VALUE = 0x80000000;
+    public static final int MAX_VALUE = 0x7fffffff;
+    public Integer(int value) {}
+    public int compareTo(Integer anotherInteger) {}
+    public static Integer valueOf(int i) {}
+    public static int parseInt(String s) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Long",
+        "public final class Long extends Number implements Comparable<Long> {
+    public Long(long value) {}
+    public int compareTo(Long anotherLong) {}
+    public static Long valueOf(long l) {}
+    public static long parseLong(String s) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Float",
+        "public final class Float extends Number implements Comparable<Float> {
+    public Float(float value) {}
+    public int compareTo(Float anotherFloat) {}
+    public static Float valueOf(float f) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Double",
+        "public final class Double extends Number implements Comparable<Double> {
+    public Double(double value) {}
+    public int compareTo(Double anotherDouble) {}
+    public static Double valueOf(double d) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Throwable",
+        "public class Throwable {
+    public Throwable() {}
+    public Throwable(String message) {}
+    public Throwable(String message, Throwable cause) {}
+    public String getMessage() {}
+    public Throwable getCause() {}
+    public void printStackTrace() {}
+}",
+    ),
+    (
+        "java.lang",
+        "Exception",
+        "public class Exception extends Throwable {
+    public Exception() {}
+    public Exception(String message) {}
+    public Exception(String message, Throwable cause) {}
+}",
+    ),
+    (
+        "java.lang",
+        "RuntimeException",
+        "public class RuntimeException extends Exception {
+    public RuntimeException() {}
+    public RuntimeException(String message) {}
+    public RuntimeException(String message, Throwable cause) {}
+}",
+    ),
+    (
+        "java.lang",
+        "Error",
+        "public class Error extends Throwable {
+    public Error() {}
+    public Error(String message) {}
+}",
+    ),
+    (
+        "java.lang",
+        "CloneNotSupportedException",
+        "public class CloneNotSupportedException extends Exception {
+    public CloneNotSupportedException() {}
+}",
+    ),
+    (
+        "java.lang",
+        "InterruptedException",
+        "public class InterruptedException extends Exception {
+    public InterruptedException() {}
+}",
+    ),
+    (
+        "java.lang.annotation",
+        "Annotation",
+        "public interface Annotation {
+    Class<? extends Annotation> annotationType();
+}",
+    ),
+    ("java.lang", "Override", "public @interface Override {}"),
+    ("java.lang", "Deprecated", "public @interface Deprecated {}"),
+    (
+        "java.lang",
+        "FunctionalInterface",
+        "public @interface FunctionalInterface {}",
+    ),
+    (
+        "java.lang",
+        "SuppressWarnings",
+        "public @interface SuppressWarnings {
+    String[] value();
+}",
+    ),
+];
+
+// The stubs are read once. They are parsed on each build, so the definitions can borrow them for
+// any lifetime.
+static SOURCE_FILES: LazyLock<Vec<SourceFile>> = LazyLock::new(|| {
+    SOURCES
+        .iter()
+        .map(|(package, name, body)| {
+            SourceFile::new(
+                &format!(
+                    "{}/{}/{}.java",
+                    BUILTIN_PATH,
+                    package.replace('.', "/"),
+                    name
+                ),
+                &format!("package {};\n\n{}\n", package, body),
+            )
+        })
+        .collect()
+});
+
+pub fn build<'def>() -> Root<'def> {
+    let source_files: &'def [SourceFile] = &SOURCE_FILES;
+    merge::apply(
+        source_files
+            .iter()
+            .map(|source| analyze::build::apply(&parse::apply(source).ok().unwrap().unit))
+            .collect(),
+    )
+}
+
+// Adds the built-in `java.lang` unless `root` already has `java.lang.Object`, e.g. from a JDK.
+pub fn apply<'def>(root: Root<'def>) -> Root<'def> {
+    let has_object = root
+        .find_package("java")
+        .and_then(|p| p.find_package("lang"))
+        .and_then(|p| p.find_class("Object"))
+        .is_some();
+
+    if has_object {
+        root
+    } else {
+        merge::apply(vec![root, build()])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use analyze::definition::MethodDef;
+    use analyze::test_common::find_class;
+    use parse::tree::{ClassBodyItem, CompilationUnitItem, Expr, Statement, Type};
+
+    #[test]
+    fn test_iterator() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  void method() {
+    Iterable<String> items;
+    items.iterator();
+  }
+}
+        "#
+        );
+
+        let test = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &test.body.items.get(0).unwrap());
+        let stmt = &method.block_opt.as_ref().unwrap().stmts[1];
+        let call = unwrap!(Expr::MethodCall, unwrap!(Statement::Expr, stmt));
+        let resolved = call.def_opt.borrow();
+        let resolved = resolved.as_ref().unwrap();
+
+        let iterable = find_class(&root, "java.lang.Iterable");
+        let iterator = find_class(&root, "java.util.Iterator");
+        assert_eq!(
+            resolved.def,
+            iterable.find_method("iterator").unwrap() as *const MethodDef
+        );
+        let return_type = unwrap!(Type::Class, &resolved.return_type);
+        assert_eq!(return_type.def_opt, Some(iterator as *const _));
+    }
+}
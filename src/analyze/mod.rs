@@ -1,5 +1,7 @@
 pub mod build;
+pub mod class_file;
 pub mod definition;
+pub mod java_lang;
 pub mod resolve;
 
 #[cfg(test)]
@@ -1,100 +1,71 @@
 use analyze::definition::{
-    Class, CompilationUnit, Decl, FieldDef, FieldGroup, MethodDef, Package, Root,
+    Class, CompilationUnit, ConstructorDef, Decl, Enum, FieldDef, FieldGroup, MethodDef, Package,
+    Root,
 };
 use analyze::resolve::assign_type::{
     resolve_and_replace_type, resolve_class_or_parameterized_type, resolve_type,
 };
 use analyze::resolve::scope::Scope;
-use crossbeam_queue::SegQueue;
 use parse::tree::{Type, TypeParamExtend};
-use std::sync::Mutex;
-use std::thread;
 
 #[derive(Debug)]
 pub struct Node<'def, 'def_ref> {
-    pub unit: *const CompilationUnit<'def>,
+    pub unit: &'def_ref CompilationUnit<'def>,
     pub scope: Scope<'def, 'def_ref>,
 }
-unsafe impl<'def, 'def_ref> Send for Node<'def, 'def_ref> {}
 
 pub fn apply(root: &mut Root) {
-    let queue = SegQueue::new();
+    let mut queue = vec![];
     let mut scope = Scope {
         root,
         levels: vec![],
         specific_imports: vec![],
         wildcard_imports: vec![],
     };
-    collect_root(&queue, &mut scope, root);
+    collect_root(&mut queue, &mut scope, root);
 
-    let mut threads = vec![];
-
-    for i in 0..(num_cpus::get() - 1) {
-        let builder = thread::Builder::new();
-        let queue = &queue;
-        threads.push(
-            unsafe {
-                builder.spawn_unchecked(move || {
-                    work(i, queue);
-                })
-            }
-            .unwrap(),
-        )
-    }
-
-    for thread in threads {
-        match thread.join() {
-            Ok(_) => (),
-            Err(_) => panic!(),
-        }
+    // The definitions are mutated through RefCells, so the units are resolved one at a time.
+    for node in queue {
+        apply_node(node);
     }
 }
 
 fn collect_root<'def, 'def_ref, 'scope, 'queue>(
-    queue: &'queue SegQueue<Node<'def, 'def_ref>>,
+    queue: &'queue mut Vec<Node<'def, 'def_ref>>,
     scope: &'scope mut Scope<'def, 'def_ref>,
     root: &'def_ref Root<'def>,
 ) {
-    for unit in &root.units {
+    for unit in root.units() {
         queue.push(Node {
-            unit: unit as *const CompilationUnit<'def>,
+            unit,
             scope: scope.clone(),
         });
     }
-    for subpackage in &root.subpackages {
+    for subpackage in root.subpackages() {
         collect_package(queue, scope, subpackage);
     }
 }
 
 fn collect_package<'def, 'def_ref, 'scope, 'queue>(
-    queue: &'queue SegQueue<Node<'def, 'def_ref>>,
+    queue: &'queue mut Vec<Node<'def, 'def_ref>>,
     scope: &'scope mut Scope<'def, 'def_ref>,
     package: &'def_ref Package<'def>,
 ) {
     scope.enter_package(package);
-    for subpackage in &package.subpackages {
+    for subpackage in package.subpackages() {
         collect_package(queue, scope, subpackage);
     }
-    for unit in &package.units {
+    for unit in package.units() {
         queue.push(Node {
-            unit: unit as *const CompilationUnit<'def>,
+            unit,
             scope: scope.clone(),
         });
     }
     scope.leave();
 }
 
-fn work(thread_index: usize, queue: &SegQueue<Node>) {
-    loop {
-        match queue.pop() {
-            Ok(node) => apply_node(node),
-            Err(_) => break,
-        };
-    }
-}
-
 fn apply_node(mut node: Node) {
-    apply_unit(unsafe { &(*node.unit) }, &mut node.scope)
+    apply_unit(node.unit, &mut node.scope)
 }
 
 fn apply_unit<'def, 'def_ref>(
@@ -115,11 +86,28 @@ fn apply_unit<'def, 'def_ref>(
 fn apply_decl<'def, 'def_ref>(decl: &'def_ref Decl<'def>, scope: &mut Scope<'def, 'def_ref>) {
     match decl {
         Decl::Class(class) => apply_class(class, scope),
-        Decl::Interface(interface) => (),
+        Decl::Interface(interface) => apply_class(&interface.class, scope),
+        Decl::Enum(enum_def) => apply_enum(enum_def, scope),
+        Decl::Annotation(annotation) => apply_class(&annotation.class, scope),
     }
 }
 
-fn apply_class<'def, 'def_ref>(class: &'def_ref Class<'def>, scope: &mut Scope<'def, 'def_ref>) {
+fn apply_enum<'def, 'def_ref>(enum_def: &'def_ref Enum<'def>, scope: &mut Scope<'def, 'def_ref>) {
+    apply_class(&enum_def.class, scope);
+
+    scope.enter_class(&enum_def.class);
+    for constant in &enum_def.constants {
+        if let Some(body) = &constant.body_opt {
+            apply_class(body, scope);
+        }
+    }
+    scope.leave();
+}
+
+pub fn apply_class<'def, 'def_ref>(
+    class: &'def_ref Class<'def>,
+    scope: &mut Scope<'def, 'def_ref>,
+) {
     scope.enter();
     for type_param in &class.type_params {
         scope.add_type_param(type_param);
@@ -141,6 +129,9 @@ fn apply_class<'def, 'def_ref>(class: &'def_ref Class<'def>, scope: &mut Scope<'
     for type_param in &class.type_params {
         scope.add_type_param(type_param);
     }
+    for constructor in &class.constructors {
+        apply_constructor(constructor, scope);
+    }
     for method in &class.methods {
         apply_method(method, scope);
     }
@@ -155,6 +146,15 @@ fn apply_class<'def, 'def_ref>(class: &'def_ref Class<'def>, scope: &mut Scope<'
     scope.leave();
 }
 
+fn apply_constructor<'def, 'def_ref>(
+    constructor: &'def_ref ConstructorDef<'def>,
+    scope: &mut Scope<'def, 'def_ref>,
+) {
+    for param in &constructor.params {
+        resolve_and_replace_type(&param.tpe, scope);
+    }
+}
+
 fn apply_method<'def, 'def_ref, 'scope_ref>(
     method: &'def_ref MethodDef<'def>,
     scope: &'scope_ref mut Scope<'def, 'def_ref>,
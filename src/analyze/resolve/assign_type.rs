@@ -1,87 +1,39 @@
 use analyze::definition::{
-    Class, CompilationUnit, Decl, FieldDef, FieldGroup, MethodDef, Package, Root,
+    Class, CompilationUnit, ConstructorDef, Decl, Enum, FieldDef, FieldGroup, MethodDef, Package,
+    Root,
 };
-use analyze::resolve::grapher::{Grapher, Node};
+use analyze::resolve::external;
+use analyze::resolve::grapher::{Grapher, Node, NodeIndex};
 use analyze::resolve::scope::{EnclosingTypeDef, Scope};
-use crossbeam_queue::SegQueue;
 use parse::tree::{
     ArrayType, ClassType, EnclosingType, PackagePrefix, ReferenceType, Type, TypeArg, WildcardType,
 };
 use std::cell::{Cell, RefCell};
-use std::collections::{HashMap, HashSet};
+use std::collections::{HashMap, HashSet, VecDeque};
 use std::marker::PhantomData;
 use std::ops::{Add, Deref};
-use std::sync::{Arc, Mutex};
-use std::thread;
-use std::time::Duration;
+use std::sync::Arc;
 
 pub fn apply(root: &mut Root) {
+    // The types that refer to an unresolved import resolve to its external.
+    external::apply(root);
+
     let mut grapher = Grapher::new(root);
     grapher.collect();
 
-    let queue = SegQueue::new();
-
-    for &node_index in grapher.pool.iter() {
-        queue.push(grapher.nodes.get(node_index).unwrap());
-    }
-
-    let mut threads = vec![];
-
-    let finished = Mutex::new(0);
-
-    for i in 0..(num_cpus::get() - 1) {
-        let builder = thread::Builder::new();
-        let finished = &finished;
-        let grapher = &grapher;
-        let queue = &queue;
-        threads.push(
-            unsafe {
-                builder.spawn_unchecked(move || {
-                    work(i, finished, grapher, queue);
-                })
-            }
-            .unwrap(),
-        )
-    }
-
-    for thread in threads {
-        match thread.join() {
-            Ok(_) => (),
-            Err(_) => panic!(),
-        }
-    }
-}
-
-fn work<'def, 'def_ref, 'grapher_ref, 'queue_ref>(
-    thread_index: usize,
-    finished: &Mutex<usize>,
-    grapher: &'grapher_ref Grapher<'def, 'def_ref>,
-    queue: &'queue_ref SegQueue<&'grapher_ref Node<'def, 'def_ref>>,
-) {
-    loop {
-        if *finished.lock().unwrap().deref() == grapher.nodes.len() {
-            break;
-        }
-        match queue.pop() {
-            Ok(node) => {
-                println!(
-                    "Worker {} works on {}",
-                    thread_index,
-                    unsafe { &(*node.class) }.name
-                );
-                apply_node(node, grapher, queue);
-                let mut counter = finished.lock().unwrap();
-                *counter += 1;
-            }
-            Err(_) => thread::sleep(Duration::from_millis(10)),
-        };
+    // A class is resolved after its super class and its enclosing class because its scope reads
+    // their definitions. The definitions are mutated through RefCells, so the classes are
+    // resolved one at a time, in the order of the graph.
+    let mut queue = grapher.pool.iter().cloned().collect::<VecDeque<_>>();
+    while let Some(node_index) = queue.pop_front() {
+        apply_node(grapher.nodes.get(node_index).unwrap(), &grapher, &mut queue);
     }
 }
 
-fn apply_node<'def, 'queue_ref, 'grapher_ref, 'def_ref>(
-    node: &'grapher_ref Node<'def, 'def_ref>,
-    grapher: &'grapher_ref Grapher<'def, 'def_ref>,
-    queue: &'queue_ref SegQueue<&'grapher_ref Node<'def, 'def_ref>>,
+fn apply_node<'def, 'def_ref>(
+    node: &Node<'def, 'def_ref>,
+    grapher: &Grapher<'def, 'def_ref>,
+    queue: &mut VecDeque<NodeIndex>,
 ) {
     apply_class(
         unsafe { &(*node.class) },
@@ -91,11 +43,11 @@ fn apply_node<'def, 'queue_ref, 'grapher_ref, 'def_ref>(
     for &dependent_index in &node.dependents {
         let dependent = grapher.nodes.get(dependent_index).unwrap();
 
-        let mut fulfilled = dependent.fulfilled.lock().unwrap();
+        let mut fulfilled = dependent.fulfilled.borrow_mut();
         fulfilled.insert(node.index);
 
         if fulfilled.len() == dependent.dependencies.len() {
-            queue.push(dependent);
+            queue.push_back(dependent_index);
         }
     }
 }
@@ -106,13 +58,27 @@ fn apply_decl<'def, 'def_ref, 'scope_ref>(
 ) {
     match decl {
         Decl::Class(class) => apply_class(class, scope),
-        _ => (),
+        Decl::Enum(enum_def) => apply_enum(enum_def, scope),
+        Decl::Interface(interface) => apply_class(&interface.class, scope),
+        Decl::Annotation(annotation) => apply_class(&annotation.class, scope),
     };
 }
 
-fn apply_class<'def, 'def_ref, 'scope_ref>(
+fn apply_enum<'def, 'def_ref>(enum_def: &'def_ref Enum<'def>, scope: &mut Scope<'def, 'def_ref>) {
+    apply_class(&enum_def.class, scope);
+
+    scope.enter_class(&enum_def.class);
+    for constant in &enum_def.constants {
+        if let Some(body) = &constant.body_opt {
+            apply_class(body, scope);
+        }
+    }
+    scope.leave();
+}
+
+pub fn apply_class<'def, 'def_ref>(
     class: &'def_ref Class<'def>,
-    scope: &'scope_ref mut Scope<'def, 'def_ref>,
+    scope: &mut Scope<'def, 'def_ref>,
 ) {
     scope.enter();
     // TypeParam can be referred to in the 'extend' section. But the class itself can't.
@@ -135,12 +101,28 @@ fn apply_class<'def, 'def_ref, 'scope_ref>(
         class.extend_opt.replace(Some(resolved_extend));
     }
 
+    let resolved_implements = class
+        .implements
+        .borrow()
+        .iter()
+        .map(
+            |implement| match resolve_class_or_parameterized_type(implement, scope) {
+                Some(Type::Class(resolved)) => resolved,
+                _ => implement.clone(),
+            },
+        )
+        .collect();
+    class.implements.replace(resolved_implements);
+
     scope.enter_class(class);
     // TypeParam should be recognized before traversing into the super classes.
     for type_param in &class.type_params {
         scope.add_type_param(type_param);
     }
 
+    for constructor in &class.constructors {
+        apply_constructor(constructor, scope);
+    }
     for method in &class.methods {
         apply_method(method, scope);
     }
@@ -155,6 +137,36 @@ fn apply_class<'def, 'def_ref, 'scope_ref>(
     scope.leave();
 }
 
+fn apply_constructor<'def, 'def_ref>(
+    constructor: &'def_ref ConstructorDef<'def>,
+    scope: &mut Scope<'def, 'def_ref>,
+) {
+    scope.enter();
+
+    for type_param in &constructor.type_params {
+        scope.add_type_param(type_param);
+    }
+
+    for param in &constructor.params {
+        resolve_and_replace_type(&param.tpe, scope);
+    }
+
+    let resolved_throws = constructor
+        .throws
+        .borrow()
+        .iter()
+        .map(
+            |throw| match resolve_class_or_parameterized_type(throw, scope) {
+                Some(Type::Class(resolved)) => resolved,
+                _ => throw.clone(),
+            },
+        )
+        .collect();
+    constructor.throws.replace(resolved_throws);
+
+    scope.leave();
+}
+
 fn apply_field_group<'def, 'def_ref, 'scope_ref>(
     field_group: &'def_ref FieldGroup<'def>,
     scope: &'scope_ref mut Scope<'def, 'def_ref>,
@@ -297,8 +309,7 @@ pub fn resolve_enclosing_type<'def, 'type_ref, 'def_ref, 'scope_ref>(
             None => return None,
         };
 
-        let result = prefix
-            .find(&unknown_type.name)
+        let result = find_accessible(&prefix, unknown_type.name, scope)
             .unwrap_or_else(|| EnclosingType::Class(unknown_type.clone()));
 
         Some(result.set_prefix_opt(Some(prefix)))
@@ -321,12 +332,12 @@ pub fn resolve_enclosing_type<'def, 'type_ref, 'def_ref, 'scope_ref>(
                 }
                 result_opt = Some(EnclosingType::Class(ClassType {
                     prefix_opt: resolved.prefix_opt.clone(),
-                    name: resolved.name.clone(),
+                    name: resolved.name,
                     span_opt: resolved.span_opt,
                     type_args_opt: Some(resolved_type_args),
                     def_opt: resolved.def_opt.clone(),
                 }))
-            } else {
+            } else if result_opt.is_some() {
                 panic!()
             }
         }
@@ -354,7 +365,7 @@ fn resolve_prefix<'def, 'type_ref, 'def_ref, 'scope_ref>(
     if let Some(Some(prefix_prefix)) = prefix_prefix_opt {
         let name = prefix.get_name();
 
-        let mut result_opt = prefix_prefix.find(prefix.get_name());
+        let mut result_opt = find_accessible(&prefix_prefix, prefix.get_name(), scope);
 
         if let Some(result) = &result_opt {
             result_opt = Some(result.set_prefix_opt(Some(prefix_prefix)))
@@ -370,6 +381,23 @@ fn resolve_prefix<'def, 'type_ref, 'def_ref, 'scope_ref>(
     }
 }
 
+// A class found in a package must be accessible from the scope.
+fn find_accessible<'def>(
+    prefix: &EnclosingType<'def>,
+    name: &str,
+    scope: &Scope<'def, '_>,
+) -> Option<EnclosingType<'def>> {
+    let found = prefix.find(name)?;
+    if let (EnclosingType::Package(_), EnclosingType::Class(class)) = (prefix, &found) {
+        if let Some(def) = class.def_opt {
+            if !scope.is_accessible(unsafe { &*def }) {
+                return None;
+            }
+        }
+    }
+    Some(found)
+}
+
 fn resolve_package_prefix<'def, 'type_ref, 'def_ref, 'scope_ref>(
     package: &'type_ref PackagePrefix<'def>,
     scope: &'scope_ref Scope<'def, 'def_ref>,
@@ -377,7 +405,7 @@ fn resolve_package_prefix<'def, 'type_ref, 'def_ref, 'scope_ref>(
     match scope.resolve_package(&package.name) {
         Some(p) => Some(EnclosingType::Package(PackagePrefix {
             prefix_opt: None,
-            name: package.name.to_owned(),
+            name: package.name,
             span_opt: package.span_opt,
             def: p as *const Package<'def>,
         })),
@@ -0,0 +1,100 @@
+use analyze::build::class;
+use analyze::definition::{Class, CompilationUnit, InheritedMethods, Modifier, Package, Root};
+use parse;
+use parse::id_gen::IdGen;
+use std::cell::RefCell;
+use std::collections::HashSet;
+use std::iter::FromIterator;
+
+// An import that doesn't resolve, e.g. of a library that isn't loaded, gets an external: a class
+// without members whose `import_path` is the imported name. The types that refer to the import
+// resolve to it, so their usages in every file link to the same definition. On-demand imports of
+// unknown packages get nothing because their names are unknown. Applying it again adds only the
+// externals of the imports that are new.
+pub fn apply(root: &mut Root) {
+    let mut missings = vec![];
+    for package in root.subpackages() {
+        collect_package(package, root, &mut missings);
+    }
+    for unit in root.units() {
+        collect_unit(unit, root, &mut missings);
+    }
+
+    for (import_path, name) in missings {
+        if root.find_external(&import_path).is_none() {
+            root.externals.push(build(import_path, name));
+        }
+    }
+}
+
+fn collect_package<'def>(
+    package: &Package<'def>,
+    root: &Root<'def>,
+    missings: &mut Vec<(String, &'def str)>,
+) {
+    for subpackage in package.subpackages() {
+        collect_package(subpackage, root, missings);
+    }
+    for unit in package.units() {
+        collect_unit(unit, root, missings);
+    }
+}
+
+fn collect_unit<'def>(
+    unit: &CompilationUnit<'def>,
+    root: &Root<'def>,
+    missings: &mut Vec<(String, &'def str)>,
+) {
+    for import in &unit.imports {
+        let import = unsafe { &**import };
+        if import.is_wildcard {
+            continue;
+        }
+
+        // The name of a static import is a member of the class, e.g. `java.util.Map.entry`.
+        let (import_path, name) = if import.is_static {
+            match &import.prefix_opt {
+                Some(prefix) => (prefix.path(), prefix.name.fragment()),
+                None => continue,
+            }
+        } else {
+            (import.path(), import.name.fragment())
+        };
+
+        if !exists(root, &import_path) {
+            missings.push((import_path, name));
+        }
+    }
+}
+
+fn exists(root: &Root, import_path: &str) -> bool {
+    let mut segments = import_path.split('.');
+    let mut current_opt = segments.next().and_then(|segment| root.find(segment));
+    for segment in segments {
+        current_opt = current_opt.and_then(|current| current.find(segment));
+    }
+    current_opt.is_some()
+}
+
+fn build<'def>(import_path: String, name: &'def str) -> Class<'def> {
+    let extend_opt = class::default_extend_opt(&import_path);
+    Class {
+        id: IdGen::new(&import_path).get_next("class", name),
+        name,
+        package_path: import_path
+            .rsplit_once('.')
+            .map_or("", |(package_path, _)| package_path)
+            .to_owned(),
+        import_path,
+        span_opt: None,
+        modifiers: HashSet::from_iter(vec![Modifier::Public]),
+        type_params: vec![],
+        extend_opt: RefCell::new(extend_opt),
+        implements: RefCell::new(vec![]),
+        constructors: vec![],
+        methods: vec![],
+        field_groups: vec![],
+        decls: vec![],
+        inherited_methods: InheritedMethods::default(),
+    }
+}
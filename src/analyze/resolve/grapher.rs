@@ -1,11 +1,10 @@
-use analyze::definition::{Class, CompilationUnit, Decl, Package, Root};
+use analyze::definition::{Class, CompilationUnit, Decl, Enum, Package, Root};
 use analyze::resolve::assign_type;
 use analyze::resolve::scope::{EnclosingTypeDef, Level, Scope};
 use parse::tree::{ClassType, Type};
 use std::cell::{Cell, RefCell};
 use std::collections::{HashMap, HashSet};
 use std::marker::PhantomData;
-use std::sync::Mutex;
 
 #[derive(Debug)]
 pub struct Node<'def, 'def_ref> {
@@ -14,12 +13,10 @@ pub struct Node<'def, 'def_ref> {
     pub scope: RefCell<Option<Scope<'def, 'def_ref>>>,
     pub dependents: HashSet<NodeIndex>,
     pub dependencies: HashSet<NodeIndex>,
-    pub fulfilled: Mutex<HashSet<NodeIndex>>,
+    pub fulfilled: RefCell<HashSet<NodeIndex>>,
 }
-unsafe impl<'def, 'def_ref> Sync for Node<'def, 'def_ref> {}
-unsafe impl<'def, 'def_ref> Send for Node<'def, 'def_ref> {}
 
-type NodeIndex = usize;
+pub type NodeIndex = usize;
 
 #[derive(Debug)]
 pub struct Grapher<'def, 'def_ref> {
@@ -29,8 +26,6 @@ pub struct Grapher<'def, 'def_ref> {
     pub scope: Scope<'def, 'def_ref>,
     pub root: &'def_ref Root<'def>,
 }
-unsafe impl<'def, 'def_ref> Sync for Grapher<'def, 'def_ref> {}
-unsafe impl<'def, 'def_ref> Send for Grapher<'def, 'def_ref> {}
 
 impl<'def, 'def_ref> Grapher<'def, 'def_ref> {
     pub fn new(root: &'def_ref Root<'def>) -> Grapher<'def, 'def_ref> {
@@ -56,21 +51,34 @@ impl<'def, 'def_ref> Grapher<'def, 'def_ref> {
     }
 
     pub fn collect(&mut self) {
-        for package in &self.root.subpackages {
+        for external in &self.root.externals {
+            self.collect_class(external, None);
+        }
+
+        for package in self.root.subpackages() {
             self.collect_package(package);
         }
 
-        for unit in &self.root.units {
+        for unit in self.root.units() {
             self.collect_unit(unit);
         }
+
+        // A class whose super class can't be resolved depends on nothing, so it starts in the pool
+        // too.
+        self.pool = self
+            .nodes
+            .iter()
+            .filter(|node| node.dependencies.is_empty())
+            .map(|node| node.index)
+            .collect();
     }
 
     pub fn collect_package(&mut self, package: &'def_ref Package<'def>) {
         self.scope.enter_package(package);
-        for subpackage in &package.subpackages {
+        for subpackage in package.subpackages() {
             self.collect_package(subpackage);
         }
-        for unit in &package.units {
+        for unit in package.units() {
             self.collect_unit(unit);
         }
         self.scope.leave()
@@ -95,10 +103,33 @@ impl<'def, 'def_ref> Grapher<'def, 'def_ref> {
     ) {
         match decl {
             Decl::Class(class) => self.collect_class(class, parent_node_opt),
-            _ => (),
+            Decl::Enum(enum_def) => self.collect_enum(enum_def, parent_node_opt),
+            Decl::Interface(interface) => self.collect_class(&interface.class, parent_node_opt),
+            Decl::Annotation(annotation) => self.collect_class(&annotation.class, parent_node_opt),
         };
     }
 
+    // The body of a constant (e.g. `RED { ... }`) is a subclass of the enum nested in the enum.
+    pub fn collect_enum(
+        &mut self,
+        enum_def: &'def_ref Enum<'def>,
+        parent_node_opt: Option<NodeIndex>,
+    ) {
+        self.collect_class(&enum_def.class, parent_node_opt);
+
+        let node_index = *self
+            .map
+            .get(&(&enum_def.class as *const Class<'def>))
+            .unwrap();
+        self.scope.enter_class(&enum_def.class);
+        for constant in &enum_def.constants {
+            if let Some(body) = &constant.body_opt {
+                self.collect_class(body, Some(node_index));
+            }
+        }
+        self.scope.leave();
+    }
+
     pub fn collect_class<'scope_ref, 'node_ref>(
         &mut self,
         class: &'def_ref Class<'def>,
@@ -132,7 +163,6 @@ impl<'def, 'def_ref> Grapher<'def, 'def_ref> {
             Some(index) => *index,
             None => self.create_node(class as *const Class<'def>, parent_node_opt.is_some()),
         };
-        self.update_pool(node_index, parent_node_opt.is_some());
         for decl in &class.decls {
             self.scope.enter_class(class);
             self.collect_decl(decl, Some(node_index));
@@ -175,7 +205,7 @@ impl<'def, 'def_ref> Grapher<'def, 'def_ref> {
             scope: RefCell::new(None),
             dependents: HashSet::new(),
             dependencies: HashSet::new(),
-            fulfilled: Mutex::new(HashSet::new()),
+            fulfilled: RefCell::new(HashSet::new()),
         };
 
         let key = node.class;
@@ -184,21 +214,6 @@ impl<'def, 'def_ref> Grapher<'def, 'def_ref> {
         index
     }
 
-    fn update_pool(&mut self, node_index: NodeIndex, has_outer: bool) {
-        let node = self.nodes.get(node_index).unwrap();
-        let has_super_class = unsafe { &(*node.class) }
-            .extend_opt
-            .borrow()
-            .as_ref()
-            .is_some();
-
-        if !has_super_class && !has_outer {
-            self.pool.insert(node.index);
-        } else {
-            self.pool.remove(&node.index);
-        }
-    }
-
     pub fn collect_node<'type_ref>(
         &mut self,
         class_type: &'type_ref ClassType<'def>,
@@ -4,59 +4,63 @@ use std::collections::HashMap;
 pub fn apply(roots: Vec<Root>) -> Root {
     let mut subpackages = vec![];
     let mut units = vec![];
+    let mut externals = vec![];
 
     for root in roots {
-        for p in root.subpackages {
+        let (root_subpackages, root_units, root_externals) = root.into_parts();
+        for p in root_subpackages {
             subpackages.push(p);
         }
 
-        for u in root.units {
+        for u in root_units {
             units.push(u);
         }
-    }
 
-    Root {
-        subpackages: merge_packages(subpackages),
-        units,
+        for e in root_externals {
+            externals.push(e);
+        }
     }
+
+    Root::new(merge_packages(subpackages), units, externals)
 }
 
 fn merge_packages(packages: Vec<Package>) -> Vec<Package> {
-    let mut by_name: HashMap<String, Vec<Package>> = HashMap::new();
+    let mut by_name: HashMap<&str, Vec<Package>> = HashMap::new();
 
     for p in packages {
-        if !by_name.contains_key(p.name.as_str()) {
-            by_name.insert(p.name.clone(), vec![]);
+        if !by_name.contains_key(p.name) {
+            by_name.insert(p.name, vec![]);
         }
 
-        by_name.get_mut(&p.name).unwrap().push(p);
+        by_name.get_mut(p.name).unwrap().push(p);
     }
 
     let mut result = vec![];
 
     for (_, ps) in by_name {
-        let import_path = ps.get(0).unwrap().import_path.clone();
-        let name = ps.get(0).unwrap().name.clone();
+        let import_path = ps.first().unwrap().import_path.clone();
+        let name = ps.first().unwrap().name;
 
         let mut subpackages = vec![];
         let mut units = vec![];
 
         for p in ps {
-            for s in p.subpackages {
+            let (package_subpackages, package_units) = p.into_parts();
+            for s in package_subpackages {
                 subpackages.push(s);
             }
 
-            for u in p.units {
+            for u in package_units {
                 units.push(u);
             }
         }
 
-        result.push(Package {
+        result.push(Package::new(
             import_path,
             name,
-            subpackages: merge_packages(subpackages),
+            merge_packages(subpackages),
             units,
-        })
+        ))
     }
 
     result.sort_by(|a, b| a.name.cmp(&b.name));
@@ -178,3 +182,63 @@ fn merge_packages(packages: Vec<Package>) -> Vec<Package> {
 //        )
 //    }
 //}
+
+#[cfg(test)]
+mod tests {
+    use super::apply;
+    use analyze::build;
+    use analyze::definition::Root;
+
+    #[test]
+    fn test_index() {
+        let files = apply_parse!(
+            r#"
+package dev;
+
+class Test {}
+
+class Other {}
+        "#,
+            r#"
+package dev.sub;
+
+class Test {}
+        "#
+        );
+        let merge = || {
+            apply(
+                files
+                    .iter()
+                    .map(|file| build::apply(&file.unit))
+                    .collect::<Vec<Root>>(),
+            )
+        };
+        let mut root = merge();
+
+        let dev = root.find_package("dev").unwrap();
+        assert_eq!(dev.find_class("Other").unwrap().import_path, "dev.Other");
+        assert_eq!(
+            dev.find_package("sub")
+                .unwrap()
+                .find_class("Test")
+                .unwrap()
+                .import_path,
+            "dev.sub.Test"
+        );
+
+        // The index is derived, so a root compares equal whether it's built or not.
+        assert_eq!(root, merge());
+
+        // A root from `build` is indexed too.
+        let built = build::apply(&files[1].unit);
+        assert!(built
+            .find_package("dev")
+            .unwrap()
+            .find_package("sub")
+            .is_some());
+
+        // Changing the subpackages drops the index.
+        root.subpackages_mut().clear();
+        assert!(root.find_package("dev").is_none());
+    }
+}
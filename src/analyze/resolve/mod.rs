@@ -5,6 +5,7 @@ use {analyze, parse};
 
 pub mod assign_parameterized_type;
 pub mod assign_type;
+pub mod external;
 pub mod grapher;
 pub mod merge;
 pub mod scope;
@@ -18,13 +19,17 @@ pub fn apply<'def, 'r>(units: &'r Vec<&parse::tree::CompilationUnit<'def>>) -> R
     root
 }
 
+// The built-in `java.lang` is added when the units don't have a JDK (see `analyze::java_lang`),
+// and the imports that don't resolve get their externals (see `external`).
 pub fn merge<'def, 'r>(units: &'r Vec<&parse::tree::CompilationUnit<'def>>) -> Root<'def> {
-    merge::apply(
+    let mut root = analyze::java_lang::apply(merge::apply(
         units
             .iter()
             .map(|unit| analyze::build::apply(*unit))
             .collect::<Vec<Root>>(),
-    )
+    ));
+    external::apply(&mut root);
+    root
 }
 
 pub fn apply_type<'def, 'def_ref, 'scope_ref>(
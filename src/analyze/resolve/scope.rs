@@ -1,4 +1,6 @@
-use analyze::definition::{Class, Decl, Method, MethodDef, Package, Param, Root, TypeParam};
+use analyze::definition::{
+    Class, Decl, Method, MethodDef, Modifier, Package, Param, Root, TypeParam,
+};
 use parse::tree::{
     ClassType, EnclosingType, ImportPrefix, InvocationContext, PackagePrefix, ParameterizedType,
     ResolvedName, VariableDeclarator, NATIVE_ARRAY_CLASS_NAME,
@@ -31,14 +33,12 @@ pub struct WildcardImport<'def> {
     pub enclosing: EnclosingTypeDef<'def>,
     pub is_static: bool,
 }
-unsafe impl<'def> Send for WildcardImport<'def> {}
 
 #[derive(Debug, PartialEq, Clone)]
 pub struct Level<'def> {
     pub enclosing_opt: Option<EnclosingTypeDef<'def>>,
     pub names: HashMap<String, Vec<Name<'def>>>,
 }
-unsafe impl<'def> Send for Level<'def> {}
 
 #[derive(Debug, PartialEq, Clone)]
 pub enum Name<'def> {
@@ -83,7 +83,7 @@ impl<'def> EnclosingTypeDef<'def> {
                 let package = unsafe { &(**package) };
                 EnclosingType::Package(PackagePrefix {
                     prefix_opt: None,
-                    name: package.name.to_owned(),
+                    name: package.name,
                     span_opt: None,
                     def: package as *const Package,
                 })
@@ -97,24 +97,47 @@ impl<'def> EnclosingTypeDef<'def> {
 }
 
 impl<'def, 'r> Scope<'def, 'r> {
-    fn resolve_import_prefix<'s>(&self, prefix: &'s ImportPrefix<'def>) -> EnclosingTypeDef<'def> {
+    fn resolve_import_prefix<'s>(
+        &self,
+        prefix: &'s ImportPrefix<'def>,
+    ) -> Option<EnclosingTypeDef<'def>> {
+        let name = prefix.name.fragment();
         match &prefix.prefix_opt {
-            None => self.root.find(prefix.name.fragment).unwrap(),
-            Some(prefix_prefix) => self.resolve_import_prefix(prefix_prefix),
+            None => self.root.find(name),
+            Some(prefix_prefix) => self
+                .resolve_import_prefix(prefix_prefix)
+                .and_then(|p| self.resolve_type_def_at(&p, name)),
         }
+        .or_else(|| {
+            self.find_external(&prefix.path())
+                .map(EnclosingTypeDef::Class)
+        })
     }
 
-    pub fn add_import<'s>(&mut self, import: &'s parse::tree::Import<'def>) {
-        let enclosing_prefix_opt = match &import.prefix_opt {
-            Some(prefix) => Some(self.resolve_import_prefix(prefix)),
-            None => None,
-        };
+    // An import that doesn't resolve refers to its external (see `analyze::resolve::external`).
+    pub fn find_external(&self, import_path: &str) -> Option<*const Class<'def>> {
+        self.root
+            .find_external(import_path)
+            .map(|class| class as *const Class<'def>)
+    }
 
-        let enclosing = match enclosing_prefix_opt {
+    pub fn add_import<'s>(&mut self, import: &'s parse::tree::Import<'def>) {
+        let name = import.name.fragment();
+        let enclosing_opt = match &import.prefix_opt {
             Some(prefix) => self
-                .resolve_type_def_at(&prefix, import.name.fragment)
-                .unwrap(),
-            None => self.root.find(import.name.fragment).unwrap(),
+                .resolve_import_prefix(prefix)
+                .and_then(|p| self.resolve_type_def_at(&p, name)),
+            None => self.root.find(name),
+        }
+        .or_else(|| {
+            self.find_external(&import.path())
+                .map(EnclosingTypeDef::Class)
+        });
+
+        // E.g. a static import of a method or an on-demand import of an unknown package.
+        let enclosing = match enclosing_opt {
+            Some(enclosing) => enclosing,
+            None => return,
         };
 
         if import.is_wildcard {
@@ -156,15 +179,12 @@ impl<'def, 'r> Scope<'def, 'r> {
     }
 
     pub fn add_param<'s>(&mut self, param: &'s Param<'def>) {
-        self.add_name(
-            param.name.fragment,
-            Name::Param(param as *const Param<'def>),
-        );
+        self.add_name(param.name, Name::Param(param as *const Param<'def>));
     }
 
     pub fn add_variable<'s>(&mut self, variable: &'s VariableDeclarator<'def>) {
         self.add_name(
-            variable.name.fragment,
+            variable.name.fragment(),
             Name::Variable(variable as *const VariableDeclarator<'def>),
         );
     }
@@ -194,6 +214,46 @@ impl<'def, 'r> Scope<'def, 'r> {
         });
     }
 
+    // The package of the code being resolved.
+    pub fn current_package_opt(&self) -> Option<*const Package<'def>> {
+        self.levels
+            .iter()
+            .rev()
+            .find_map(|level| match &level.enclosing_opt {
+                Some(EnclosingTypeDef::Package(package)) => Some(*package),
+                _ => None,
+            })
+    }
+
+    // A top-level class that isn't public is only accessible from its own package.
+    pub fn is_accessible(&self, class: &Class<'def>) -> bool {
+        if class.modifiers.contains(&Modifier::Public) {
+            return true;
+        }
+
+        let package_path = class
+            .import_path
+            .rsplit_once('.')
+            .map_or("", |(package_path, _)| package_path);
+        let current_path = self
+            .current_package_opt()
+            .map_or("", |package| unsafe { &*package }.import_path.as_str());
+        package_path == current_path
+    }
+
+    // The innermost class, e.g. the class of `this(...)` and `super(...)`.
+    pub fn enclosing_class_opt(&self) -> Option<*const Class<'def>> {
+        for level in self.levels.iter().rev() {
+            match &level.enclosing_opt {
+                Some(EnclosingTypeDef::Class(c)) => return Some(*c),
+                Some(EnclosingTypeDef::Package(_)) => return None,
+                None => (),
+            }
+        }
+
+        None
+    }
+
     pub fn resolve_methods(&self, name: &str, context: &InvocationContext) -> Vec<Method<'def>> {
         let mut methods = vec![];
         for i in 0..self.levels.len() {
@@ -256,6 +316,10 @@ impl<'def, 'r> Scope<'def, 'r> {
                 }
             }
         }
+        if let Some(class) = self.resolve_imported_class(name) {
+            return Some(ResolvedName::Class(class));
+        }
+
         self.root.find(name).map(|e| match e {
             EnclosingTypeDef::Package(p) => ResolvedName::Package(p),
             EnclosingTypeDef::Class(c) => ResolvedName::Class(c),
@@ -273,7 +337,7 @@ impl<'def, 'r> Scope<'def, 'r> {
                         Name::TypeParam(type_param) => {
                             let type_param = unsafe { &(**type_param) };
                             return Some(EnclosingType::Parameterized(ParameterizedType {
-                                name: name.to_owned(),
+                                name: type_param.name,
                                 span_opt: None,
                                 def: type_param,
                             }));
@@ -296,32 +360,35 @@ impl<'def, 'r> Scope<'def, 'r> {
             }
         }
 
-        if let Some(result) = self.resolve_type_with_specific_import(name) {
-            return Some(result);
-        }
-
-        if let Some(found) = self
-            .root
-            .find_package("java")
-            .and_then(|p| p.find_package("lang"))
-            .and_then(|p| p.find(name))
-            .map(|e| e.to_type())
-        {
-            return Some(found);
+        if let Some(class) = self.resolve_imported_class(name) {
+            return Some(EnclosingType::Class(unsafe { &*class }.to_type()));
         }
 
         self.root.find(name).map(|e| e.to_type())
     }
 
-    pub fn resolve_type_with_specific_import(&self, name: &str) -> Option<EnclosingType<'def>> {
+    // Single-type imports shadow on-demand imports, which include the implicit `java.lang.*`.
+    pub fn resolve_imported_class(&self, name: &str) -> Option<*const Class<'def>> {
         for import in &self.specific_imports {
-            let class = unsafe { &(*import.class) };
-            if &class.name == name {
-                return Some(EnclosingType::Class(class.to_type()));
+            if unsafe { &(*import.class) }.name == name {
+                return Some(import.class);
             }
         }
 
-        None
+        for import in &self.wildcard_imports {
+            // A package's wildcard imports its classes but not its subpackages.
+            if let Some(EnclosingTypeDef::Class(class)) =
+                self.resolve_type_def_at(&import.enclosing, name)
+            {
+                return Some(class);
+            }
+        }
+
+        self.root
+            .find_package("java")
+            .and_then(|p| p.find_package("lang"))
+            .and_then(|p| p.find_class(name))
+            .map(|c| c as *const Class<'def>)
     }
 
     pub fn resolve_type_at(
@@ -359,16 +426,19 @@ impl<'def, 'r> Scope<'def, 'r> {
         match current {
             EnclosingTypeDef::Package(package) => {
                 if let Some(found) = unsafe { &(**package) }.find(name) {
+                    if let EnclosingTypeDef::Class(class) = found {
+                        if !self.is_accessible(unsafe { &*class }) {
+                            return None;
+                        }
+                    }
                     return Some(found);
                 }
             }
             EnclosingTypeDef::Class(class) => {
                 let class = unsafe { &(**class) };
                 for decl in &class.decls {
-                    if let Decl::Class(subclass) = decl {
-                        if &subclass.name == name {
-                            return Some(EnclosingTypeDef::Class(subclass.deref()));
-                        }
+                    if decl.class().name == name {
+                        return Some(EnclosingTypeDef::Class(decl.class()));
                     }
                 }
             }
@@ -0,0 +1,193 @@
+use encoding_rs::{DecoderResult, Encoding, UTF_8};
+use SourceFile;
+
+#[derive(Debug, PartialEq)]
+pub struct Decoded {
+    pub source: SourceFile,
+    // The name of the encoding that was used, e.g. "UTF-16LE".
+    pub encoding: &'static str,
+    pub diagnostics: Vec<Diagnostic>,
+}
+
+// A problem found while decoding. The source is still usable.
+#[derive(Debug, PartialEq)]
+pub struct Diagnostic {
+    // The offset in the decoded content, so `SourceFile::line_col` locates it.
+    pub offset: u32,
+    // The offset in the original bytes.
+    pub byte_offset: usize,
+    pub message: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum DecodeError {
+    UnknownEncoding(String),
+}
+
+impl SourceFile {
+    // Decodes the bytes of a source file. A byte order mark decides the encoding and is stripped.
+    // Otherwise, the declared encoding (a label like javac's `-encoding`, e.g. "ISO-8859-1") is
+    // used, and UTF-8 is the default. Malformed bytes are replaced with U+FFFD and reported.
+    pub fn decode(
+        path: &str,
+        bytes: &[u8],
+        declared_opt: Option<&str>,
+    ) -> Result<Decoded, DecodeError> {
+        let declared_opt = match declared_opt {
+            Some(label) => match Encoding::for_label(label.trim().as_bytes()) {
+                Some(encoding) => Some(encoding),
+                None => return Err(DecodeError::UnknownEncoding(label.to_owned())),
+            },
+            None => None,
+        };
+
+        let mut diagnostics = vec![];
+        let (encoding, bom_len) = match Encoding::for_bom(bytes) {
+            Some((encoding, bom_len)) => {
+                if let Some(declared) = declared_opt {
+                    if declared != encoding {
+                        diagnostics.push(Diagnostic {
+                            offset: 0,
+                            byte_offset: 0,
+                            message: format!(
+                                "The byte order mark of {} overrides the declared encoding {}",
+                                encoding.name(),
+                                declared.name()
+                            ),
+                        });
+                    }
+                }
+                (encoding, bom_len)
+            }
+            None => (declared_opt.unwrap_or(UTF_8), 0),
+        };
+
+        let content = decode_with_diagnostics(encoding, bom_len, bytes, &mut diagnostics);
+
+        Ok(Decoded {
+            source: SourceFile::new(path, &content),
+            encoding: encoding.name(),
+            diagnostics,
+        })
+    }
+}
+
+fn decode_with_diagnostics(
+    encoding: &'static Encoding,
+    bom_len: usize,
+    bytes: &[u8],
+    diagnostics: &mut Vec<Diagnostic>,
+) -> String {
+    let mut decoder = encoding.new_decoder_without_bom_handling();
+    let mut content = String::with_capacity(
+        decoder
+            .max_utf8_buffer_length_without_replacement(bytes.len())
+            .unwrap_or(bytes.len()),
+    );
+    let mut read = bom_len;
+
+    loop {
+        let (result, len) =
+            decoder.decode_to_string_without_replacement(&bytes[read..], &mut content, true);
+        read += len;
+
+        match result {
+            DecoderResult::InputEmpty => break,
+            DecoderResult::OutputFull => content.reserve(bytes.len() - read + 4),
+            DecoderResult::Malformed(malformed_len, consumed_after) => {
+                let byte_offset = read - malformed_len as usize - consumed_after as usize;
+                diagnostics.push(Diagnostic {
+                    offset: content.len() as u32,
+                    byte_offset,
+                    message: format!(
+                        "Malformed {} at byte {} is replaced with U+FFFD",
+                        encoding.name(),
+                        byte_offset
+                    ),
+                });
+
+                if content.capacity() - content.len() < 3 {
+                    content.reserve(bytes.len() - read + 4);
+                }
+                content.push('\u{FFFD}');
+            }
+        }
+    }
+
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use encoding::{DecodeError, Diagnostic};
+    use parse::tree::CompilationUnitItem;
+    use {parse, SourceFile};
+
+    #[test]
+    fn test_utf8() {
+        let decoded = SourceFile::decode("Test.java", b"\xEF\xBB\xBFclass A {}", None).unwrap();
+
+        assert_eq!(decoded.encoding, "UTF-8");
+        assert_eq!(decoded.source.content, "class A {}");
+        assert!(decoded.diagnostics.is_empty());
+
+        let file = parse::apply(&decoded.source).unwrap();
+        match &file.unit.items[0] {
+            CompilationUnitItem::Class(class) => {
+                assert_eq!((class.name.line(), class.name.col()), (1, 7))
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_utf16_bom() {
+        let mut bytes = vec![0xFF, 0xFE];
+        for unit in "class A { char c = 'é'; }".encode_utf16() {
+            bytes.extend_from_slice(&unit.to_le_bytes());
+        }
+        let decoded = SourceFile::decode("Test.java", &bytes, Some("ISO-8859-1")).unwrap();
+
+        assert_eq!(decoded.encoding, "UTF-16LE");
+        assert_eq!(decoded.source.content, "class A { char c = 'é'; }");
+        assert_eq!(decoded.diagnostics.len(), 1);
+        assert!(parse::apply(&decoded.source).is_ok());
+    }
+
+    #[test]
+    fn test_declared() {
+        let decoded =
+            SourceFile::decode("Test.java", b"// caf\xE9\nclass A {}", Some("ISO-8859-1")).unwrap();
+
+        assert_eq!(decoded.encoding, "windows-1252");
+        assert_eq!(decoded.source.content, "// café\nclass A {}");
+        assert!(decoded.diagnostics.is_empty());
+
+        assert_eq!(
+            SourceFile::decode("Test.java", b"", Some("nope")),
+            Err(DecodeError::UnknownEncoding("nope".to_owned()))
+        );
+    }
+
+    #[test]
+    fn test_lossy() {
+        let decoded = SourceFile::decode("Test.java", b"// caf\xE9\nclass A {}", None).unwrap();
+
+        assert_eq!(decoded.source.content, "// caf\u{FFFD}\nclass A {}");
+        assert_eq!(
+            decoded.diagnostics,
+            vec![Diagnostic {
+                offset: 6,
+                byte_offset: 6,
+                message: "Malformed UTF-8 at byte 6 is replaced with U+FFFD".to_owned()
+            }]
+        );
+        assert_eq!(
+            decoded.source.line_col(decoded.diagnostics[0].offset),
+            (1, 7)
+        );
+
+        let file = parse::apply(&decoded.source).unwrap();
+        assert_eq!(file.unit.items.len(), 1);
+    }
+}
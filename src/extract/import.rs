@@ -47,7 +47,10 @@ fn apply_prefix<'def, 'def_ref, 'overlay_ref>(
 
 #[cfg(test)]
 mod tests {
+    use analyze::test_common::find_class;
+    use extract;
     use extract::test_common::assert_extract;
+    use extract::Definition;
 
     #[test]
     fn test() {
@@ -64,7 +67,7 @@ class Test {}
                 r#"
 package dev2;
 
-class Super {}
+public class Super {}
         "#,
             ],
             vec![
@@ -79,9 +82,80 @@ class *0:Test* {}
                 r#"
 package dev2;
 
-class *1:Super* {}
+public class *1:Super* {}
         "#,
             ],
         );
     }
+
+    #[test]
+    fn test_external() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+import com.example.Widget;
+import com.example.util.*;
+import static com.example.Widget.create;
+import static dev2.Super.helper;
+
+class Test {
+  void method() {
+    Widget widget;
+  }
+}
+        "#,
+            r#"
+package dev2;
+
+import com.example.Widget;
+import com.example.util.Tool;
+
+class Super {
+  static void helper() {
+    Widget widget;
+    Tool tool;
+  }
+}
+        "#,
+            r#"
+package com.example.util;
+
+public class Tool {}
+        "#
+        );
+
+        let widget = root.find_external("com.example.Widget").unwrap();
+        assert_eq!(widget.name, "Widget");
+        assert_eq!(root.externals.len(), 1);
+
+        // It's a class, so it extends `Object` like any other.
+        let object = find_class(&root, "java.lang.Object");
+        assert_eq!(
+            widget.extend_opt.borrow().as_ref().unwrap().def_opt,
+            Some(object as *const _)
+        );
+
+        let tool = find_class(&root, "com.example.util.Tool");
+        let mut usages = vec![];
+        for file in &files {
+            for usage in extract::apply(&file.unit).usages {
+                if usage.span.fragment() == "Widget" || usage.span.fragment() == "Tool" {
+                    usages.push((usage.span.line(), usage.def));
+                }
+            }
+        }
+        assert_eq!(
+            usages,
+            vec![
+                (3, Definition::Class(widget)),
+                (5, Definition::Class(widget)),
+                (10, Definition::Class(widget)),
+                (3, Definition::Class(widget)),
+                (4, Definition::Class(tool)),
+                (8, Definition::Class(widget)),
+                (9, Definition::Class(tool)),
+            ]
+        );
+    }
 }
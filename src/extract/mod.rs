@@ -54,7 +54,7 @@ impl<'a> Definition<'a> {
                 Some(&v.name)
             }
             Definition::TypeParam(t) => unsafe { &**t }.span_opt.as_ref(),
-            Definition::Param(p) => Some(&unsafe { &**p }.name),
+            Definition::Param(p) => unsafe { &**p }.span_opt.as_ref(),
         }
     }
 }
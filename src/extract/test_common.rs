@@ -5,7 +5,7 @@ use std::any::Any;
 use std::collections::HashMap;
 use std::iter::Zip;
 use std::ops::Deref;
-use {extract, JavaFile};
+use {extract, JavaFile, SourceFile};
 
 pub fn assert_extract(sources: Vec<&str>, expecteds: Vec<&str>) {
     let (files, root) = apply_semantics!(vec sources);
@@ -15,10 +15,10 @@ pub fn assert_extract(sources: Vec<&str>, expecteds: Vec<&str>) {
     for file in &files {
         let mut overlay = extract::apply(&file.unit);
         overlay.usages.sort_by(|a, b| {
-            if a.span.line == b.span.line {
-                a.span.col.cmp(&b.span.col)
+            if a.span.line() == b.span.line() {
+                a.span.col().cmp(&b.span.col())
             } else {
-                a.span.line.cmp(&b.span.line)
+                a.span.line().cmp(&b.span.line())
             }
         });
         usagess.push(overlay.usages);
@@ -34,9 +34,9 @@ pub fn assert_extract(sources: Vec<&str>, expecteds: Vec<&str>) {
         defss.push(defs);
     }
 
-    let mut file_rank: HashMap<*const JavaFile, usize> = HashMap::new();
+    let mut file_rank: HashMap<*const SourceFile, usize> = HashMap::new();
     for (index, file) in files.iter().enumerate() {
-        file_rank.insert(&**file, index);
+        file_rank.insert(file.source, index);
     }
 
     let mut id = 0;
@@ -46,14 +46,15 @@ pub fn assert_extract(sources: Vec<&str>, expecteds: Vec<&str>) {
             let a = a.span().unwrap();
             let b = b.span().unwrap();
 
-            if a.file == b.file {
-                if a.line == b.line {
-                    a.col.cmp(&b.col)
+            if a.file_id() == b.file_id() {
+                if a.line() == b.line() {
+                    a.col().cmp(&b.col())
                 } else {
-                    a.line.cmp(&b.line)
+                    a.line().cmp(&b.line())
                 }
             } else {
-                (*file_rank.get(&a.file).unwrap()).cmp(file_rank.get(&b.file).unwrap())
+                (*file_rank.get(&(a.file() as *const SourceFile)).unwrap())
+                    .cmp(file_rank.get(&(b.file() as *const SourceFile)).unwrap())
             }
         });
 
@@ -76,10 +77,10 @@ pub fn assert_extract(sources: Vec<&str>, expecteds: Vec<&str>) {
         let mut line = 1;
         let mut col = 1;
 
-        for c in file.content.chars() {
+        for c in file.source.content.chars() {
             if usage_index < usages.len() {
                 let current_usage = &usages[usage_index];
-                if current_usage.span.line == line && current_usage.span.col == col {
+                if current_usage.span.line() == line && current_usage.span.col() == col {
                     s.push('[');
                     match def_ids.get(&current_usage.def.ptr()) {
                         Some(id) => {
@@ -92,7 +93,7 @@ pub fn assert_extract(sources: Vec<&str>, expecteds: Vec<&str>) {
             }
             if def_index < defs.len() {
                 let current = &defs[def_index];
-                if current.span().unwrap().line == line && current.span().unwrap().col == col {
+                if current.span().unwrap().line() == line && current.span().unwrap().col() == col {
                     s.push('*');
                     match def_ids.get(&current.ptr()) {
                         Some(id) => {
@@ -115,8 +116,8 @@ pub fn assert_extract(sources: Vec<&str>, expecteds: Vec<&str>) {
 
             if usage_index < usages.len() {
                 let current = &usages[usage_index];
-                if current.span.line == line
-                    && (current.span.col + current.span.fragment.len()) == col
+                if current.span.line() == line
+                    && (current.span.col() + current.span.fragment().len()) == col
                 {
                     s.push(']');
                     usage_index += 1;
@@ -125,8 +126,9 @@ pub fn assert_extract(sources: Vec<&str>, expecteds: Vec<&str>) {
 
             if def_index < defs.len() {
                 let current = &defs[def_index];
-                if current.span().unwrap().line == line
-                    && (current.span().unwrap().col + current.span().unwrap().fragment.len()) == col
+                if current.span().unwrap().line() == line
+                    && (current.span().unwrap().col() + current.span().unwrap().fragment().len())
+                        == col
                 {
                     s.push('*');
                     def_index += 1;
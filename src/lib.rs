@@ -1,30 +1,97 @@
 #![allow(unused_imports)]
 #![allow(unused_variables)]
-#![feature(thread_spawn_unchecked)]
-#![feature(maybe_uninit_extra)]
 
 extern crate core;
 extern crate crossbeam_queue;
 extern crate either;
+extern crate encoding_rs;
+extern crate globset;
 extern crate num_cpus;
 extern crate proc_macro;
+extern crate typed_arena;
+extern crate zip;
+#[cfg(feature = "serde")]
+#[macro_use]
+extern crate serde;
+#[cfg(feature = "serde")]
+extern crate serde_json;
 
 use parse::tree::CompilationUnit;
+use std::sync::atomic::{AtomicU64, Ordering};
+use tokenize::token::Token;
 
 #[cfg(test)]
 #[macro_use]
 pub mod test_common;
 
 pub mod analyze;
+pub mod encoding;
 pub mod extract;
 pub mod parse;
 pub mod semantics;
 pub mod tokenize;
 
+#[derive(Debug, PartialEq)]
+pub struct SourceFile {
+    // Unique in the process.
+    id: u64,
+    pub path: String,
+    pub content: String,
+    // The byte offset at which each line starts
+    pub line_starts: Vec<u32>,
+}
+
+impl SourceFile {
+    pub fn new(path: &str, content: &str) -> SourceFile {
+        let mut line_starts = vec![0];
+        for (index, b) in content.bytes().enumerate() {
+            if b == b'\n' {
+                line_starts.push((index + 1) as u32);
+            }
+        }
+
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+
+        SourceFile {
+            id: NEXT_ID.fetch_add(1, Ordering::Relaxed),
+            path: path.to_owned(),
+            content: content.to_owned(),
+            line_starts,
+        }
+    }
+
+    pub fn id(&self) -> u64 {
+        self.id
+    }
+
+    // 1-based line and column (in bytes) of the offset
+    pub fn line_col(&self, offset: u32) -> (usize, usize) {
+        let line = match self.line_starts.binary_search(&offset) {
+            Ok(index) => index,
+            Err(index) => index - 1,
+        };
+
+        (line + 1, (offset - self.line_starts[line]) as usize + 1)
+    }
+
+    // The byte offset of the 1-based line and column
+    pub fn offset(&self, line: usize, col: usize) -> u32 {
+        self.line_starts[line - 1] + (col - 1) as u32
+    }
+}
+
+// The parse tree of a source file. The tree borrows the source file.
 #[derive(Debug, PartialEq)]
 pub struct JavaFile<'def> {
     pub unit: CompilationUnit<'def>,
-    pub content: String,
-    pub path: String,
+    pub source: &'def SourceFile,
 }
 unsafe impl<'a> Sync for JavaFile<'a> {}
+
+impl<'def> JavaFile<'def> {
+    // The tokens of the source without comments. They aren't kept with the tree to save memory, so
+    // the source is lexed again.
+    pub fn tokens(&self) -> Vec<Token<'def>> {
+        tokenize::apply(self.source).expect("The source was parsed.")
+    }
+}
@@ -0,0 +1,644 @@
+use parse::tree::{
+    Annotated, ArrayType, AssignOperator, Assigned, Assignment, BinaryOperation, BinaryOperator,
+    Block, Boolean, Cast, Class, ClassBody, ClassBodyItem, ClassType, Constructor, EnclosingType,
+    Expr, FieldAccess, FieldAccessPrefix, FieldDeclarator, FieldDeclarators, IfElse, Int, Keyword,
+    LiteralString, Long, MarkerAnnotated, Method, MethodCall, Modifier, Name, NewObject, Null,
+    Param, PrimitiveType, PrimitiveTypeType, ReturnStmt, Statement, Ternary, This, Throw, Type,
+    TypeParam, TypeParamExtend, UnaryOperation, UnaryOperator, VariableDeclarator,
+    VariableDeclarators, Void, NATIVE_ARRAY_CLASS_NAME,
+};
+use std::cell::{Cell, RefCell};
+use tokenize::span::Span;
+use typed_arena::Arena;
+use SourceFile;
+
+// Builds nodes that don't come from a source file, e.g. for code generation. A built node can be
+// inserted into a parsed tree and printed with `parse::print`. The ids are unassigned, so call
+// `walk::assign_ids` before the semantics.
+//
+// Types, statements, and the other nodes without a name are built by the free functions. Names,
+// keywords, and literals need a span, so they are built by a `Builder`, which owns their text in
+// files whose path is `GENERATED_PATH`. The built nodes borrow the builder, so it must outlive the
+// tree they are inserted into.
+pub const GENERATED_PATH: &str = "<generated>";
+
+#[derive(Default)]
+pub struct Builder {
+    files: Arena<SourceFile>,
+}
+
+impl Builder {
+    pub fn new() -> Builder {
+        Builder::default()
+    }
+
+    // A span of its own file that contains just the text.
+    pub fn name<'a>(&'a self, text: &str) -> Span<'a> {
+        let file = self.files.alloc(SourceFile::new(GENERATED_PATH, text));
+        Span::new(file, 0, text.len())
+    }
+
+    pub fn keyword<'a>(&'a self, text: &str) -> Modifier<'a> {
+        Modifier::Keyword(Keyword {
+            name: self.name(text),
+        })
+    }
+
+    pub fn name_expr<'a>(&'a self, text: &str) -> Expr<'a> {
+        Expr::Name(Name {
+            name: self.name(text),
+            resolved_opt: Cell::new(None),
+        })
+    }
+
+    // A negative value is a negation, like in the source.
+    pub fn int<'a>(&'a self, value: i32) -> Expr<'a> {
+        let literal = Expr::Int(Int {
+            value: self.name(&(value as i64).abs().to_string()),
+        });
+        if value < 0 {
+            self.unary(UnaryOperator::Minus, literal)
+        } else {
+            literal
+        }
+    }
+
+    pub fn long<'a>(&'a self, value: i64) -> Expr<'a> {
+        let literal = Expr::Long(Long {
+            value: self.name(&format!("{}L", (value as i128).abs())),
+        });
+        if value < 0 {
+            self.unary(UnaryOperator::Minus, literal)
+        } else {
+            literal
+        }
+    }
+
+    // The value is escaped and quoted.
+    pub fn string<'a>(&'a self, value: &str) -> Expr<'a> {
+        Expr::String(LiteralString {
+            value: self.name(&format!("\"{}\"", escape(value))),
+        })
+    }
+
+    pub fn boolean<'a>(&'a self, value: bool) -> Expr<'a> {
+        Expr::Boolean(Boolean {
+            value: self.name(if value { "true" } else { "false" }),
+        })
+    }
+
+    pub fn null<'a>(&'a self) -> Expr<'a> {
+        Expr::Null(Null {
+            value: self.name("null"),
+        })
+    }
+
+    pub fn this<'a>(&'a self) -> Expr<'a> {
+        Expr::This(This {
+            tpe_opt: None,
+            span: self.name("this"),
+        })
+    }
+
+    pub fn call<'a>(
+        &'a self,
+        prefix_opt: Option<Expr<'a>>,
+        method: &str,
+        args: Vec<Expr<'a>>,
+    ) -> Expr<'a> {
+        Expr::MethodCall(MethodCall {
+            prefix_opt: prefix_opt.map(Box::new),
+            name: self.name(method),
+            type_args_opt: None,
+            args,
+            def_opt: RefCell::new(None),
+        })
+    }
+
+    pub fn field<'a>(&'a self, prefix: Expr<'a>, field: &str) -> Expr<'a> {
+        Expr::FieldAccess(self.field_access(prefix, field))
+    }
+
+    fn field_access<'a>(&'a self, prefix: Expr<'a>, field: &str) -> FieldAccess<'a> {
+        FieldAccess {
+            prefix: RefCell::new(Box::new(FieldAccessPrefix::Expr(prefix))),
+            name: self.name(field),
+            def_opt: RefCell::new(None),
+        }
+    }
+
+    pub fn binary<'a>(&'a self, left: Expr<'a>, kind: BinaryOperator, right: Expr<'a>) -> Expr<'a> {
+        Expr::BinaryOperation(BinaryOperation {
+            left: Box::new(left),
+            operator: self.name(kind.text()),
+            kind,
+            right: Box::new(right),
+        })
+    }
+
+    pub fn unary<'a>(&'a self, kind: UnaryOperator, expr: Expr<'a>) -> Expr<'a> {
+        Expr::UnaryOperation(UnaryOperation {
+            expr: Box::new(expr),
+            operator: self.name(kind.text()),
+            kind,
+            is_post: kind == UnaryOperator::PostIncrement || kind == UnaryOperator::PostDecrement,
+        })
+    }
+
+    // The target is a name, a field access, or an array access.
+    pub fn assign<'a>(
+        &'a self,
+        target: Expr<'a>,
+        kind: AssignOperator,
+        expr: Expr<'a>,
+    ) -> Expr<'a> {
+        let assigned = match target {
+            Expr::Name(name) => Assigned::Name(name),
+            Expr::FieldAccess(field) => Assigned::Field(field),
+            Expr::ArrayAccess(access) => Assigned::ArrayAccess(access),
+            _ => panic!("{:?} can't be assigned", target),
+        };
+        Expr::Assignment(Assignment {
+            assigned: Box::new(assigned),
+            operator: self.name(kind.text()),
+            kind,
+            expr: Box::new(expr),
+        })
+    }
+
+    pub fn var<'a>(
+        &'a self,
+        tpe: Type<'a>,
+        variable: &str,
+        expr_opt: Option<Expr<'a>>,
+    ) -> Statement<'a> {
+        Statement::VariableDeclarators(VariableDeclarators {
+            modifiers: vec![],
+            declarators: vec![VariableDeclarator {
+                tpe: RefCell::new(tpe),
+                name: self.name(variable),
+                expr_opt,
+                id: Default::default(),
+            }],
+        })
+    }
+
+    pub fn param<'a>(&'a self, tpe: Type<'a>, param: &str) -> Param<'a> {
+        Param {
+            modifiers: vec![],
+            tpe,
+            is_varargs: false,
+            name: self.name(param),
+        }
+    }
+
+    pub fn type_param<'a>(&'a self, param: &str, extends: Vec<ClassType<'a>>) -> TypeParam<'a> {
+        TypeParam {
+            name: self.name(param),
+            extends: extends.into_iter().map(TypeParamExtend::Class).collect(),
+            id: Default::default(),
+            def_opt: RefCell::new(None),
+        }
+    }
+}
+
+pub fn marker<'a>(qualified: &'a str) -> Modifier<'a> {
+    Modifier::Annotated(Annotated::Marker(MarkerAnnotated {
+        class: class_type(qualified),
+    }))
+}
+
+// A dotted name, e.g. "java.util.List", is a chain of prefixes like the parser produces.
+pub fn class_type<'a>(qualified: &'a str) -> ClassType<'a> {
+    let mut tpe: Option<ClassType<'a>> = None;
+    for segment in qualified.split('.') {
+        tpe = Some(ClassType {
+            prefix_opt: tpe.map(|prefix| Box::new(EnclosingType::Class(prefix))),
+            name: segment,
+            span_opt: None,
+            type_args_opt: None,
+            def_opt: None,
+        });
+    }
+    tpe.unwrap()
+}
+
+// An empty `args` is the diamond, e.g. `ArrayList<>`.
+pub fn generic<'a>(qualified: &'a str, args: Vec<Type<'a>>) -> ClassType<'a> {
+    let mut tpe = class_type(qualified);
+    tpe.type_args_opt = Some(args.into_iter().map(Type::to_type_arg).collect());
+    tpe
+}
+
+pub fn tpe<'a>(qualified: &'a str) -> Type<'a> {
+    Type::Class(class_type(qualified))
+}
+
+pub fn primitive<'a>(tpe: PrimitiveTypeType) -> Type<'a> {
+    Type::Primitive(PrimitiveType {
+        span_opt: None,
+        tpe,
+    })
+}
+
+pub fn array<'a>(element: Type<'a>) -> Type<'a> {
+    Type::Array(ArrayType {
+        size_opt: None,
+        underlying: ClassType {
+            prefix_opt: None,
+            name: NATIVE_ARRAY_CLASS_NAME,
+            span_opt: None,
+            type_args_opt: Some(vec![element.clone().to_type_arg()]),
+            def_opt: None,
+        },
+        tpe: Box::new(element),
+    })
+}
+
+pub fn void<'a>() -> Type<'a> {
+    Type::Void(Void { span_opt: None })
+}
+
+// Escapes the value for a string literal.
+pub fn escape(value: &str) -> String {
+    let mut literal = String::new();
+    for c in value.chars() {
+        match c {
+            '"' => literal.push_str("\\\""),
+            '\\' => literal.push_str("\\\\"),
+            '\n' => literal.push_str("\\n"),
+            '\r' => literal.push_str("\\r"),
+            '\t' => literal.push_str("\\t"),
+            '\u{8}' => literal.push_str("\\b"),
+            '\u{c}' => literal.push_str("\\f"),
+            c if c.is_control() => literal.push_str(&format!("\\u{:04x}", c as u32)),
+            c => literal.push(c),
+        }
+    }
+    literal
+}
+
+pub fn new_object<'a>(tpe: ClassType<'a>, args: Vec<Expr<'a>>) -> Expr<'a> {
+    Expr::NewObject(NewObject {
+        prefix_opt: None,
+        tpe,
+        constructor_type_args_opt: None,
+        args,
+        body_opt: None,
+        def_opt: RefCell::new(None),
+        anonymous_class_opt: None,
+        id: Default::default(),
+    })
+}
+
+pub fn cast<'a>(tpe: Type<'a>, expr: Expr<'a>) -> Expr<'a> {
+    Expr::Cast(Cast {
+        tpes: vec![tpe],
+        expr: Box::new(expr),
+    })
+}
+
+pub fn ternary<'a>(cond: Expr<'a>, true_expr: Expr<'a>, false_expr: Expr<'a>) -> Expr<'a> {
+    Expr::Ternary(Ternary {
+        cond: Box::new(cond),
+        true_expr: Box::new(true_expr),
+        false_expr: Box::new(false_expr),
+    })
+}
+
+pub fn block<'a>(stmts: Vec<Statement<'a>>) -> Block<'a> {
+    Block {
+        stmts,
+        span_opt: None,
+        is_skipped: false,
+        return_type: Type::UnknownType,
+    }
+}
+
+pub fn expr_stmt(expr: Expr) -> Statement {
+    Statement::Expr(expr)
+}
+
+pub fn ret(expr_opt: Option<Expr>) -> Statement {
+    Statement::Return(ReturnStmt { expr_opt })
+}
+
+pub fn throw(expr: Expr) -> Statement {
+    Statement::Throw(Throw { expr })
+}
+
+pub fn if_else<'a>(
+    cond: Expr<'a>,
+    then: Vec<Statement<'a>>,
+    else_opt: Option<Vec<Statement<'a>>>,
+) -> Statement<'a> {
+    Statement::IfElse(IfElse {
+        cond,
+        block: block(then),
+        else_block_opt: else_opt.map(block),
+    })
+}
+
+pub struct ClassBuilder<'a> {
+    class: Class<'a>,
+}
+
+impl<'a> ClassBuilder<'a> {
+    pub fn new(builder: &'a Builder, class: &str) -> ClassBuilder<'a> {
+        ClassBuilder {
+            class: Class {
+                modifiers: vec![],
+                name: builder.name(class),
+                type_params: vec![],
+                extend_opt: None,
+                implements: vec![],
+                body: ClassBody { items: vec![] },
+                span_opt: None,
+                def_opt: RefCell::new(None),
+                id: Default::default(),
+            },
+        }
+    }
+
+    pub fn modifier(mut self, modifier: Modifier<'a>) -> ClassBuilder<'a> {
+        self.class.modifiers.push(modifier);
+        self
+    }
+
+    pub fn type_param(mut self, type_param: TypeParam<'a>) -> ClassBuilder<'a> {
+        self.class.type_params.push(type_param);
+        self
+    }
+
+    pub fn extends(mut self, tpe: ClassType<'a>) -> ClassBuilder<'a> {
+        self.class.extend_opt = Some(tpe);
+        self
+    }
+
+    pub fn implements(mut self, tpe: ClassType<'a>) -> ClassBuilder<'a> {
+        self.class.implements.push(tpe);
+        self
+    }
+
+    pub fn item(mut self, item: ClassBodyItem<'a>) -> ClassBuilder<'a> {
+        self.class.body.items.push(item);
+        self
+    }
+
+    pub fn build(self) -> Class<'a> {
+        self.class
+    }
+}
+
+// A method without a body is abstract.
+pub struct MethodBuilder<'a> {
+    method: Method<'a>,
+}
+
+impl<'a> MethodBuilder<'a> {
+    pub fn new(builder: &'a Builder, method: &str) -> MethodBuilder<'a> {
+        MethodBuilder {
+            method: Method {
+                modifiers: vec![],
+                type_params: vec![],
+                return_type: void(),
+                name: builder.name(method),
+                params: vec![],
+                throws: vec![],
+                block_opt: None,
+                span_opt: None,
+                def_opt: RefCell::new(None),
+                id: Default::default(),
+            },
+        }
+    }
+
+    pub fn modifier(mut self, modifier: Modifier<'a>) -> MethodBuilder<'a> {
+        self.method.modifiers.push(modifier);
+        self
+    }
+
+    pub fn type_param(mut self, type_param: TypeParam<'a>) -> MethodBuilder<'a> {
+        self.method.type_params.push(type_param);
+        self
+    }
+
+    pub fn returns(mut self, tpe: Type<'a>) -> MethodBuilder<'a> {
+        self.method.return_type = tpe;
+        self
+    }
+
+    pub fn param(mut self, param: Param<'a>) -> MethodBuilder<'a> {
+        self.method.params.push(param);
+        self
+    }
+
+    pub fn throws(mut self, tpe: ClassType<'a>) -> MethodBuilder<'a> {
+        self.method.throws.push(tpe);
+        self
+    }
+
+    pub fn body(mut self, stmts: Vec<Statement<'a>>) -> MethodBuilder<'a> {
+        self.method.block_opt = Some(block(stmts));
+        self
+    }
+
+    pub fn build(self) -> Method<'a> {
+        self.method
+    }
+}
+
+pub struct ConstructorBuilder<'a> {
+    constructor: Constructor<'a>,
+}
+
+impl<'a> ConstructorBuilder<'a> {
+    pub fn new(builder: &'a Builder, class: &str) -> ConstructorBuilder<'a> {
+        ConstructorBuilder {
+            constructor: Constructor {
+                modifiers: vec![],
+                type_params: vec![],
+                name: builder.name(class),
+                params: vec![],
+                throws: vec![],
+                block: block(vec![]),
+                span_opt: None,
+                def_opt: RefCell::new(None),
+                id: Default::default(),
+            },
+        }
+    }
+
+    pub fn modifier(mut self, modifier: Modifier<'a>) -> ConstructorBuilder<'a> {
+        self.constructor.modifiers.push(modifier);
+        self
+    }
+
+    pub fn param(mut self, param: Param<'a>) -> ConstructorBuilder<'a> {
+        self.constructor.params.push(param);
+        self
+    }
+
+    pub fn throws(mut self, tpe: ClassType<'a>) -> ConstructorBuilder<'a> {
+        self.constructor.throws.push(tpe);
+        self
+    }
+
+    pub fn body(mut self, stmts: Vec<Statement<'a>>) -> ConstructorBuilder<'a> {
+        self.constructor.block = block(stmts);
+        self
+    }
+
+    pub fn build(self) -> Constructor<'a> {
+        self.constructor
+    }
+}
+
+pub struct FieldBuilder<'a> {
+    field: FieldDeclarators<'a>,
+}
+
+impl<'a> FieldBuilder<'a> {
+    pub fn new(builder: &'a Builder, tpe: Type<'a>, field: &str) -> FieldBuilder<'a> {
+        FieldBuilder {
+            field: FieldDeclarators {
+                modifiers: vec![],
+                declarators: vec![FieldDeclarator {
+                    tpe: RefCell::new(tpe),
+                    name: builder.name(field),
+                    expr_opt: None,
+                    id: Default::default(),
+                    def_opt: RefCell::new(None),
+                }],
+                span_opt: None,
+            },
+        }
+    }
+
+    pub fn modifier(mut self, modifier: Modifier<'a>) -> FieldBuilder<'a> {
+        self.field.modifiers.push(modifier);
+        self
+    }
+
+    pub fn init(mut self, expr: Expr<'a>) -> FieldBuilder<'a> {
+        self.field.declarators[0].expr_opt = Some(expr);
+        self
+    }
+
+    pub fn build(self) -> FieldDeclarators<'a> {
+        self.field
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::builder::{
+        class_type, generic, if_else, marker, primitive, ret, throw, tpe, Builder, ClassBuilder,
+        ConstructorBuilder, FieldBuilder, MethodBuilder,
+    };
+    use parse::tree::{
+        AssignOperator, BinaryOperator, ClassBodyItem, CompilationUnitItem, PrimitiveTypeType,
+        UnaryOperator,
+    };
+    use parse::{print, walk};
+    use {parse, SourceFile};
+
+    #[test]
+    fn test_build() {
+        let b = Builder::new();
+        let int_type = primitive(PrimitiveTypeType::Int);
+        let class = ClassBuilder::new(&b, "Counter")
+            .modifier(b.keyword("public"))
+            .implements(generic("java.lang.Comparable", vec![tpe("Counter")]))
+            .item(ClassBodyItem::FieldDeclarators(
+                FieldBuilder::new(&b, int_type.clone(), "count")
+                    .modifier(b.keyword("private"))
+                    .init(b.int(-1))
+                    .build(),
+            ))
+            .item(ClassBodyItem::Constructor(
+                ConstructorBuilder::new(&b, "Counter")
+                    .param(b.param(int_type.clone(), "count"))
+                    .body(vec![parse::builder::expr_stmt(b.assign(
+                        b.field(b.this(), "count"),
+                        AssignOperator::Assign,
+                        b.name_expr("count"),
+                    ))])
+                    .build(),
+            ))
+            .item(ClassBodyItem::Method(
+                MethodBuilder::new(&b, "compareTo")
+                    .modifier(marker("Override"))
+                    .modifier(b.keyword("public"))
+                    .returns(int_type.clone())
+                    .param(b.param(tpe("Counter"), "other"))
+                    .throws(class_type("java.lang.IllegalStateException"))
+                    .body(vec![
+                        b.var(
+                            int_type,
+                            "diff",
+                            Some(b.binary(
+                                b.binary(b.name_expr("count"), BinaryOperator::Subtract, b.int(1)),
+                                BinaryOperator::Multiply,
+                                b.field(b.name_expr("other"), "count"),
+                            )),
+                        ),
+                        if_else(
+                            b.binary(b.name_expr("diff"), BinaryOperator::Less, b.int(0)),
+                            vec![throw(b.call(
+                                None,
+                                "error",
+                                vec![b.string("negative \"diff\"\n")],
+                            ))],
+                            None,
+                        ),
+                        ret(Some(b.unary(
+                            UnaryOperator::Minus,
+                            b.unary(UnaryOperator::Minus, b.name_expr("diff")),
+                        ))),
+                    ])
+                    .build(),
+            ))
+            .build();
+
+        assert_eq!(
+            print::class_body_item(&ClassBodyItem::Class(class)),
+            r#"public class Counter implements java.lang.Comparable<Counter> {
+    private int count = -1;
+
+    Counter(int count) {
+        this.count = count;
+    }
+
+    @Override public int compareTo(Counter other) throws java.lang.IllegalStateException {
+        int diff = (count - 1) * other.count;
+        if (diff < 0) {
+            throw error("negative \"diff\"\n");
+        }
+        return - -diff;
+    }
+}"#
+        );
+    }
+
+    #[test]
+    fn test_insert() {
+        let source = SourceFile::new("Test.java", "class Test {\n  void a() { b(); }\n}");
+        let b = Builder::new();
+        let mut file = parse::apply(&source).unwrap();
+
+        if let CompilationUnitItem::Class(class) = &mut file.unit.items[0] {
+            let mut method = MethodBuilder::new(&b, "c").body(vec![ret(None)]).build();
+            walk::assign_ids(&mut method, &mut parse::id_gen::IdGen::new("Test.java"));
+            class.body.items.push(ClassBodyItem::Method(method));
+        }
+
+        let printed = print::unit(&file.unit);
+        assert_eq!(
+            printed,
+            "class Test {\n    void a() {\n        b();\n    }\n\n    void c() {\n        return;\n    }\n}\n"
+        );
+        let reparsed_source = SourceFile::new("Test.java", &printed);
+        assert!(parse::apply(&reparsed_source).is_ok());
+    }
+}
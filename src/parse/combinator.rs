@@ -13,7 +13,7 @@ pub fn any_symbol<'def: 'r, 'r>(
         }
 
         if let Token::Symbol(span) = &input[0] {
-            if span.fragment.len() == 1 && s.contains(span.fragment) {
+            if span.fragment().len() == 1 && s.contains(span.fragment()) {
                 return Ok((&input[1..], *span));
             }
         }
@@ -30,7 +30,7 @@ pub fn symbol<'def: 'r, 'r>(
         }
 
         if let Token::Symbol(span) = input[0] {
-            if span.fragment.len() == 1 && span.fragment.char_at(0) == c {
+            if span.fragment().len() == 1 && span.fragment().char_at(0) == c {
                 return Ok((&input[1..], span));
             }
         }
@@ -49,27 +49,13 @@ pub fn symbol2<'def: 'r, 'r>(
 
         if let Token::Symbol(first) = &input[0] {
             if let Token::Symbol(second) = &input[1] {
-                if first.fragment.len() == 1
-                    && second.fragment.len() == 1
-                    && first.fragment.char_at(0) == a
-                    && second.fragment.char_at(0) == b
-                    && first.line == second.line
-                    && first.col + 1 == second.col
+                if first.len() == 1
+                    && second.len() == 1
+                    && first.fragment().char_at(0) == a
+                    && second.fragment().char_at(0) == b
+                    && first.end == second.start
                 {
-                    return Ok((
-                        &input[2..],
-                        Span {
-                            line: first.line,
-                            col: first.col,
-                            fragment: unsafe {
-                                std::str::from_utf8_unchecked(slice::from_raw_parts(
-                                    first.fragment.as_ptr(),
-                                    first.fragment.len() + second.fragment.len(),
-                                ))
-                            },
-                            file: first.file,
-                        },
-                    ));
+                    return Ok((&input[2..], first.to(second)));
                 }
             }
         }
@@ -91,33 +77,16 @@ pub fn symbol3<'def: 'r, 'r>(
         if let Token::Symbol(first) = input[0] {
             if let Token::Symbol(second) = input[1] {
                 if let Token::Symbol(third) = input[2] {
-                    if first.fragment.len() == 1
-                        && second.fragment.len() == 1
-                        && third.fragment.len() == 1
-                        && first.fragment.char_at(0) == a
-                        && second.fragment.char_at(0) == b
-                        && third.fragment.char_at(0) == c
-                        && first.line == second.line
-                        && first.line == third.line
-                        && first.col + 1 == second.col
-                        && first.col + 2 == third.col
+                    if first.len() == 1
+                        && second.len() == 1
+                        && third.len() == 1
+                        && first.fragment().char_at(0) == a
+                        && second.fragment().char_at(0) == b
+                        && third.fragment().char_at(0) == c
+                        && first.end == second.start
+                        && second.end == third.start
                     {
-                        return Ok((
-                            &input[3..],
-                            Span {
-                                line: first.line,
-                                col: first.col,
-                                fragment: unsafe {
-                                    std::str::from_utf8_unchecked(slice::from_raw_parts(
-                                        first.fragment.as_ptr(),
-                                        first.fragment.len()
-                                            + second.fragment.len()
-                                            + third.fragment.len(),
-                                    ))
-                                },
-                                file: first.file,
-                            },
-                        ));
+                        return Ok((&input[3..], first.to(&third)));
                     }
                 }
             }
@@ -142,38 +111,19 @@ pub fn symbol4<'def: 'r, 'r>(
             if let Token::Symbol(second) = input[1] {
                 if let Token::Symbol(third) = input[2] {
                     if let Token::Symbol(fourth) = input[3] {
-                        if first.fragment.len() == 1
-                            && second.fragment.len() == 1
-                            && third.fragment.len() == 1
-                            && fourth.fragment.len() == 1
-                            && first.fragment.char_at(0) == a
-                            && second.fragment.char_at(0) == b
-                            && third.fragment.char_at(0) == c
-                            && fourth.fragment.char_at(0) == d
-                            && first.line == second.line
-                            && first.line == third.line
-                            && first.line == fourth.line
-                            && first.col + 1 == second.col
-                            && first.col + 2 == third.col
-                            && first.col + 3 == fourth.col
+                        if first.len() == 1
+                            && second.len() == 1
+                            && third.len() == 1
+                            && fourth.len() == 1
+                            && first.fragment().char_at(0) == a
+                            && second.fragment().char_at(0) == b
+                            && third.fragment().char_at(0) == c
+                            && fourth.fragment().char_at(0) == d
+                            && first.end == second.start
+                            && second.end == third.start
+                            && third.end == fourth.start
                         {
-                            return Ok((
-                                &input[4..],
-                                Span {
-                                    line: first.line,
-                                    col: first.col,
-                                    fragment: unsafe {
-                                        std::str::from_utf8_unchecked(slice::from_raw_parts(
-                                            first.fragment.as_ptr(),
-                                            first.fragment.len()
-                                                + second.fragment.len()
-                                                + third.fragment.len()
-                                                + fourth.fragment.len(),
-                                        ))
-                                    },
-                                    file: first.file,
-                                },
-                            ));
+                            return Ok((&input[4..], first.to(&fourth)));
                         }
                     }
                 }
@@ -193,7 +143,7 @@ pub fn keyword<'def: 'r, 'r>(
         }
 
         if let Token::Keyword(span) = &input[0] {
-            if span.fragment == s {
+            if span.fragment() == s {
                 return Ok((&input[1..], *span));
             }
         }
@@ -249,10 +199,7 @@ where
     move |original: Tokens<'def, 'r>| {
         let (input, result) = item(original)?;
 
-        if input.len() > 0
-            && result.line == input[0].span().line
-            && result.col + result.fragment.len() == input[0].span().col
-        {
+        if !input.is_empty() && result.end == input[0].span().start {
             if let Ok((_, followed)) = followed(input) {
                 return Err(original);
             }
@@ -354,3 +301,10 @@ where
         }
     }
 }
+
+// The span of the tokens consumed from `original` when `rest` is left. At least one token must
+// have been consumed.
+pub fn consumed<'def: 'r, 'r>(original: Tokens<'def, 'r>, rest: Tokens<'def, 'r>) -> Span<'def> {
+    let last = original[original.len() - rest.len() - 1];
+    original[0].span().to(&last.span())
+}
@@ -1,27 +1,41 @@
-use parse::combinator::{many1, opt};
+use parse::combinator::{consumed, many1, opt};
 use parse::def::{annotation, class, enum_def, imports, interface, modifiers, package};
-use parse::id_gen::IdGen;
 use parse::tree::{CompilationUnit, CompilationUnitItem};
+use parse::Context;
 use parse::{ParseResult, Tokens};
 use tokenize::token::Token;
 
 pub fn parse_item<'def, 'r>(
     original: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, CompilationUnitItem<'def>> {
-    let (input, modifiers) = modifiers::parse(original, id_gen)?;
+    if let Some(reused) = context.reusable.take_item(original) {
+        return Ok(reused);
+    }
+
+    let (input, mut item) = parse_declaration(original, context)?;
+    item.set_span(consumed(original, input));
+
+    Ok((input, item))
+}
+
+fn parse_declaration<'def, 'r>(
+    original: Tokens<'def, 'r>,
+    context: &mut Context<'def>,
+) -> ParseResult<'def, 'r, CompilationUnitItem<'def>> {
+    let (input, modifiers) = modifiers::parse(original, context)?;
 
     if let Ok((input, _)) = class::parse_prefix(input) {
-        let (input, class) = class::parse_tail(input, modifiers, id_gen)?;
+        let (input, class) = class::parse_tail(input, modifiers, context)?;
         Ok((input, CompilationUnitItem::Class(class)))
     } else if let Ok((input, _)) = interface::parse_prefix(input) {
-        let (input, interface) = interface::parse_tail(input, modifiers, id_gen)?;
+        let (input, interface) = interface::parse_tail(input, modifiers, context)?;
         Ok((input, CompilationUnitItem::Interface(interface)))
     } else if let Ok((input, _)) = enum_def::parse_prefix(input) {
-        let (input, enum_def) = enum_def::parse_tail(input, modifiers, id_gen)?;
+        let (input, enum_def) = enum_def::parse_tail(input, modifiers, context)?;
         Ok((input, CompilationUnitItem::Enum(enum_def)))
     } else if let Ok((input, _)) = annotation::parse_prefix(input) {
-        let (input, annotation) = annotation::parse_tail(input, modifiers, id_gen)?;
+        let (input, annotation) = annotation::parse_tail(input, modifiers, context)?;
         Ok((input, CompilationUnitItem::Annotation(annotation)))
     } else {
         Err(input)
@@ -30,13 +44,13 @@ pub fn parse_item<'def, 'r>(
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, CompilationUnit<'def>> {
-    let (input, package_opt) = opt(|i| package::parse(i, id_gen))(input)?;
+    let (input, package_opt) = opt(|i| package::parse(i, context))(input)?;
 
     let (input, imports) = imports::parse(input)?;
 
-    let (input, items) = many1(|i| parse_item(i, id_gen))(input)?;
+    let (input, items) = many1(|i| parse_item(i, context))(input)?;
 
     Ok((
         input,
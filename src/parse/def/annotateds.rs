@@ -1,20 +1,20 @@
 use parse::combinator::{identifier, many0, opt, separated_list, separated_nonempty_list, symbol};
-use parse::id_gen::IdGen;
 use parse::tpe::class;
 use parse::tree::{
     Annotated, AnnotatedParam, AnnotatedValue, AnnotatedValueArray, ClassType, EnclosingType,
     MarkerAnnotated, NormalAnnotated, SingleAnnotated,
 };
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
-use std::cell::Cell;
+use std::cell::{Cell, RefCell};
 use tokenize::span::Span;
 
 fn parse_array_value<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotatedValueArray<'def>> {
     let (input, _) = symbol('{')(input)?;
-    let (input, items) = separated_list(symbol(','), |i| parse_value(i, id_gen))(input)?;
+    let (input, items) = separated_list(symbol(','), |i| parse_value(i, context))(input)?;
     let (input, _) = opt(symbol(','))(input)?;
     let (input, _) = symbol('}')(input)?;
 
@@ -23,27 +23,34 @@ fn parse_array_value<'def, 'r>(
 
 fn parse_value<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotatedValue<'def>> {
-    if let Ok((input, annotated)) = parse_annotated(input, id_gen) {
+    if let Ok((input, annotated)) = parse_annotated(input, context) {
         Ok((input, AnnotatedValue::Annotated(Box::new(annotated))))
-    } else if let Ok((input, array)) = parse_array_value(input, id_gen) {
+    } else if let Ok((input, array)) = parse_array_value(input, context) {
         Ok((input, AnnotatedValue::Array(array)))
     } else {
-        let (input, expr) = expr::parse(input, id_gen)?;
+        let (input, expr) = expr::parse(input, context)?;
         Ok((input, AnnotatedValue::Expr(expr)))
     }
 }
 
 fn parse_param<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotatedParam<'def>> {
     let (input, name) = identifier(input)?;
     let (input, _) = symbol('=')(input)?;
-    let (input, value) = parse_value(input, id_gen)?;
+    let (input, value) = parse_value(input, context)?;
 
-    Ok((input, AnnotatedParam { name, value }))
+    Ok((
+        input,
+        AnnotatedParam {
+            name,
+            value,
+            def_opt: RefCell::new(None),
+        },
+    ))
 }
 
 fn parse_class<'def, 'r>(
@@ -56,7 +63,7 @@ fn parse_class<'def, 'r>(
         parse_class(
             Some(ClassType {
                 prefix_opt: prefix_opt.map(|c| Box::new(EnclosingType::Class(c))),
-                name: name.fragment.to_owned(),
+                name: name.fragment(),
                 span_opt: Some(name),
                 type_args_opt: None,
                 def_opt: None,
@@ -68,7 +75,7 @@ fn parse_class<'def, 'r>(
             input,
             ClassType {
                 prefix_opt: prefix_opt.map(|c| Box::new(EnclosingType::Class(c))),
-                name: name.fragment.to_owned(),
+                name: name.fragment(),
                 span_opt: Some(name),
                 type_args_opt: None,
                 def_opt: None,
@@ -79,7 +86,7 @@ fn parse_class<'def, 'r>(
 
 pub fn parse_annotated<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Annotated<'def>> {
     let (input, _) = symbol('@')(input)?;
     let (input, class) = parse_class(None, input)?;
@@ -94,25 +101,32 @@ pub fn parse_annotated<'def, 'r>(
                 }),
             ));
         } else if let Ok((input, params)) =
-            separated_nonempty_list(symbol(','), |i| parse_param(i, id_gen))(input)
+            separated_nonempty_list(symbol(','), |i| parse_param(i, context))(input)
         {
             let (input, _) = symbol(')')(input)?;
             return Ok((input, Annotated::Normal(NormalAnnotated { class, params })));
         }
 
-        let (input, value) = parse_value(input, id_gen)?;
+        let (input, value) = parse_value(input, context)?;
         let (input, _) = symbol(')')(input)?;
-        return Ok((input, Annotated::Single(SingleAnnotated { class, value })));
+        Ok((
+            input,
+            Annotated::Single(SingleAnnotated {
+                class,
+                value,
+                def_opt: RefCell::new(None),
+            }),
+        ))
     } else {
-        return Ok((input, Annotated::Marker(MarkerAnnotated { class })));
+        Ok((input, Annotated::Marker(MarkerAnnotated { class })))
     }
 }
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Vec<Annotated<'def>>> {
-    many0(|i| parse_annotated(i, id_gen))(input)
+    many0(|i| parse_annotated(i, context))(input)
 }
 
 //#[cfg(test)]
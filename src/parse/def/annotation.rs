@@ -1,25 +1,30 @@
 use parse::combinator::{identifier, keyword, symbol};
 use parse::def::{annotation_body, modifiers};
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::tree::{Annotation, Modifier};
+use parse::Context;
 use parse::{ParseResult, Tokens};
+use std::cell::RefCell;
 use tokenize::span::Span;
 
 pub fn parse_tail<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Annotation<'def>> {
     let (input, name) = identifier(input)?;
 
-    let (input, body) = annotation_body::parse(input, id_gen)?;
+    let (input, body) = annotation_body::parse(input, context)?;
 
     Ok((
         input,
         Annotation {
             modifiers,
+            span_opt: None,
             name,
             body,
+            def_opt: RefCell::new(None),
+            id: Id::default(),
         },
     ))
 }
@@ -2,44 +2,44 @@ use parse::combinator::{identifier, many0, symbol};
 use parse::def::{
     annotation, annotation_param, class, enum_def, field_declarators, interface, modifiers,
 };
-use parse::id_gen::IdGen;
 use parse::tree::{AnnotationBody, AnnotationBodyItem, Modifier, Type};
+use parse::Context;
 use parse::{tpe, ParseResult, Tokens};
 use tokenize::span::Span;
 
 fn parse_class<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationBodyItem<'def>> {
-    let (input, class) = class::parse_tail(input, modifiers, id_gen)?;
+    let (input, class) = class::parse_tail(input, modifiers, context)?;
     Ok((input, AnnotationBodyItem::Class(class)))
 }
 
 fn parse_interface<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationBodyItem<'def>> {
-    let (input, interface) = interface::parse_tail(input, modifiers, id_gen)?;
+    let (input, interface) = interface::parse_tail(input, modifiers, context)?;
     Ok((input, AnnotationBodyItem::Interface(interface)))
 }
 
 fn parse_annotation<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationBodyItem<'def>> {
-    let (input, annotation) = annotation::parse_tail(input, modifiers, id_gen)?;
+    let (input, annotation) = annotation::parse_tail(input, modifiers, context)?;
     Ok((input, AnnotationBodyItem::Annotation(annotation)))
 }
 
 fn parse_enum<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationBodyItem<'def>> {
-    let (input, enum_def) = enum_def::parse_tail(input, modifiers, id_gen)?;
+    let (input, enum_def) = enum_def::parse_tail(input, modifiers, context)?;
     Ok((input, AnnotationBodyItem::Enum(enum_def)))
 }
 
@@ -47,9 +47,9 @@ fn parse_field_declarators<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
     tpe: Type<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationBodyItem<'def>> {
-    let (input, field_declarators) = field_declarators::parse(input, modifiers, tpe, id_gen)?;
+    let (input, field_declarators) = field_declarators::parse(input, modifiers, tpe, context)?;
     Ok((
         input,
         AnnotationBodyItem::FieldDeclarators(field_declarators),
@@ -61,60 +61,60 @@ fn parse_param<'def, 'r>(
     modifiers: Vec<Modifier<'def>>,
     tpe: Type<'def>,
     name: Span<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationBodyItem<'def>> {
-    let (input, param) = annotation_param::parse(input, modifiers, tpe, name, id_gen)?;
+    let (input, param) = annotation_param::parse(input, modifiers, tpe, name, context)?;
     Ok((input, AnnotationBodyItem::Param(param)))
 }
 
 fn parse_param_or_field_declarators<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationBodyItem<'def>> {
     let (input_before_name, tpe) = tpe::parse(input)?;
     let (input, name) = identifier(input_before_name)?;
 
     if let Ok(_) = symbol('(')(input) {
-        parse_param(input, modifiers, tpe, name, id_gen)
+        parse_param(input, modifiers, tpe, name, context)
     } else {
-        parse_field_declarators(input_before_name, modifiers, tpe, id_gen)
+        parse_field_declarators(input_before_name, modifiers, tpe, context)
     }
 }
 
 pub fn parse_item<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationBodyItem<'def>> {
     let (input, _) = many0(symbol(';'))(input)?;
-    let (input, modifiers) = modifiers::parse(input, id_gen)?;
+    let (input, modifiers) = modifiers::parse(input, context)?;
 
     if let Ok((input, _)) = enum_def::parse_prefix(input) {
-        parse_enum(input, modifiers, id_gen)
+        parse_enum(input, modifiers, context)
     } else if let Ok((input, _)) = class::parse_prefix(input) {
-        parse_class(input, modifiers, id_gen)
+        parse_class(input, modifiers, context)
     } else if let Ok((input, _)) = interface::parse_prefix(input) {
-        parse_interface(input, modifiers, id_gen)
+        parse_interface(input, modifiers, context)
     } else if let Ok((input, _)) = annotation::parse_prefix(input) {
-        parse_annotation(input, modifiers, id_gen)
+        parse_annotation(input, modifiers, context)
     } else {
-        parse_param_or_field_declarators(input, modifiers, id_gen)
+        parse_param_or_field_declarators(input, modifiers, context)
     }
 }
 
 pub fn parse_items<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Vec<AnnotationBodyItem<'def>>> {
-    many0(|i| parse_item(i, id_gen))(input)
+    many0(|i| parse_item(i, context))(input)
 }
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationBody<'def>> {
     let (input, _) = symbol('{')(input)?;
-    let (input, items) = parse_items(input, id_gen)?;
+    let (input, items) = parse_items(input, context)?;
     let (input, _) = many0(symbol(';'))(input)?;
     let (input, _) = symbol('}')(input)?;
 
@@ -1,17 +1,19 @@
 use parse::combinator::{keyword, symbol};
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::tpe::array;
 use parse::tree::{AnnotationParam, Expr, Modifier, Type};
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
+use std::cell::RefCell;
 use tokenize::span::Span;
 
 fn parse_default<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Option<Expr<'def>>> {
     match keyword("default")(input) {
         Ok((input, _)) => {
-            let (input, default) = expr::parse(input, id_gen)?;
+            let (input, default) = expr::parse(input, context)?;
             Ok((input, Some(default)))
         }
         Err(_) => Ok((input, None)),
@@ -23,13 +25,13 @@ pub fn parse<'def, 'r>(
     modifiers: Vec<Modifier<'def>>,
     tpe: Type<'def>,
     name: Span<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, AnnotationParam<'def>> {
     let (input, _) = symbol('(')(input)?;
     let (input, _) = symbol(')')(input)?;
 
     let (input, tpe) = array::parse_tail(input, tpe)?;
-    let (input, default_opt) = parse_default(input, id_gen)?;
+    let (input, default_opt) = parse_default(input, context)?;
 
     let (input, _) = symbol(';')(input)?;
 
@@ -40,6 +42,8 @@ pub fn parse<'def, 'r>(
             tpe,
             name,
             default_opt,
+            def_opt: RefCell::new(None),
+            id: Id::default(),
         },
     ))
 }
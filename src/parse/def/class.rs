@@ -1,8 +1,9 @@
 use parse::combinator::{identifier, keyword, opt, separated_nonempty_list, symbol};
 use parse::def::{class_body, type_params};
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::tpe::class;
 use parse::tree::{Class, ClassBody, ClassType, Modifier};
+use parse::Context;
 use parse::{ParseResult, Tokens};
 use std::cell::RefCell;
 use tokenize::span::Span;
@@ -32,27 +33,28 @@ fn parse_extend<'def, 'r>(
 pub fn parse_tail<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Class<'def>> {
     let (input, name) = identifier(input)?;
-    let (input, type_params) = type_params::parse(input, id_gen)?;
+    let (input, type_params) = type_params::parse(input, context)?;
     let (input, extend_opt) = parse_extend(input)?;
     let (input, implements) = parse_implements(input)?;
 
-    let (input, body) = class_body::parse(input, id_gen)?;
+    let (input, body) = class_body::parse(input, context)?;
     let (input, _) = opt(symbol(';'))(input)?;
 
     Ok((
         input,
         Class {
             modifiers,
+            span_opt: None,
             name,
             type_params,
             extend_opt,
             implements,
             body,
             def_opt: RefCell::new(None),
-            id: id_gen.get_next("class", name.fragment),
+            id: Id::default(),
         },
     ))
 }
@@ -1,47 +1,47 @@
-use parse::combinator::{identifier, many0, symbol};
+use parse::combinator::{consumed, identifier, many0, symbol};
 use parse::def::{
     annotation, class, constructor, enum_def, field_declarators, interface, method, modifiers,
     type_params,
 };
-use parse::id_gen::IdGen;
 use parse::statement::block;
 use parse::tree::{ClassBody, ClassBodyItem, Modifier, Type, TypeParam};
+use parse::Context;
 use parse::{tpe, ParseResult, Tokens};
 use tokenize::span::Span;
 
 fn parse_class<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, class) = class::parse_tail(input, modifiers, id_gen)?;
+    let (input, class) = class::parse_tail(input, modifiers, context)?;
     Ok((input, ClassBodyItem::Class(class)))
 }
 
 fn parse_interface<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, interface) = interface::parse_tail(input, modifiers, id_gen)?;
+    let (input, interface) = interface::parse_tail(input, modifiers, context)?;
     Ok((input, ClassBodyItem::Interface(interface)))
 }
 
 fn parse_annotation<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, annotation) = annotation::parse_tail(input, modifiers, id_gen)?;
+    let (input, annotation) = annotation::parse_tail(input, modifiers, context)?;
     Ok((input, ClassBodyItem::Annotation(annotation)))
 }
 
 fn parse_enum<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, enum_def) = enum_def::parse_tail(input, modifiers, id_gen)?;
+    let (input, enum_def) = enum_def::parse_tail(input, modifiers, context)?;
     Ok((input, ClassBodyItem::Enum(enum_def)))
 }
 
@@ -51,9 +51,9 @@ fn parse_method<'def, 'r>(
     type_params: Vec<TypeParam<'def>>,
     tpe: Type<'def>,
     name: Span<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, method) = method::parse(input, modifiers, type_params, tpe, name, id_gen)?;
+    let (input, method) = method::parse(input, modifiers, type_params, tpe, name, context)?;
     Ok((input, ClassBodyItem::Method(method)))
 }
 
@@ -62,9 +62,9 @@ fn parse_constructor<'def, 'r>(
     modifiers: Vec<Modifier<'def>>,
     type_params: Vec<TypeParam<'def>>,
     name: Span<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, constructor) = constructor::parse(input, modifiers, type_params, name, id_gen)?;
+    let (input, constructor) = constructor::parse(input, modifiers, type_params, name, context)?;
     Ok((input, ClassBodyItem::Constructor(constructor)))
 }
 
@@ -72,30 +72,30 @@ fn parse_field_declarators<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
     tpe: Type<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, field_declarators) = field_declarators::parse(input, modifiers, tpe, id_gen)?;
+    let (input, field_declarators) = field_declarators::parse(input, modifiers, tpe, context)?;
     Ok((input, ClassBodyItem::FieldDeclarators(field_declarators)))
 }
 
 fn parse_static_block<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, block) = block::parse_block(input, id_gen)?;
+    let (input, block) = block::parse_body(input, context)?;
     Ok((input, ClassBodyItem::StaticInitializer(block)))
 }
 
 fn parse_method_constructor_or_field<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, type_params) = type_params::parse(input, id_gen)?;
+    let (input, type_params) = type_params::parse(input, context)?;
 
     if let Ok((input, ident)) = identifier(input) {
         if let Ok(_) = symbol('(')(input) {
-            return parse_constructor(input, modifiers, type_params, ident, id_gen);
+            return parse_constructor(input, modifiers, type_params, ident, context);
         }
     }
 
@@ -103,47 +103,62 @@ fn parse_method_constructor_or_field<'def, 'r>(
     let (input, name) = identifier(input_before_name)?;
 
     if let Ok(_) = symbol('(')(input) {
-        parse_method(input, modifiers, type_params, tpe, name, id_gen)
+        parse_method(input, modifiers, type_params, tpe, name, context)
     } else {
-        parse_field_declarators(input_before_name, modifiers, tpe, id_gen)
+        parse_field_declarators(input_before_name, modifiers, tpe, context)
     }
 }
 
 pub fn parse_item<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
-    let (input, _) = many0(symbol(';'))(input)?;
-    let (input, modifiers) = modifiers::parse(input, id_gen)?;
+    let (original, _) = many0(symbol(';'))(input)?;
+
+    if let Some(reused) = context.reusable.take_member(original) {
+        return Ok(reused);
+    }
+
+    let (input, mut item) = parse_member(original, context)?;
+    item.set_span(consumed(original, input));
+
+    Ok((input, item))
+}
+
+fn parse_member<'def, 'r>(
+    input: Tokens<'def, 'r>,
+    context: &mut Context<'def>,
+) -> ParseResult<'def, 'r, ClassBodyItem<'def>> {
+    let (input, modifiers) = modifiers::parse(input, context)?;
 
     if let Ok((input, _)) = class::parse_prefix(input) {
-        parse_class(input, modifiers, id_gen)
+        parse_class(input, modifiers, context)
     } else if let Ok((input, _)) = enum_def::parse_prefix(input) {
-        parse_enum(input, modifiers, id_gen)
+        parse_enum(input, modifiers, context)
     } else if let Ok((input, _)) = interface::parse_prefix(input) {
-        parse_interface(input, modifiers, id_gen)
+        parse_interface(input, modifiers, context)
     } else if let Ok((input, _)) = annotation::parse_prefix(input) {
-        parse_annotation(input, modifiers, id_gen)
+        parse_annotation(input, modifiers, context)
     } else if let Ok(_) = symbol('{')(input) {
-        parse_static_block(input, id_gen)
+        parse_static_block(input, context)
     } else {
-        parse_method_constructor_or_field(input, modifiers, id_gen)
+        parse_method_constructor_or_field(input, modifiers, context)
     }
 }
 
 pub fn parse_items<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Vec<ClassBodyItem<'def>>> {
-    many0(|input| parse_item(input, id_gen))(input)
+    many0(|input| parse_item(input, context))(input)
 }
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ClassBody<'def>> {
     let (input, _) = symbol('{')(input)?;
-    let (input, items) = parse_items(input, id_gen)?;
+    let (input, items) = parse_items(input, context)?;
     let (input, _) = many0(symbol(';'))(input)?;
     let (input, _) = symbol('}')(input)?;
 
@@ -1,10 +1,12 @@
 use parse::combinator::{opt, separated_list, symbol};
 use parse::def::method::parse_throws;
 use parse::def::param;
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::statement::block;
 use parse::tree::{Constructor, Modifier, TypeParam};
+use parse::Context;
 use parse::{ParseResult, Tokens};
+use std::cell::RefCell;
 use tokenize::span::Span;
 
 pub fn parse<'def, 'r>(
@@ -12,25 +14,28 @@ pub fn parse<'def, 'r>(
     modifiers: Vec<Modifier<'def>>,
     type_params: Vec<TypeParam<'def>>,
     name: Span<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Constructor<'def>> {
     let (input, _) = symbol('(')(input)?;
-    let (input, params) = separated_list(symbol(','), |i| param::parse(i, id_gen))(input)?;
+    let (input, params) = separated_list(symbol(','), |i| param::parse(i, context))(input)?;
     let (input, _) = symbol(')')(input)?;
     let (input, throws) = parse_throws(input)?;
 
-    let (input, block) = block::parse_block(input, id_gen)?;
+    let (input, block) = block::parse_body(input, context)?;
     let (input, _) = opt(symbol(';'))(input)?;
 
     Ok((
         input,
         Constructor {
             modifiers,
+            span_opt: None,
             type_params,
             name,
             params,
             throws,
             block,
+            def_opt: RefCell::new(None),
+            id: Id::default(),
         },
     ))
 }
@@ -1,20 +1,21 @@
 use parse::combinator::{identifier, opt};
 use parse::def::{annotateds, class_body};
 use parse::expr::atom::method_call;
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::tree::EnumConstant;
+use parse::Context;
 use parse::{ParseResult, Tokens};
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, EnumConstant<'def>> {
-    let (input, annotateds) = annotateds::parse(input, id_gen)?;
+    let (input, annotateds) = annotateds::parse(input, context)?;
     let (input, name) = identifier(input)?;
 
-    let (input, args_opt) = opt(|i| method_call::parse_args(i, id_gen))(input)?;
+    let (input, args_opt) = opt(|i| method_call::parse_args(i, context))(input)?;
 
-    let (input, body_opt) = opt(|i| class_body::parse(i, id_gen))(input)?;
+    let (input, body_opt) = opt(|i| class_body::parse(i, context))(input)?;
 
     Ok((
         input,
@@ -23,6 +24,8 @@ pub fn parse<'def, 'r>(
             name,
             args_opt,
             body_opt,
+            id: Id::default(),
+            body_id: Id::default(),
         },
     ))
 }
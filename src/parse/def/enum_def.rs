@@ -1,14 +1,16 @@
 use parse::combinator::{identifier, keyword, opt, separated_list, symbol};
 use parse::def::{class, class_body, enum_constant, modifiers};
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::tree::{ClassBody, Enum, Modifier};
+use parse::Context;
 use parse::{ParseResult, Tokens};
+use std::cell::RefCell;
 use tokenize::span::Span;
 
 pub fn parse_tail<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Enum<'def>> {
     let (input, name) = identifier(input)?;
 
@@ -17,13 +19,13 @@ pub fn parse_tail<'def, 'r>(
     let (input, _) = symbol('{')(input)?;
 
     let (input, constants) =
-        separated_list(symbol(','), |i| enum_constant::parse(i, id_gen))(input)?;
+        separated_list(symbol(','), |i| enum_constant::parse(i, context))(input)?;
 
     let (input, _) = opt(symbol(','))(input)?;
 
     let (input, body_opt) = match symbol(';')(input) {
         Ok((input, _)) => {
-            let (input, items) = class_body::parse_items(input, id_gen)?;
+            let (input, items) = class_body::parse_items(input, context)?;
             (input, Some(ClassBody { items }))
         }
         Err(_) => (input, None),
@@ -36,10 +38,13 @@ pub fn parse_tail<'def, 'r>(
         input,
         Enum {
             modifiers,
+            span_opt: None,
             name,
             implements,
             constants,
             body_opt,
+            def_opt: RefCell::new(None),
+            id: Id::default(),
         },
     ))
 }
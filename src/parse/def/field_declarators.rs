@@ -1,7 +1,8 @@
 use parse::combinator::{identifier, separated_nonempty_list, symbol};
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::statement::variable_declarators;
 use parse::tree::{FieldDeclarator, FieldDeclarators, Modifier, Type};
+use parse::Context;
 use parse::{expr, tpe, ParseResult, Tokens};
 use std::cell::RefCell;
 
@@ -9,10 +10,10 @@ pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
     tpe: Type<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, FieldDeclarators<'def>> {
     let (input, declarators) =
-        separated_nonempty_list(symbol(','), |i| parse_single(i, tpe.clone(), id_gen))(input)?;
+        separated_nonempty_list(symbol(','), |i| parse_single(i, tpe.clone(), context))(input)?;
 
     let (input, _) = symbol(';')(input)?;
 
@@ -20,22 +21,23 @@ pub fn parse<'def, 'r>(
         input,
         FieldDeclarators {
             modifiers,
+            span_opt: None,
             declarators,
         },
     ))
 }
 
-fn parse_single<'def: 'r, 'r, 'id_gen_ref>(
+fn parse_single<'def: 'r, 'r, 'context_ref>(
     input: Tokens<'def, 'r>,
     tpe: Type<'def>,
-    id_gen: &'id_gen_ref mut IdGen,
+    context: &'context_ref mut Context<'def>,
 ) -> ParseResult<'def, 'r, FieldDeclarator<'def>> {
     let (input, name) = identifier(input)?;
     let (input, tpe) = tpe::array::parse_tail(input, tpe)?;
 
     let (input, expr_opt) = match symbol('=')(input) {
         Ok((input, _)) => {
-            let (input, expr) = expr::parse(input, id_gen)?;
+            let (input, expr) = expr::parse(input, context)?;
             (input, Some(expr))
         }
         Err(_) => (input, None),
@@ -47,7 +49,7 @@ fn parse_single<'def: 'r, 'r, 'id_gen_ref>(
             tpe: RefCell::new(tpe),
             name,
             expr_opt,
-            id: id_gen.get_next("var", name.fragment),
+            id: Id::default(),
             def_opt: RefCell::new(None),
         },
     ))
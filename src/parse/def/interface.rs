@@ -1,9 +1,11 @@
 use parse::combinator::{identifier, keyword, opt, separated_nonempty_list, symbol};
 use parse::def::{class_body, type_params};
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::tpe::class;
 use parse::tree::{ClassType, Interface, Modifier};
+use parse::Context;
 use parse::{ParseResult, Tokens};
+use std::cell::RefCell;
 use tokenize::span::Span;
 
 fn parse_extends<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, Vec<ClassType<'def>>> {
@@ -18,24 +20,27 @@ fn parse_extends<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, Vec
 pub fn parse_tail<'def, 'r>(
     input: Tokens<'def, 'r>,
     modifiers: Vec<Modifier<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Interface<'def>> {
     let (input, name) = identifier(input)?;
-    let (input, type_params) = type_params::parse(input, id_gen)?;
+    let (input, type_params) = type_params::parse(input, context)?;
 
     let (input, extends) = parse_extends(input)?;
 
-    let (input, body) = class_body::parse(input, id_gen)?;
+    let (input, body) = class_body::parse(input, context)?;
     let (input, _) = opt(symbol(';'))(input)?;
 
     Ok((
         input,
         Interface {
             modifiers,
+            span_opt: None,
             name,
             type_params,
             extends,
             body,
+            def_opt: RefCell::new(None),
+            id: Id::default(),
         },
     ))
 }
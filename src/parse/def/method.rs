@@ -1,9 +1,10 @@
 use parse::combinator::{keyword, opt, separated_list, separated_nonempty_list, symbol};
 use parse::def::param;
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::statement::block;
 use parse::tpe::array;
 use parse::tree::{ClassType, Method, Modifier, Type, TypeParam};
+use parse::Context;
 use parse::{tpe, ParseResult, Tokens};
 use std::cell::RefCell;
 use tokenize::span::Span;
@@ -24,10 +25,10 @@ pub fn parse<'def, 'r>(
     type_params: Vec<TypeParam<'def>>,
     return_type: Type<'def>,
     name: Span<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Method<'def>> {
     let (input, _) = symbol('(')(input)?;
-    let (input, params) = separated_list(symbol(','), |i| param::parse(i, id_gen))(input)?;
+    let (input, params) = separated_list(symbol(','), |i| param::parse(i, context))(input)?;
     let (input, _) = symbol(')')(input)?;
 
     let (input, return_type) = array::parse_tail(input, return_type)?;
@@ -37,7 +38,7 @@ pub fn parse<'def, 'r>(
     let (input, block_opt) = if let Ok((input, _)) = symbol(';')(input) {
         (input, None)
     } else {
-        let (input, block) = block::parse_block(input, id_gen)?;
+        let (input, block) = block::parse_body(input, context)?;
         let (input, _) = opt(symbol(';'))(input)?;
         (input, Some(block))
     };
@@ -46,6 +47,7 @@ pub fn parse<'def, 'r>(
         input,
         Method {
             modifiers,
+            span_opt: None,
             type_params,
             return_type,
             name,
@@ -53,7 +55,7 @@ pub fn parse<'def, 'r>(
             throws,
             block_opt,
             def_opt: RefCell::new(None),
-            id: id_gen.get_next("method", name.fragment),
+            id: Id::default(),
         },
     ))
 }
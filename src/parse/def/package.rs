@@ -1,14 +1,14 @@
 use parse::combinator::{identifier, keyword, separated_nonempty_list, symbol};
 use parse::def::annotateds;
-use parse::id_gen::IdGen;
 use parse::tree::Package;
+use parse::Context;
 use parse::{ParseResult, Tokens};
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Package<'def>> {
-    let (input, annotateds) = annotateds::parse(input, id_gen)?;
+    let (input, annotateds) = annotateds::parse(input, context)?;
 
     let (input, _) = keyword("package")(input)?;
 
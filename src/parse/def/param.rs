@@ -1,8 +1,8 @@
 use parse::combinator::{identifier, opt, symbol};
 use parse::def::modifiers;
-use parse::id_gen::IdGen;
 use parse::tpe::array;
 use parse::tree::Param;
+use parse::Context;
 use parse::{tpe, ParseResult, Tokens};
 use tokenize::span::Span;
 
@@ -15,9 +15,9 @@ pub fn parse_varargs<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r,
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Param<'def>> {
-    let (input, modifiers) = modifiers::parse(input, id_gen)?;
+    let (input, modifiers) = modifiers::parse(input, context)?;
     let (input, tpe) = tpe::parse(input)?;
     let (input, varargs_opt) = opt(parse_varargs)(input)?;
     let (input, name) = identifier(input)?;
@@ -1,7 +1,8 @@
 use parse::combinator::{identifier, keyword, separated_list, separated_nonempty_list, symbol};
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::tpe::class;
 use parse::tree::{ClassType, TypeParam, TypeParamExtend};
+use parse::Context;
 use parse::{ParseResult, Tokens};
 use std::cell::RefCell;
 
@@ -20,7 +21,7 @@ pub fn parse_extends<'def, 'r>(
 
 pub fn parse_type_param<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, TypeParam<'def>> {
     let (input, name) = identifier(input)?;
     let (input, extends) = parse_extends(input)?;
@@ -31,18 +32,18 @@ pub fn parse_type_param<'def, 'r>(
             name,
             extends,
             def_opt: RefCell::new(None),
-            id: id_gen.get_next("TypeParam", name.fragment),
+            id: Id::default(),
         },
     ))
 }
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Vec<TypeParam<'def>>> {
     if let Ok((input, _)) = symbol('<')(input) {
         let (input, type_params) =
-            separated_list(symbol(','), |i| parse_type_param(i, id_gen))(input)?;
+            separated_list(symbol(','), |i| parse_type_param(i, context))(input)?;
         let (input, _) = symbol('>')(input)?;
         Ok((input, type_params))
     } else {
@@ -0,0 +1,963 @@
+use parse::tree::{
+    Annotation, AnnotationBodyItem, Block, ClassBody, ClassBodyItem, CompilationUnitItem, Param,
+};
+use parse::{print, statement_ranges, token_range};
+use std::collections::HashMap;
+use std::mem;
+use std::ops::Range;
+use tokenize::span::Span;
+use tokenize::token::Token;
+use JavaFile;
+
+#[derive(Debug, PartialEq)]
+pub enum Diff {
+    Identical,
+    // Only whitespace or comments changed.
+    NoSemanticChange,
+    Changes(Vec<Change>),
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum ChangeKind {
+    Added,
+    Removed,
+    Moved,
+    Modified,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum NodeKind {
+    // The package and the imports.
+    CompilationUnit,
+    // A class, an interface, an enum, or an annotation.
+    Class,
+    Method,
+    Constructor,
+    Field,
+    Initializer,
+    Statement,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Change {
+    pub kind: ChangeKind,
+    pub node: NodeKind,
+    // E.g. `Outer.Inner.run(int,String[])[2]` for the third statement of a method. The statement
+    // index is in the new file, except for a removed statement.
+    pub path: String,
+    // The byte ranges of what changed. A modification is narrowed to the tokens that differ, and an
+    // empty range is where tokens were inserted.
+    pub old_range_opt: Option<Range<u32>>,
+    pub new_range_opt: Option<Range<u32>>,
+}
+
+// Diffs two versions of a file by their structure. Classes and members are matched by their names
+// and signatures, and statements by their tokens, so moving code around isn't a removal and an
+// addition. Comments and whitespace are ignored.
+pub fn diff(old: &JavaFile, new: &JavaFile) -> Diff {
+    let old_tokens = old.tokens();
+    let new_tokens = new.tokens();
+    if same_tokens(&old_tokens, &new_tokens) {
+        return if old.source.content == new.source.content {
+            Diff::Identical
+        } else {
+            Diff::NoSemanticChange
+        };
+    }
+
+    let old_root = build_root(Lexed {
+        file: old,
+        tokens: &old_tokens,
+    });
+    let new_root = build_root(Lexed {
+        file: new,
+        tokens: &new_tokens,
+    });
+    let mut differ = Differ {
+        changes: vec![],
+        removed: vec![],
+        added: vec![],
+    };
+    differ.compare(&old_root, &new_root);
+    differ.pair_moves();
+
+    Diff::Changes(differ.changes)
+}
+
+// A file with its tokens (see `JavaFile::tokens`).
+#[derive(Clone, Copy)]
+struct Lexed<'f, 'a: 'f> {
+    file: &'f JavaFile<'a>,
+    tokens: &'f [Token<'a>],
+}
+
+struct Node<'f, 'a: 'f> {
+    kind: NodeKind,
+    // Matches the node among its siblings. Statements have none.
+    key_opt: Option<String>,
+    path: String,
+    tokens: &'f [Token<'a>],
+    children: Vec<Node<'f, 'a>>,
+}
+
+impl<'f, 'a> Node<'f, 'a> {
+    fn range(&self) -> Range<u32> {
+        match (self.tokens.first(), self.tokens.last()) {
+            (Some(first), Some(last)) => first.span().start..last.span().end,
+            _ => 0..0,
+        }
+    }
+
+    // The tokens that aren't in the children, e.g. the signature and the braces of a method.
+    fn own_tokens(&self) -> Vec<&'f Token<'a>> {
+        let mut tokens = vec![];
+        let mut children = self.children.iter().peekable();
+
+        for token in self.tokens {
+            let start = token.span().start;
+            while children
+                .peek()
+                .is_some_and(|child| child.range().end <= start)
+            {
+                children.next();
+            }
+
+            if children
+                .peek()
+                .is_none_or(|child| start < child.range().start)
+            {
+                tokens.push(token);
+            }
+        }
+
+        tokens
+    }
+}
+
+fn build_root<'f, 'a>(file: Lexed<'f, 'a>) -> Node<'f, 'a> {
+    let mut root = Node {
+        kind: NodeKind::CompilationUnit,
+        key_opt: None,
+        path: String::new(),
+        tokens: file.tokens,
+        children: vec![],
+    };
+
+    for item in &file.file.unit.items {
+        let child = match item {
+            CompilationUnitItem::Class(class) => build_class(
+                file,
+                "",
+                "class",
+                class.name.fragment(),
+                class.span_opt,
+                Some(&class.body),
+            ),
+            CompilationUnitItem::Interface(interface) => build_class(
+                file,
+                "",
+                "interface",
+                interface.name.fragment(),
+                interface.span_opt,
+                Some(&interface.body),
+            ),
+            CompilationUnitItem::Enum(enum_def) => build_class(
+                file,
+                "",
+                "enum",
+                enum_def.name.fragment(),
+                enum_def.span_opt,
+                enum_def.body_opt.as_ref(),
+            ),
+            CompilationUnitItem::Annotation(annotation) => {
+                build_annotation(file, "", annotation.name.fragment(), annotation)
+            }
+        };
+        root.children.extend(child);
+    }
+
+    root
+}
+
+fn tokens_of<'f, 'a>(file: Lexed<'f, 'a>, span_opt: Option<Span<'a>>) -> Option<&'f [Token<'a>]> {
+    let (first, last) = token_range(file.tokens, span_opt?)?;
+    Some(&file.tokens[first..=last])
+}
+
+fn join(parent: &str, name: &str) -> String {
+    if parent.is_empty() {
+        name.to_owned()
+    } else {
+        format!("{}.{}", parent, name)
+    }
+}
+
+// The key is the kind of the declaration with its name, e.g. `interface Runnable`, so a class that
+// becomes an interface is a removal and an addition.
+fn build_class<'f, 'a>(
+    file: Lexed<'f, 'a>,
+    parent: &str,
+    keyword: &str,
+    name: &str,
+    span_opt: Option<Span<'a>>,
+    body_opt: Option<&'f ClassBody<'a>>,
+) -> Option<Node<'f, 'a>> {
+    let path = join(parent, name);
+    let mut initializers = 0;
+    let mut children = vec![];
+
+    for item in body_opt.map_or(&[][..], |body| &body.items[..]) {
+        let child = match item {
+            ClassBodyItem::Class(class) => build_class(
+                file,
+                &path,
+                "class",
+                class.name.fragment(),
+                class.span_opt,
+                Some(&class.body),
+            ),
+            ClassBodyItem::Interface(interface) => build_class(
+                file,
+                &path,
+                "interface",
+                interface.name.fragment(),
+                interface.span_opt,
+                Some(&interface.body),
+            ),
+            ClassBodyItem::Enum(enum_def) => build_class(
+                file,
+                &path,
+                "enum",
+                enum_def.name.fragment(),
+                enum_def.span_opt,
+                enum_def.body_opt.as_ref(),
+            ),
+            ClassBodyItem::Annotation(annotation) => {
+                build_annotation(file, &path, annotation.name.fragment(), annotation)
+            }
+            ClassBodyItem::Method(method) => build_member(
+                file,
+                NodeKind::Method,
+                &path,
+                signature(method.name.fragment(), &method.params),
+                method.span_opt,
+                method.block_opt.as_ref(),
+            ),
+            ClassBodyItem::Constructor(constructor) => build_member(
+                file,
+                NodeKind::Constructor,
+                &path,
+                signature(constructor.name.fragment(), &constructor.params),
+                constructor.span_opt,
+                Some(&constructor.block),
+            ),
+            ClassBodyItem::FieldDeclarators(field) => {
+                let names = field
+                    .declarators
+                    .iter()
+                    .map(|declarator| declarator.name.fragment())
+                    .collect::<Vec<_>>();
+                build_member(
+                    file,
+                    NodeKind::Field,
+                    &path,
+                    names.join(","),
+                    field.span_opt,
+                    None,
+                )
+            }
+            ClassBodyItem::StaticInitializer(block) => {
+                initializers += 1;
+                build_member(
+                    file,
+                    NodeKind::Initializer,
+                    &path,
+                    format!("<clinit>#{}", initializers - 1),
+                    block.span_opt,
+                    Some(block),
+                )
+            }
+        };
+        children.extend(child);
+    }
+
+    Some(Node {
+        kind: NodeKind::Class,
+        key_opt: Some(format!("{} {}", keyword, name)),
+        path,
+        tokens: tokens_of(file, span_opt)?,
+        children,
+    })
+}
+
+fn build_annotation<'f, 'a>(
+    file: Lexed<'f, 'a>,
+    parent: &str,
+    name: &str,
+    annotation: &'f Annotation<'a>,
+) -> Option<Node<'f, 'a>> {
+    let mut node = build_class(file, parent, "@interface", name, annotation.span_opt, None)?;
+
+    for item in &annotation.body.items {
+        let child = match item {
+            AnnotationBodyItem::Class(class) => build_class(
+                file,
+                &node.path,
+                "class",
+                class.name.fragment(),
+                class.span_opt,
+                Some(&class.body),
+            ),
+            AnnotationBodyItem::Interface(interface) => build_class(
+                file,
+                &node.path,
+                "interface",
+                interface.name.fragment(),
+                interface.span_opt,
+                Some(&interface.body),
+            ),
+            AnnotationBodyItem::Enum(enum_def) => build_class(
+                file,
+                &node.path,
+                "enum",
+                enum_def.name.fragment(),
+                enum_def.span_opt,
+                enum_def.body_opt.as_ref(),
+            ),
+            AnnotationBodyItem::Annotation(inner) => {
+                build_annotation(file, &node.path, inner.name.fragment(), inner)
+            }
+            AnnotationBodyItem::FieldDeclarators(field) => {
+                let names = field
+                    .declarators
+                    .iter()
+                    .map(|declarator| declarator.name.fragment())
+                    .collect::<Vec<_>>();
+                build_member(
+                    file,
+                    NodeKind::Field,
+                    &node.path,
+                    names.join(","),
+                    field.span_opt,
+                    None,
+                )
+            }
+            // An annotation param has no extent, so it is a part of the annotation itself.
+            AnnotationBodyItem::Param(_) => None,
+        };
+        node.children.extend(child);
+    }
+
+    Some(node)
+}
+
+// The key is the name of the member with the types of its params as they are written, e.g.
+// `run(int,java.util.List<String>,String...)`. It can contain dots, so it isn't taken from the path.
+fn build_member<'f, 'a>(
+    file: Lexed<'f, 'a>,
+    kind: NodeKind,
+    parent: &str,
+    key: String,
+    span_opt: Option<Span<'a>>,
+    block_opt: Option<&'f Block<'a>>,
+) -> Option<Node<'f, 'a>> {
+    let path = join(parent, &key);
+    let children = match block_opt {
+        Some(block) if !block.is_skipped => build_statements(file, &path, block),
+        _ => vec![],
+    };
+
+    Some(Node {
+        kind,
+        key_opt: Some(key),
+        tokens: tokens_of(file, span_opt)?,
+        path,
+        children,
+    })
+}
+
+fn build_statements<'f, 'a>(
+    file: Lexed<'f, 'a>,
+    parent: &str,
+    block: &Block<'a>,
+) -> Vec<Node<'f, 'a>> {
+    let (first, last) = match block
+        .span_opt
+        .and_then(|span| token_range(file.tokens, span))
+    {
+        Some(range) => range,
+        None => return vec![],
+    };
+
+    statement_ranges(file.tokens, first + 1, last, &file.file.source.path)
+        .into_iter()
+        .enumerate()
+        .map(|(index, range)| Node {
+            kind: NodeKind::Statement,
+            key_opt: None,
+            path: format!("{}[{}]", parent, index),
+            tokens: &file.tokens[range],
+            children: vec![],
+        })
+        .collect()
+}
+
+fn signature(name: &str, params: &[Param]) -> String {
+    let types = params
+        .iter()
+        .map(|param| {
+            let mut text = print::tpe(&param.tpe);
+            if param.is_varargs {
+                text.push_str("...");
+            }
+            text
+        })
+        .collect::<Vec<_>>();
+    format!("{}({})", name, types.join(","))
+}
+
+fn same_token(a: &Token, b: &Token) -> bool {
+    mem::discriminant(a) == mem::discriminant(b) && a.span().fragment() == b.span().fragment()
+}
+
+fn same_tokens(a: &[Token], b: &[Token]) -> bool {
+    a.len() == b.len() && a.iter().zip(b).all(|(a, b)| same_token(a, b))
+}
+
+// The ranges of the tokens that differ after dropping the common prefix and suffix.
+fn narrow(old: &[&Token], new: &[&Token], old_at: u32, new_at: u32) -> (Range<u32>, Range<u32>) {
+    let prefix = old
+        .iter()
+        .zip(new)
+        .take_while(|(a, b)| same_token(a, b))
+        .count();
+    let max_suffix = old.len().min(new.len()) - prefix;
+    let suffix = old
+        .iter()
+        .rev()
+        .zip(new.iter().rev())
+        .take(max_suffix)
+        .take_while(|(a, b)| same_token(a, b))
+        .count();
+
+    let range = |tokens: &[&Token], at: u32| {
+        if prefix < tokens.len() - suffix {
+            tokens[prefix].span().start..tokens[tokens.len() - suffix - 1].span().end
+        } else {
+            let point = match tokens.get(prefix) {
+                Some(token) => token.span().start,
+                None => tokens.last().map_or(at, |token| token.span().end),
+            };
+            point..point
+        }
+    };
+
+    (range(old, old_at), range(new, new_at))
+}
+
+struct Differ<'o, 'n, 'a: 'o, 'b: 'n> {
+    changes: Vec<Change>,
+    // The unmatched nodes, which may have moved to another parent.
+    removed: Vec<&'o Node<'o, 'a>>,
+    added: Vec<&'n Node<'n, 'b>>,
+}
+
+impl<'o, 'n, 'a, 'b> Differ<'o, 'n, 'a, 'b> {
+    fn push(
+        &mut self,
+        kind: ChangeKind,
+        node: NodeKind,
+        path: &str,
+        old_range_opt: Option<Range<u32>>,
+        new_range_opt: Option<Range<u32>>,
+    ) {
+        self.changes.push(Change {
+            kind,
+            node,
+            path: path.to_owned(),
+            old_range_opt,
+            new_range_opt,
+        });
+    }
+
+    fn compare(&mut self, old: &'o Node<'o, 'a>, new: &'n Node<'n, 'b>) {
+        if same_tokens(old.tokens, new.tokens) {
+            return;
+        }
+
+        let old_own = old.own_tokens();
+        let new_own = new.own_tokens();
+        let same_own = old_own.len() == new_own.len()
+            && old_own.iter().zip(&new_own).all(|(a, b)| same_token(a, b));
+        if !same_own {
+            let (old_range, new_range) =
+                narrow(&old_own, &new_own, old.range().start, new.range().start);
+            self.push(
+                ChangeKind::Modified,
+                new.kind,
+                &new.path,
+                Some(old_range),
+                Some(new_range),
+            );
+        }
+
+        let pairs = if new.children.iter().all(|child| child.key_opt.is_some()) {
+            self.match_by_key(&old.children, &new.children)
+        } else {
+            self.match_by_tokens(&old.children, &new.children)
+        };
+
+        // The pairs out of the longest run in the same order are the ones that moved.
+        let in_order = longest_increasing(&pairs);
+        for (index, &(old_index, new_index)) in pairs.iter().enumerate() {
+            let old_child = &old.children[old_index];
+            let new_child = &new.children[new_index];
+            if !in_order[index] {
+                self.push(
+                    ChangeKind::Moved,
+                    new_child.kind,
+                    &new_child.path,
+                    Some(old_child.range()),
+                    Some(new_child.range()),
+                );
+            }
+            self.compare(old_child, new_child);
+        }
+    }
+
+    // Pairs the children with the same key in the order they appear.
+    fn match_by_key(
+        &mut self,
+        old: &'o [Node<'o, 'a>],
+        new: &'n [Node<'n, 'b>],
+    ) -> Vec<(usize, usize)> {
+        let mut new_indices: HashMap<&str, Vec<usize>> = HashMap::new();
+        for (index, child) in new.iter().enumerate().rev() {
+            if let Some(key) = &child.key_opt {
+                new_indices.entry(key).or_default().push(index);
+            }
+        }
+
+        let mut pairs = vec![];
+        let mut is_matched = vec![false; new.len()];
+        for (old_index, child) in old.iter().enumerate() {
+            let new_index_opt = child
+                .key_opt
+                .as_ref()
+                .and_then(|key| new_indices.get_mut(key.as_str()))
+                .and_then(|indices| indices.pop());
+            match new_index_opt {
+                Some(new_index) => {
+                    is_matched[new_index] = true;
+                    pairs.push((old_index, new_index));
+                }
+                None => self.removed.push(child),
+            }
+        }
+
+        for (index, child) in new.iter().enumerate() {
+            if !is_matched[index] {
+                self.added.push(child);
+            }
+        }
+
+        pairs
+    }
+
+    // Keeps the longest common run of identical statements. Between two kept statements, the
+    // remaining ones are paired in order as modifications.
+    fn match_by_tokens(
+        &mut self,
+        old: &'o [Node<'o, 'a>],
+        new: &'n [Node<'n, 'b>],
+    ) -> Vec<(usize, usize)> {
+        let common = common_statements(old, new);
+
+        let mut pairs = vec![];
+        let mut gaps = vec![];
+        let (mut gap_i, mut gap_j) = (0, 0);
+        for &(i, j) in &common {
+            gaps.push((gap_i..i, gap_j..j));
+            pairs.push((i, j));
+            gap_i = i + 1;
+            gap_j = j + 1;
+        }
+        gaps.push((gap_i..old.len(), gap_j..new.len()));
+
+        // A statement that is identical to one in another gap has moved, so it is left to
+        // `pair_moves` instead of being paired as a modification.
+        let mut is_moved_old = vec![false; old.len()];
+        let mut is_moved_new = vec![false; new.len()];
+        for (old_gap, _) in &gaps {
+            for i in old_gap.clone() {
+                let found_opt = gaps
+                    .iter()
+                    .flat_map(|(_, new_gap)| new_gap.clone())
+                    .find(|&j| !is_moved_new[j] && same_tokens(old[i].tokens, new[j].tokens));
+                if let Some(j) = found_opt {
+                    is_moved_old[i] = true;
+                    is_moved_new[j] = true;
+                }
+            }
+        }
+
+        for (old_gap, new_gap) in gaps {
+            let old_rest = old_gap.filter(|&i| !is_moved_old[i]).collect::<Vec<_>>();
+            let new_rest = new_gap.filter(|&j| !is_moved_new[j]).collect::<Vec<_>>();
+            let paired = old_rest.len().min(new_rest.len());
+            pairs.extend(old_rest.iter().cloned().zip(new_rest.iter().cloned()));
+
+            for &i in &old_rest[paired..] {
+                self.removed.push(&old[i]);
+            }
+            for &j in &new_rest[paired..] {
+                self.added.push(&new[j]);
+            }
+        }
+        for (i, child) in old.iter().enumerate() {
+            if is_moved_old[i] {
+                self.removed.push(child);
+            }
+        }
+        for (j, child) in new.iter().enumerate() {
+            if is_moved_new[j] {
+                self.added.push(child);
+            }
+        }
+
+        pairs.sort();
+        pairs
+    }
+
+    // An unmatched node that appears elsewhere with the same tokens has moved.
+    fn pair_moves(&mut self) {
+        let removed = mem::take(&mut self.removed);
+        let mut added = mem::take(&mut self.added)
+            .into_iter()
+            .map(Some)
+            .collect::<Vec<_>>();
+
+        for old in removed {
+            let moved_opt = added.iter_mut().find(|new_opt| {
+                new_opt
+                    .is_some_and(|new| new.kind == old.kind && same_tokens(old.tokens, new.tokens))
+            });
+
+            match moved_opt.and_then(|new_opt| new_opt.take()) {
+                Some(new) => self.push(
+                    ChangeKind::Moved,
+                    new.kind,
+                    &new.path,
+                    Some(old.range()),
+                    Some(new.range()),
+                ),
+                None => self.push(
+                    ChangeKind::Removed,
+                    old.kind,
+                    &old.path,
+                    Some(old.range()),
+                    None,
+                ),
+            }
+        }
+
+        for new in added.into_iter().flatten() {
+            self.push(
+                ChangeKind::Added,
+                new.kind,
+                &new.path,
+                None,
+                Some(new.range()),
+            );
+        }
+    }
+}
+
+// The largest table of the longest common subsequence, in cells. Past it, the statements between
+// the common prefix and suffix are all unpaired.
+const MAX_LCS_CELLS: usize = 1 << 20;
+
+// The pairs of identical statements in the longest common subsequence. The common prefix and suffix
+// are dropped first, so the table only covers the statements around the edits.
+fn common_statements(old: &[Node], new: &[Node]) -> Vec<(usize, usize)> {
+    let same = |i: usize, j: usize| same_tokens(old[i].tokens, new[j].tokens);
+
+    let prefix = (0..old.len().min(new.len()))
+        .take_while(|&i| same(i, i))
+        .count();
+    let suffix = (0..(old.len().min(new.len()) - prefix))
+        .take_while(|&k| same(old.len() - 1 - k, new.len() - 1 - k))
+        .count();
+    let (old_end, new_end) = (old.len() - suffix, new.len() - suffix);
+
+    let mut common = (0..prefix).map(|i| (i, i)).collect::<Vec<_>>();
+
+    let (rows, cols) = (old_end - prefix, new_end - prefix);
+    if rows > 0 && cols > 0 && (rows + 1).saturating_mul(cols + 1) <= MAX_LCS_CELLS {
+        let mut lengths = vec![0u32; (rows + 1) * (cols + 1)];
+        let at = |i: usize, j: usize| i * (cols + 1) + j;
+        for i in (0..rows).rev() {
+            for j in (0..cols).rev() {
+                lengths[at(i, j)] = if same(prefix + i, prefix + j) {
+                    lengths[at(i + 1, j + 1)] + 1
+                } else {
+                    lengths[at(i + 1, j)].max(lengths[at(i, j + 1)])
+                };
+            }
+        }
+
+        let (mut i, mut j) = (0, 0);
+        while i < rows && j < cols {
+            if same(prefix + i, prefix + j) {
+                common.push((prefix + i, prefix + j));
+                i += 1;
+                j += 1;
+            } else if lengths[at(i + 1, j)] >= lengths[at(i, j + 1)] {
+                i += 1;
+            } else {
+                j += 1;
+            }
+        }
+    }
+
+    common.extend((0..suffix).map(|k| (old_end + k, new_end + k)));
+    common
+}
+
+// Marks the pairs, ordered by the old index, that are in the longest run ordered by the new index.
+fn longest_increasing(pairs: &[(usize, usize)]) -> Vec<bool> {
+    let mut lengths = vec![1usize; pairs.len()];
+    let mut previous = vec![None; pairs.len()];
+    for i in 0..pairs.len() {
+        for j in 0..i {
+            if pairs[j].1 < pairs[i].1 && lengths[j] + 1 > lengths[i] {
+                lengths[i] = lengths[j] + 1;
+                previous[i] = Some(j);
+            }
+        }
+    }
+
+    let mut in_order = vec![false; pairs.len()];
+    let mut current = (0..pairs.len()).max_by_key(|&i| (lengths[i], usize::MAX - i));
+    while let Some(i) = current {
+        in_order[i] = true;
+        current = previous[i];
+    }
+    in_order
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::diff::{diff, Change, ChangeKind, Diff, NodeKind};
+    use {parse, SourceFile};
+
+    fn run(old: &str, new: &str) -> Diff {
+        let old_source = SourceFile::new("Test.java", old);
+        let new_source = SourceFile::new("Test.java", new);
+        let old_file = parse::apply(&old_source).unwrap();
+        let new_file = parse::apply(&new_source).unwrap();
+        diff(&old_file, &new_file)
+    }
+
+    fn changes(old: &str, new: &str) -> Vec<(ChangeKind, NodeKind, String, String, String)> {
+        match run(old, new) {
+            Diff::Changes(changes) => changes
+                .into_iter()
+                .map(|change: Change| {
+                    let fragment = |content: &str, range_opt: Option<std::ops::Range<u32>>| {
+                        range_opt.map_or("-".to_owned(), |range| {
+                            content[(range.start as usize)..(range.end as usize)].to_owned()
+                        })
+                    };
+                    (
+                        change.kind,
+                        change.node,
+                        change.path,
+                        fragment(old, change.old_range_opt),
+                        fragment(new, change.new_range_opt),
+                    )
+                })
+                .collect(),
+            other => panic!("{:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_no_change() {
+        let code = "class A { void run() { int a = 1; } }";
+        assert_eq!(run(code, code), Diff::Identical);
+        assert_eq!(
+            run(
+                code,
+                "class A {\n  // Runs.\n  void run() {\n    int a = 1;\n  }\n}"
+            ),
+            Diff::NoSemanticChange
+        );
+    }
+
+    #[test]
+    fn test_statement() {
+        assert_eq!(
+            changes(
+                "class A { void run(int a) { a = 1; call(a + 2); return; } }",
+                "class A { void run(int a) { a = 1; call(a * 2, a); return; } }"
+            ),
+            vec![(
+                ChangeKind::Modified,
+                NodeKind::Statement,
+                "A.run(int)[1]".to_owned(),
+                "+ 2".to_owned(),
+                "* 2, a".to_owned()
+            )]
+        );
+        assert_eq!(
+            changes(
+                "class A { void run() { a(); b(); c(); } }",
+                "class A { void run() { c(); a(); b(); d(); } }"
+            ),
+            vec![
+                (
+                    ChangeKind::Moved,
+                    NodeKind::Statement,
+                    "A.run()[0]".to_owned(),
+                    "c();".to_owned(),
+                    "c();".to_owned()
+                ),
+                (
+                    ChangeKind::Added,
+                    NodeKind::Statement,
+                    "A.run()[3]".to_owned(),
+                    "-".to_owned(),
+                    "d();".to_owned()
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_members() {
+        assert_eq!(
+            changes(
+                "class A {\n  int x;\n  void run(String[] s) {}\n  A() {}\n  void stop() {}\n}",
+                "class A {\n  void run(String[] s) {}\n  A() {}\n  int x;\n  void start() {}\n}"
+            ),
+            vec![
+                (
+                    ChangeKind::Moved,
+                    NodeKind::Field,
+                    "A.x".to_owned(),
+                    "int x;".to_owned(),
+                    "int x;".to_owned()
+                ),
+                (
+                    ChangeKind::Removed,
+                    NodeKind::Method,
+                    "A.stop()".to_owned(),
+                    "void stop() {}".to_owned(),
+                    "-".to_owned()
+                ),
+                (
+                    ChangeKind::Added,
+                    NodeKind::Method,
+                    "A.start()".to_owned(),
+                    "-".to_owned(),
+                    "void start() {}".to_owned()
+                )
+            ]
+        );
+        assert_eq!(
+            changes(
+                "class A { class B { void run(int a) { a++; } } }",
+                "class A { class B { public void run(int a) { a++; } } }"
+            ),
+            vec![(
+                ChangeKind::Modified,
+                NodeKind::Method,
+                "A.B.run(int)".to_owned(),
+                "".to_owned(),
+                "public".to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_varargs() {
+        // The members are matched by their signatures, so a swap is a move of one of them and not a
+        // modification of both.
+        assert_eq!(
+            changes(
+                "class A {\n  void a(String... s) {}\n  void b(int... s) {}\n}",
+                "class A {\n  void b(int... s) {}\n  void a(String... s) {}\n}"
+            ),
+            vec![(
+                ChangeKind::Moved,
+                NodeKind::Method,
+                "A.b(int...)".to_owned(),
+                "void b(int... s) {}".to_owned(),
+                "void b(int... s) {}".to_owned()
+            )]
+        );
+    }
+
+    #[test]
+    fn test_declaration_kind() {
+        assert_eq!(
+            changes(
+                "class A {}
+class B {}",
+                "interface A {}
+class B {}"
+            ),
+            vec![
+                (
+                    ChangeKind::Removed,
+                    NodeKind::Class,
+                    "A".to_owned(),
+                    "class A {}".to_owned(),
+                    "-".to_owned()
+                ),
+                (
+                    ChangeKind::Added,
+                    NodeKind::Class,
+                    "A".to_owned(),
+                    "-".to_owned(),
+                    "interface A {}".to_owned()
+                )
+            ]
+        );
+    }
+
+    #[test]
+    fn test_full_types() {
+        // The overloads differ only in their type args and qualifiers.
+        assert_eq!(
+            changes(
+                "class A {\n  void a(List<String> s) {}\n  void a(List<Integer> s) {}\n  void b(java.util.Date d) {}\n}",
+                "class A {\n  void a(List<Integer> s) {}\n  void a(List<String> s) {}\n  void b(java.sql.Date d) {}\n}"
+            ),
+            vec![
+                (
+                    ChangeKind::Moved,
+                    NodeKind::Method,
+                    "A.a(List<Integer>)".to_owned(),
+                    "void a(List<Integer> s) {}".to_owned(),
+                    "void a(List<Integer> s) {}".to_owned()
+                ),
+                (
+                    ChangeKind::Removed,
+                    NodeKind::Method,
+                    "A.b(java.util.Date)".to_owned(),
+                    "void b(java.util.Date d) {}".to_owned(),
+                    "-".to_owned()
+                ),
+                (
+                    ChangeKind::Added,
+                    NodeKind::Method,
+                    "A.b(java.sql.Date)".to_owned(),
+                    "-".to_owned(),
+                    "void b(java.sql.Date d) {}".to_owned()
+                )
+            ]
+        );
+    }
+}
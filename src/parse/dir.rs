@@ -0,0 +1,607 @@
+use encoding::DecodeError;
+use globset::{Glob, GlobSet, GlobSetBuilder};
+use parse;
+use std::io::Read as _;
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
+use std::{fs, io, thread};
+use {JavaFile, SourceFile};
+
+pub struct DirOptions<'a> {
+    // Globs matched against the path relative to the directory, e.g. "**/*.java".
+    pub includes: Vec<String>,
+    pub excludes: Vec<String>,
+    pub threads: usize,
+    // The encoding of the files (see `SourceFile::decode`).
+    pub encoding_opt: Option<String>,
+    // Skip the bodies (see `parse::apply_signatures`).
+    pub signatures: bool,
+    // Called after each file, possibly from several threads at once.
+    pub progress_opt: Option<&'a (dyn Fn(Progress) + Sync)>,
+    // Setting it stops the parse after the files in progress.
+    pub cancel_opt: Option<&'a AtomicBool>,
+}
+
+impl<'a> DirOptions<'a> {
+    pub fn new() -> DirOptions<'a> {
+        DirOptions {
+            includes: vec!["**/*.java".to_owned()],
+            // The parser doesn't support these.
+            excludes: vec![
+                "**/package-info.java".to_owned(),
+                "**/module-info.java".to_owned(),
+            ],
+            threads: num_cpus::get(),
+            encoding_opt: None,
+            signatures: false,
+            progress_opt: None,
+            cancel_opt: None,
+        }
+    }
+}
+
+impl<'a> Default for DirOptions<'a> {
+    fn default() -> Self {
+        DirOptions::new()
+    }
+}
+
+#[derive(Debug)]
+pub struct Progress<'p> {
+    pub done: usize,
+    pub total: usize,
+    pub path: &'p Path,
+}
+
+#[derive(Debug)]
+pub enum DirError {
+    Glob(globset::Error),
+    Io(io::Error),
+    Zip(zip::result::ZipError),
+}
+
+#[derive(Debug)]
+pub struct ParsedFile<'def> {
+    pub file: JavaFile<'def>,
+    // The time spent on reading, decoding, and parsing the file.
+    pub duration: Duration,
+}
+
+#[derive(Debug)]
+pub enum FileErrorKind {
+    Io(io::Error),
+    Decode(DecodeError),
+    Parse {
+        offset: u32,
+        line: usize,
+        col: usize,
+    },
+}
+
+#[derive(Debug)]
+pub struct FileError {
+    pub path: PathBuf,
+    pub kind: FileErrorKind,
+    pub duration: Duration,
+}
+
+// The sources of a directory or an archive in the order of their paths, and the errors of the files
+// that couldn't be read. The parsed files borrow the sources (see `parse_dir`).
+pub struct ReadDir {
+    pub sources: Vec<SourceFile>,
+    pub errors: Vec<FileError>,
+    pub cancelled: bool,
+    // The time spent on reading and decoding each source.
+    durations: Vec<Duration>,
+}
+
+// The parsed files in the order of their paths, and the errors of the sources that couldn't be
+// parsed.
+pub struct ParsedDir<'a> {
+    pub files: Vec<ParsedFile<'a>>,
+    pub errors: Vec<FileError>,
+    pub cancelled: bool,
+}
+
+// Reads the matching files under `root` on `options.threads` threads.
+pub fn read_dir(root: &Path, options: &DirOptions) -> Result<ReadDir, DirError> {
+    let includes = build_glob_set(&options.includes)?;
+    let excludes = build_glob_set(&options.excludes)?;
+    let mut read = ReadFiles::default();
+    let paths = collect(root, &includes, &excludes, &mut read.errors).map_err(DirError::Io)?;
+    let reporter = Reporter::new(options, 0, paths.len() + read.errors.len());
+
+    for (index, result) in run(
+        options.threads,
+        paths.len(),
+        &|| is_cancelled(options),
+        |index| {
+            let start = Instant::now();
+            let result = read_file(&paths[index], options);
+            (result, start.elapsed())
+        },
+    ) {
+        read.push(&paths[index], result, &reporter);
+    }
+
+    Ok(read.finish(options))
+}
+
+// Reads the matching entries of a `.jar` or `.zip` archive, e.g. a `-sources.jar` or the JDK's
+// `lib/src.zip`, without extracting it. The path of an entry is `archive_entry_path`, so the
+// spans point into the archive. The globs are matched against the path of an entry without its
+// module directory (e.g. `java/lang/String.java` for `java.base/java/lang/String.java` in the
+// `src.zip` of JDK 9 and later).
+pub fn read_archive(archive: &Path, options: &DirOptions) -> Result<ReadDir, DirError> {
+    let includes = build_glob_set(&options.includes)?;
+    let excludes = build_glob_set(&options.excludes)?;
+    let file = fs::File::open(archive).map_err(DirError::Io)?;
+    let mut zip = zip::ZipArchive::new(file).map_err(DirError::Zip)?;
+
+    let mut indices = vec![];
+    for index in 0..zip.len() {
+        let entry = zip.by_index_raw(index).map_err(DirError::Zip)?;
+        let relative = strip_module(entry.name());
+        if !entry.is_dir() && includes.is_match(relative) && !excludes.is_match(relative) {
+            indices.push((entry.name().to_owned(), index));
+        }
+    }
+    indices.sort();
+    let reporter = Reporter::new(options, 0, indices.len());
+
+    // The entries are read one by one, since the archive has a single reader.
+    let mut read = ReadFiles::default();
+    for (name, index) in indices {
+        if is_cancelled(options) {
+            break;
+        }
+        let start = Instant::now();
+        let path = archive_entry_path(archive, &name);
+        let result = zip
+            .by_index(index)
+            .map_err(|e| FileErrorKind::Io(e.into()))
+            .and_then(|mut entry| {
+                let mut bytes = vec![];
+                entry.read_to_end(&mut bytes).map_err(FileErrorKind::Io)?;
+                decode(&path, &bytes, options)
+            });
+        read.push(Path::new(&path), (result, start.elapsed()), &reporter);
+    }
+
+    Ok(read.finish(options))
+}
+
+// The path of an archive entry, e.g. "jar:/lib/guava-sources.jar!/com/google/common/base/Strings.java".
+pub fn archive_entry_path(archive: &Path, entry: &str) -> String {
+    format!("jar:{}!/{}", archive.to_string_lossy(), entry)
+}
+
+// The archive and the entry of a path from `archive_entry_path`.
+pub fn split_archive_entry_path(path: &str) -> Option<(&Path, &str)> {
+    let path = path.strip_prefix("jar:")?;
+    let index = path.rfind("!/")?;
+    Some((Path::new(&path[..index]), &path[index + 2..]))
+}
+
+// Reads an entry, e.g. to open the file of a span whose path is from `archive_entry_path`.
+pub fn read_archive_entry(path: &str) -> Result<Vec<u8>, DirError> {
+    let (archive, entry) = split_archive_entry_path(path)
+        .ok_or_else(|| DirError::Io(io::Error::from(io::ErrorKind::InvalidInput)))?;
+    let file = fs::File::open(archive).map_err(DirError::Io)?;
+    let mut zip = zip::ZipArchive::new(file).map_err(DirError::Zip)?;
+    let mut entry = zip.by_name(entry).map_err(DirError::Zip)?;
+    let mut bytes = vec![];
+    entry.read_to_end(&mut bytes).map_err(DirError::Io)?;
+    Ok(bytes)
+}
+
+// Strips the module directory of a JDK 9+ `src.zip` entry. A package directory can't contain a
+// '.', but a module directory (e.g. `java.base`) does unless the module name is a single word.
+fn strip_module(entry: &str) -> &str {
+    match entry.split_once('/') {
+        Some((first, rest)) if first.contains('.') => rest,
+        _ => entry,
+    }
+}
+
+fn is_cancelled(options: &DirOptions) -> bool {
+    options
+        .cancel_opt
+        .is_some_and(|cancel| cancel.load(Ordering::Relaxed))
+}
+
+struct Reporter<'a> {
+    done: AtomicUsize,
+    total: usize,
+    progress_opt: Option<&'a (dyn Fn(Progress) + Sync)>,
+}
+
+impl<'a> Reporter<'a> {
+    fn new(options: &DirOptions<'a>, done: usize, total: usize) -> Reporter<'a> {
+        Reporter {
+            done: AtomicUsize::new(done),
+            total,
+            progress_opt: options.progress_opt,
+        }
+    }
+
+    fn report(&self, path: &Path) {
+        let done = self.done.fetch_add(1, Ordering::Relaxed) + 1;
+        if let Some(progress) = self.progress_opt {
+            progress(Progress {
+                done,
+                total: self.total,
+                path,
+            });
+        }
+    }
+}
+
+// The sources that were read, and the errors of the others.
+#[derive(Default)]
+struct ReadFiles {
+    sources: Vec<SourceFile>,
+    durations: Vec<Duration>,
+    errors: Vec<FileError>,
+}
+
+impl ReadFiles {
+    fn push(
+        &mut self,
+        path: &Path,
+        result: (Result<SourceFile, FileErrorKind>, Duration),
+        reporter: &Reporter,
+    ) {
+        match result {
+            (Ok(source), duration) => {
+                self.sources.push(source);
+                self.durations.push(duration);
+            }
+            (Err(kind), duration) => {
+                reporter.report(path);
+                self.errors.push(FileError {
+                    path: path.to_path_buf(),
+                    kind,
+                    duration,
+                });
+            }
+        }
+    }
+
+    fn finish(self, options: &DirOptions) -> ReadDir {
+        let mut errors = self.errors;
+        errors.sort_by(|a, b| a.path.cmp(&b.path));
+        ReadDir {
+            sources: self.sources,
+            errors,
+            cancelled: is_cancelled(options),
+            durations: self.durations,
+        }
+    }
+}
+
+// A tree fresh from the parser has no definitions, so its pointers (e.g. `def_opt`) are all null
+// and it can be sent to the thread that collects the results.
+struct Parsed<'a>(Result<ParsedFile<'a>, FileError>);
+
+unsafe impl<'a> Send for Parsed<'a> {}
+
+// Parses the sources on `options.threads` threads. The progress continues from the reading, so the
+// errors of the reading count as done.
+pub fn parse_dir<'a>(read: &'a ReadDir, options: &DirOptions) -> ParsedDir<'a> {
+    let reporter = Reporter::new(
+        options,
+        read.errors.len(),
+        read.sources.len() + read.errors.len(),
+    );
+
+    let mut files = vec![];
+    let mut errors = vec![];
+    for (index, result) in run(
+        options.threads,
+        read.sources.len(),
+        &|| is_cancelled(options),
+        |index| {
+            let source = &read.sources[index];
+            let start = Instant::now();
+            let result = if options.signatures {
+                parse::apply_signatures(source)
+            } else {
+                parse::apply(source)
+            };
+            let duration = start.elapsed() + read.durations[index];
+            reporter.report(Path::new(&source.path));
+
+            Parsed(match result {
+                Ok(file) => Ok(ParsedFile { file, duration }),
+                Err(span) => Err(FileError {
+                    path: PathBuf::from(&source.path),
+                    kind: FileErrorKind::Parse {
+                        offset: span.start,
+                        line: span.line(),
+                        col: span.col(),
+                    },
+                    duration,
+                }),
+            })
+        },
+    ) {
+        match result.0 {
+            Ok(file) => files.push(file),
+            Err(error) => errors.push(error),
+        }
+    }
+
+    ParsedDir {
+        files,
+        errors,
+        cancelled: is_cancelled(options),
+    }
+}
+
+fn build_glob_set(globs: &[String]) -> Result<GlobSet, DirError> {
+    let mut builder = GlobSetBuilder::new();
+    for glob in globs {
+        builder.add(Glob::new(glob).map_err(DirError::Glob)?);
+    }
+    builder.build().map_err(DirError::Glob)
+}
+
+// The matching files in the order of their paths. Only an unreadable `root` is an error. A matching
+// path whose metadata can't be read (e.g. a broken symlink) is added to `errors`.
+fn collect(
+    root: &Path,
+    includes: &GlobSet,
+    excludes: &GlobSet,
+    errors: &mut Vec<FileError>,
+) -> io::Result<Vec<PathBuf>> {
+    let mut paths = vec![];
+    let mut dirs = vec![root.to_path_buf()];
+
+    while let Some(dir) = dirs.pop() {
+        for entry in fs::read_dir(&dir)? {
+            let path = entry?.path();
+            let relative = path.strip_prefix(root).unwrap_or(&path);
+            if excludes.is_match(relative) {
+                continue;
+            }
+
+            let metadata = match fs::metadata(&path) {
+                Ok(metadata) => metadata,
+                Err(e) => {
+                    if includes.is_match(relative) {
+                        errors.push(FileError {
+                            path,
+                            kind: FileErrorKind::Io(e),
+                            duration: Duration::default(),
+                        });
+                    }
+                    continue;
+                }
+            };
+
+            // Symlinked files are read, but symlinked directories aren't entered to avoid cycles.
+            if metadata.is_dir() {
+                let is_symlink = fs::symlink_metadata(&path)
+                    .map_or(true, |metadata| metadata.file_type().is_symlink());
+                if !is_symlink {
+                    dirs.push(path);
+                }
+            } else if includes.is_match(relative) {
+                paths.push(path);
+            }
+        }
+    }
+
+    paths.sort();
+    Ok(paths)
+}
+
+fn read_file(path: &Path, options: &DirOptions) -> Result<SourceFile, FileErrorKind> {
+    let bytes = fs::read(path).map_err(FileErrorKind::Io)?;
+    decode(&path.to_string_lossy(), &bytes, options)
+}
+
+fn decode(path: &str, bytes: &[u8], options: &DirOptions) -> Result<SourceFile, FileErrorKind> {
+    let decoded = SourceFile::decode(path, bytes, options.encoding_opt.as_deref())
+        .map_err(FileErrorKind::Decode)?;
+    Ok(decoded.source)
+}
+
+// Runs `work` on the indices 0 until `len` on up to `threads` threads, and returns the results in
+// the order of the indices. The indices that aren't started before the cancellation are skipped.
+fn run<T, W, C>(threads: usize, len: usize, is_cancelled: &C, work: W) -> Vec<(usize, T)>
+where
+    T: Send,
+    W: Fn(usize) -> T + Sync,
+    C: Fn() -> bool + Sync,
+{
+    let next = AtomicUsize::new(0);
+    let mut results = thread::scope(|s| {
+        let handles = (0..threads.max(1).min(len))
+            .map(|_| {
+                s.spawn(|| {
+                    let mut results = vec![];
+                    while !is_cancelled() {
+                        let index = next.fetch_add(1, Ordering::Relaxed);
+                        if index >= len {
+                            break;
+                        }
+                        results.push((index, work(index)));
+                    }
+                    results
+                })
+            })
+            .collect::<Vec<_>>();
+
+        handles
+            .into_iter()
+            .flat_map(|handle| handle.join().unwrap())
+            .collect::<Vec<_>>()
+    });
+
+    results.sort_by_key(|(index, _)| *index);
+    results
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::dir::{
+        parse_dir, read_archive, read_archive_entry, read_dir, split_archive_entry_path,
+        DirOptions, FileErrorKind, Progress,
+    };
+    use std::fs;
+    use std::io::Write;
+    use std::path::{Path, PathBuf};
+    use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+    fn setup(name: &str) -> PathBuf {
+        let root =
+            std::env::temp_dir().join(format!("javaparser-dir-{}-{}", name, std::process::id()));
+        let _ = fs::remove_dir_all(&root);
+        fs::create_dir_all(root.join("a/generated")).unwrap();
+        fs::write(root.join("A.java"), "class A {}").unwrap();
+        fs::write(root.join("a/B.java"), "class B { void run() {} }").unwrap();
+        fs::write(root.join("a/Broken.java"), "class Broken {\n  void\n}").unwrap();
+        fs::write(root.join("a/generated/C.java"), "class C {}").unwrap();
+        fs::write(root.join("a/package-info.java"), "package a;").unwrap();
+        fs::write(root.join("a/notes.txt"), "class D {}").unwrap();
+        root
+    }
+
+    #[test]
+    fn test_parse_dir() {
+        let root = setup("parse");
+        let done = AtomicUsize::new(0);
+        let progress = |_: Progress| {
+            done.fetch_add(1, Ordering::Relaxed);
+        };
+        let mut options = DirOptions::new();
+        options.excludes.push("**/generated".to_owned());
+        options.threads = 2;
+        options.progress_opt = Some(&progress);
+
+        let read = read_dir(&root, &options).unwrap();
+        let parsed = parse_dir(&read, &options);
+
+        let names = parsed
+            .files
+            .iter()
+            .map(|file| file.file.source.path.replace(root.to_str().unwrap(), ""))
+            .collect::<Vec<_>>();
+        assert_eq!(names, vec!["/A.java", "/a/B.java"]);
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(parsed.errors[0].path, root.join("a/Broken.java"));
+        match parsed.errors[0].kind {
+            FileErrorKind::Parse { line, col, .. } => assert_eq!((line, col), (1, 1)),
+            _ => panic!(),
+        }
+        assert_eq!(done.load(Ordering::Relaxed), 3);
+        assert!(!parsed.cancelled);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_cancel() {
+        let root = setup("cancel");
+        let cancel = AtomicBool::new(true);
+        let mut options = DirOptions::new();
+        options.cancel_opt = Some(&cancel);
+
+        let read = read_dir(&root, &options).unwrap();
+        let parsed = parse_dir(&read, &options);
+
+        assert!(read.sources.is_empty());
+        assert!(read.cancelled);
+        assert!(parsed.files.is_empty());
+        assert!(parsed.cancelled);
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn test_broken_symlink() {
+        let root = setup("symlink");
+        std::os::unix::fs::symlink(root.join("Missing.java"), root.join("a/Link.java")).unwrap();
+
+        let read = read_dir(&root, &DirOptions::new()).unwrap();
+        let parsed = parse_dir(&read, &DirOptions::new());
+
+        assert_eq!(parsed.files.len(), 3);
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(read.errors.len(), 1);
+        assert_eq!(read.errors[0].path, root.join("a/Link.java"));
+        match &read.errors[0].kind {
+            FileErrorKind::Io(e) => assert_eq!(e.kind(), std::io::ErrorKind::NotFound),
+            _ => panic!(),
+        }
+
+        fs::remove_dir_all(&root).unwrap();
+    }
+
+    #[test]
+    fn test_parse_archive() {
+        // Laid out like the `src.zip` of JDK 9 and later.
+        let archive =
+            std::env::temp_dir().join(format!("javaparser-dir-archive-{}.zip", std::process::id()));
+        let mut zip = zip::ZipWriter::new(fs::File::create(&archive).unwrap());
+        for (name, content) in &[
+            (
+                "java.base/java/lang/A.java",
+                "package java.lang;\nclass A {}",
+            ),
+            (
+                "java.base/java/lang/Broken.java",
+                "class Broken {\n  void\n}",
+            ),
+            ("java.base/module-info.java", "module java.base {}"),
+            (
+                "java.base/java/lang/package-info.java",
+                "package java.lang;",
+            ),
+            (
+                "jdk.compiler/com/sun/B.java",
+                "package com.sun;\nclass B {}",
+            ),
+            ("META-INF/MANIFEST.MF", "Manifest-Version: 1.0"),
+        ] {
+            zip.start_file(*name, Default::default()).unwrap();
+            zip.write_all(content.as_bytes()).unwrap();
+        }
+        zip.finish().unwrap();
+        let mut options = DirOptions::new();
+        options.excludes.push("com/**".to_owned());
+
+        let read = read_archive(&archive, &options).unwrap();
+        let parsed = parse_dir(&read, &options);
+
+        let prefix = format!("jar:{}!/", archive.to_str().unwrap());
+        let paths = parsed
+            .files
+            .iter()
+            .map(|file| file.file.source.path.replace(&prefix, ""))
+            .collect::<Vec<_>>();
+        assert_eq!(paths, vec!["java.base/java/lang/A.java"]);
+        assert_eq!(parsed.errors.len(), 1);
+        assert_eq!(
+            parsed.errors[0].path,
+            PathBuf::from(format!("{}java.base/java/lang/Broken.java", prefix))
+        );
+
+        let path = &parsed.files[0].file.source.path;
+        assert_eq!(
+            split_archive_entry_path(path),
+            Some((archive.as_path(), "java.base/java/lang/A.java"))
+        );
+        assert_eq!(
+            read_archive_entry(path).unwrap(),
+            b"package java.lang;\nclass A {}".to_vec()
+        );
+        assert_eq!(split_archive_entry_path("/src/A.java"), None);
+
+        fs::remove_file(&archive).unwrap();
+    }
+}
@@ -1,14 +1,14 @@
 use parse::combinator::{opt, symbol};
-use parse::id_gen::IdGen;
 use parse::tree::{ArrayAccess, Expr};
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 
 pub fn parse_index<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     let (input, _) = symbol('[')(input)?;
-    let (input, index) = expr::parse(input, id_gen)?;
+    let (input, index) = expr::parse(input, context)?;
     let (input, _) = symbol(']')(input)?;
 
     Ok((input, index))
@@ -17,9 +17,9 @@ pub fn parse_index<'def, 'r>(
 pub fn parse_tail<'def, 'r>(
     input: Tokens<'def, 'r>,
     expr: Expr<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
-    let (input, index_opt) = opt(|i| parse_index(i, id_gen))(input)?;
+    let (input, index_opt) = opt(|i| parse_index(i, context))(input)?;
 
     match index_opt {
         Some(index) => parse_tail(
@@ -28,7 +28,7 @@ pub fn parse_tail<'def, 'r>(
                 expr: Box::new(expr),
                 index: Box::new(index),
             }),
-            id_gen,
+            context,
         ),
         None => Ok((input, expr)),
     }
@@ -1,15 +1,15 @@
 use parse::combinator::{opt, separated_list, symbol};
-use parse::id_gen::IdGen;
 use parse::tree::{ArrayInitializer, Expr};
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 
 pub fn parse_initializer<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, ArrayInitializer<'def>> {
     let (input, _) = symbol('{')(input)?;
 
-    let (input, items) = separated_list(symbol(','), |i| expr::parse(i, id_gen))(input)?;
+    let (input, items) = separated_list(symbol(','), |i| expr::parse(i, context))(input)?;
     let (input, _) = opt(symbol(','))(input)?;
 
     let (input, _) = symbol('}')(input)?;
@@ -19,9 +19,9 @@ pub fn parse_initializer<'def, 'r>(
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
-    let (input, init) = parse_initializer(input, id_gen)?;
+    let (input, init) = parse_initializer(input, context)?;
     Ok((input, Expr::ArrayInitializer(init)))
 }
 
@@ -2,19 +2,19 @@ use either::Either;
 use parse::combinator::{identifier, separated_list, symbol, symbol2};
 use parse::def::param;
 use parse::expr::atom::name;
-use parse::id_gen::IdGen;
 use parse::statement::block::parse_block;
 use parse::tree::{Block, Expr, Lambda, Param, Type};
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 
 fn parse_block_or_single_expr<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Either<Block<'def>, Expr<'def>>> {
-    match parse_block(input, id_gen) {
+    match parse_block(input, context) {
         Ok((input, block)) => Ok((input, Either::Left(block))),
         Err(_) => {
-            let (input, expr) = expr::parse(input, id_gen)?;
+            let (input, expr) = expr::parse(input, context)?;
             Ok((input, Either::Right(expr)))
         }
     }
@@ -22,9 +22,9 @@ fn parse_block_or_single_expr<'def, 'r>(
 
 fn parse_param_with_type_or_without_type<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Param<'def>> {
-    match param::parse(input, id_gen) {
+    match param::parse(input, context) {
         Ok(result) => Ok(result),
         Err(_) => {
             let (input, name) = identifier(input)?;
@@ -43,11 +43,11 @@ fn parse_param_with_type_or_without_type<'def, 'r>(
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     let (input, params) = if let Ok((input, _)) = symbol('(')(input) {
         let (input, params) = separated_list(symbol(','), |i| {
-            parse_param_with_type_or_without_type(i, id_gen)
+            parse_param_with_type_or_without_type(i, context)
         })(input)?;
         let (input, _) = symbol(')')(input)?;
 
@@ -68,7 +68,7 @@ pub fn parse<'def, 'r>(
 
     let (input, _) = symbol2('-', '>')(input)?;
 
-    let (input, block_or_expr) = parse_block_or_single_expr(input, id_gen)?;
+    let (input, block_or_expr) = parse_block_or_single_expr(input, context)?;
 
     let (block_opt, expr_opt) = match block_or_expr {
         Either::Left(block) => (Some(block), None),
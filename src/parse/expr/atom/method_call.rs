@@ -1,19 +1,19 @@
 use either::Either;
 use parse::combinator::{identifier, separated_list, symbol};
 use parse::expr::atom::name;
-use parse::id_gen::IdGen;
 use parse::tpe::type_args;
 use parse::tree::{Expr, Keyword, MethodCall, Name, SuperConstructorCall, TypeArg};
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 use std::cell::RefCell;
 use tokenize::span::Span;
 
 pub fn parse_args<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Vec<Expr<'def>>> {
     let (input, _) = symbol('(')(input)?;
-    let (input, args) = separated_list(symbol(','), |i| expr::parse(i, id_gen))(input)?;
+    let (input, args) = separated_list(symbol(','), |i| expr::parse(i, context))(input)?;
     let (input, _) = symbol(')')(input)?;
 
     Ok((input, args))
@@ -24,13 +24,13 @@ pub fn parse_tail<'def, 'r>(
     prefix_opt: Option<Expr<'def>>,
     keyword_or_name: Either<Keyword<'def>, Name<'def>>,
     type_args_opt: Option<Vec<TypeArg<'def>>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
-    let (input, args) = parse_args(input, id_gen)?;
+    let (input, args) = parse_args(input, context)?;
 
     match keyword_or_name {
         Either::Left(keyword) => {
-            if keyword.name.fragment == "super" {
+            if keyword.name.fragment() == "super" {
                 return Ok((
                     input,
                     Expr::SuperConstructorCall(SuperConstructorCall {
@@ -38,6 +38,7 @@ pub fn parse_tail<'def, 'r>(
                         name: keyword.name,
                         type_args_opt,
                         args,
+                        def_opt: RefCell::new(None),
                     }),
                 ));
             }
@@ -60,7 +61,7 @@ pub fn parse_tail<'def, 'r>(
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
     prefix_opt: Option<Expr<'def>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     let (input, type_args_opt) = if prefix_opt.is_some() {
         type_args::parse(input)?
@@ -70,7 +71,7 @@ pub fn parse<'def, 'r>(
 
     let (input, keyword_or_name) = name::parse(input)?;
 
-    parse_tail(input, prefix_opt, keyword_or_name, type_args_opt, id_gen)
+    parse_tail(input, prefix_opt, keyword_or_name, type_args_opt, context)
 }
 
 //#[cfg(test)]
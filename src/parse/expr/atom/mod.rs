@@ -1,8 +1,8 @@
 use either::Either;
 use parse::combinator::{identifier, symbol, symbol2};
-use parse::id_gen::IdGen;
 use parse::tpe::{primitive, type_args};
 use parse::tree::{Boolean, Expr, Keyword, MethodCall, Name, Null, Super, This, Type};
+use parse::Context;
 use parse::{tpe, ParseResult, Tokens};
 use std::cell::RefCell;
 
@@ -21,7 +21,7 @@ pub mod string;
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     if let Ok(ok) = number::parse(input) {
         Ok(ok)
@@ -29,13 +29,13 @@ pub fn parse<'def, 'r>(
         Ok(ok)
     } else if let Ok(ok) = literal_char::parse(input) {
         Ok(ok)
-    } else if let Ok(ok) = array_initializer::parse(input, id_gen) {
+    } else if let Ok(ok) = array_initializer::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = constructor_call::parse(input, id_gen) {
+    } else if let Ok(ok) = constructor_call::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = parse_prefix_keyword_or_identifier(input, id_gen) {
+    } else if let Ok(ok) = parse_prefix_keyword_or_identifier(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = parse_lambda_or_parenthesized(input, id_gen) {
+    } else if let Ok(ok) = parse_lambda_or_parenthesized(input, context) {
         Ok(ok)
     } else {
         Err(input)
@@ -44,29 +44,29 @@ pub fn parse<'def, 'r>(
 
 fn parse_lambda_or_parenthesized<'def, 'r>(
     original: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     let (input, _) = symbol('(')(original)?;
 
     if let Ok((input, _)) = symbol(')')(input) {
         if let Ok((input, _)) = symbol2('-', '>')(input) {
-            return lambda::parse(original, id_gen);
+            return lambda::parse(original, context);
         }
     }
 
     if let Ok(_) = primitive::parse(input) {
-        return lambda::parse(original, id_gen);
+        return lambda::parse(original, context);
     }
 
     let (input, ident) = match identifier(input) {
         Ok(ok) => ok,
-        Err(_) => return parenthesized::parse(original, id_gen),
+        Err(_) => return parenthesized::parse(original, context),
     };
 
     // a single unknown type param name
     if let Ok((input, _)) = symbol(')')(input) {
         if let Ok(_) = symbol2('-', '>')(input) {
-            return lambda::parse(original, id_gen);
+            return lambda::parse(original, context);
         }
     }
 
@@ -79,12 +79,12 @@ fn parse_lambda_or_parenthesized<'def, 'r>(
         }),
     )) = name::parse(input)
     {
-        return lambda::parse(original, id_gen);
+        return lambda::parse(original, context);
     }
 
     // Unknown type first param
     if let Ok((input, _)) = symbol(',')(input) {
-        return lambda::parse(original, id_gen);
+        return lambda::parse(original, context);
     }
 
     // The first param has typed with type arg
@@ -97,29 +97,29 @@ fn parse_lambda_or_parenthesized<'def, 'r>(
             }),
         )) = name::parse(input)
         {
-            return lambda::parse(original, id_gen);
+            return lambda::parse(original, context);
         }
     }
 
-    parenthesized::parse(original, id_gen)
+    parenthesized::parse(original, context)
 }
 
 fn parse_new_object_or_array<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     if let Ok((input, Some(type_args))) = type_args::parse(input) {
-        return new_object::parse_tail2(None, input, Some(type_args), id_gen);
+        return new_object::parse_tail2(None, input, Some(type_args), context);
     }
 
     let (input, tpe) = tpe::parse_no_array(input)?;
     let copied = tpe.clone();
 
-    if let Ok((input, expr)) = new_array::parse_tail(input, tpe, id_gen) {
+    if let Ok((input, expr)) = new_array::parse_tail(input, tpe, context) {
         Ok((input, expr))
     } else {
         match copied {
-            Type::Class(class) => new_object::parse_tail3(None, input, None, class, id_gen),
+            Type::Class(class) => new_object::parse_tail3(None, input, None, class, context),
             _ => Err(input),
         }
     }
@@ -127,12 +127,12 @@ fn parse_new_object_or_array<'def, 'r>(
 
 fn parse_prefix_keyword_or_identifier<'def, 'r>(
     original: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     let (input, keyword_or_name) = name::parse(original)?;
 
     match keyword_or_name {
-        Either::Left(keyword) => match keyword.name.fragment {
+        Either::Left(keyword) => match keyword.name.fragment() {
             "true" | "false" => Ok((
                 input,
                 Expr::Boolean(Boolean {
@@ -145,7 +145,7 @@ fn parse_prefix_keyword_or_identifier<'def, 'r>(
                     value: keyword.name,
                 }),
             )),
-            "new" => parse_new_object_or_array(input, id_gen),
+            "new" => parse_new_object_or_array(input, context),
             "this" => Ok((
                 input,
                 Expr::This(This {
@@ -164,8 +164,8 @@ fn parse_prefix_keyword_or_identifier<'def, 'r>(
         },
         Either::Right(name) => {
             if let Ok(_) = symbol2('-', '>')(input) {
-                lambda::parse(original, id_gen)
-            } else if let Ok((input, args)) = method_call::parse_args(input, id_gen) {
+                lambda::parse(original, context)
+            } else if let Ok((input, args)) = method_call::parse_args(input, context) {
                 Ok((
                     input,
                     Expr::MethodCall(MethodCall {
@@ -177,7 +177,7 @@ fn parse_prefix_keyword_or_identifier<'def, 'r>(
                     }),
                 ))
             } else {
-                array_access::parse_tail(input, Expr::Name(name), id_gen)
+                array_access::parse_tail(input, Expr::Name(name), context)
             }
         }
     }
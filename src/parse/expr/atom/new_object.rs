@@ -1,29 +1,31 @@
 use parse::combinator::{identifier, keyword, opt, separated_list, symbol};
 use parse::def::class_body;
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::tpe::type_args;
 use parse::tree::{ClassType, Expr, NewObject, TypeArg};
+use parse::Context;
 use parse::{expr, tpe, ParseResult, Tokens};
+use std::cell::RefCell;
 
 pub fn parse_tail<'def, 'r>(
     prefix_opt: Option<Expr<'def>>,
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     let (input, constructor_type_args_opt) = type_args::parse(input)?;
 
-    parse_tail2(prefix_opt, input, constructor_type_args_opt, id_gen)
+    parse_tail2(prefix_opt, input, constructor_type_args_opt, context)
 }
 
 pub fn parse_tail2<'def, 'r>(
     prefix_opt: Option<Expr<'def>>,
     input: Tokens<'def, 'r>,
     constructor_type_args_opt: Option<Vec<TypeArg<'def>>>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     let (input, tpe) = tpe::class::parse_no_array(input)?;
 
-    parse_tail3(prefix_opt, input, constructor_type_args_opt, tpe, id_gen)
+    parse_tail3(prefix_opt, input, constructor_type_args_opt, tpe, context)
 }
 
 pub fn parse_tail3<'def, 'r>(
@@ -31,13 +33,13 @@ pub fn parse_tail3<'def, 'r>(
     input: Tokens<'def, 'r>,
     constructor_type_args_opt: Option<Vec<TypeArg<'def>>>,
     tpe: ClassType<'def>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     let (input, _) = symbol('(')(input)?;
-    let (input, args) = separated_list(symbol(','), |i| expr::parse(i, id_gen))(input)?;
+    let (input, args) = separated_list(symbol(','), |i| expr::parse(i, context))(input)?;
     let (input, _) = symbol(')')(input)?;
 
-    let (input, body_opt) = opt(|i| class_body::parse(i, id_gen))(input)?;
+    let (input, body_opt) = opt(|i| class_body::parse(i, context))(input)?;
 
     Ok((
         input,
@@ -47,6 +49,9 @@ pub fn parse_tail3<'def, 'r>(
             constructor_type_args_opt,
             args,
             body_opt,
+            def_opt: RefCell::new(None),
+            anonymous_class_opt: None,
+            id: Id::default(),
         }),
     ))
 }
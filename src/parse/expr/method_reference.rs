@@ -1,13 +1,13 @@
 use either::Either;
 use parse::combinator::symbol2;
 use parse::expr::atom::name;
-use parse::expr::precedence_16;
-use parse::id_gen::IdGen;
+use parse::expr::primary;
 use parse::tpe::type_args;
 use parse::tree::{
     ClassType, ConstructorReference, EnclosingType, Expr, FieldAccess, FieldAccessPrefix,
     MethodReference, MethodReferencePrimary, ReferenceType,
 };
+use parse::Context;
 use parse::{ParseResult, Tokens};
 use std::cell::Cell;
 
@@ -17,7 +17,7 @@ fn convert_field_to_class<'def, 'r>(field: &'r FieldAccess<'def>) -> Result<Clas
             Expr::FieldAccess(parent) => convert_field_to_class(parent)?,
             Expr::Name(parent) => ClassType {
                 prefix_opt: None,
-                name: parent.name.fragment.to_owned(),
+                name: parent.name.fragment(),
                 span_opt: Some(parent.name),
                 type_args_opt: None,
                 def_opt: None,
@@ -29,7 +29,7 @@ fn convert_field_to_class<'def, 'r>(field: &'r FieldAccess<'def>) -> Result<Clas
 
     Ok(ClassType {
         prefix_opt: Some(Box::new(EnclosingType::Class(prefix))),
-        name: field.name.fragment.to_owned(),
+        name: field.name.fragment(),
         span_opt: Some(field.name),
         type_args_opt: None,
         def_opt: None,
@@ -40,7 +40,7 @@ pub fn convert_to_type(expr: Expr) -> Result<ClassType, ()> {
     match expr {
         Expr::Name(name) => Ok(ClassType {
             prefix_opt: None,
-            name: name.name.fragment.to_owned(),
+            name: name.name.fragment(),
             span_opt: Some(name.name),
             type_args_opt: None,
             def_opt: None,
@@ -67,7 +67,7 @@ pub fn parse_tail<'def, 'r>(
 
     match keyword_or_name {
         Either::Left(keyword) => {
-            if keyword.name.fragment == "new" {
+            if keyword.name.fragment() == "new" {
                 let ref_type = match primary {
                     MethodReferencePrimary::Array(arr) => ReferenceType::Array(arr),
                     MethodReferencePrimary::Class(class) => ReferenceType::Class(class),
@@ -104,9 +104,9 @@ pub fn parse_tail<'def, 'r>(
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
-    let (input, expr) = precedence_16::parse(input, id_gen)?;
+    let (input, expr) = primary::parse(input, context)?;
 
     if let Ok(_) = symbol2(':', ':')(input) {
         let (input, method_ref) = parse_tail(MethodReferencePrimary::Expr(Box::new(expr)), input)?;
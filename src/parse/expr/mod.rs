@@ -1,49 +1,47 @@
 use parse::combinator::symbol2;
-use parse::id_gen::IdGen;
 use parse::tree::{Expr, MethodReferencePrimary, Type};
+use parse::Context;
 use parse::{tpe, ParseResult, Tokens};
 
 pub mod atom;
-pub mod precedence_1;
-pub mod precedence_10;
-pub mod precedence_11;
-pub mod precedence_12;
-pub mod precedence_13;
-pub mod precedence_14;
-pub mod precedence_15;
-pub mod precedence_16;
-pub mod precedence_2;
-pub mod precedence_3;
-pub mod precedence_4;
-pub mod precedence_5;
-pub mod precedence_6;
-pub mod precedence_7;
-pub mod precedence_8;
-pub mod precedence_9;
+pub mod method_reference;
+pub mod postfix;
+pub mod precedence;
+pub mod primary;
+pub mod unary;
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
-    if let Ok((input, tpe)) = tpe::parse(input) {
-        if let Ok(_) = symbol2(':', ':')(input) {
-            match tpe {
-                Type::Array(arr) => {
-                    return precedence_15::parse_tail(MethodReferencePrimary::Array(arr), input)
-                }
-                Type::Class(class) => {
-                    if tpe::class::contains_type_args(&class) {
-                        return precedence_15::parse_tail(
-                            MethodReferencePrimary::Class(class),
-                            input,
-                        );
-                    }
-                }
-                _ => (),
+    precedence::parse(input, context)
+}
+
+// An expression may start with a method reference on a type that isn't an expression, e.g.
+// `int[]::new` or `List<String>::size`. Nothing follows such a method reference.
+pub fn parse_type_method_reference<'def, 'r>(
+    input: Tokens<'def, 'r>,
+) -> Option<ParseResult<'def, 'r, Expr<'def>>> {
+    let (input, tpe) = tpe::parse(input).ok()?;
+    symbol2(':', ':')(input).ok()?;
+
+    match tpe {
+        Type::Array(arr) => Some(method_reference::parse_tail(
+            MethodReferencePrimary::Array(arr),
+            input,
+        )),
+        Type::Class(class) => {
+            if tpe::class::contains_type_args(&class) {
+                Some(method_reference::parse_tail(
+                    MethodReferencePrimary::Class(class),
+                    input,
+                ))
+            } else {
+                None
             }
         }
+        _ => None,
     }
-    precedence_1::parse(input, id_gen)
 }
 
 //#[cfg(test)]
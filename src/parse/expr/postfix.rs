@@ -1,15 +1,15 @@
 use parse::combinator::symbol2;
-use parse::expr::precedence_15;
-use parse::id_gen::IdGen;
-use parse::tree::{Expr, UnaryOperation};
+use parse::expr::method_reference;
+use parse::tree::{Expr, UnaryOperation, UnaryOperator};
+use parse::Context;
 use parse::{ParseResult, Tokens};
 use tokenize::span::Span;
 
-fn op<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, Span<'def>> {
-    if let Ok(ok) = symbol2('+', '+')(input) {
-        Ok(ok)
-    } else if let Ok(ok) = symbol2('-', '-')(input) {
-        Ok(ok)
+fn op<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, (Span<'def>, UnaryOperator)> {
+    if let Ok((input, operator)) = symbol2('+', '+')(input) {
+        Ok((input, (operator, UnaryOperator::PostIncrement)))
+    } else if let Ok((input, operator)) = symbol2('-', '-')(input) {
+        Ok((input, (operator, UnaryOperator::PostDecrement)))
     } else {
         Err(input)
     }
@@ -17,11 +17,11 @@ fn op<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, Span<'def>> {
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
-    let (input, expr) = precedence_15::parse(input, id_gen)?;
+    let (input, expr) = method_reference::parse(input, context)?;
 
-    let (input, operator) = match op(input) {
+    let (input, (operator, kind)) = match op(input) {
         Ok(ok) => ok,
         Err(_) => return Ok((input, expr)),
     };
@@ -31,6 +31,7 @@ pub fn parse<'def, 'r>(
         Expr::UnaryOperation(UnaryOperation {
             expr: Box::new(expr),
             operator,
+            kind,
             is_post: true,
         }),
     ))
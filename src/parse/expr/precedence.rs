@@ -0,0 +1,437 @@
+use parse::combinator::symbol;
+use parse::expr::{parse_type_method_reference, unary};
+use parse::tree::{
+    AssignOperator, Assigned, Assignment, BinaryOperation, BinaryOperator, Expr, InstanceOf,
+    Ternary,
+};
+use parse::Context;
+use parse::{tpe, ParseResult, Tokens};
+use tokenize::span::Span;
+use tokenize::token::Token;
+
+// The operators with the symbols that must not immediately follow them (e.g. `+` isn't followed by
+// `+` or `=`), in the order they are tried.
+const BINARY_OPERATORS: [(BinaryOperator, &str); 19] = [
+    (BinaryOperator::Multiply, "="),
+    (BinaryOperator::Divide, "="),
+    (BinaryOperator::Remainder, "="),
+    (BinaryOperator::Add, "+="),
+    (BinaryOperator::Subtract, "-="),
+    (BinaryOperator::UnsignedRightShift, "="),
+    (BinaryOperator::LeftShift, "="),
+    (BinaryOperator::RightShift, ">="),
+    (BinaryOperator::LessOrEqual, ""),
+    (BinaryOperator::GreaterOrEqual, ""),
+    (BinaryOperator::Less, "<"),
+    (BinaryOperator::Greater, ">"),
+    (BinaryOperator::Equal, ""),
+    (BinaryOperator::NotEqual, ""),
+    (BinaryOperator::BitwiseAnd, "&="),
+    (BinaryOperator::BitwiseXor, "="),
+    (BinaryOperator::BitwiseOr, "|="),
+    (BinaryOperator::And, ""),
+    (BinaryOperator::Or, ""),
+];
+
+const ASSIGN_OPERATORS: [(AssignOperator, &str); 12] = [
+    (AssignOperator::Assign, "="),
+    (AssignOperator::Add, ""),
+    (AssignOperator::Subtract, ""),
+    (AssignOperator::Multiply, ""),
+    (AssignOperator::Divide, ""),
+    (AssignOperator::Remainder, ""),
+    (AssignOperator::BitwiseOr, ""),
+    (AssignOperator::BitwiseAnd, ""),
+    (AssignOperator::BitwiseXor, ""),
+    (AssignOperator::LeftShift, ""),
+    (AssignOperator::RightShift, ""),
+    (AssignOperator::UnsignedRightShift, ""),
+];
+
+// The precedences that aren't in `BinaryOperator` and `AssignOperator`.
+const INSTANCE_OF: u8 = 9;
+const TERNARY: u8 = 2;
+
+enum Operator<'def> {
+    Binary(Span<'def>, BinaryOperator),
+    InstanceOf(Span<'def>),
+    Ternary,
+    Assignment(Span<'def>, AssignOperator),
+}
+
+impl<'def> Operator<'def> {
+    fn precedence(&self) -> u8 {
+        match self {
+            Operator::Binary(_, kind) => kind.precedence(),
+            Operator::InstanceOf(_) => INSTANCE_OF,
+            Operator::Ternary => TERNARY,
+            Operator::Assignment(_, kind) => kind.precedence(),
+        }
+    }
+}
+
+// What is waiting for the operand being parsed.
+// The operands are boxed because they end up boxed in the tree anyway.
+enum Frame<'def> {
+    Binary(Box<Expr<'def>>, Span<'def>, BinaryOperator),
+    Assignment(Box<Assigned<'def>>, Span<'def>, AssignOperator),
+    TernaryTrue(Box<Expr<'def>>),
+    TernaryFalse(Box<Expr<'def>>, Box<Expr<'def>>),
+}
+
+fn operator<'def, 'r>(input: Tokens<'def, 'r>) -> Option<(Tokens<'def, 'r>, Operator<'def>)> {
+    // An operator is made of adjacent single-char symbols, and the longest is 4 chars plus the
+    // symbol that must not follow it.
+    let mut symbols = [0u8; 5];
+    let mut len = 0;
+
+    for (index, token) in input.iter().take(symbols.len()).enumerate() {
+        match token {
+            Token::Symbol(span)
+                if span.len() == 1 && (index == 0 || input[index - 1].span().end == span.start) =>
+            {
+                symbols[len] = span.fragment().as_bytes()[0];
+                len += 1;
+            }
+            Token::Keyword(span) if index == 0 && span.fragment() == "instanceof" => {
+                return Some((&input[1..], Operator::InstanceOf(*span)));
+            }
+            _ => break,
+        }
+    }
+
+    let symbols = &symbols[..len];
+    let matches = |op: &str, not_followed_by: &str| {
+        symbols.starts_with(op.as_bytes())
+            && symbols
+                .get(op.len())
+                .is_none_or(|next| !not_followed_by.as_bytes().contains(next))
+    };
+    let span = |op: &str| input[0].span().to(&input[op.len() - 1].span());
+
+    for &(kind, not_followed_by) in BINARY_OPERATORS.iter() {
+        let op = kind.text();
+        if matches(op, not_followed_by) {
+            return Some((&input[op.len()..], Operator::Binary(span(op), kind)));
+        }
+    }
+
+    if symbols.first() == Some(&b'?') {
+        return Some((&input[1..], Operator::Ternary));
+    }
+
+    for &(kind, not_followed_by) in ASSIGN_OPERATORS.iter() {
+        let op = kind.text();
+        if matches(op, not_followed_by) {
+            return Some((&input[op.len()..], Operator::Assignment(span(op), kind)));
+        }
+    }
+
+    None
+}
+
+// Folds the pending binary operations whose precedence is at least `min` into `right`.
+fn reduce<'def>(stack: &mut Vec<Frame<'def>>, mut right: Expr<'def>, min: u8) -> Expr<'def> {
+    while let Some(Frame::Binary(_, _, kind)) = stack.last() {
+        if kind.precedence() < min {
+            break;
+        }
+
+        if let Some(Frame::Binary(left, operator, kind)) = stack.pop() {
+            right = Expr::BinaryOperation(BinaryOperation {
+                left,
+                operator,
+                kind,
+                right: Box::new(right),
+            });
+        }
+    }
+
+    right
+}
+
+// Parses assignments, ternaries, and binary operations with an explicit stack instead of one
+// function per precedence level, so a long chain (e.g. `a + b + ... + z`) doesn't recurse.
+pub fn parse<'def, 'r>(
+    input: Tokens<'def, 'r>,
+    context: &mut Context<'def>,
+) -> ParseResult<'def, 'r, Expr<'def>> {
+    let lowest = BinaryOperator::Or.precedence();
+    let mut input = input;
+    let mut stack = vec![];
+    // Whether the next operand is a whole expression (e.g. a ternary branch), which may be a
+    // method reference on a type.
+    let mut is_whole = true;
+
+    'operand: loop {
+        let type_method_reference = if is_whole {
+            parse_type_method_reference(input)
+        } else {
+            None
+        };
+        let (rest, mut expr, mut highest) = match type_method_reference {
+            Some(result) => {
+                let (rest, expr) = result?;
+                (rest, expr, 0)
+            }
+            None => {
+                let (rest, expr) = unary::parse(input, context)?;
+                (rest, expr, BinaryOperator::Multiply.precedence())
+            }
+        };
+        input = rest;
+
+        loop {
+            match operator(input).filter(|(_, op)| op.precedence() <= highest) {
+                Some((rest, Operator::Binary(operator, kind))) => {
+                    let left = reduce(&mut stack, expr, kind.precedence());
+                    stack.push(Frame::Binary(Box::new(left), operator, kind));
+                    input = rest;
+                    is_whole = false;
+                    continue 'operand;
+                }
+                Some((rest, Operator::InstanceOf(operator))) => {
+                    let left = reduce(&mut stack, expr, INSTANCE_OF);
+                    let (rest, tpe) = tpe::parse(rest)?;
+                    expr = Expr::InstanceOf(InstanceOf {
+                        expr: Box::new(left),
+                        operator,
+                        tpe,
+                    });
+                    input = rest;
+                    highest = INSTANCE_OF - 1;
+                }
+                Some((rest, Operator::Ternary)) => {
+                    let cond = reduce(&mut stack, expr, lowest);
+                    stack.push(Frame::TernaryTrue(Box::new(cond)));
+                    input = rest;
+                    is_whole = true;
+                    continue 'operand;
+                }
+                Some((rest, Operator::Assignment(operator, kind))) => {
+                    let assigned = match reduce(&mut stack, expr, lowest) {
+                        Expr::FieldAccess(field) => Assigned::Field(field),
+                        Expr::ArrayAccess(arr) => Assigned::ArrayAccess(arr),
+                        Expr::Name(name) => Assigned::Name(name),
+                        _ => return Err(rest),
+                    };
+                    stack.push(Frame::Assignment(Box::new(assigned), operator, kind));
+                    input = rest;
+                    is_whole = false;
+                    continue 'operand;
+                }
+                None => {
+                    let right = reduce(&mut stack, expr, lowest);
+                    match stack.pop() {
+                        None => return Ok((input, right)),
+                        Some(Frame::TernaryTrue(cond)) => {
+                            let (rest, _) = symbol(':')(input)?;
+                            stack.push(Frame::TernaryFalse(cond, Box::new(right)));
+                            input = rest;
+                            is_whole = true;
+                            continue 'operand;
+                        }
+                        Some(Frame::TernaryFalse(cond, true_expr)) => {
+                            expr = Expr::Ternary(Ternary {
+                                cond,
+                                true_expr,
+                                false_expr: Box::new(right),
+                            });
+                            highest = lowest;
+                        }
+                        Some(Frame::Assignment(assigned, operator, kind)) => {
+                            expr = Expr::Assignment(Assignment {
+                                assigned,
+                                operator,
+                                kind,
+                                expr: Box::new(right),
+                            });
+                            highest = 0;
+                        }
+                        Some(Frame::Binary(..)) => unreachable!(),
+                    }
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{parse, ASSIGN_OPERATORS, BINARY_OPERATORS};
+    use parse::id_gen::IdGen;
+    use parse::tree::{Assigned, BinaryOperation, Expr, Type, UnaryOperator};
+    use parse::{walk, Context};
+    use test_common::generate_tokens;
+
+    fn render_type(tpe: &Type) -> String {
+        match tpe {
+            Type::Primitive(primitive) => primitive.span_opt.unwrap().fragment().to_owned(),
+            Type::Class(class) => class.name.to_owned(),
+            _ => panic!(),
+        }
+    }
+
+    fn render(expr: &Expr) -> String {
+        match expr {
+            Expr::Name(name) => name.name.fragment().to_owned(),
+            Expr::Int(int) => int.value.fragment().to_owned(),
+            Expr::BinaryOperation(op) => format!(
+                "({} {} {})",
+                render(&op.left),
+                op.operator.fragment(),
+                render(&op.right)
+            ),
+            Expr::UnaryOperation(op) => format!("({}{})", op.operator.fragment(), render(&op.expr)),
+            Expr::Cast(cast) => {
+                format!("(({}) {})", render_type(&cast.tpes[0]), render(&cast.expr))
+            }
+            Expr::InstanceOf(op) => {
+                format!("({} instanceof {})", render(&op.expr), render_type(&op.tpe))
+            }
+            Expr::Ternary(ternary) => format!(
+                "({} ? {} : {})",
+                render(&ternary.cond),
+                render(&ternary.true_expr),
+                render(&ternary.false_expr)
+            ),
+            Expr::Assignment(assignment) => {
+                let assigned = match &*assignment.assigned {
+                    Assigned::Name(name) => name.name.fragment(),
+                    _ => panic!(),
+                };
+                format!(
+                    "({} {} {})",
+                    assigned,
+                    assignment.operator.fragment(),
+                    render(&assignment.expr)
+                )
+            }
+            _ => panic!(),
+        }
+    }
+
+    fn parse_and_render(content: &str) -> String {
+        let tokens = generate_tokens(content);
+        let (rest, expr) = parse(&tokens, &mut Context::new("")).ok().unwrap();
+        assert!(rest.is_empty());
+        render(&expr)
+    }
+
+    #[test]
+    fn test_precedence() {
+        assert_eq!(parse_and_render("a + b * c - d"), "((a + (b * c)) - d)");
+        assert_eq!(
+            parse_and_render("a || b && c | d ^ e & f == g < h << i + j * k"),
+            "(a || (b && (c | (d ^ (e & (f == (g < (h << (i + (j * k))))))))))"
+        );
+        assert_eq!(
+            parse_and_render("a * b + c << d > e != f & g ^ h | i && j || k"),
+            "((((((((((a * b) + c) << d) > e) != f) & g) ^ h) | i) && j) || k)"
+        );
+        assert_eq!(
+            parse_and_render("a >> b >>> c > d >= e"),
+            "((((a >> b) >>> c) > d) >= e)"
+        );
+        assert_eq!(
+            parse_and_render("(int) a + -b * c"),
+            "(((int) a) + ((-b) * c))"
+        );
+    }
+
+    #[test]
+    fn test_instanceof() {
+        assert_eq!(
+            parse_and_render("a + b instanceof C == d"),
+            "(((a + b) instanceof C) == d)"
+        );
+    }
+
+    #[test]
+    fn test_ternary_and_assignment() {
+        assert_eq!(
+            parse_and_render("x = a ? b : c ? d : e"),
+            "(x = (a ? b : (c ? d : e)))"
+        );
+        assert_eq!(
+            parse_and_render("x += y >>>= a || b"),
+            "(x += (y >>>= (a || b)))"
+        );
+        assert_eq!(
+            parse_and_render("a ? x = 1 : b || c"),
+            "(a ? (x = 1) : (b || c))"
+        );
+
+        let tokens = generate_tokens("a + b = c");
+        assert!(parse(&tokens, &mut Context::new("")).is_err());
+    }
+
+    #[test]
+    fn test_operator_kinds() {
+        for &(kind, _) in BINARY_OPERATORS.iter() {
+            let tokens = generate_tokens(&format!("a {} b", kind.text()));
+            match parse(&tokens, &mut Context::new("")).ok().unwrap().1 {
+                Expr::BinaryOperation(op) => {
+                    assert_eq!(op.kind, kind);
+                    assert_eq!(op.operator.fragment(), kind.text());
+                }
+                _ => panic!(),
+            }
+        }
+
+        for &(kind, _) in ASSIGN_OPERATORS.iter() {
+            let tokens = generate_tokens(&format!("a {} b", kind.text()));
+            match parse(&tokens, &mut Context::new("")).ok().unwrap().1 {
+                Expr::Assignment(assignment) => {
+                    assert_eq!(assignment.kind, kind);
+                    assert_eq!(assignment.operator.fragment(), kind.text());
+                }
+                _ => panic!(),
+            }
+        }
+
+        let tokens = generate_tokens("-a++");
+        match parse(&tokens, &mut Context::new("")).ok().unwrap().1 {
+            Expr::UnaryOperation(minus) => {
+                assert_eq!(minus.kind, UnaryOperator::Minus);
+                match *minus.expr {
+                    Expr::UnaryOperation(increment) => {
+                        assert_eq!(increment.kind, UnaryOperator::PostIncrement)
+                    }
+                    _ => panic!(),
+                }
+            }
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let content = vec!["a"; 100_000].join(" + ");
+        let tokens = generate_tokens(&content);
+        let (rest, mut expr) = parse(&tokens, &mut Context::new("")).ok().unwrap();
+        assert!(rest.is_empty());
+
+        walk::assign_ids(&mut expr, &mut IdGen::new(""));
+
+        let copy = expr.clone();
+        assert_eq!(copy, expr);
+        assert!(format!("{:?}", copy).starts_with("BinaryOperation(BinaryOperation { left: Name("));
+    }
+
+    #[test]
+    fn test_debug() {
+        let tokens = generate_tokens("a + b - c");
+        let (_, expr) = parse(&tokens, &mut Context::new("")).ok().unwrap();
+        let minus = unwrap!(Expr::BinaryOperation, &expr);
+        let plus = unwrap!(Expr::BinaryOperation, &*minus.left);
+
+        assert_eq!(
+            format!("{:?}", minus),
+            format!(
+                "BinaryOperation {{ left: {:?}, operations: [({:?}, Add, {:?}), ({:?}, Subtract, {:?})] }}",
+                plus.left, plus.operator, plus.right, minus.operator, minus.right
+            )
+        );
+    }
+}
@@ -1,17 +1,17 @@
 use parse::combinator::{separated_nonempty_list, symbol};
-use parse::expr::precedence_13;
-use parse::id_gen::IdGen;
+use parse::expr::unary;
 use parse::tree::{Cast, Expr};
+use parse::Context;
 use parse::{tpe, ParseResult, Tokens};
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
     let (input, _) = symbol('(')(input)?;
     let (input, tpes) = separated_nonempty_list(symbol('&'), tpe::parse)(input)?;
     let (input, _) = symbol(')')(input)?;
-    let (input, expr) = precedence_13::parse(input, id_gen)?;
+    let (input, expr) = unary::parse(input, context)?;
 
     Ok((
         input,
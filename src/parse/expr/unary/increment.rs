@@ -1,15 +1,15 @@
 use parse::combinator::symbol2;
-use parse::expr::precedence_14;
-use parse::id_gen::IdGen;
-use parse::tree::{Expr, UnaryOperation};
+use parse::expr::postfix;
+use parse::tree::{Expr, UnaryOperation, UnaryOperator};
+use parse::Context;
 use parse::{ParseResult, Tokens};
 use tokenize::span::Span;
 
-fn op<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, Span<'def>> {
-    if let Ok(ok) = symbol2('+', '+')(input) {
-        Ok(ok)
-    } else if let Ok(ok) = symbol2('-', '-')(input) {
-        Ok(ok)
+fn op<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, (Span<'def>, UnaryOperator)> {
+    if let Ok((input, operator)) = symbol2('+', '+')(input) {
+        Ok((input, (operator, UnaryOperator::PreIncrement)))
+    } else if let Ok((input, operator)) = symbol2('-', '-')(input) {
+        Ok((input, (operator, UnaryOperator::PreDecrement)))
     } else {
         Err(input)
     }
@@ -17,16 +17,17 @@ fn op<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, Span<'def>> {
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
-    let (input, operator) = op(input)?;
-    let (input, expr) = precedence_14::parse(input, id_gen)?;
+    let (input, (operator, kind)) = op(input)?;
+    let (input, expr) = postfix::parse(input, context)?;
 
     Ok((
         input,
         Expr::UnaryOperation(UnaryOperation {
             expr: Box::new(expr),
             operator,
+            kind,
             is_post: false,
         }),
     ))
@@ -1,24 +1,24 @@
-use parse::expr::precedence_14;
-use parse::id_gen::IdGen;
+use parse::expr::postfix;
 use parse::tree::Expr;
+use parse::Context;
 use parse::{ParseResult, Tokens};
 
 pub mod cast;
-pub mod unary;
-pub mod unary_pre;
+pub mod increment;
+pub mod operator;
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
-    if let Ok(ok) = unary_pre::parse(input, id_gen) {
+    if let Ok(ok) = increment::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = unary::parse(input, id_gen) {
+    } else if let Ok(ok) = operator::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = cast::parse(input, id_gen) {
+    } else if let Ok(ok) = cast::parse(input, context) {
         Ok(ok)
     } else {
-        precedence_14::parse(input, id_gen)
+        postfix::parse(input, context)
     }
 }
 
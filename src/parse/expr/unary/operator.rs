@@ -1,37 +1,40 @@
 use parse::combinator::symbol;
-use parse::expr::precedence_13;
-use parse::id_gen::IdGen;
-use parse::tree::{Expr, UnaryOperation};
+use parse::expr::unary;
+use parse::tree::{Expr, UnaryOperation, UnaryOperator};
+use parse::Context;
 use parse::{ParseResult, Tokens};
 use tokenize::span::Span;
 
-fn op<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, Span<'def>> {
-    if let Ok(ok) = symbol('+')(input) {
-        Ok(ok)
-    } else if let Ok(ok) = symbol('-')(input) {
-        Ok(ok)
-    } else if let Ok(ok) = symbol('!')(input) {
-        Ok(ok)
-    } else if let Ok(ok) = symbol('~')(input) {
-        Ok(ok)
-    } else {
-        Err(input)
+const OPERATORS: [(char, UnaryOperator); 4] = [
+    ('+', UnaryOperator::Plus),
+    ('-', UnaryOperator::Minus),
+    ('!', UnaryOperator::Not),
+    ('~', UnaryOperator::BitwiseNot),
+];
+
+fn op<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, (Span<'def>, UnaryOperator)> {
+    for &(c, kind) in OPERATORS.iter() {
+        if let Ok((input, operator)) = symbol(c)(input) {
+            return Ok((input, (operator, kind)));
+        }
     }
+    Err(input)
 }
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Expr<'def>> {
-    let (input, operator) = op(input)?;
+    let (input, (operator, kind)) = op(input)?;
 
-    let (input, expr) = precedence_13::parse(input, id_gen)?;
+    let (input, expr) = unary::parse(input, context)?;
 
     Ok((
         input,
         Expr::UnaryOperation(UnaryOperation {
             expr: Box::new(expr),
             operator,
+            kind,
             is_post: false,
         }),
     ))
@@ -1,16 +1,244 @@
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+// An id is a hash of the file's path (`file`) and a hash of the node's enclosing declarations, its
+// kind, its name, and its occurrence among the nodes of the same kind and name in the same
+// declaration (`local`). Therefore, an id doesn't change when an unrelated part of the file changes,
+// e.g. a variable added to one method doesn't change the ids of the variables of another method.
+// `local` is 64 bits wide, so two nodes of a file practically never collide.
+#[derive(Debug, PartialEq, Eq, Hash, PartialOrd, Ord, Clone, Copy, Default)]
+pub struct Id {
+    pub file: u32,
+    pub local: u64,
+}
+
+impl fmt::Display for Id {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "u{:08x}{:016x}", self.file, self.local)
+    }
+}
+
 #[derive(Clone)]
 pub struct IdGen {
-    pub uuid: usize,
     pub path: String,
-    pub runner: usize,
+    scope: String,
+    file: u32,
+    // The hashes of the enclosing declarations, innermost last, with their readable names.
+    enclosing: Vec<(u64, String)>,
+    occurrences: HashMap<u64, u32>,
+    used: HashSet<u64>,
+    debug_names_opt: Option<HashMap<Id, String>>,
 }
 
 impl IdGen {
-    pub fn get_next(&mut self, part1: &str, part2: &str) -> String {
-        self.runner += 1;
-        format!(
-            "u{}_{}_{}_{}_{}",
-            self.uuid, self.path, part1, part2, self.runner
-        )
+    pub fn new(path: &str) -> IdGen {
+        IdGen::new_scoped(path, "")
+    }
+
+    // The ids of a part of the file that is parsed on its own (e.g. a skipped body). They don't
+    // collide with the ids of the rest of the file.
+    pub fn new_scoped(path: &str, scope: &str) -> IdGen {
+        IdGen {
+            path: path.to_owned(),
+            scope: scope.to_owned(),
+            file: fold(hash(&[path.as_bytes()])),
+            enclosing: vec![],
+            occurrences: HashMap::new(),
+            used: HashSet::new(),
+            debug_names_opt: None,
+        }
+    }
+
+    // Records a human-readable name (e.g. `Test.java:class:Test:1/method:run:1`) for every id
+    // generated from now on. Assigning the ids of a parsed tree again with this generator gives the
+    // names of the tree's ids.
+    pub fn with_debug_names(mut self) -> IdGen {
+        self.debug_names_opt = Some(HashMap::new());
+        self
+    }
+
+    pub fn debug_name(&self, id: &Id) -> Option<&str> {
+        self.debug_names_opt
+            .as_ref()
+            .and_then(|names| names.get(id))
+            .map(|name| name.as_str())
+    }
+
+    // The nodes generated until `leave` are enclosed in the declaration with the kind and name,
+    // whose own id is generated after `leave`.
+    pub fn enter(&mut self, kind: &str, name: &str) {
+        let occurrence = self.occurrences.get(&self.key(kind, name)).unwrap_or(&0) + 1;
+        let enclosing = hash(&[
+            &self.enclosing_hash().to_le_bytes(),
+            kind.as_bytes(),
+            name.as_bytes(),
+            &occurrence.to_le_bytes(),
+        ]);
+        let readable = format!("{}:{}:{}", kind, name, occurrence);
+        self.enclosing.push((enclosing, readable));
+    }
+
+    pub fn leave(&mut self) {
+        self.enclosing.pop();
+    }
+
+    pub fn get_next(&mut self, kind: &str, name: &str) -> Id {
+        let occurrence = self.occurrences.entry(self.key(kind, name)).or_insert(0);
+        *occurrence += 1;
+        let occurrence = *occurrence;
+
+        let mut local = hash(&[
+            self.path.as_bytes(),
+            self.scope.as_bytes(),
+            &self.enclosing_hash().to_le_bytes(),
+            kind.as_bytes(),
+            name.as_bytes(),
+            &occurrence.to_le_bytes(),
+        ]);
+        // A collision is rehashed until the id is free. Only then does an id depend on the order of
+        // the other nodes.
+        while !self.used.insert(local) {
+            local = hash(&[&local.to_le_bytes()]);
+        }
+        let id = Id {
+            file: self.file,
+            local,
+        };
+
+        if let Some(names) = &mut self.debug_names_opt {
+            let mut debug_name = if self.scope.is_empty() {
+                format!("{}:", self.path)
+            } else {
+                format!("{}@{}:", self.path, self.scope)
+            };
+            for (_, enclosing) in &self.enclosing {
+                debug_name.push_str(enclosing);
+                debug_name.push('/');
+            }
+            debug_name.push_str(&format!("{}:{}:{}", kind, name, occurrence));
+            self.debug_names_opt
+                .as_mut()
+                .unwrap()
+                .insert(id, debug_name);
+        }
+
+        id
+    }
+
+    fn enclosing_hash(&self) -> u64 {
+        self.enclosing.last().map(|(hash, _)| *hash).unwrap_or(0)
+    }
+
+    fn key(&self, kind: &str, name: &str) -> u64 {
+        hash(&[
+            &self.enclosing_hash().to_le_bytes(),
+            kind.as_bytes(),
+            name.as_bytes(),
+        ])
+    }
+}
+
+// 64-bit FNV-1a. The parts are separated, so ("ab", "c") and ("a", "bc") hash differently.
+fn hash(parts: &[&[u8]]) -> u64 {
+    let mut h: u64 = 0xcbf2_9ce4_8422_2325;
+    for part in parts {
+        for b in part.iter().chain(&[0xff]) {
+            h ^= u64::from(*b);
+            h = h.wrapping_mul(0x0100_0000_01b3);
+        }
+    }
+    h
+}
+
+fn fold(h: u64) -> u32 {
+    (h ^ (h >> 32)) as u32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::IdGen;
+
+    #[test]
+    fn test_stable() {
+        let mut before = IdGen::new("Test.java");
+        let class = before.get_next("class", "Test");
+        let method = before.get_next("method", "run");
+
+        let mut after = IdGen::new("Test.java");
+        after.get_next("method", "other");
+        assert_eq!(after.get_next("class", "Test"), class);
+        assert_eq!(after.get_next("method", "run"), method);
+
+        assert_ne!(IdGen::new("Other.java").get_next("class", "Test"), class);
+    }
+
+    #[test]
+    fn test_occurrence() {
+        let mut id_gen = IdGen::new("Test.java");
+        let first = id_gen.get_next("var", "a");
+        let second = id_gen.get_next("var", "a");
+
+        assert_ne!(first, second);
+    }
+
+    #[test]
+    fn test_per_declaration() {
+        let mut before = IdGen::new("Test.java");
+        before.enter("method", "run");
+        let var = before.get_next("var", "i");
+        before.leave();
+        before.get_next("method", "run");
+
+        let mut after = IdGen::new("Test.java");
+        after.enter("method", "other");
+        after.get_next("var", "i");
+        after.leave();
+        after.get_next("method", "other");
+        after.enter("method", "run");
+        assert_eq!(after.get_next("var", "i"), var);
+        after.leave();
+
+        // The same variable of another overload.
+        after.get_next("method", "run");
+        after.enter("method", "run");
+        assert_ne!(after.get_next("var", "i"), var);
+    }
+
+    #[test]
+    fn test_collision() {
+        let mut id_gen = IdGen::new("Test.java");
+        let first = id_gen.get_next("var", "a");
+
+        // The same kind, name, and occurrence again.
+        id_gen.occurrences.clear();
+        let second = id_gen.get_next("var", "a");
+
+        assert_eq!(second.file, first.file);
+        assert_ne!(second, first);
+    }
+
+    #[test]
+    fn test_scoped() {
+        let id = IdGen::new("Test.java").get_next("var", "a");
+        let scoped = IdGen::new_scoped("Test.java", "120").get_next("var", "a");
+
+        assert_eq!(scoped.file, id.file);
+        assert_ne!(scoped, id);
+    }
+
+    #[test]
+    fn test_debug_name() {
+        let mut id_gen = IdGen::new("Debug.java").with_debug_names();
+        id_gen.enter("class", "Test");
+        let method = id_gen.get_next("method", "run");
+        id_gen.leave();
+        let class = id_gen.get_next("class", "Test");
+
+        assert_eq!(id_gen.debug_name(&class), Some("Debug.java:class:Test:1"));
+        assert_eq!(
+            id_gen.debug_name(&method),
+            Some("Debug.java:class:Test:1/method:run:1")
+        );
+        assert_eq!(IdGen::new("Debug.java").debug_name(&class), None);
     }
 }
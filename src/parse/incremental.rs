@@ -0,0 +1,584 @@
+use parse::tree::{Block, ClassBodyItem, CompilationUnitItem};
+use parse::walk::{Move, Mover};
+use parse::{apply_with_context, Context, Tokens};
+use std::collections::HashMap;
+use tokenize::span::Span;
+use tokenize::token::Token;
+use {tokenize, JavaFile, SourceFile};
+
+// Replaces the bytes `start..end` of the previous content with `text`.
+#[derive(Debug, PartialEq, Clone)]
+pub struct TextEdit {
+    pub start: usize,
+    pub end: usize,
+    pub text: String,
+}
+
+// Applies the edits, which must be sorted and must not overlap, to `content`.
+pub fn apply_edits(content: &str, edits: &[TextEdit]) -> String {
+    let mut result = String::with_capacity(content.len());
+    let mut last = 0;
+
+    for edit in edits {
+        result.push_str(&content[last..edit.start]);
+        result.push_str(&edit.text);
+        last = edit.end;
+    }
+    result.push_str(&content[last..]);
+
+    result
+}
+
+// A parse tree with the tokens of its source, which `reparse` needs. `parse::apply` doesn't keep
+// the tokens.
+pub struct Parsed<'def> {
+    pub file: JavaFile<'def>,
+    pub tokens: Vec<Token<'def>>,
+}
+
+// Parses like `parse::apply` and keeps the tokens for `reparse`.
+pub fn parse(source: &SourceFile) -> Result<Parsed<'_>, Span<'_>> {
+    let tokens = tokenize::apply(source)?;
+    let file = apply_with_context(source, &tokens, Context::new(&source.path))?;
+    Ok(Parsed { file, tokens })
+}
+
+pub struct Reparse<'def> {
+    pub parsed: Parsed<'def>,
+    // The indices of the items of the compilation unit that were parsed again.
+    pub changed_items: Vec<usize>,
+    // The (item, member) indices of the class body members that were parsed again. Only the members
+    // of the changed items are listed. A removed member is reported through its item.
+    pub changed_members: Vec<(usize, usize)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum ReparseError<'def> {
+    // The edit is out of the previous content, or it starts before the previous edit ends.
+    InvalidEdit(TextEdit),
+    // The edits make the previous content `expected` bytes long, but the new source is `actual`
+    // bytes long.
+    LengthMismatch { expected: usize, actual: usize },
+    // The new source doesn't parse.
+    Parse(Span<'def>),
+}
+
+impl<'def> From<Span<'def>> for ReparseError<'def> {
+    fn from(span: Span<'def>) -> ReparseError<'def> {
+        ReparseError::Parse(span)
+    }
+}
+
+// The nodes of a previous parse keyed by their start in the new source, with their end.
+#[derive(Default)]
+pub struct Reusable<'def> {
+    items: HashMap<u32, (u32, CompilationUnitItem<'def>)>,
+    members: HashMap<u32, (u32, ClassBodyItem<'def>)>,
+    blocks: HashMap<u32, (u32, Block<'def>)>,
+}
+
+impl<'def> Reusable<'def> {
+    pub fn take_item<'r>(
+        &mut self,
+        input: Tokens<'def, 'r>,
+    ) -> Option<(Tokens<'def, 'r>, CompilationUnitItem<'def>)> {
+        take(&mut self.items, input)
+    }
+
+    pub fn take_member<'r>(
+        &mut self,
+        input: Tokens<'def, 'r>,
+    ) -> Option<(Tokens<'def, 'r>, ClassBodyItem<'def>)> {
+        take(&mut self.members, input)
+    }
+
+    pub fn take_block<'r>(
+        &mut self,
+        input: Tokens<'def, 'r>,
+    ) -> Option<(Tokens<'def, 'r>, Block<'def>)> {
+        take(&mut self.blocks, input)
+    }
+}
+
+// Parsing the same tokens gives the same node, so a node can be reused wherever the parser starts
+// at its first token.
+fn take<'def, 'r, T>(
+    nodes: &mut HashMap<u32, (u32, T)>,
+    input: Tokens<'def, 'r>,
+) -> Option<(Tokens<'def, 'r>, T)> {
+    if nodes.is_empty() {
+        return None;
+    }
+
+    let (end, node) = nodes.remove(&input.first()?.span().start)?;
+    let last = input
+        .binary_search_by_key(&end, |token| token.span().end)
+        .ok()?;
+
+    Some((&input[(last + 1)..], node))
+}
+
+fn check_edits<'def>(
+    old_len: usize,
+    new_len: usize,
+    edits: &[TextEdit],
+) -> Result<i64, ReparseError<'def>> {
+    let mut last = 0;
+    for edit in edits {
+        if edit.start < last || edit.end < edit.start || edit.end > old_len {
+            return Err(ReparseError::InvalidEdit(edit.clone()));
+        }
+        last = edit.end;
+    }
+
+    let delta = edits.iter().fold(0, |delta, edit| {
+        delta + edit.text.len() as i64 - (edit.end - edit.start) as i64
+    });
+    if old_len as i64 + delta != new_len as i64 {
+        return Err(ReparseError::LengthMismatch {
+            expected: (old_len as i64 + delta) as usize,
+            actual: new_len,
+        });
+    }
+
+    Ok(delta)
+}
+
+// Parses `source`, the content of `old.file.source` after `edits`, and returns a tree identical to
+// `parse::apply(source)`.
+//
+// Only the tokens around each edit are lexed again. The members and the method bodies that don't
+// overlap them are copied from `old` to `source` instead of being parsed again. Edits whose
+// relexed regions meet are handled as one region. The edits must be sorted and must not overlap.
+// `old` should come straight from the parser: a node that the semantics filled in loses what they
+// filled in.
+pub fn reparse<'old, 'def>(
+    old: &Parsed<'old>,
+    source: &'def SourceFile,
+    edits: &[TextEdit],
+) -> Result<Reparse<'def>, ReparseError<'def>> {
+    let old_len = old.file.source.content.len();
+    check_edits(old_len, source.content.len(), edits)?;
+    let old_tokens = &old.tokens;
+
+    // The old tokens before `keeps[i]` end before the edit `i` and are kept. The token before an
+    // edit is lexed again too because lexing it might look at the next character.
+    let keeps = edits
+        .iter()
+        .map(|edit| {
+            old_tokens
+                .iter()
+                .take_while(|token| (token.span().end as usize) < edit.start)
+                .count()
+                .saturating_sub(1)
+        })
+        .collect::<Vec<_>>();
+
+    let mut relocation = Relocation {
+        source,
+        old_id: old.file.source.id(),
+        segments: vec![],
+        relexed: vec![],
+    };
+    let mut tokens = Vec::with_capacity(old_tokens.len());
+    // The first old token and the offset (old and new) of the segment that is copied next.
+    let mut copied = (0, 0, 0);
+    let mut index = 0;
+
+    loop {
+        let (first, old_start, new_start) = copied;
+        let delta = new_start as i64 - old_start as i64;
+        let (last, old_end) = match edits.get(index) {
+            Some(edit) => {
+                let last = std::cmp::max(keeps[index], first);
+                let end = if last > first {
+                    old_tokens[last - 1].span().end
+                } else {
+                    old_start
+                };
+                (last, end)
+            }
+            None => (old_tokens.len(), old_len as u32),
+        };
+
+        for token in old_tokens[first..last].iter() {
+            let span = token.span();
+            tokens.push(token.with_span(Span::new(
+                source,
+                (i64::from(span.start) + delta) as usize,
+                (i64::from(span.end) + delta) as usize,
+            )));
+        }
+        relocation.segments.push(Segment {
+            start: old_start,
+            end: old_end,
+            delta,
+        });
+
+        let edit = match edits.get(index) {
+            Some(edit) => edit,
+            None => break,
+        };
+
+        // Lexing stops at the first token that starts where an old token after the edit started,
+        // unless the next edit lexes that token again.
+        let restart = (i64::from(old_end) + delta) as u32;
+        let (lexed, resync_opt) = tokenize::apply_from(source, restart as usize, |token| {
+            let start = token.span().start;
+            match old_offset(edits, start as usize) {
+                Some(old) if old >= edit.end => match old_tokens
+                    .binary_search_by_key(&(old as u32), |token| token.span().start)
+                {
+                    Ok(found) => match edits.iter().position(|next| next.start >= old) {
+                        Some(next) => found < keeps[next],
+                        None => true,
+                    },
+                    Err(_) => false,
+                },
+                _ => false,
+            }
+        })?;
+        tokens.extend(lexed);
+
+        match resync_opt {
+            Some(resync) => {
+                let old_resync = old_offset(edits, resync as usize).unwrap();
+                relocation.relexed.push((restart, resync));
+                copied = (
+                    old_tokens
+                        .binary_search_by_key(&(old_resync as u32), |token| token.span().start)
+                        .unwrap(),
+                    old_resync as u32,
+                    resync,
+                );
+                index = edits
+                    .iter()
+                    .position(|next| next.start >= old_resync)
+                    .unwrap_or(edits.len());
+            }
+            None => {
+                relocation
+                    .relexed
+                    .push((restart, source.content.len() as u32));
+                break;
+            }
+        }
+    }
+
+    let mut context = Context::new(&source.path);
+    for item in &old.file.unit.items {
+        relocation.collect_item(item, &mut context.reusable);
+    }
+
+    let file = apply_with_context(source, &tokens, context)?;
+
+    let changed = |span_opt: Option<Span>| match span_opt {
+        Some(span) => relocation.overlaps_new(span.start, span.end),
+        None => true,
+    };
+    let mut changed_items = vec![];
+    let mut changed_members = vec![];
+    for (index, item) in file.unit.items.iter().enumerate() {
+        if !changed(item.span_opt()) {
+            continue;
+        }
+        changed_items.push(index);
+
+        let members = match item {
+            CompilationUnitItem::Class(class) => &class.body.items,
+            CompilationUnitItem::Interface(interface) => &interface.body.items,
+            CompilationUnitItem::Enum(enum_def) => match &enum_def.body_opt {
+                Some(body) => &body.items,
+                None => continue,
+            },
+            CompilationUnitItem::Annotation(_) => continue,
+        };
+        for (member_index, member) in members.iter().enumerate() {
+            if changed(member.span_opt()) {
+                changed_members.push((index, member_index));
+            }
+        }
+    }
+
+    Ok(Reparse {
+        parsed: Parsed { file, tokens },
+        changed_items,
+        changed_members,
+    })
+}
+
+// The offset in the previous content of the offset in the new content, or None if it is in the
+// text of an edit.
+fn old_offset(edits: &[TextEdit], offset: usize) -> Option<usize> {
+    let mut delta: i64 = 0;
+    for edit in edits {
+        let start = (edit.start as i64 + delta) as usize;
+        if offset < start {
+            break;
+        }
+        if offset < start + edit.text.len() {
+            return None;
+        }
+        delta += edit.text.len() as i64 - (edit.end - edit.start) as i64;
+    }
+    Some((offset as i64 - delta) as usize)
+}
+
+// The bytes `start..end` of the previous content are unchanged and move by `delta`.
+struct Segment {
+    start: u32,
+    end: u32,
+    delta: i64,
+}
+
+// Copies nodes of the old source to the new source. The text of the segments is unchanged.
+// Everything else was lexed again, which is `relexed` in the new source.
+struct Relocation<'def> {
+    source: &'def SourceFile,
+    old_id: u64,
+    segments: Vec<Segment>,
+    relexed: Vec<(u32, u32)>,
+}
+
+impl<'def> Relocation<'def> {
+    fn segment(&self, start: u32, end: u32) -> Option<&Segment> {
+        self.segments
+            .iter()
+            .find(|segment| segment.start <= start && end <= segment.end)
+    }
+
+    fn is_reusable(&self, span_opt: Option<Span>) -> bool {
+        match span_opt {
+            Some(span) => self.segment(span.start, span.end).is_some(),
+            None => false,
+        }
+    }
+
+    fn overlaps_new(&self, start: u32, end: u32) -> bool {
+        self.relexed
+            .iter()
+            .any(|&(restart, resync)| end > restart && start < resync)
+    }
+
+    fn collect_item<'old>(
+        &mut self,
+        item: &CompilationUnitItem<'old>,
+        reusable: &mut Reusable<'def>,
+    ) {
+        if self.is_reusable(item.span_opt()) {
+            if let Some(item) = item.move_to(self) {
+                let span = item.span_opt().unwrap();
+                reusable.items.insert(span.start, (span.end, item));
+            }
+            return;
+        }
+
+        match item {
+            CompilationUnitItem::Class(class) => self.collect_members(&class.body.items, reusable),
+            CompilationUnitItem::Interface(interface) => {
+                self.collect_members(&interface.body.items, reusable)
+            }
+            CompilationUnitItem::Enum(enum_def) => {
+                if let Some(body) = &enum_def.body_opt {
+                    self.collect_members(&body.items, reusable)
+                }
+            }
+            CompilationUnitItem::Annotation(_) => (),
+        }
+    }
+
+    fn collect_members<'old>(
+        &mut self,
+        members: &[ClassBodyItem<'old>],
+        reusable: &mut Reusable<'def>,
+    ) {
+        for member in members {
+            if self.is_reusable(member.span_opt()) {
+                if let Some(member) = member.move_to(self) {
+                    let span = member.span_opt().unwrap();
+                    reusable.members.insert(span.start, (span.end, member));
+                }
+                continue;
+            }
+
+            match member {
+                ClassBodyItem::Class(class) => self.collect_members(&class.body.items, reusable),
+                ClassBodyItem::Interface(interface) => {
+                    self.collect_members(&interface.body.items, reusable)
+                }
+                ClassBodyItem::Enum(enum_def) => {
+                    if let Some(body) = &enum_def.body_opt {
+                        self.collect_members(&body.items, reusable)
+                    }
+                }
+                ClassBodyItem::Method(method) => {
+                    if let Some(block) = &method.block_opt {
+                        self.collect_block(block, reusable)
+                    }
+                }
+                ClassBodyItem::Constructor(constructor) => {
+                    self.collect_block(&constructor.block, reusable)
+                }
+                ClassBodyItem::StaticInitializer(block) => self.collect_block(block, reusable),
+                ClassBodyItem::FieldDeclarators(_) | ClassBodyItem::Annotation(_) => (),
+            }
+        }
+    }
+
+    fn collect_block<'old>(&mut self, block: &Block<'old>, reusable: &mut Reusable<'def>) {
+        if self.is_reusable(block.span_opt) {
+            if let Some(block) = block.move_to(self) {
+                let span = block.span_opt.unwrap();
+                reusable.blocks.insert(span.start, (span.end, block));
+            }
+        }
+    }
+}
+
+impl<'old, 'def> Mover<'old, 'def> for Relocation<'def> {
+    fn span(&mut self, span: Span<'old>) -> Option<Span<'def>> {
+        if span.file_id() != self.old_id {
+            return None;
+        }
+        let delta = self.segment(span.start, span.end)?.delta;
+        Some(Span::new(
+            self.source,
+            (i64::from(span.start) + delta) as usize,
+            (i64::from(span.end) + delta) as usize,
+        ))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{apply_edits, reparse, ReparseError, TextEdit};
+    use {parse, SourceFile};
+
+    fn check(content: &str, edits: Vec<TextEdit>) -> (Vec<usize>, Vec<(usize, usize)>) {
+        let old_source = SourceFile::new("Test.java", content);
+        let old = super::parse(&old_source).unwrap();
+
+        let source = SourceFile::new("Test.java", &apply_edits(content, &edits));
+        let result = reparse(&old, &source, &edits).unwrap();
+
+        assert_eq!(result.parsed.file, parse::apply(&source).unwrap());
+        assert_eq!(result.parsed.tokens, ::tokenize::apply(&source).unwrap());
+        (result.changed_items, result.changed_members)
+    }
+
+    fn edit(content: &str, before: &str, after: &str) -> Vec<TextEdit> {
+        let start = content.find(before).unwrap();
+        vec![TextEdit {
+            start,
+            end: start + before.len(),
+            text: after.to_owned(),
+        }]
+    }
+
+    const CONTENT: &str = r#"
+package dev;
+
+class First {
+  int a = 1;
+  <T> void run(T t) { int b = t.length; /* comment */ }
+  class Inner { void inner() { run(a); } }
+}
+
+class Second {
+  Second() { super(); }
+  String name() { return "second"; }
+}
+"#;
+
+    #[test]
+    fn test_body() {
+        assert_eq!(
+            check(CONTENT, edit(CONTENT, "t.length", "t.length + 1")),
+            (vec![0], vec![(0, 1)])
+        );
+    }
+
+    #[test]
+    fn test_signature() {
+        assert_eq!(
+            check(CONTENT, edit(CONTENT, "void inner()", "int inner(int i)")),
+            (vec![0], vec![(0, 2)])
+        );
+    }
+
+    #[test]
+    fn test_new_member() {
+        let start = CONTENT.find("  Second()").unwrap();
+        let edits = vec![TextEdit {
+            start,
+            end: start,
+            text: "  int added;\n".to_owned(),
+        }];
+        assert_eq!(check(CONTENT, edits), (vec![1], vec![(1, 0)]));
+    }
+
+    #[test]
+    fn test_removed_member() {
+        assert_eq!(
+            check(CONTENT, edit(CONTENT, "  int a = 1;", "  // int a = 1;")),
+            (vec![0], vec![])
+        );
+    }
+
+    #[test]
+    fn test_multiple() {
+        let mut edits = edit(CONTENT, "int a", "long a");
+        edits.extend(edit(CONTENT, "\"second\"", "\"2\""));
+        // Each edit is reparsed on its own.
+        assert_eq!(check(CONTENT, edits), (vec![0, 1], vec![(0, 0), (1, 1)]));
+
+        // Edits next to each other are one region.
+        let mut edits = edit(CONTENT, "int a", "long a");
+        edits.extend(edit(CONTENT, " = 1", " = 2"));
+        assert_eq!(check(CONTENT, edits), (vec![0], vec![(0, 0)]));
+    }
+
+    #[test]
+    fn test_parse_error() {
+        let old_source = SourceFile::new("Test.java", CONTENT);
+        let old = super::parse(&old_source).unwrap();
+
+        let edits = edit(CONTENT, "int a = 1;", "int a = ;");
+        let source = SourceFile::new("Test.java", &apply_edits(CONTENT, &edits));
+        match reparse(&old, &source, &edits) {
+            Err(ReparseError::Parse(_)) => (),
+            _ => panic!(),
+        }
+
+        // The previous tree is still there for the next edit.
+        let edits = edit(CONTENT, "int a = 1;", "int a = 2;");
+        let source = SourceFile::new("Test.java", &apply_edits(CONTENT, &edits));
+        let result = reparse(&old, &source, &edits).unwrap();
+        assert_eq!(result.parsed.file, parse::apply(&source).unwrap());
+    }
+
+    #[test]
+    fn test_invalid_edits() {
+        let old_source = SourceFile::new("Test.java", CONTENT);
+        let source = SourceFile::new("Test.java", CONTENT);
+
+        let edits = edit(CONTENT, "int a", "long a");
+        assert_eq!(
+            reparse(&super::parse(&old_source).unwrap(), &source, &edits).err(),
+            Some(ReparseError::LengthMismatch {
+                expected: CONTENT.len() + 1,
+                actual: CONTENT.len(),
+            })
+        );
+
+        let edits = vec![TextEdit {
+            start: CONTENT.len(),
+            end: CONTENT.len() + 1,
+            text: "".to_owned(),
+        }];
+        assert_eq!(
+            reparse(&super::parse(&old_source).unwrap(), &source, &edits).err(),
+            Some(ReparseError::InvalidEdit(edits[0].clone()))
+        );
+    }
+}
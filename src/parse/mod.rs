@@ -1,34 +1,62 @@
 use parse::id_gen::IdGen;
-use parse::tree::CompilationUnit;
-use std::borrow::Borrow;
-use std::ops::Deref;
-use std::pin::Pin;
-use std::ptr::null;
+use parse::incremental::Reusable;
+use parse::tree::{Block, CompilationUnit};
+use std::ops::Range;
 use tokenize::span::Span;
 use tokenize::token::Token;
-use {tokenize, JavaFile};
+use {tokenize, JavaFile, SourceFile};
 
+pub mod builder;
 pub mod combinator;
 pub mod compilation_unit;
 pub mod def;
+pub mod diff;
+pub mod dir;
 pub mod expr;
 pub mod id_gen;
+pub mod incremental;
+pub mod print;
+pub mod rewrite;
+pub mod search;
+#[cfg(feature = "serde")]
+pub mod serialize;
 pub mod statement;
 pub mod tpe;
 pub mod tree;
+pub mod walk;
 
 pub type Tokens<'def, 'r> = &'r [Token<'def>];
 pub type ParseResult<'def, 'r, T> = Result<(Tokens<'def, 'r>, T), Tokens<'def, 'r>>;
 
+// The state threaded through the parser.
+pub struct Context<'def> {
+    pub id_gen: IdGen,
+    // The subtrees of a previous parse that an incremental reparse reuses.
+    pub reusable: Reusable<'def>,
+    // Only match the braces of method, constructor, and initializer bodies.
+    pub skip_bodies: bool,
+}
+
+impl<'def> Context<'def> {
+    pub fn new(path: &str) -> Context<'def> {
+        Context {
+            id_gen: IdGen::new(path),
+            reusable: Reusable::default(),
+            skip_bodies: false,
+        }
+    }
+}
+
 pub fn apply_tokens<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> Result<CompilationUnit<'def>, Tokens<'def, 'r>> {
-    let result = compilation_unit::parse(input, id_gen);
+    let result = compilation_unit::parse(input, context);
 
     match result {
-        Ok((err_input, unit)) => {
+        Ok((err_input, mut unit)) => {
             if err_input.is_empty() {
+                walk::assign_ids(&mut unit, &mut context.id_gen);
                 Ok(unit)
             } else {
                 Err(err_input)
@@ -38,33 +66,243 @@ pub fn apply_tokens<'def, 'r>(
     }
 }
 
-pub fn apply<'def, 'input, 'path>(
-    input: &'input str,
-    path: &'path str,
-) -> Result<Pin<Box<JavaFile<'def>>>, Span<'def>> {
-    let mut file = Pin::new(Box::new(JavaFile {
-        unit: unsafe { std::mem::MaybeUninit::zeroed().assume_init() },
-        content: input.to_owned(),
-        path: path.to_owned(),
-    }));
-    let tokens = match tokenize::apply(unsafe { &*(file.content.as_ref() as *const str) }, &*file) {
-        Ok(tokens) => tokens,
-        Err(span) => return Err(span),
-    };
-    let mut id_gen = IdGen {
-        uuid: 1,
-        path: path.to_string(),
-        runner: 0,
-    };
-    let unit = match apply_tokens(
-        unsafe { &*(&tokens as *const Vec<Token<'def>>) },
-        &mut id_gen,
-    ) {
-        Ok(unit) => unit,
-        Err(tokens) => return Err(tokens.first().unwrap().span()),
-    };
-
-    file.unit = unit;
-
-    Ok(file)
+pub fn apply<'def>(source: &'def SourceFile) -> Result<JavaFile<'def>, Span<'def>> {
+    let tokens = tokenize::apply(source)?;
+    apply_with_context(source, &tokens, Context::new(&source.path))
+}
+
+// Parses the declarations without the bodies of methods, constructors, and initializers. This is
+// much faster when only the signatures are needed. A skipped body is an empty block with
+// `is_skipped`, and `parse_body` parses it later.
+pub fn apply_signatures<'def>(source: &'def SourceFile) -> Result<JavaFile<'def>, Span<'def>> {
+    let tokens = tokenize::apply(source)?;
+    let mut context = Context::new(&source.path);
+    context.skip_bodies = true;
+    apply_with_context(source, &tokens, context)
+}
+
+// Parses a body skipped by `apply_signatures`. Its ids differ from the ones of a full parse.
+pub fn parse_body<'def>(
+    file: &JavaFile<'def>,
+    block: &Block<'def>,
+) -> Result<Block<'def>, Span<'def>> {
+    let span = block.span_opt.expect("A skipped body has braces.");
+    assert_eq!(
+        span.file_id(),
+        file.source.id(),
+        "The body isn't from this file."
+    );
+    let (tokens, _) = tokenize::apply_from(file.source, span.start as usize, |token| {
+        token.span().start >= span.end
+    })?;
+
+    let mut context = Context::new(&file.source.path);
+    context.id_gen = IdGen::new_scoped(&file.source.path, &span.start.to_string());
+
+    match statement::block::parse_block(&tokens, &mut context) {
+        Ok(([], mut body)) => {
+            walk::assign_ids(&mut body, &mut context.id_gen);
+            Ok(body)
+        }
+        Ok((rest, _)) | Err(rest) => Err(rest.first().map(|token| token.span()).unwrap_or(span)),
+    }
+}
+
+// The indices of the first and the last token of the span, which starts and ends at token
+// boundaries.
+pub fn token_range(tokens: &[Token], span: Span) -> Option<(usize, usize)> {
+    let first = tokens
+        .binary_search_by_key(&span.start, |token| token.span().start)
+        .ok()?;
+    let last = tokens
+        .binary_search_by_key(&span.end, |token| token.span().end)
+        .ok()?;
+    Some((first, last))
+}
+
+// The token ranges of the statements from `start` until `end`. Statements don't have spans, so
+// they are parsed again to find where each one ends. It stops at a token that doesn't start a
+// statement, e.g. `case`.
+pub fn statement_ranges(
+    tokens: &[Token],
+    start: usize,
+    end: usize,
+    path: &str,
+) -> Vec<Range<usize>> {
+    let mut context = Context::new(path);
+    let mut ranges = vec![];
+    let mut start = start;
+    while start < end {
+        let next = match statement::parse(&tokens[start..end], &mut context) {
+            Ok((rest, _)) => end - rest.len(),
+            Err(_) => break,
+        };
+        ranges.push(start..next);
+        start = next;
+    }
+
+    ranges
+}
+
+fn apply_with_context<'def>(
+    source: &'def SourceFile,
+    tokens: &[Token<'def>],
+    mut context: Context<'def>,
+) -> Result<JavaFile<'def>, Span<'def>> {
+    match apply_tokens(tokens, &mut context) {
+        Ok(unit) => Ok(JavaFile { unit, source }),
+        Err(remainder) => Err(match remainder.first() {
+            Some(token) => token.span(),
+            None => Span::new(source, source.content.len(), source.content.len()),
+        }),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::id_gen::Id;
+    use parse::tree::{ClassBodyItem, CompilationUnitItem};
+    use parse::walk::{NodeVisitor, Visitor, Walk};
+    use std::collections::HashSet;
+    use tokenize::span::Span;
+    use {parse, JavaFile, SourceFile};
+
+    struct Ids(Vec<Id>);
+
+    impl<'a> Visitor<'a> for Ids {
+        fn id(&mut self, _kind: &'static str, _name: Span<'a>, id: &mut Id) {
+            self.0.push(*id);
+        }
+    }
+
+    impl<'f, 'a> NodeVisitor<'f, 'a> for Ids {}
+
+    fn ids<'a, T: Walk<'a>>(node: &T) -> Vec<Id> {
+        let mut ids = Ids(vec![]);
+        node.visit(&mut ids);
+        ids.0
+    }
+
+    fn members<'a, 'f>(file: &'f JavaFile<'a>) -> &'f [ClassBodyItem<'a>] {
+        match &file.unit.items[0] {
+            CompilationUnitItem::Class(class) => &class.body.items,
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_signatures() {
+        let source = SourceFile::new(
+            "Test.java",
+            r#"
+class Test {
+  static { init("{"); }
+  Test() { this(1); }
+  int run(int a) { if (a > 0) { return a; } return run(a + 1); }
+  abstract void stop();
+}
+"#,
+        );
+        let full = parse::apply(&source).unwrap();
+        let signatures = parse::apply_signatures(&source).unwrap();
+
+        let full_members = members(&full);
+        let members = members(&signatures);
+
+        let bodies = members
+            .iter()
+            .zip(full_members.iter())
+            .filter_map(|pair| match pair {
+                (
+                    ClassBodyItem::StaticInitializer(block),
+                    ClassBodyItem::StaticInitializer(full),
+                ) => Some((block, full)),
+                (ClassBodyItem::Constructor(c), ClassBodyItem::Constructor(full)) => {
+                    Some((&c.block, &full.block))
+                }
+                (ClassBodyItem::Method(m), ClassBodyItem::Method(full)) => {
+                    assert_eq!(m.name, full.name);
+                    assert_eq!(m.params, full.params);
+                    match (&m.block_opt, &full.block_opt) {
+                        (Some(block), Some(full)) => Some((block, full)),
+                        (None, None) => None,
+                        _ => panic!(),
+                    }
+                }
+                _ => panic!(),
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(bodies.len(), 3);
+
+        for (block, full) in bodies {
+            assert!(block.is_skipped);
+            assert!(block.stmts.is_empty());
+            assert_eq!(block.span_opt, full.span_opt);
+
+            let parsed = parse::parse_body(&signatures, block).unwrap();
+            assert_eq!(parsed, *full);
+        }
+    }
+
+    #[test]
+    fn test_ids_per_declaration() {
+        let before = SourceFile::new(
+            "Test.java",
+            "class Test { void a() { int i; } void b() { int i; } }",
+        );
+        let after = SourceFile::new(
+            "Test.java",
+            "class Test { void a() { int i; int i; } void b() { int i; } }",
+        );
+        let before = parse::apply(&before).unwrap();
+        let after = parse::apply(&after).unwrap();
+
+        // The variable added to `a` doesn't change the ids of `b`.
+        assert_eq!(ids(&members(&before)[1]), ids(&members(&after)[1]));
+        assert_ne!(ids(&members(&before)[0]), ids(&members(&after)[0]));
+    }
+
+    #[test]
+    fn test_body_ids() {
+        let source = SourceFile::new(
+            "Test.java",
+            r#"
+class Test {
+  int a;
+  void run() { int a = 1; int b = a; class Local { int a; } }
+}
+"#,
+        );
+        let full = parse::apply(&source).unwrap();
+        let signatures = parse::apply_signatures(&source).unwrap();
+
+        let (block, full_block) = match (&members(&signatures)[1], &members(&full)[1]) {
+            (ClassBodyItem::Method(m), ClassBodyItem::Method(full)) => (
+                m.block_opt.as_ref().unwrap(),
+                full.block_opt.as_ref().unwrap(),
+            ),
+            _ => panic!(),
+        };
+        let parsed = parse::parse_body(&signatures, block).unwrap();
+        let body_ids = ids(&parsed);
+        let full_ids = ids(full_block);
+
+        // a, b, Local, and Local.a
+        assert_eq!(body_ids.len(), 4);
+        assert_eq!(full_ids.len(), 4);
+        assert_eq!(body_ids.iter().collect::<HashSet<_>>().len(), 4);
+
+        // The ids differ from the ones of a full parse, and don't collide with the signatures.
+        let others = full_ids
+            .into_iter()
+            .chain(ids(&signatures.unit))
+            .collect::<HashSet<_>>();
+        assert!(body_ids.iter().all(|id| !others.contains(id)));
+
+        // Parsing the same body again gives the same ids.
+        assert_eq!(
+            ids(&parse::parse_body(&signatures, block).unwrap()),
+            body_ids
+        );
+    }
 }
@@ -0,0 +1,1141 @@
+use parse::tree::{
+    Annotated, AnnotatedValue, Annotation, AnnotationBodyItem, AnnotationParam, ArrayType,
+    Assigned, Block, Case, Catch, Class, ClassBody, ClassBodyItem, ClassType, CompilationUnit,
+    CompilationUnitItem, Constructor, EnclosingType, Enum, EnumConstant, Expr, FieldAccess,
+    FieldAccessPrefix, FieldDeclarators, Import, ImportPrefix, Interface, Lambda, Method,
+    MethodReferencePrimary, Modifier, Package, PackagePrefix, Param, PrimitiveTypeType,
+    ReferenceType, Statement, StaticType, Try, TryResource, Type, TypeArg, TypeParam,
+    TypeParamExtend, UnaryOperator, VariableDeclarators, WildcardType,
+};
+use std::ops::Deref;
+
+// Prints a tree back to Java source. The printed code has its own layout: comments are dropped,
+// blocks are always braced, and parentheses are only added where the precedence of the operators
+// needs them. A skipped body (see `parse::apply_signatures`) is printed as its original text.
+pub fn unit(unit: &CompilationUnit) -> String {
+    let mut printer = Printer::new();
+    printer.unit(unit);
+    printer.out
+}
+
+pub fn class_body_item(item: &ClassBodyItem) -> String {
+    let mut printer = Printer::new();
+    printer.class_body_item(item);
+    printer.out
+}
+
+pub fn statement(stmt: &Statement) -> String {
+    let mut printer = Printer::new();
+    printer.statement(stmt);
+    printer.out
+}
+
+pub fn expr(expr: &Expr) -> String {
+    let mut printer = Printer::new();
+    printer.expr(expr, LOWEST);
+    printer.out
+}
+
+pub fn tpe(tpe: &Type) -> String {
+    let mut printer = Printer::new();
+    printer.tpe(tpe);
+    printer.out
+}
+
+// The precedences of `BinaryOperator::precedence` and the like, extended with a lambda below all
+// of them and the primary expressions (e.g. a method call) above all of them.
+const LOWEST: u8 = 0;
+const TERNARY: u8 = 2;
+const RELATIONAL: u8 = 9;
+const PREFIX: u8 = 13;
+const POSTFIX: u8 = 14;
+const PRIMARY: u8 = 15;
+
+const INDENT: &str = "    ";
+
+struct Printer {
+    out: String,
+    indent: usize,
+}
+
+impl Printer {
+    fn new() -> Printer {
+        Printer {
+            out: String::new(),
+            indent: 0,
+        }
+    }
+
+    fn write(&mut self, s: &str) {
+        self.out.push_str(s);
+    }
+
+    fn newline(&mut self) {
+        self.out.push('\n');
+        for _ in 0..self.indent {
+            self.out.push_str(INDENT);
+        }
+    }
+
+    fn separated<T, F>(&mut self, items: &[T], separator: &str, mut print: F)
+    where
+        F: FnMut(&mut Printer, &T),
+    {
+        for (index, item) in items.iter().enumerate() {
+            if index > 0 {
+                self.write(separator);
+            }
+            print(self, item);
+        }
+    }
+
+    fn unit(&mut self, unit: &CompilationUnit) {
+        if let Some(package) = &unit.package_opt {
+            for annotated in &package.annotateds {
+                self.annotated(annotated);
+                self.newline();
+            }
+            self.write("package ");
+            self.package(package);
+            self.write(";\n\n");
+        }
+
+        for import in &unit.imports {
+            self.import(import);
+            self.write("\n");
+        }
+        if !unit.imports.is_empty() {
+            self.write("\n");
+        }
+
+        for (index, item) in unit.items.iter().enumerate() {
+            if index > 0 {
+                self.write("\n");
+            }
+            match item {
+                CompilationUnitItem::Class(class) => self.class(class),
+                CompilationUnitItem::Interface(interface) => self.interface(interface),
+                CompilationUnitItem::Annotation(annotation) => self.annotation(annotation),
+                CompilationUnitItem::Enum(enum_def) => self.enum_def(enum_def),
+            }
+            self.write("\n");
+        }
+    }
+
+    fn package(&mut self, package: &Package) {
+        if let Some(prefix) = &package.prefix_opt {
+            self.package(prefix);
+            self.write(".");
+        }
+        self.write(package.name.fragment());
+    }
+
+    fn import(&mut self, import: &Import) {
+        self.write("import ");
+        if import.is_static {
+            self.write("static ");
+        }
+        if let Some(prefix) = &import.prefix_opt {
+            self.import_prefix(prefix);
+            self.write(".");
+        }
+        self.write(import.name.fragment());
+        if import.is_wildcard {
+            self.write(".*");
+        }
+        self.write(";");
+    }
+
+    fn import_prefix(&mut self, prefix: &ImportPrefix) {
+        if let Some(prefix) = &prefix.prefix_opt {
+            self.import_prefix(prefix);
+            self.write(".");
+        }
+        self.write(prefix.name.fragment());
+    }
+
+    fn modifiers(&mut self, modifiers: &[Modifier]) {
+        for modifier in modifiers {
+            match modifier {
+                Modifier::Annotated(annotated) => self.annotated(annotated),
+                Modifier::Keyword(keyword) => self.write(keyword.name.fragment()),
+            }
+            self.write(" ");
+        }
+    }
+
+    fn annotated(&mut self, annotated: &Annotated) {
+        self.write("@");
+        match annotated {
+            Annotated::Marker(marker) => self.class_type(&marker.class),
+            Annotated::Single(single) => {
+                self.class_type(&single.class);
+                self.write("(");
+                self.annotated_value(&single.value);
+                self.write(")");
+            }
+            Annotated::Normal(normal) => {
+                self.class_type(&normal.class);
+                self.write("(");
+                self.separated(&normal.params, ", ", |p, param| {
+                    p.write(param.name.fragment());
+                    p.write(" = ");
+                    p.annotated_value(&param.value);
+                });
+                self.write(")");
+            }
+        }
+    }
+
+    fn annotated_value(&mut self, value: &AnnotatedValue) {
+        match value {
+            AnnotatedValue::Expr(expr) => self.expr(expr, TERNARY),
+            AnnotatedValue::Annotated(annotated) => self.annotated(annotated),
+            AnnotatedValue::Array(array) => {
+                self.write("{");
+                self.separated(&array.items, ", ", |p, item| p.annotated_value(item));
+                self.write("}");
+            }
+        }
+    }
+
+    fn class(&mut self, class: &Class) {
+        self.modifiers(&class.modifiers);
+        self.write("class ");
+        self.write(class.name.fragment());
+        self.type_params(&class.type_params);
+        if let Some(extend) = &class.extend_opt {
+            self.write(" extends ");
+            self.class_type(extend);
+        }
+        if !class.implements.is_empty() {
+            self.write(" implements ");
+            self.separated(&class.implements, ", ", |p, t| p.class_type(t));
+        }
+        self.write(" ");
+        self.class_body(&class.body);
+    }
+
+    fn interface(&mut self, interface: &Interface) {
+        self.modifiers(&interface.modifiers);
+        self.write("interface ");
+        self.write(interface.name.fragment());
+        self.type_params(&interface.type_params);
+        if !interface.extends.is_empty() {
+            self.write(" extends ");
+            self.separated(&interface.extends, ", ", |p, t| p.class_type(t));
+        }
+        self.write(" ");
+        self.class_body(&interface.body);
+    }
+
+    fn annotation(&mut self, annotation: &Annotation) {
+        self.modifiers(&annotation.modifiers);
+        self.write("@interface ");
+        self.write(annotation.name.fragment());
+        self.write(" {");
+        self.indent += 1;
+        for (index, item) in annotation.body.items.iter().enumerate() {
+            if index > 0 {
+                self.write("\n");
+            }
+            self.newline();
+            match item {
+                AnnotationBodyItem::Param(param) => self.annotation_param(param),
+                AnnotationBodyItem::FieldDeclarators(field) => self.field_declarators(field),
+                AnnotationBodyItem::Class(class) => self.class(class),
+                AnnotationBodyItem::Interface(interface) => self.interface(interface),
+                AnnotationBodyItem::Enum(enum_def) => self.enum_def(enum_def),
+                AnnotationBodyItem::Annotation(annotation) => self.annotation(annotation),
+            }
+        }
+        self.indent -= 1;
+        if !annotation.body.items.is_empty() {
+            self.newline();
+        }
+        self.write("}");
+    }
+
+    fn annotation_param(&mut self, param: &AnnotationParam) {
+        self.modifiers(&param.modifiers);
+        self.tpe(&param.tpe);
+        self.write(" ");
+        self.write(param.name.fragment());
+        self.write("()");
+        if let Some(default) = &param.default_opt {
+            self.write(" default ");
+            self.expr(default, TERNARY);
+        }
+        self.write(";");
+    }
+
+    fn enum_def(&mut self, enum_def: &Enum) {
+        self.modifiers(&enum_def.modifiers);
+        self.write("enum ");
+        self.write(enum_def.name.fragment());
+        if !enum_def.implements.is_empty() {
+            self.write(" implements ");
+            self.separated(&enum_def.implements, ", ", |p, t| p.class_type(t));
+        }
+        self.write(" {");
+        self.indent += 1;
+        for (index, constant) in enum_def.constants.iter().enumerate() {
+            if index > 0 {
+                self.write(",");
+            }
+            self.newline();
+            self.enum_constant(constant);
+        }
+        if let Some(body) = &enum_def.body_opt {
+            if enum_def.constants.is_empty() {
+                self.newline();
+            }
+            self.write(";");
+            self.class_body_items(body);
+        }
+        self.indent -= 1;
+        if !enum_def.constants.is_empty() || enum_def.body_opt.is_some() {
+            self.newline();
+        }
+        self.write("}");
+    }
+
+    fn enum_constant(&mut self, constant: &EnumConstant) {
+        for annotated in &constant.annotateds {
+            self.annotated(annotated);
+            self.write(" ");
+        }
+        self.write(constant.name.fragment());
+        if let Some(args) = &constant.args_opt {
+            self.args(args);
+        }
+        if let Some(body) = &constant.body_opt {
+            self.write(" ");
+            self.class_body(body);
+        }
+    }
+
+    fn class_body(&mut self, body: &ClassBody) {
+        self.write("{");
+        self.indent += 1;
+        self.class_body_items(body);
+        self.indent -= 1;
+        if !body.items.is_empty() {
+            self.newline();
+        }
+        self.write("}");
+    }
+
+    // Each item is on its own line after a blank line, except the first one.
+    fn class_body_items(&mut self, body: &ClassBody) {
+        for (index, item) in body.items.iter().enumerate() {
+            if index > 0 {
+                self.write("\n");
+            }
+            self.newline();
+            self.class_body_item(item);
+        }
+    }
+
+    fn class_body_item(&mut self, item: &ClassBodyItem) {
+        match item {
+            ClassBodyItem::Method(method) => self.method(method),
+            ClassBodyItem::FieldDeclarators(field) => self.field_declarators(field),
+            ClassBodyItem::Class(class) => self.class(class),
+            ClassBodyItem::Interface(interface) => self.interface(interface),
+            ClassBodyItem::Enum(enum_def) => self.enum_def(enum_def),
+            ClassBodyItem::Annotation(annotation) => self.annotation(annotation),
+            ClassBodyItem::StaticInitializer(block) => {
+                self.write("static ");
+                self.block(block);
+            }
+            ClassBodyItem::Constructor(constructor) => self.constructor(constructor),
+        }
+    }
+
+    fn method(&mut self, method: &Method) {
+        self.modifiers(&method.modifiers);
+        if !method.type_params.is_empty() {
+            self.type_params(&method.type_params);
+            self.write(" ");
+        }
+        self.tpe(&method.return_type);
+        self.write(" ");
+        self.write(method.name.fragment());
+        self.params(&method.params);
+        self.throws(&method.throws);
+        match &method.block_opt {
+            Some(block) => {
+                self.write(" ");
+                self.block(block);
+            }
+            None => self.write(";"),
+        }
+    }
+
+    fn constructor(&mut self, constructor: &Constructor) {
+        self.modifiers(&constructor.modifiers);
+        if !constructor.type_params.is_empty() {
+            self.type_params(&constructor.type_params);
+            self.write(" ");
+        }
+        self.write(constructor.name.fragment());
+        self.params(&constructor.params);
+        self.throws(&constructor.throws);
+        self.write(" ");
+        self.block(&constructor.block);
+    }
+
+    fn params(&mut self, params: &[Param]) {
+        self.write("(");
+        self.separated(params, ", ", |p, param| p.param(param));
+        self.write(")");
+    }
+
+    fn param(&mut self, param: &Param) {
+        self.modifiers(&param.modifiers);
+        self.tpe(&param.tpe);
+        if param.is_varargs {
+            self.write("...");
+        }
+        self.write(" ");
+        self.write(param.name.fragment());
+    }
+
+    fn throws(&mut self, throws: &[ClassType]) {
+        if !throws.is_empty() {
+            self.write(" throws ");
+            self.separated(throws, ", ", |p, t| p.class_type(t));
+        }
+    }
+
+    fn field_declarators(&mut self, field: &FieldDeclarators) {
+        self.modifiers(&field.modifiers);
+        let declarators = field
+            .declarators
+            .iter()
+            .map(|d| {
+                (
+                    d.tpe.borrow().deref().clone(),
+                    d.name.fragment(),
+                    d.expr_opt.as_ref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        self.declarators(&declarators);
+        self.write(";");
+    }
+
+    fn variable_declarators(&mut self, variable: &VariableDeclarators) {
+        self.modifiers(&variable.modifiers);
+        let declarators = variable
+            .declarators
+            .iter()
+            .map(|d| {
+                (
+                    d.tpe.borrow().deref().clone(),
+                    d.name.fragment(),
+                    d.expr_opt.as_ref(),
+                )
+            })
+            .collect::<Vec<_>>();
+        self.declarators(&declarators);
+    }
+
+    // The declarators share the shortest type, and the others add `[]` to their names, e.g.
+    // `int a, b[]`.
+    fn declarators(&mut self, declarators: &[(Type, &str, Option<&Expr>)]) {
+        let tpes = declarators
+            .iter()
+            .map(|(t, _, _)| tpe(t))
+            .collect::<Vec<_>>();
+        let base = tpes
+            .iter()
+            .min_by_key(|t| t.len())
+            .cloned()
+            .unwrap_or_default();
+        self.write(&base);
+        self.write(" ");
+        for (index, (name, expr_opt)) in declarators.iter().map(|(_, n, e)| (n, e)).enumerate() {
+            if index > 0 {
+                self.write(", ");
+            }
+            self.write(name);
+            self.write(&tpes[index][base.len()..]);
+            if let Some(expr) = expr_opt {
+                self.write(" = ");
+                self.expr(expr, LOWEST);
+            }
+        }
+    }
+
+    fn block(&mut self, block: &Block) {
+        if block.is_skipped {
+            if let Some(span) = &block.span_opt {
+                self.write(span.fragment());
+                return;
+            }
+        }
+
+        if block.stmts.is_empty() {
+            self.write("{}");
+            return;
+        }
+
+        self.write("{");
+        self.indent += 1;
+        for stmt in &block.stmts {
+            self.newline();
+            self.statement(stmt);
+        }
+        self.indent -= 1;
+        self.newline();
+        self.write("}");
+    }
+
+    fn statement(&mut self, stmt: &Statement) {
+        match stmt {
+            Statement::Assert(assert) => {
+                self.write("assert ");
+                self.expr(&assert.expr, LOWEST);
+                if let Some(error) = &assert.error_opt {
+                    self.write(" : ");
+                    self.expr(error, LOWEST);
+                }
+                self.write(";");
+            }
+            Statement::Block(block) => self.block(block),
+            Statement::Break(b) => {
+                self.write("break");
+                if let Some(identifier) = &b.identifier_opt {
+                    self.write(" ");
+                    self.write(identifier.fragment());
+                }
+                self.write(";");
+            }
+            Statement::Class(class) => self.class(class),
+            Statement::Continue(c) => {
+                self.write("continue");
+                if let Some(identifier) = &c.identifier_opt {
+                    self.write(" ");
+                    self.write(identifier.fragment());
+                }
+                self.write(";");
+            }
+            Statement::Empty => self.write(";"),
+            Statement::DoWhile(do_while) => {
+                self.write("do ");
+                self.block(&do_while.block);
+                self.write(" while (");
+                self.expr(&do_while.cond, LOWEST);
+                self.write(");");
+            }
+            Statement::Expr(expr) => {
+                self.expr(expr, LOWEST);
+                self.write(";");
+            }
+            Statement::ForLoop(for_loop) => {
+                self.write("for (");
+                self.separated(&for_loop.inits, ", ", |p, init| match init {
+                    Statement::VariableDeclarators(v) => p.variable_declarators(v),
+                    Statement::Expr(expr) => p.expr(expr, LOWEST),
+                    _ => panic!(),
+                });
+                self.write(";");
+                if let Some(cond) = &for_loop.cond_opt {
+                    self.write(" ");
+                    self.expr(cond, LOWEST);
+                }
+                self.write(";");
+                if !for_loop.updates.is_empty() {
+                    self.write(" ");
+                }
+                self.separated(&for_loop.updates, ", ", |p, update| match update {
+                    Statement::Expr(expr) => p.expr(expr, LOWEST),
+                    _ => panic!(),
+                });
+                self.write(") ");
+                self.block(&for_loop.block);
+            }
+            Statement::Foreach(foreach) => {
+                self.write("for (");
+                self.modifiers(&foreach.declarator.modifiers);
+                self.tpe(foreach.declarator.tpe.borrow().deref());
+                self.write(" ");
+                self.write(foreach.declarator.name.fragment());
+                self.write(" : ");
+                self.expr(&foreach.expr, LOWEST);
+                self.write(") ");
+                self.block(&foreach.block);
+            }
+            Statement::IfElse(if_else) => {
+                self.write("if (");
+                self.expr(&if_else.cond, LOWEST);
+                self.write(") ");
+                self.block(&if_else.block);
+                if let Some(else_block) = &if_else.else_block_opt {
+                    self.write(" else ");
+                    match else_block.stmts.as_slice() {
+                        [stmt @ Statement::IfElse(_)] if else_block.span_opt.is_none() => {
+                            self.statement(stmt)
+                        }
+                        _ => self.block(else_block),
+                    }
+                }
+            }
+            Statement::Labeled(labeled) => {
+                self.write(labeled.label.fragment());
+                self.write(": ");
+                self.statement(&labeled.statement);
+            }
+            Statement::Return(ret) => {
+                self.write("return");
+                if let Some(expr) = &ret.expr_opt {
+                    self.write(" ");
+                    self.expr(expr, LOWEST);
+                }
+                self.write(";");
+            }
+            Statement::Switch(switch) => {
+                self.write("switch (");
+                self.expr(&switch.expr, LOWEST);
+                self.write(") {");
+                for case in &switch.cases {
+                    self.newline();
+                    self.case(case);
+                }
+                self.newline();
+                self.write("}");
+            }
+            Statement::Synchronized(synchronized) => {
+                self.write("synchronized (");
+                self.expr(&synchronized.expr, LOWEST);
+                self.write(") ");
+                self.block(&synchronized.block);
+            }
+            Statement::Throw(throw) => {
+                self.write("throw ");
+                self.expr(&throw.expr, LOWEST);
+                self.write(";");
+            }
+            Statement::Try(t) => self.try_stmt(t),
+            Statement::WhileLoop(while_loop) => {
+                self.write("while (");
+                self.expr(&while_loop.cond, LOWEST);
+                self.write(") ");
+                self.block(&while_loop.block);
+            }
+            Statement::VariableDeclarators(v) => {
+                self.variable_declarators(v);
+                self.write(";");
+            }
+        }
+    }
+
+    fn case(&mut self, case: &Case) {
+        match &case.label_opt {
+            Some(label) => {
+                self.write("case ");
+                self.expr(label, LOWEST);
+                self.write(":");
+            }
+            None => self.write("default:"),
+        }
+        self.indent += 1;
+        for stmt in &case.stmts {
+            self.newline();
+            self.statement(stmt);
+        }
+        self.indent -= 1;
+    }
+
+    fn try_stmt(&mut self, t: &Try) {
+        self.write("try ");
+        if !t.resources.is_empty() {
+            self.write("(");
+            self.separated(&t.resources, "; ", |p, resource| match resource {
+                TryResource::Name(name) => p.write(name.name.fragment()),
+                TryResource::FieldAccess(field) => p.field_access(field),
+                TryResource::Declarator(declarator) => {
+                    p.modifiers(&declarator.modifiers);
+                    p.tpe(declarator.tpe.borrow().deref());
+                    p.write(" ");
+                    p.write(declarator.name.fragment());
+                    if let Some(expr) = &declarator.expr_opt {
+                        p.write(" = ");
+                        p.expr(expr, LOWEST);
+                    }
+                }
+            });
+            self.write(") ");
+        }
+        self.block(&t.try);
+        for catch in &t.catches {
+            self.write(" ");
+            self.catch(catch);
+        }
+        if let Some(finally) = &t.finally_opt {
+            self.write(" finally ");
+            self.block(finally);
+        }
+    }
+
+    fn catch(&mut self, catch: &Catch) {
+        self.write("catch (");
+        self.modifiers(&catch.modifiers);
+        self.separated(&catch.class_types, " | ", |p, t| p.class_type(t));
+        self.write(" ");
+        self.write(catch.param_name.fragment());
+        self.write(") ");
+        self.block(&catch.block);
+    }
+
+    fn precedence(expr: &Expr) -> u8 {
+        match expr {
+            Expr::Lambda(_) => LOWEST,
+            Expr::Assignment(assignment) => assignment.kind.precedence(),
+            Expr::Ternary(_) => TERNARY,
+            Expr::BinaryOperation(binary) => binary.kind.precedence(),
+            Expr::InstanceOf(_) => RELATIONAL,
+            Expr::Cast(_) => PREFIX,
+            Expr::UnaryOperation(unary) => unary.kind.precedence(),
+            // `new int[3][0]` is a two-dimensional array, so an access needs `(new int[3])[0]`.
+            Expr::NewArray(new_array) if new_array.initializer_opt.is_none() => POSTFIX,
+            _ => PRIMARY,
+        }
+    }
+
+    // Prints the expression, and parenthesizes it if it binds looser than `min`.
+    fn expr(&mut self, expr: &Expr, min: u8) {
+        let parenthesized = Printer::precedence(expr) < min;
+        if parenthesized {
+            self.write("(");
+        }
+        self.expr_unparenthesized(expr);
+        if parenthesized {
+            self.write(")");
+        }
+    }
+
+    fn expr_unparenthesized(&mut self, expr: &Expr) {
+        match expr {
+            Expr::ArrayAccess(access) => {
+                self.expr(&access.expr, PRIMARY);
+                self.write("[");
+                self.expr(&access.index, LOWEST);
+                self.write("]");
+            }
+            Expr::ArrayInitializer(initializer) => {
+                self.write("{");
+                self.separated(&initializer.items, ", ", |p, item| p.expr(item, LOWEST));
+                self.write("}");
+            }
+            Expr::Assignment(assignment) => {
+                match assignment.assigned.as_ref() {
+                    Assigned::Name(name) => self.write(name.name.fragment()),
+                    Assigned::ArrayAccess(access) => {
+                        self.expr(&access.expr, PRIMARY);
+                        self.write("[");
+                        self.expr(&access.index, LOWEST);
+                        self.write("]");
+                    }
+                    Assigned::Field(field) => self.field_access(field),
+                }
+                self.write(" ");
+                self.write(assignment.kind.text());
+                self.write(" ");
+                // The right side is any expression, e.g. a lambda.
+                self.expr(&assignment.expr, LOWEST);
+            }
+            Expr::BinaryOperation(_) => self.binary_operation(expr),
+            Expr::Boolean(b) => self.write(b.value.fragment()),
+            Expr::Cast(cast) => {
+                self.write("(");
+                self.separated(&cast.tpes, " & ", |p, t| p.tpe(t));
+                self.write(") ");
+                // `(A) -b` would be a subtraction.
+                let min = match cast.expr.as_ref() {
+                    Expr::UnaryOperation(unary) if !unary.is_post => match unary.kind {
+                        UnaryOperator::Plus
+                        | UnaryOperator::Minus
+                        | UnaryOperator::PreIncrement
+                        | UnaryOperator::PreDecrement => PRIMARY,
+                        _ => PREFIX,
+                    },
+                    _ => PREFIX,
+                };
+                self.expr(&cast.expr, min);
+            }
+            Expr::Char(c) => self.write(c.value.fragment()),
+            Expr::ConstructorReference(reference) => {
+                self.reference_type(&reference.tpe);
+                self.write("::");
+                if let Some(type_args) = &reference.type_args_opt {
+                    self.type_args(type_args);
+                }
+                self.write("new");
+            }
+            Expr::Double(d) => self.write(d.value.fragment()),
+            Expr::FieldAccess(field) => self.field_access(field),
+            Expr::Float(f) => self.write(f.value.fragment()),
+            Expr::Hex(h) => self.write(h.value.fragment()),
+            Expr::InstanceOf(instance_of) => {
+                self.expr(&instance_of.expr, RELATIONAL);
+                self.write(" instanceof ");
+                self.tpe(&instance_of.tpe);
+            }
+            Expr::Int(i) => self.write(i.value.fragment()),
+            Expr::Lambda(lambda) => self.lambda(lambda),
+            Expr::Long(l) => self.write(l.value.fragment()),
+            Expr::MethodCall(call) => {
+                if let Some(prefix) = &call.prefix_opt {
+                    self.expr(prefix, PRIMARY);
+                    self.write(".");
+                }
+                if let Some(type_args) = &call.type_args_opt {
+                    self.type_args(type_args);
+                }
+                self.write(call.name.fragment());
+                self.args(&call.args);
+            }
+            Expr::MethodReference(reference) => {
+                match &reference.primary {
+                    MethodReferencePrimary::Class(class) => self.class_type(class),
+                    MethodReferencePrimary::Array(array) => self.array_type(array),
+                    MethodReferencePrimary::Expr(expr) => self.expr(expr, PRIMARY),
+                }
+                self.write("::");
+                if let Some(type_args) = &reference.type_args_opt {
+                    self.type_args(type_args);
+                }
+                self.write(reference.name.fragment());
+            }
+            Expr::Name(name) => self.write(name.name.fragment()),
+            Expr::NewArray(new_array) => {
+                self.write("new ");
+                self.array_type(&new_array.tpe);
+                if let Some(initializer) = &new_array.initializer_opt {
+                    self.write(" {");
+                    self.separated(&initializer.items, ", ", |p, item| p.expr(item, LOWEST));
+                    self.write("}");
+                }
+            }
+            Expr::NewObject(new_object) => {
+                if let Some(prefix) = &new_object.prefix_opt {
+                    self.expr(prefix, PRIMARY);
+                    self.write(".");
+                }
+                self.write("new ");
+                if let Some(type_args) = &new_object.constructor_type_args_opt {
+                    self.type_args(type_args);
+                    self.write(" ");
+                }
+                self.class_type(&new_object.tpe);
+                self.args(&new_object.args);
+                if let Some(body) = &new_object.body_opt {
+                    self.write(" ");
+                    self.class_body(body);
+                }
+            }
+            Expr::Null(n) => self.write(n.value.fragment()),
+            Expr::Class(class) => {
+                self.tpe(&class.tpe);
+                self.write(".class");
+            }
+            Expr::StaticClass(static_class) => match &static_class.tpe {
+                StaticType::Class(class) => self.class_type(class),
+                StaticType::Parameterized(parameterized) => self.write(parameterized.name),
+            },
+            Expr::String(s) => self.write(s.value.fragment()),
+            Expr::Super(s) => {
+                if let Some(tpe) = &s.tpe_opt {
+                    self.tpe(tpe);
+                    self.write(".");
+                }
+                self.write("super");
+            }
+            Expr::SuperConstructorCall(call) => {
+                if let Some(prefix) = &call.prefix_opt {
+                    self.expr(prefix, PRIMARY);
+                    self.write(".");
+                }
+                if let Some(type_args) = &call.type_args_opt {
+                    self.type_args(type_args);
+                }
+                self.write("super");
+                self.args(&call.args);
+            }
+            Expr::This(this) => {
+                if let Some(tpe) = &this.tpe_opt {
+                    self.tpe(tpe);
+                    self.write(".");
+                }
+                self.write("this");
+            }
+            Expr::ThisConstructorCall(call) => {
+                if let Some(type_args) = &call.type_args_opt {
+                    self.type_args(type_args);
+                }
+                self.write("this");
+                self.args(&call.args);
+            }
+            Expr::Ternary(ternary) => {
+                self.expr(&ternary.cond, TERNARY + 1);
+                self.write(" ? ");
+                self.expr(&ternary.true_expr, TERNARY);
+                self.write(" : ");
+                self.expr(&ternary.false_expr, TERNARY);
+            }
+            Expr::UnaryOperation(unary) => {
+                let text = unary.kind.text();
+                if unary.is_post {
+                    self.expr(&unary.expr, PRIMARY);
+                    self.write(text);
+                } else {
+                    self.write(text);
+                    // Keep `- -a` and `+ ++a` apart.
+                    let mut operand = Printer {
+                        out: String::new(),
+                        indent: self.indent,
+                    };
+                    operand.expr(&unary.expr, PREFIX);
+                    if operand.out.starts_with(&text[text.len() - 1..]) {
+                        self.write(" ");
+                    }
+                    self.write(&operand.out);
+                }
+            }
+        }
+    }
+
+    // A chain like `a + b + c` nests on the left, so it is printed in a loop.
+    fn binary_operation(&mut self, expr: &Expr) {
+        let mut chain: Vec<&Expr> = vec![];
+        let mut current = expr;
+        while let Expr::BinaryOperation(binary) = current {
+            if let Some(Expr::BinaryOperation(parent)) = chain.last() {
+                if binary.kind.precedence() < parent.kind.precedence() {
+                    break;
+                }
+            }
+            chain.push(current);
+            current = &binary.left;
+        }
+
+        let innermost = match chain.last() {
+            Some(Expr::BinaryOperation(binary)) => binary.kind.precedence(),
+            _ => panic!(),
+        };
+        self.expr(current, innermost);
+        for link in chain.iter().rev() {
+            if let Expr::BinaryOperation(binary) = link {
+                self.write(" ");
+                self.write(binary.kind.text());
+                self.write(" ");
+                self.expr(&binary.right, binary.kind.precedence() + 1);
+            }
+        }
+    }
+
+    fn lambda(&mut self, lambda: &Lambda) {
+        match lambda.params.as_slice() {
+            [param] if param.tpe == Type::UnknownType => self.write(param.name.fragment()),
+            params => {
+                self.write("(");
+                self.separated(params, ", ", |p, param| {
+                    if param.tpe == Type::UnknownType {
+                        p.write(param.name.fragment());
+                    } else {
+                        p.param(param);
+                    }
+                });
+                self.write(")");
+            }
+        }
+        self.write(" -> ");
+        if let Some(expr) = &lambda.expr_opt {
+            self.expr(expr, LOWEST);
+        }
+        if let Some(block) = &lambda.block_opt {
+            self.block(block);
+        }
+    }
+
+    fn field_access(&mut self, field: &FieldAccess) {
+        match field.prefix.borrow().as_ref() {
+            FieldAccessPrefix::Package(package) => self.package_prefix(package),
+            FieldAccessPrefix::Expr(expr) => self.expr(expr, PRIMARY),
+        }
+        self.write(".");
+        self.write(field.name.fragment());
+    }
+
+    fn args(&mut self, args: &[Expr]) {
+        self.write("(");
+        self.separated(args, ", ", |p, arg| p.expr(arg, LOWEST));
+        self.write(")");
+    }
+
+    fn tpe(&mut self, tpe: &Type) {
+        match tpe {
+            Type::Class(class) => self.class_type(class),
+            Type::Primitive(primitive) => self.write(primitive_name(&primitive.tpe)),
+            Type::Array(array) => self.array_type(array),
+            Type::Parameterized(parameterized) => self.write(parameterized.name),
+            Type::Void(_) => self.write("void"),
+            Type::Wildcard(wildcard) => self.wildcard(wildcard),
+            // An inferred type, e.g. of a lambda parameter.
+            Type::UnknownType => self.write("var"),
+        }
+    }
+
+    fn class_type(&mut self, class: &ClassType) {
+        if let Some(prefix) = &class.prefix_opt {
+            match prefix.as_ref() {
+                EnclosingType::Package(package) => self.package_prefix(package),
+                EnclosingType::Class(class) => self.class_type(class),
+                EnclosingType::Parameterized(parameterized) => self.write(parameterized.name),
+            }
+            self.write(".");
+        }
+        self.write(class.name);
+        if let Some(type_args) = &class.type_args_opt {
+            self.type_args(type_args);
+        }
+    }
+
+    fn package_prefix(&mut self, package: &PackagePrefix) {
+        if let Some(prefix) = &package.prefix_opt {
+            self.package_prefix(prefix);
+            self.write(".");
+        }
+        self.write(package.name);
+    }
+
+    // The outermost array comes first, e.g. `new int[3][4]` is an array of 3 `int[4]`.
+    fn array_type(&mut self, array: &ArrayType) {
+        let mut sizes = vec![&array.size_opt];
+        let mut element = array.tpe.as_ref();
+        while let Type::Array(inner) = element {
+            sizes.push(&inner.size_opt);
+            element = inner.tpe.as_ref();
+        }
+
+        self.tpe(element);
+        for size_opt in sizes {
+            self.write("[");
+            if let Some(size) = size_opt {
+                self.expr(size, LOWEST);
+            }
+            self.write("]");
+        }
+    }
+
+    fn reference_type(&mut self, tpe: &ReferenceType) {
+        match tpe {
+            ReferenceType::Class(class) => self.class_type(class),
+            ReferenceType::Array(array) => self.array_type(array),
+            ReferenceType::Parameterized(parameterized) => self.write(parameterized.name),
+        }
+    }
+
+    fn wildcard(&mut self, wildcard: &WildcardType) {
+        self.write("?");
+        if !wildcard.extends.is_empty() {
+            self.write(" extends ");
+            self.separated(&wildcard.extends, " & ", |p, t| p.reference_type(t));
+        }
+        if let Some(sup) = &wildcard.super_opt {
+            self.write(" super ");
+            self.reference_type(sup);
+        }
+    }
+
+    fn type_args(&mut self, type_args: &[TypeArg]) {
+        self.write("<");
+        self.separated(type_args, ", ", |p, arg| match arg {
+            TypeArg::Class(class) => p.class_type(class),
+            TypeArg::Parameterized(parameterized) => p.write(parameterized.name),
+            TypeArg::Array(array) => p.array_type(array),
+            TypeArg::Wildcard(wildcard) => p.wildcard(wildcard),
+            TypeArg::Primitive(primitive) => p.write(primitive_name(&primitive.tpe)),
+        });
+        self.write(">");
+    }
+
+    fn type_params(&mut self, type_params: &[TypeParam]) {
+        if type_params.is_empty() {
+            return;
+        }
+        self.write("<");
+        self.separated(type_params, ", ", |p, param| {
+            p.write(param.name.fragment());
+            if !param.extends.is_empty() {
+                p.write(" extends ");
+                p.separated(&param.extends, " & ", |p, extend| match extend {
+                    TypeParamExtend::Class(class) => p.class_type(class),
+                    TypeParamExtend::Parameterized(parameterized) => p.write(parameterized.name),
+                });
+            }
+        });
+        self.write(">");
+    }
+}
+
+fn primitive_name(tpe: &PrimitiveTypeType) -> &'static str {
+    match tpe {
+        PrimitiveTypeType::Boolean => "boolean",
+        PrimitiveTypeType::Byte => "byte",
+        PrimitiveTypeType::Char => "char",
+        PrimitiveTypeType::Double => "double",
+        PrimitiveTypeType::Float => "float",
+        PrimitiveTypeType::Int => "int",
+        PrimitiveTypeType::Long => "long",
+        PrimitiveTypeType::Short => "short",
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::print;
+    use parse::tree::{ClassBodyItem, CompilationUnitItem};
+    use {parse, SourceFile};
+
+    fn reprint(code: &str) -> String {
+        let source = SourceFile::new(
+            "Test.java",
+            &format!("class A {{ void f() {{ {} }} }}", code),
+        );
+        let file = parse::apply(&source).unwrap();
+        match &file.unit.items[0] {
+            CompilationUnitItem::Class(class) => match &class.body.items[0] {
+                ClassBodyItem::Method(method) => {
+                    print::statement(&method.block_opt.as_ref().unwrap().stmts[0])
+                }
+                _ => panic!(),
+            },
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_parentheses() {
+        for code in &[
+            "x = (a + b) * c - (d - e);",
+            "x = a - b - c + (a + b);",
+            "x = (a = b) + 1;",
+            "x = - -a + -(a + b) + - --a + a++ + ++b;",
+            "x = (int) (-a) + (int) ~a;",
+            "x = (a ? b : c) ? d : e ? f : g;",
+            "x = a ? b : (c = d);",
+            "x = (new int[3])[0] + new int[] {1}.length;",
+            "x = ((Object) a).b() + (a + b).c;",
+            "x = !(a instanceof B) && a << 1 < b && (c || d);",
+            "x = \"s\" + (1 + 2) + 'c';",
+            "r = () -> a + (b = c);",
+            "f(x -> x, (int y, int z) -> {\n    return;\n});",
+        ] {
+            assert_eq!(reprint(code), *code);
+        }
+    }
+}
@@ -0,0 +1,935 @@
+use parse::incremental::{apply_edits, TextEdit};
+use parse::search::{expr_span, statement_spans};
+use parse::tree::{ClassBodyItem, CompilationUnitItem, Expr, Import, Package, Statement};
+use parse::walk::{NodeVisitor, Visitor, Walk};
+use std::collections::{HashMap, HashSet};
+use tokenize::span::Span;
+use tokenize::token::Token;
+use JavaFile;
+
+// A node of a parsed file that an edit is anchored to.
+#[derive(Debug, Clone, Copy)]
+pub enum Node<'f, 'a: 'f> {
+    Item(&'f CompilationUnitItem<'a>),
+    Member(&'f ClassBodyItem<'a>),
+    Statement(&'f Statement<'a>),
+    Expr(&'f Expr<'a>),
+    Import(&'f Import<'a>),
+}
+
+#[derive(Debug, PartialEq)]
+pub enum RewriteError {
+    // The node isn't located in the file, e.g. it was built or it is in a skipped body.
+    NoSpan,
+    // The edit overlaps an edit that was added before.
+    Overlap { existing: TextEdit, edit: TextEdit },
+}
+
+// A position in the Language Server Protocol: a 0-based line and a 0-based column in UTF-16 code
+// units.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Position {
+    pub line: u32,
+    pub character: u32,
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct LspRange {
+    pub start: Position,
+    pub end: Position,
+}
+
+#[derive(Debug, PartialEq, Clone)]
+pub struct LspTextEdit {
+    pub range: LspRange,
+    pub new_text: String,
+}
+
+// Collects text edits anchored to the nodes of a file and applies them to its content. The code
+// outside the edited nodes keeps its formatting. An inserted or replacing text is indented like the
+// node, so it can be printed with `parse::print`, which doesn't indent the first level.
+pub struct Rewriter<'f, 'a: 'f> {
+    file: &'f JavaFile<'a>,
+    tokens: Vec<Token<'a>>,
+    // In the order they were added, which is the order of the insertions at the same offset.
+    edits: Vec<TextEdit>,
+    imports: Vec<String>,
+    statement_spans_opt: Option<HashMap<*const Statement<'a>, Span<'a>>>,
+    bodies_opt: Option<HashSet<*const Statement<'a>>>,
+}
+
+impl<'f, 'a> Rewriter<'f, 'a> {
+    pub fn new(file: &'f JavaFile<'a>) -> Rewriter<'f, 'a> {
+        Rewriter {
+            file,
+            tokens: file.tokens(),
+            edits: vec![],
+            imports: vec![],
+            statement_spans_opt: None,
+            bodies_opt: None,
+        }
+    }
+
+    pub fn replace(&mut self, node: Node<'f, 'a>, text: &str) -> Result<(), RewriteError> {
+        let (start, end) = self.range(node)?;
+        let text = reindent(text, self.indentation(start), false);
+        self.push(TextEdit { start, end, text })
+    }
+
+    // A node on its own lines is deleted with its lines. The only statement of a body without
+    // braces, e.g. of `if (a) b();` or `label: b();`, is replaced with `{}`.
+    pub fn delete(&mut self, node: Node<'f, 'a>) -> Result<(), RewriteError> {
+        let (start, end) = self.range(node)?;
+        let (start, end, text) = match node {
+            Node::Expr(_) => (start, end, ""),
+            Node::Statement(statement) if self.is_body(statement) => (start, end, "{}"),
+            _ => match self.own_lines(start, end) {
+                Some((line_start, line_end)) => {
+                    let (line_start, line_end) = self.blank_lines(line_start, line_end);
+                    (line_start, line_end, "")
+                }
+                None => (start, end, ""),
+            },
+        };
+        self.push(TextEdit {
+            start,
+            end,
+            text: text.to_owned(),
+        })
+    }
+
+    // A member or an item is separated by a blank line, and a statement or an import by a line
+    // break. An expression gets the text as it is.
+    pub fn insert_before(&mut self, node: Node<'f, 'a>, text: &str) -> Result<(), RewriteError> {
+        let (start, end) = self.range(node)?;
+        let edit = match (node, self.own_lines(start, end)) {
+            (Node::Expr(_), _) => (start, text.to_owned()),
+            (_, Some((line_start, _))) => (
+                line_start,
+                reindent(text, self.indentation(start), true) + separator(node),
+            ),
+            (_, None) => (start, format!("{} ", text)),
+        };
+        self.push(TextEdit {
+            start: edit.0,
+            end: edit.0,
+            text: edit.1,
+        })
+    }
+
+    pub fn insert_after(&mut self, node: Node<'f, 'a>, text: &str) -> Result<(), RewriteError> {
+        let (start, end) = self.range(node)?;
+        let text = match (node, self.own_lines(start, end)) {
+            (Node::Expr(_), _) => text.to_owned(),
+            (_, Some(_)) => {
+                separator(node).to_owned() + &reindent(text, self.indentation(start), true)
+            }
+            (_, None) => format!(" {}", text),
+        };
+        self.push(TextEdit {
+            start: end,
+            end,
+            text,
+        })
+    }
+
+    // Imports a class by its qualified name unless it is already visible through an import, its
+    // package, or `java.lang`. The import is placed before the first import that sorts after it.
+    pub fn add_import(&mut self, qualified: &str) -> Result<(), RewriteError> {
+        let package = qualified
+            .rsplit_once('.')
+            .map_or("", |(package, _)| package);
+        let unit = &self.file.unit;
+        if package == "java.lang"
+            || unit
+                .package_opt
+                .as_ref()
+                .is_some_and(|p| package_name(p) == package)
+            || self.imports.iter().any(|import| import == qualified)
+            || unit.imports.iter().any(|import| {
+                let name = import_name(import);
+                !import.is_static
+                    && ((!import.is_wildcard && name == qualified)
+                        || (import.is_wildcard && name == package))
+            })
+        {
+            return Ok(());
+        }
+
+        let content = &self.file.source.content;
+        let text = format!("import {};", qualified);
+        let next_opt = unit
+            .imports
+            .iter()
+            .find(|import| !import.is_static && import_name(import).as_str() > qualified);
+        let (offset, text) = if let Some(next) = next_opt {
+            let (start, _) = self.range(Node::Import(next))?;
+            match self.line_start(start) {
+                Some(line_start) => (line_start, text + "\n"),
+                None => (start, text + " "),
+            }
+        } else if let Some(last) = unit.imports.last() {
+            (self.range(Node::Import(last))?.1, format!("\n{}", text))
+        } else if let Some(package) = &unit.package_opt {
+            let end = self
+                .token_index(package.name.start as usize)
+                .and_then(|index| self.find_token(index, ";", true))
+                .ok_or(RewriteError::NoSpan)?;
+            (end, format!("\n\n{}", text))
+        } else if let Some(item) = unit.items.first() {
+            let (start, _) = self.range(Node::Item(item))?;
+            let line_start = self.line_start(start).unwrap_or(start);
+            (line_start, text + "\n\n")
+        } else {
+            (content.len(), text + "\n")
+        };
+
+        self.push(TextEdit {
+            start: offset,
+            end: offset,
+            text,
+        })?;
+        self.imports.push(qualified.to_owned());
+        Ok(())
+    }
+
+    // The edits in the order of the content.
+    pub fn text_edits(&self) -> Vec<TextEdit> {
+        let mut edits = self.edits.clone();
+        edits.sort_by_key(|edit| (edit.start, edit.end));
+        edits
+    }
+
+    pub fn apply(&self) -> String {
+        apply_edits(&self.file.source.content, &self.text_edits())
+    }
+
+    pub fn lsp_edits(&self) -> Vec<LspTextEdit> {
+        self.text_edits()
+            .into_iter()
+            .map(|edit| LspTextEdit {
+                range: LspRange {
+                    start: self.position(edit.start),
+                    end: self.position(edit.end),
+                },
+                new_text: edit.text,
+            })
+            .collect()
+    }
+
+    // The changes as a unified diff with `context` lines around each change, or an empty string if
+    // nothing changes.
+    pub fn unified_diff(&self, context: usize) -> String {
+        let old_lines = self
+            .file
+            .source
+            .content
+            .split_inclusive('\n')
+            .collect::<Vec<_>>();
+        let changes = self.line_changes(old_lines.len());
+        if changes.is_empty() {
+            return String::new();
+        }
+
+        let path = &self.file.source.path;
+        let mut out = format!("--- a/{}\n+++ b/{}\n", path, path);
+        let mut delta = 0isize;
+        let mut index = 0;
+        while index < changes.len() {
+            let mut last = index;
+            while last + 1 < changes.len()
+                && changes[last + 1].old_start - changes[last].old_end() <= 2 * context
+            {
+                last += 1;
+            }
+
+            let old_start = changes[index].old_start.saturating_sub(context);
+            let old_end = (changes[last].old_end() + context).min(old_lines.len());
+            let new_start = (old_start as isize + delta) as usize;
+            let delta_before = delta;
+            let mut body = String::new();
+            let mut line = old_start;
+            for change in &changes[index..=last] {
+                for old in &old_lines[line..change.old_start] {
+                    push_line(&mut body, ' ', old);
+                }
+                for old in &old_lines[change.old_start..change.old_end()] {
+                    push_line(&mut body, '-', old);
+                }
+                for new in &change.new_lines {
+                    push_line(&mut body, '+', new);
+                }
+                line = change.old_end();
+                delta += change.new_lines.len() as isize - change.old_len as isize;
+            }
+            for old in &old_lines[line..old_end] {
+                push_line(&mut body, ' ', old);
+            }
+
+            let old_len = old_end - old_start;
+            let new_len = (old_len as isize + delta - delta_before) as usize;
+            out.push_str(&format!(
+                "@@ -{},{} +{},{} @@\n",
+                hunk_start(old_start, old_len),
+                old_len,
+                hunk_start(new_start, new_len),
+                new_len
+            ));
+            out.push_str(&body);
+            index = last + 1;
+        }
+
+        out
+    }
+
+    // The edits as replacements of whole lines, without the lines that stay the same.
+    fn line_changes(&self, line_count: usize) -> Vec<LineChange> {
+        let content = &self.file.source.content;
+        let line_starts = &self.file.source.line_starts;
+        let line_offset = |line: usize| {
+            line_starts
+                .get(line)
+                .map_or(content.len(), |start| *start as usize)
+        };
+        let line_of = |offset: usize| match line_starts.binary_search(&(offset as u32)) {
+            Ok(line) => line,
+            Err(line) => line - 1,
+        };
+
+        let edits = self.text_edits();
+        let mut changes = vec![];
+        let mut index = 0;
+        while index < edits.len() {
+            let first = line_of(edits[index].start);
+            let mut end = first;
+            let mut group = index;
+            while group < edits.len() && (group == index || line_of(edits[group].start) < end) {
+                let edit = &edits[group];
+                let last = line_of(edit.end);
+                // An edit that ends at the start of a line doesn't touch that line.
+                let edit_end = if edit.end > edit.start && line_offset(last) == edit.end {
+                    last
+                } else {
+                    last + 1
+                };
+                end = end.max(edit_end.min(line_count));
+                group += 1;
+            }
+
+            let chunk_start = line_offset(first);
+            let chunk = &content[chunk_start..line_offset(end)];
+            let shifted = edits[index..group]
+                .iter()
+                .map(|edit| TextEdit {
+                    start: edit.start - chunk_start,
+                    end: edit.end - chunk_start,
+                    text: edit.text.clone(),
+                })
+                .collect::<Vec<_>>();
+            let new_chunk = apply_edits(chunk, &shifted);
+
+            let old = chunk.split_inclusive('\n').collect::<Vec<_>>();
+            let new = new_chunk.split_inclusive('\n').collect::<Vec<_>>();
+            let prefix = old.iter().zip(&new).take_while(|(o, n)| o == n).count();
+            let suffix = old[prefix..]
+                .iter()
+                .rev()
+                .zip(new[prefix..].iter().rev())
+                .take_while(|(o, n)| o == n)
+                .count();
+            if old.len() != new.len() || prefix != old.len() {
+                changes.push(LineChange {
+                    old_start: first + prefix,
+                    old_len: old.len() - prefix - suffix,
+                    new_lines: new[prefix..new.len() - suffix]
+                        .iter()
+                        .map(|line| line.to_string())
+                        .collect(),
+                });
+            }
+            index = group;
+        }
+
+        changes
+    }
+
+    fn push(&mut self, edit: TextEdit) -> Result<(), RewriteError> {
+        for existing in &self.edits {
+            if *existing == edit {
+                return Ok(());
+            }
+            let overlaps = existing.start < edit.end && edit.start < existing.end;
+            let inside =
+                |outer: &TextEdit, offset: usize| outer.start < offset && offset < outer.end;
+            if overlaps || inside(existing, edit.start) || inside(&edit, existing.start) {
+                return Err(RewriteError::Overlap {
+                    existing: existing.clone(),
+                    edit,
+                });
+            }
+        }
+        self.edits.push(edit);
+        Ok(())
+    }
+
+    fn range(&mut self, node: Node<'f, 'a>) -> Result<(usize, usize), RewriteError> {
+        let span_opt = match node {
+            Node::Item(item) => item.span_opt(),
+            Node::Member(ClassBodyItem::StaticInitializer(block)) => block.span_opt.map(|span| {
+                // The span of the block starts at `{`.
+                match self.token_index(span.start as usize) {
+                    Some(index) if index > 0 && self.is_token(index - 1, "static") => {
+                        self.tokens[index - 1].span().to(&span)
+                    }
+                    _ => span,
+                }
+            }),
+            Node::Member(member) => member.span_opt(),
+            Node::Statement(statement) => {
+                let (file, tokens) = (self.file, &self.tokens);
+                self.statement_spans_opt
+                    .get_or_insert_with(|| statement_spans(file, tokens))
+                    .get(&(statement as *const _))
+                    .cloned()
+            }
+            Node::Expr(expr) => expr_span(&self.tokens, expr),
+            Node::Import(import) => self.import_span(import),
+        };
+
+        match span_opt {
+            Some(span) if span.file_id() == self.file.source.id() => {
+                Ok((span.start as usize, span.end as usize))
+            }
+            _ => Err(RewriteError::NoSpan),
+        }
+    }
+
+    // From `import` to `;`.
+    fn import_span(&self, import: &Import<'a>) -> Option<Span<'a>> {
+        let name = self.token_index(import.name.start as usize)?;
+        let first = (0..name)
+            .rev()
+            .find(|index| self.is_token(*index, "import"))?;
+        let last = self.find_token(name, ";", false)?;
+        Some(self.tokens[first].span().to(&self.tokens[last].span()))
+    }
+
+    fn token_index(&self, offset: usize) -> Option<usize> {
+        self.tokens
+            .binary_search_by_key(&(offset as u32), |token| token.span().start)
+            .ok()
+    }
+
+    fn is_token(&self, index: usize, fragment: &str) -> bool {
+        self.tokens
+            .get(index)
+            .is_some_and(|token| token.span().fragment() == fragment)
+    }
+
+    // The index of the next token with the fragment, or its end offset if `as_offset`.
+    fn find_token(&self, from: usize, fragment: &str, as_offset: bool) -> Option<usize> {
+        let index = (from..self.tokens.len()).find(|index| self.is_token(*index, fragment))?;
+        if as_offset {
+            Some(self.tokens[index].span().end as usize)
+        } else {
+            Some(index)
+        }
+    }
+
+    fn is_body(&mut self, statement: &Statement<'a>) -> bool {
+        let file = self.file;
+        self.bodies_opt
+            .get_or_insert_with(|| bodies(file))
+            .contains(&(statement as *const _))
+    }
+
+    // The start of the line if only whitespace is before `offset` on it.
+    fn line_start(&self, offset: usize) -> Option<usize> {
+        let content = &self.file.source.content;
+        let line_start = content[..offset].rfind('\n').map_or(0, |index| index + 1);
+        if content[line_start..offset].trim().is_empty() {
+            Some(line_start)
+        } else {
+            None
+        }
+    }
+
+    // The lines of `start..end` including the line break if nothing else is on them.
+    fn own_lines(&self, start: usize, end: usize) -> Option<(usize, usize)> {
+        let content = &self.file.source.content;
+        let line_start = self.line_start(start)?;
+
+        let rest = &content[end..];
+        let (tail, line_end) = match rest.find('\n') {
+            Some(index) => (&rest[..index], end + index + 1),
+            None => (rest, content.len()),
+        };
+        if !tail.trim().is_empty() {
+            return None;
+        }
+
+        Some((line_start, line_end))
+    }
+
+    // Extends deleted lines over a blank line, so no two blank lines or a blank line before `}`
+    // remain.
+    fn blank_lines(&self, line_start: usize, line_end: usize) -> (usize, usize) {
+        let content = &self.file.source.content;
+        if line_start == 0 {
+            return (line_start, line_end);
+        }
+
+        let previous_start = content[..line_start - 1]
+            .rfind('\n')
+            .map_or(0, |index| index + 1);
+        if !content[previous_start..line_start].trim().is_empty() {
+            return (line_start, line_end);
+        }
+
+        let next = &content[line_end..];
+        let next_line = next.find('\n').map_or(next, |index| &next[..=index]);
+        if !next_line.is_empty() && next_line.trim().is_empty() {
+            (line_start, line_end + next_line.len())
+        } else if next_line.trim_start().starts_with('}') {
+            (previous_start, line_end)
+        } else {
+            (line_start, line_end)
+        }
+    }
+
+    fn indentation(&self, offset: usize) -> &'f str {
+        let content = &self.file.source.content;
+        let line_start = content[..offset].rfind('\n').map_or(0, |index| index + 1);
+        let line = &content[line_start..];
+        let len = line
+            .find(|c: char| c == '\n' || !c.is_whitespace())
+            .unwrap_or(line.len());
+        &line[..len]
+    }
+
+    fn position(&self, offset: usize) -> Position {
+        let (line, _) = self.file.source.line_col(offset as u32);
+        let line_start = self.file.source.line_starts[line - 1] as usize;
+        Position {
+            line: (line - 1) as u32,
+            character: self.file.source.content[line_start..offset]
+                .encode_utf16()
+                .count() as u32,
+        }
+    }
+}
+
+struct LineChange {
+    old_start: usize,
+    old_len: usize,
+    new_lines: Vec<String>,
+}
+
+impl LineChange {
+    fn old_end(&self) -> usize {
+        self.old_start + self.old_len
+    }
+}
+
+fn separator(node: Node) -> &'static str {
+    match node {
+        Node::Item(_) | Node::Member(_) => "\n\n",
+        _ => "\n",
+    }
+}
+
+// Indents the lines after the first one, and the first one too if `first`.
+fn reindent(text: &str, indentation: &str, first: bool) -> String {
+    text.split('\n')
+        .enumerate()
+        .map(|(index, line)| {
+            if (index > 0 || first) && !line.is_empty() {
+                format!("{}{}", indentation, line)
+            } else {
+                line.to_owned()
+            }
+        })
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+// The 1-based line of a hunk, or the line before an empty one.
+fn hunk_start(start: usize, len: usize) -> usize {
+    if len == 0 {
+        start
+    } else {
+        start + 1
+    }
+}
+
+fn push_line(out: &mut String, prefix: char, line: &str) {
+    out.push(prefix);
+    out.push_str(line);
+    if !line.ends_with('\n') {
+        out.push_str("\n\\ No newline at end of file\n");
+    }
+}
+
+// The statements that are the body of `if`, `else`, `while`, `for`, or `do` without braces, or the
+// statement of a label.
+fn bodies<'a>(file: &JavaFile<'a>) -> HashSet<*const Statement<'a>> {
+    struct Bodies<'a> {
+        statements: HashSet<*const Statement<'a>>,
+    }
+
+    impl<'a> Visitor<'a> for Bodies<'a> {}
+
+    impl<'f, 'a> NodeVisitor<'f, 'a> for Bodies<'a> {
+        fn statement(&mut self, statement: &'f Statement<'a>) {
+            let blocks = match statement {
+                Statement::IfElse(if_else) => {
+                    let mut blocks = vec![&if_else.block];
+                    blocks.extend(&if_else.else_block_opt);
+                    blocks
+                }
+                Statement::WhileLoop(while_loop) => vec![&while_loop.block],
+                Statement::DoWhile(do_while) => vec![&do_while.block],
+                Statement::ForLoop(for_loop) => vec![&for_loop.block],
+                Statement::Foreach(foreach) => vec![&foreach.block],
+                Statement::Labeled(labeled) => {
+                    self.statements.insert(&*labeled.statement);
+                    vec![]
+                }
+                _ => vec![],
+            };
+            for block in blocks {
+                if block.span_opt.is_none() {
+                    self.statements
+                        .extend(block.stmts.iter().map(|statement| statement as *const _));
+                }
+            }
+        }
+    }
+
+    let mut bodies = Bodies {
+        statements: HashSet::new(),
+    };
+    file.unit.visit(&mut bodies);
+    bodies.statements
+}
+
+fn import_name(import: &Import) -> String {
+    let mut name = import.name.fragment().to_owned();
+    let mut prefix_opt = import.prefix_opt.as_ref();
+    while let Some(prefix) = prefix_opt {
+        name = format!("{}.{}", prefix.name.fragment(), name);
+        prefix_opt = prefix.prefix_opt.as_ref();
+    }
+    name
+}
+
+fn package_name(package: &Package) -> String {
+    match &package.prefix_opt {
+        Some(prefix) => format!("{}.{}", package_name(prefix), package.name.fragment()),
+        None => package.name.fragment().to_owned(),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::builder::{expr_stmt, Builder, MethodBuilder};
+    use parse::incremental::TextEdit;
+    use parse::print;
+    use parse::rewrite::{LspRange, LspTextEdit, Node, Position, RewriteError, Rewriter};
+    use parse::tree::{AssignOperator, ClassBodyItem, CompilationUnitItem, Expr, Statement};
+    use {parse, SourceFile};
+
+    fn members<'f, 'a>(item: &'f CompilationUnitItem<'a>) -> &'f [ClassBodyItem<'a>] {
+        match item {
+            CompilationUnitItem::Class(class) => &class.body.items,
+            _ => panic!(),
+        }
+    }
+
+    #[test]
+    fn test_rewrite() {
+        let source = SourceFile::new(
+            "Test.java",
+            r#"package app;
+
+import java.util.List;
+import java.util.Map;
+
+class Test {
+    int count;
+
+    void run(List<String> names) {
+        log("start");
+        if (names.isEmpty()) return;
+        count = names.size() + 1; // one more
+    }
+}
+"#,
+        );
+        let file = parse::apply(&source).unwrap();
+        let members = members(&file.unit.items[0]);
+        let stmts = match &members[1] {
+            ClassBodyItem::Method(method) => &method.block_opt.as_ref().unwrap().stmts,
+            _ => panic!(),
+        };
+        let (sum, if_body) = match (&stmts[1], &stmts[2]) {
+            (Statement::IfElse(if_else), Statement::Expr(Expr::Assignment(assignment))) => {
+                (assignment.expr.as_ref(), &if_else.block.stmts[0])
+            }
+            _ => panic!(),
+        };
+        let b = Builder::new();
+        let reset = MethodBuilder::new(&b, "reset")
+            .body(vec![expr_stmt(b.assign(
+                b.name_expr("count"),
+                AssignOperator::Assign,
+                b.int(0),
+            ))])
+            .build();
+
+        let mut rewriter = Rewriter::new(&file);
+        rewriter.replace(Node::Expr(sum), "names.size()").unwrap();
+        rewriter.delete(Node::Statement(&stmts[0])).unwrap();
+        rewriter.delete(Node::Statement(if_body)).unwrap();
+        rewriter
+            .insert_after(
+                Node::Member(&members[0]),
+                &print::class_body_item(&ClassBodyItem::Method(reset)),
+            )
+            .unwrap();
+        rewriter.add_import("java.util.ArrayList").unwrap();
+        rewriter.add_import("java.util.Map").unwrap();
+        rewriter.add_import("java.lang.String").unwrap();
+        match rewriter.delete(Node::Statement(&stmts[2])) {
+            Err(RewriteError::Overlap { existing, .. }) => {
+                assert_eq!(existing.text, "names.size()")
+            }
+            _ => panic!(),
+        }
+
+        let content = rewriter.apply();
+        assert_eq!(
+            content,
+            r#"package app;
+
+import java.util.ArrayList;
+import java.util.List;
+import java.util.Map;
+
+class Test {
+    int count;
+
+    void reset() {
+        count = 0;
+    }
+
+    void run(List<String> names) {
+        if (names.isEmpty()) {}
+        count = names.size(); // one more
+    }
+}
+"#
+        );
+        assert!(parse::apply(&SourceFile::new("Test.java", &content)).is_ok());
+    }
+
+    #[test]
+    fn test_diff() {
+        let source = SourceFile::new(
+            "A.java",
+            "class A {\n  String s = \"é\"; int x = 1;\n  int y;\n}",
+        );
+        let file = parse::apply(&source).unwrap();
+        let members = members(&file.unit.items[0]);
+        let one = match &members[1] {
+            ClassBodyItem::FieldDeclarators(field) => {
+                field.declarators[0].expr_opt.as_ref().unwrap()
+            }
+            _ => panic!(),
+        };
+
+        let mut rewriter = Rewriter::new(&file);
+        rewriter.replace(Node::Expr(one), "2").unwrap();
+        rewriter
+            .insert_after(Node::Member(&members[2]), "int z;")
+            .unwrap();
+
+        let edits = rewriter.lsp_edits();
+        assert_eq!(
+            edits[0].range,
+            LspRange {
+                start: Position {
+                    line: 1,
+                    character: 26
+                },
+                end: Position {
+                    line: 1,
+                    character: 27
+                },
+            }
+        );
+        assert_eq!(
+            rewriter.unified_diff(1),
+            "--- a/A.java\n+++ b/A.java\n@@ -1,4 +1,6 @@\n class A {\n-  String s = \"é\"; int x = 1;\n+  String s = \"é\"; int x = 2;\n   int y;\n+\n+  int z;\n }\n\\ No newline at end of file\n"
+        );
+        assert_eq!(Rewriter::new(&file).unified_diff(3), "");
+    }
+
+    #[test]
+    fn test_delete_body() {
+        let source = SourceFile::new(
+            "A.java",
+            r#"class A {
+    void run(boolean a) {
+        if (a) one(); else if (!a) two();
+        outer: three();
+        @Deprecated(since = "1") class Local {}
+        do four(); while (a);
+    }
+}
+"#,
+        );
+        let file = parse::apply(&source).unwrap();
+        let stmts = match &members(&file.unit.items[0])[0] {
+            ClassBodyItem::Method(method) => &method.block_opt.as_ref().unwrap().stmts,
+            _ => panic!(),
+        };
+        let (one, two) = match &stmts[0] {
+            Statement::IfElse(if_else) => {
+                match &if_else.else_block_opt.as_ref().unwrap().stmts[0] {
+                    Statement::IfElse(else_if) => {
+                        (&if_else.block.stmts[0], &else_if.block.stmts[0])
+                    }
+                    _ => panic!(),
+                }
+            }
+            _ => panic!(),
+        };
+        let three = match &stmts[1] {
+            Statement::Labeled(labeled) => labeled.statement.as_ref(),
+            _ => panic!(),
+        };
+        let four = match &stmts[3] {
+            Statement::DoWhile(do_while) => &do_while.block.stmts[0],
+            _ => panic!(),
+        };
+
+        let mut rewriter = Rewriter::new(&file);
+        for statement in &[one, two, three, four] {
+            rewriter.delete(Node::Statement(statement)).unwrap();
+        }
+        // A local class after `)` isn't a body.
+        rewriter.delete(Node::Statement(&stmts[2])).unwrap();
+
+        assert_eq!(
+            rewriter.apply(),
+            r#"class A {
+    void run(boolean a) {
+        if (a) {} else if (!a) {}
+        outer: {}
+        do {} while (a);
+    }
+}
+"#
+        );
+    }
+
+    #[test]
+    fn test_overlap() {
+        let source = SourceFile::new("A.java", "class A {\n  int x = 1 + 2;\n}\n");
+        let file = parse::apply(&source).unwrap();
+        let sum = match &members(&file.unit.items[0])[0] {
+            ClassBodyItem::FieldDeclarators(field) => {
+                field.declarators[0].expr_opt.as_ref().unwrap()
+            }
+            _ => panic!(),
+        };
+        let (left, right) = match sum {
+            Expr::BinaryOperation(operation) => (&operation.left, &operation.right),
+            _ => panic!(),
+        };
+
+        let mut rewriter = Rewriter::new(&file);
+        rewriter.replace(Node::Expr(left), "3").unwrap();
+        // The same edit again is ignored, and an edit next to it doesn't overlap.
+        rewriter.replace(Node::Expr(left), "3").unwrap();
+        rewriter.insert_before(Node::Expr(right), "-").unwrap();
+        rewriter.insert_after(Node::Expr(right), "0").unwrap();
+
+        assert_eq!(
+            rewriter.replace(Node::Expr(sum), "4"),
+            Err(RewriteError::Overlap {
+                existing: TextEdit {
+                    start: 20,
+                    end: 21,
+                    text: "3".to_owned()
+                },
+                edit: TextEdit {
+                    start: 20,
+                    end: 25,
+                    text: "4".to_owned()
+                },
+            })
+        );
+        assert_eq!(rewriter.apply(), "class A {\n  int x = 3 + -20;\n}\n");
+    }
+
+    #[test]
+    fn test_lsp_position() {
+        // `😀` is 4 bytes in UTF-8 and 2 code units in UTF-16, and `é` is 2 bytes and 1 code unit.
+        let source = SourceFile::new("A.java", "class A {\n  String s = \"😀é\"; int x = 1;\n}\n");
+        let file = parse::apply(&source).unwrap();
+        let one = match &members(&file.unit.items[0])[1] {
+            ClassBodyItem::FieldDeclarators(field) => {
+                field.declarators[0].expr_opt.as_ref().unwrap()
+            }
+            _ => panic!(),
+        };
+
+        let mut rewriter = Rewriter::new(&file);
+        rewriter.replace(Node::Expr(one), "10").unwrap();
+
+        assert_eq!(
+            rewriter.lsp_edits(),
+            vec![LspTextEdit {
+                range: LspRange {
+                    start: Position {
+                        line: 1,
+                        character: 28
+                    },
+                    end: Position {
+                        line: 1,
+                        character: 29
+                    },
+                },
+                new_text: "10".to_owned(),
+            }]
+        );
+    }
+
+    #[test]
+    fn test_unified_diff() {
+        let source = SourceFile::new(
+            "A.java",
+            "class A {\n  int a;\n  int b;\n  int c;\n  int d;\n  int e;\n  int f;\n}\n",
+        );
+        let file = parse::apply(&source).unwrap();
+        let members = members(&file.unit.items[0]);
+
+        let mut rewriter = Rewriter::new(&file);
+        rewriter.delete(Node::Member(&members[0])).unwrap();
+        rewriter
+            .replace(Node::Member(&members[5]), "long f;")
+            .unwrap();
+
+        // The changes are more than twice the context apart, so they are in separate hunks.
+        assert_eq!(
+            rewriter.unified_diff(1),
+            "--- a/A.java\n+++ b/A.java\n@@ -1,3 +1,2 @@\n class A {\n-  int a;\n   int b;\n@@ -6,3 +5,3 @@\n   int e;\n-  int f;\n+  long f;\n }\n"
+        );
+        assert_eq!(
+            rewriter.unified_diff(2),
+            "--- a/A.java\n+++ b/A.java\n@@ -1,8 +1,7 @@\n class A {\n-  int a;\n   int b;\n   int c;\n   int d;\n   int e;\n-  int f;\n+  long f;\n }\n"
+        );
+    }
+}
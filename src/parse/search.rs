@@ -0,0 +1,1509 @@
+use parse::statement::try::parse_catch;
+use parse::tree::{
+    Annotated, AnnotatedValue, ArrayType, Assigned, Block, Catch, ClassType, EnclosingType, Expr,
+    FieldAccessPrefix, MethodReferencePrimary, Modifier, PackagePrefix, Param, PrimitiveTypeType,
+    ReferenceType, StandaloneVariableDeclarator, Statement, StaticType, TryResource, Type, TypeArg,
+    VariableDeclarator,
+};
+use parse::walk::{NodeVisitor, Visitor, Walk};
+use parse::{expr, statement, statement_ranges, token_range, Context};
+use std::collections::HashMap;
+use std::ops::Range;
+use tokenize::span::Span;
+use tokenize::token::Token;
+use {tokenize, JavaFile, SourceFile};
+
+// A piece of Java code to search for, e.g. `$x.equals($y)`. An identifier starting with `$` is a
+// placeholder: in place of an expression it matches any expression, in place of a type any type,
+// in place of a name any name, and `$s;` matches any statement. A placeholder used twice matches
+// the same code twice.
+pub struct Template<'t> {
+    pattern: Pattern<'t>,
+    // The placeholders whose expressions must have the types, e.g. ("$x", "java.lang.String").
+    types: Vec<(String, String)>,
+}
+
+// Boxed, since the nodes are large.
+enum Pattern<'t> {
+    Expr(Box<Expr<'t>>),
+    Statement(Box<Statement<'t>>),
+    Catch(Box<Catch<'t>>),
+}
+
+impl<'t> Template<'t> {
+    // Parses an expression, a statement, or a catch clause.
+    pub fn parse(source: &'t SourceFile) -> Result<Template<'t>, Span<'t>> {
+        let tokens = tokenize::apply(source)?;
+        let end = Span::new(source, source.content.len(), source.content.len());
+        let mut context = Context::new(&source.path);
+
+        let result = if tokens
+            .first()
+            .is_some_and(|token| token.span().fragment() == "catch")
+        {
+            parse_catch(&tokens, &mut context)
+                .map(|(rest, catch)| (rest, Pattern::Catch(Box::new(catch))))
+        } else {
+            match expr::parse(&tokens, &mut context) {
+                Ok(([], expr)) => Ok((&[][..], Pattern::Expr(Box::new(expr)))),
+                _ => statement::parse(&tokens, &mut context)
+                    .map(|(rest, statement)| (rest, Pattern::Statement(Box::new(statement)))),
+            }
+        };
+
+        match result {
+            Ok(([], pattern)) => Ok(Template {
+                pattern,
+                types: vec![],
+            }),
+            Ok((rest, _)) | Err(rest) => Err(rest.first().map_or(end, |token| token.span())),
+        }
+    }
+
+    // Requires the expression of the placeholder to have the type, which is the qualified name of
+    // a class (e.g. "java.lang.String"), a primitive (e.g. "int"), or an array (e.g. "int[]"). It
+    // only matches an analyzed tree whose type is known (see `Expr::tpe_opt`).
+    pub fn with_type(mut self, placeholder: &str, tpe: &str) -> Template<'t> {
+        self.types.push((placeholder.to_owned(), tpe.to_owned()));
+        self
+    }
+}
+
+#[derive(Debug)]
+pub struct Match<'f, 'a: 'f> {
+    pub span: Span<'a>,
+    // In the order the placeholders appear in the template.
+    pub bindings: Vec<Binding<'f, 'a>>,
+}
+
+impl<'f, 'a> Match<'f, 'a> {
+    pub fn get(&self, placeholder: &str) -> Option<&Binding<'f, 'a>> {
+        self.bindings
+            .iter()
+            .find(|binding| binding.placeholder == placeholder)
+    }
+}
+
+#[derive(Debug)]
+pub struct Binding<'f, 'a: 'f> {
+    // With the `$`, e.g. "$x".
+    pub placeholder: String,
+    pub node: Bound<'f, 'a>,
+    pub span: Span<'a>,
+}
+
+#[derive(Debug)]
+pub enum Bound<'f, 'a: 'f> {
+    Expr(&'f Expr<'a>),
+    Statement(&'f Statement<'a>),
+    Type(&'f Type<'a>),
+    ClassType(&'f ClassType<'a>),
+    TypeArg(&'f TypeArg<'a>),
+    Name(Span<'a>),
+}
+
+// Finds the code that matches the template, outer matches first.
+pub fn search<'f, 'a>(file: &'f JavaFile<'a>, template: &Template) -> Vec<Match<'f, 'a>> {
+    let tokens = file.tokens();
+    let mut collector = Collector::new(&tokens, &file.source.path);
+    file.unit.visit(&mut collector);
+
+    let mut matches = vec![];
+    for candidate in &collector.candidates {
+        let mut matcher = Matcher {
+            extents: collector.extents,
+            statement_spans: &collector.statement_spans,
+            bindings: vec![],
+        };
+        let span_opt = match (&template.pattern, candidate) {
+            (Pattern::Expr(t), Candidate::Expr(e)) => {
+                if matcher.expr(t, e) {
+                    collector.extents.expr(e)
+                } else {
+                    None
+                }
+            }
+            (Pattern::Statement(t), Candidate::Statement(e)) => {
+                if matcher.statement(t, e) {
+                    collector.statement_spans.get(&(*e as *const _)).cloned()
+                } else {
+                    None
+                }
+            }
+            (Pattern::Catch(t), Candidate::Catch(e)) => {
+                if matcher.catch(t, e) {
+                    collector.extents.catch(e)
+                } else {
+                    None
+                }
+            }
+            _ => None,
+        };
+
+        if let Some(span) = span_opt {
+            if matcher.has_types(&template.types) {
+                matches.push(Match {
+                    span,
+                    bindings: matcher.bindings,
+                });
+            }
+        }
+    }
+
+    matches
+}
+
+// The spans of the statements of the file by their addresses, since statements don't have spans.
+// The statements of skipped bodies have none. `tokens` are the tokens of the file (see
+// `JavaFile::tokens`).
+pub fn statement_spans<'a>(
+    file: &JavaFile<'a>,
+    tokens: &[Token<'a>],
+) -> HashMap<*const Statement<'a>, Span<'a>> {
+    let mut collector = Collector::new(tokens, &file.source.path);
+    file.unit.visit(&mut collector);
+    collector.statement_spans
+}
+
+// The span of an expression without its redundant parentheses. `tokens` are the tokens of its file.
+pub fn expr_span<'a>(tokens: &[Token<'a>], expr: &Expr<'a>) -> Option<Span<'a>> {
+    Extents { tokens }.expr(expr)
+}
+
+fn is_placeholder(name: &str) -> bool {
+    name.len() > 1 && name.starts_with('$')
+}
+
+// The qualified name of a type, e.g. "java.util.List" or "int[]".
+fn type_name(tpe: &Type) -> Option<String> {
+    match tpe {
+        Type::Class(class) => class
+            .def_opt
+            .map(|def| unsafe { &*def }.import_path.clone()),
+        Type::Primitive(primitive) => Some(
+            match primitive.tpe {
+                PrimitiveTypeType::Boolean => "boolean",
+                PrimitiveTypeType::Byte => "byte",
+                PrimitiveTypeType::Char => "char",
+                PrimitiveTypeType::Double => "double",
+                PrimitiveTypeType::Float => "float",
+                PrimitiveTypeType::Int => "int",
+                PrimitiveTypeType::Long => "long",
+                PrimitiveTypeType::Short => "short",
+            }
+            .to_owned(),
+        ),
+        Type::Array(array) => type_name(&array.tpe).map(|name| format!("{}[]", name)),
+        Type::Parameterized(parameterized) => Some(parameterized.name.to_owned()),
+        Type::Void(_) => Some("void".to_owned()),
+        Type::Wildcard(_) | Type::UnknownType => None,
+    }
+}
+
+fn package_name(package: &PackagePrefix) -> String {
+    match &package.prefix_opt {
+        Some(prefix) => format!("{}.{}", package_name(prefix), package.name),
+        None => package.name.to_owned(),
+    }
+}
+
+fn enclosing_name(enclosing: &EnclosingType) -> String {
+    match enclosing {
+        EnclosingType::Package(package) => package_name(package),
+        EnclosingType::Class(class) => match &class.prefix_opt {
+            Some(prefix) => format!("{}.{}", enclosing_name(prefix), class.name),
+            None => class.name.to_owned(),
+        },
+        EnclosingType::Parameterized(parameterized) => parameterized.name.to_owned(),
+    }
+}
+
+// The nodes are borrowed from the file for as long as the matches live.
+enum Candidate<'f, 'a: 'f> {
+    Expr(&'f Expr<'a>),
+    Statement(&'f Statement<'a>),
+    Catch(&'f Catch<'a>),
+}
+
+// Collects the expressions, the statements, and the catch clauses of a file in the order of the
+// source. Statements don't have spans, so their spans are found along the way: a statement's range
+// of tokens is registered by the block, the switch case, or the statement that contains it, and the
+// statement takes it when it is visited.
+struct Collector<'t, 'p, 'f, 'a: 't + 'f> {
+    extents: Extents<'t, 'a>,
+    path: &'p str,
+    candidates: Vec<Candidate<'f, 'a>>,
+    statement_spans: HashMap<*const Statement<'a>, Span<'a>>,
+    ranges: HashMap<*const Statement<'a>, Range<usize>>,
+}
+
+impl<'t, 'p, 'f, 'a> Visitor<'a> for Collector<'t, 'p, 'f, 'a> {}
+
+impl<'t, 'p, 'f, 'a> NodeVisitor<'f, 'a> for Collector<'t, 'p, 'f, 'a> {
+    fn expr(&mut self, expr: &'f Expr<'a>) {
+        self.candidates.push(Candidate::Expr(expr));
+    }
+
+    fn statement(&mut self, statement: &'f Statement<'a>) {
+        let start_opt = match self.ranges.remove(&(statement as *const _)) {
+            Some(range) => {
+                let span = self.extents.span(range.start, range.end - 1);
+                self.statement_spans.insert(statement, span);
+                Some(range.start)
+            }
+            None => None,
+        };
+        self.candidates.push(Candidate::Statement(statement));
+
+        // The body after `keyword (...)`.
+        let extents = self.extents;
+        let after_parens = |start_opt: Option<usize>| {
+            start_opt
+                .and_then(|start| extents.close(start + 1))
+                .map(|close| close + 1)
+        };
+
+        match statement {
+            Statement::ForLoop(for_loop) => {
+                self.body(&for_loop.block, after_parens(start_opt));
+            }
+            Statement::Foreach(foreach) => {
+                self.body(&foreach.block, after_parens(start_opt));
+            }
+            Statement::IfElse(if_else) => {
+                let last_opt = self.body(&if_else.block, after_parens(start_opt));
+                if let Some(else_block) = &if_else.else_block_opt {
+                    self.body(else_block, last_opt.map(|last| last + 2));
+                }
+            }
+            Statement::Labeled(labeled) => {
+                if let Some(range) = start_opt.and_then(|start| self.single_range(start + 2)) {
+                    self.ranges.insert(&*labeled.statement, range);
+                }
+            }
+            Statement::Switch(switch) => {
+                let mut next_opt = after_parens(start_opt).map(|brace| brace + 1);
+                for case in &switch.cases {
+                    let ranges = match next_opt.and_then(|next| self.extents.case_colon(next)) {
+                        Some(colon) => {
+                            let ranges = statement_ranges(
+                                self.extents.tokens,
+                                colon + 1,
+                                self.extents.tokens.len(),
+                                self.path,
+                            );
+                            next_opt = Some(ranges.last().map_or(colon + 1, |range| range.end));
+                            ranges
+                        }
+                        None => vec![],
+                    };
+                    self.register(&case.stmts, ranges);
+                }
+            }
+            Statement::WhileLoop(while_loop) => {
+                self.body(&while_loop.block, after_parens(start_opt));
+            }
+            Statement::DoWhile(do_while) => {
+                self.body(&do_while.block, start_opt.map(|start| start + 1));
+            }
+            _ => (),
+        }
+    }
+
+    fn block(&mut self, block: &'f Block<'a>) {
+        if let Some((first, last)) = self.braces(block) {
+            let ranges = statement_ranges(self.extents.tokens, first + 1, last, self.path);
+            self.register(&block.stmts, ranges);
+        }
+    }
+
+    fn catch(&mut self, catch: &'f Catch<'a>) {
+        self.candidates.push(Candidate::Catch(catch));
+    }
+}
+
+impl<'t, 'p, 'f, 'a> Collector<'t, 'p, 'f, 'a> {
+    fn new(tokens: &'t [Token<'a>], path: &'p str) -> Collector<'t, 'p, 'f, 'a> {
+        Collector {
+            extents: Extents { tokens },
+            path,
+            candidates: vec![],
+            statement_spans: HashMap::new(),
+            ranges: HashMap::new(),
+        }
+    }
+
+    // The indices of the braces of a block, which has none when it is a single statement.
+    fn braces(&self, block: &Block<'a>) -> Option<(usize, usize)> {
+        block
+            .span_opt
+            .and_then(|span| token_range(self.extents.tokens, span))
+    }
+
+    // Registers the range of the body of a statement, which is either a block or a single statement
+    // that starts at `start_opt`, and returns the index of its last token. A block registers its
+    // own statements.
+    fn body(&mut self, block: &Block<'a>, start_opt: Option<usize>) -> Option<usize> {
+        if block.span_opt.is_some() {
+            return self.braces(block).map(|(_, last)| last);
+        }
+
+        let range_opt = start_opt.and_then(|start| self.single_range(start));
+        let last_opt = range_opt.as_ref().map(|range| range.end - 1);
+        self.register(&block.stmts, range_opt.into_iter().collect());
+        last_opt
+    }
+
+    fn single_range(&self, start: usize) -> Option<Range<usize>> {
+        let tokens = self.extents.tokens;
+        match statement::parse(
+            &tokens[start.min(tokens.len())..],
+            &mut Context::new(self.path),
+        ) {
+            Ok((rest, _)) => Some(start..(tokens.len() - rest.len())),
+            Err(_) => None,
+        }
+    }
+
+    // The ranges are only used when there is one for each statement.
+    fn register(&mut self, statements: &[Statement<'a>], ranges: Vec<Range<usize>>) {
+        if ranges.len() == statements.len() {
+            for (statement, range) in statements.iter().zip(ranges) {
+                self.ranges.insert(statement, range);
+            }
+        }
+    }
+}
+
+// Compares a template with the tree. Names, literals, and operators must be the same, and the
+// placeholders bind to what they match.
+struct Matcher<'m, 'f: 'm, 'a: 'f> {
+    extents: Extents<'m, 'a>,
+    statement_spans: &'m HashMap<*const Statement<'a>, Span<'a>>,
+    bindings: Vec<Binding<'f, 'a>>,
+}
+
+impl<'m, 'f, 'a> Matcher<'m, 'f, 'a> {
+    fn bind(&mut self, placeholder: &str, node: Bound<'f, 'a>, span_opt: Option<Span<'a>>) -> bool {
+        let span = match span_opt {
+            Some(span) => span,
+            None => return false,
+        };
+
+        match self
+            .bindings
+            .iter()
+            .find(|binding| binding.placeholder == placeholder)
+        {
+            Some(binding) => self.extents.same_code(binding.span, span),
+            None => {
+                self.bindings.push(Binding {
+                    placeholder: placeholder.to_owned(),
+                    node,
+                    span,
+                });
+                true
+            }
+        }
+    }
+
+    fn has_types(&self, types: &[(String, String)]) -> bool {
+        types.iter().all(|(placeholder, tpe)| {
+            self.bindings.iter().any(|binding| match binding.node {
+                Bound::Expr(expr) if &binding.placeholder == placeholder => expr
+                    .tpe_opt()
+                    .and_then(|actual| type_name(&actual))
+                    .is_some_and(|actual| &actual == tpe),
+                _ => false,
+            })
+        })
+    }
+
+    fn name(&mut self, t: Span, e: Span<'a>) -> bool {
+        if is_placeholder(t.fragment()) {
+            self.bind(t.fragment(), Bound::Name(e), Some(e))
+        } else {
+            t.fragment() == e.fragment()
+        }
+    }
+
+    fn name_opt(&mut self, t: &Option<Span>, e: &Option<Span<'a>>) -> bool {
+        match (t, e) {
+            (Some(t), Some(e)) => self.name(*t, *e),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn expr(&mut self, t: &Expr, e: &'f Expr<'a>) -> bool {
+        if let Expr::Name(name) = t {
+            if is_placeholder(name.name.fragment()) {
+                let span_opt = self.extents.expr(e);
+                return self.bind(name.name.fragment(), Bound::Expr(e), span_opt);
+            }
+        }
+
+        match (t, e) {
+            (Expr::ArrayAccess(t), Expr::ArrayAccess(e)) => {
+                self.expr(&t.expr, &e.expr) && self.expr(&t.index, &e.index)
+            }
+            (Expr::ArrayInitializer(t), Expr::ArrayInitializer(e)) => {
+                self.exprs(&t.items, &e.items)
+            }
+            (Expr::Assignment(t), Expr::Assignment(e)) => {
+                t.kind == e.kind
+                    && self.assigned(&t.assigned, &e.assigned)
+                    && self.expr(&t.expr, &e.expr)
+            }
+            (Expr::BinaryOperation(t), Expr::BinaryOperation(e)) => {
+                t.kind == e.kind && self.expr(&t.left, &e.left) && self.expr(&t.right, &e.right)
+            }
+            (Expr::Boolean(t), Expr::Boolean(e)) => t.value.fragment() == e.value.fragment(),
+            (Expr::Char(t), Expr::Char(e)) => t.value.fragment() == e.value.fragment(),
+            (Expr::Double(t), Expr::Double(e)) => t.value.fragment() == e.value.fragment(),
+            (Expr::Float(t), Expr::Float(e)) => t.value.fragment() == e.value.fragment(),
+            (Expr::Hex(t), Expr::Hex(e)) => t.value.fragment() == e.value.fragment(),
+            (Expr::Int(t), Expr::Int(e)) => t.value.fragment() == e.value.fragment(),
+            (Expr::Long(t), Expr::Long(e)) => t.value.fragment() == e.value.fragment(),
+            (Expr::Null(_), Expr::Null(_)) => true,
+            (Expr::String(t), Expr::String(e)) => t.value.fragment() == e.value.fragment(),
+            (Expr::Cast(t), Expr::Cast(e)) => {
+                t.tpes.len() == e.tpes.len()
+                    && t.tpes.iter().zip(&e.tpes).all(|(t, e)| self.tpe(t, e))
+                    && self.expr(&t.expr, &e.expr)
+            }
+            (Expr::ConstructorReference(t), Expr::ConstructorReference(e)) => {
+                self.reference_type(&t.tpe, &e.tpe)
+                    && self.type_args_opt(&t.type_args_opt, &e.type_args_opt)
+            }
+            (Expr::FieldAccess(t), Expr::FieldAccess(e)) => {
+                let e_prefix = unsafe { &**e.prefix.as_ptr() };
+                let is_prefix_matched = match (&**t.prefix.borrow(), e_prefix) {
+                    (FieldAccessPrefix::Expr(t), FieldAccessPrefix::Expr(e)) => self.expr(t, e),
+                    (FieldAccessPrefix::Package(t), FieldAccessPrefix::Package(e)) => {
+                        self.package(t, e)
+                    }
+                    _ => false,
+                };
+                is_prefix_matched && self.name(t.name, e.name)
+            }
+            (Expr::InstanceOf(t), Expr::InstanceOf(e)) => {
+                self.expr(&t.expr, &e.expr) && self.tpe(&t.tpe, &e.tpe)
+            }
+            (Expr::Lambda(t), Expr::Lambda(e)) => {
+                t.params.len() == e.params.len()
+                    && t.params
+                        .iter()
+                        .zip(&e.params)
+                        .all(|(t, e)| self.param(t, e))
+                    && match (&t.expr_opt, &e.expr_opt) {
+                        (Some(t), Some(e)) => self.expr(t, e),
+                        (None, None) => true,
+                        _ => false,
+                    }
+                    && match (&t.block_opt, &e.block_opt) {
+                        (Some(t), Some(e)) => self.block(t, e),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            (Expr::MethodCall(t), Expr::MethodCall(e)) => {
+                self.expr_opt(&t.prefix_opt, &e.prefix_opt)
+                    && self.name(t.name, e.name)
+                    && self.type_args_opt(&t.type_args_opt, &e.type_args_opt)
+                    && self.exprs(&t.args, &e.args)
+            }
+            (Expr::MethodReference(t), Expr::MethodReference(e)) => {
+                let is_primary_matched = match (&t.primary, &e.primary) {
+                    (MethodReferencePrimary::Class(t), MethodReferencePrimary::Class(e)) => {
+                        self.class_type(t, e)
+                    }
+                    (MethodReferencePrimary::Array(t), MethodReferencePrimary::Array(e)) => {
+                        self.array_type(t, e)
+                    }
+                    (MethodReferencePrimary::Expr(t), MethodReferencePrimary::Expr(e)) => {
+                        self.expr(t, e)
+                    }
+                    _ => false,
+                };
+                is_primary_matched
+                    && self.type_args_opt(&t.type_args_opt, &e.type_args_opt)
+                    && self.name(t.name, e.name)
+            }
+            (Expr::Name(t), Expr::Name(e)) => t.name.fragment() == e.name.fragment(),
+            (Expr::NewArray(t), Expr::NewArray(e)) => {
+                self.array_type(&t.tpe, &e.tpe)
+                    && match (&t.initializer_opt, &e.initializer_opt) {
+                        (Some(t), Some(e)) => self.exprs(&t.items, &e.items),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            (Expr::NewObject(t), Expr::NewObject(e)) => {
+                self.expr_opt(&t.prefix_opt, &e.prefix_opt)
+                    && self.class_type(&t.tpe, &e.tpe)
+                    && self.type_args_opt(
+                        &t.constructor_type_args_opt,
+                        &e.constructor_type_args_opt,
+                    )
+                    && self.exprs(&t.args, &e.args)
+                    // Only an empty anonymous class matches an empty one.
+                    && match (&t.body_opt, &e.body_opt) {
+                        (Some(t), Some(e)) => t.items.is_empty() && e.items.is_empty(),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            (Expr::Class(t), Expr::Class(e)) => self.tpe(&t.tpe, &e.tpe),
+            (Expr::StaticClass(t), Expr::StaticClass(e)) => match (&t.tpe, &e.tpe) {
+                (StaticType::Class(t), StaticType::Class(e)) => self.class_type(t, e),
+                (StaticType::Parameterized(t), StaticType::Parameterized(e)) => t.name == e.name,
+                _ => false,
+            },
+            (Expr::Super(t), Expr::Super(e)) => self.tpe_opt(&t.tpe_opt, &e.tpe_opt),
+            (Expr::This(t), Expr::This(e)) => self.tpe_opt(&t.tpe_opt, &e.tpe_opt),
+            (Expr::SuperConstructorCall(t), Expr::SuperConstructorCall(e)) => {
+                self.expr_opt(&t.prefix_opt, &e.prefix_opt)
+                    && self.type_args_opt(&t.type_args_opt, &e.type_args_opt)
+                    && self.exprs(&t.args, &e.args)
+            }
+            (Expr::ThisConstructorCall(t), Expr::ThisConstructorCall(e)) => {
+                self.type_args_opt(&t.type_args_opt, &e.type_args_opt)
+                    && self.exprs(&t.args, &e.args)
+            }
+            (Expr::Ternary(t), Expr::Ternary(e)) => {
+                self.expr(&t.cond, &e.cond)
+                    && self.expr(&t.true_expr, &e.true_expr)
+                    && self.expr(&t.false_expr, &e.false_expr)
+            }
+            (Expr::UnaryOperation(t), Expr::UnaryOperation(e)) => {
+                t.kind == e.kind && self.expr(&t.expr, &e.expr)
+            }
+            _ => false,
+        }
+    }
+
+    fn expr_opt(&mut self, t: &Option<Box<Expr>>, e: &'f Option<Box<Expr<'a>>>) -> bool {
+        match (t, e) {
+            (Some(t), Some(e)) => self.expr(t, e),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn exprs(&mut self, t: &[Expr], e: &'f [Expr<'a>]) -> bool {
+        t.len() == e.len() && t.iter().zip(e).all(|(t, e)| self.expr(t, e))
+    }
+
+    fn assigned(&mut self, t: &Assigned, e: &'f Assigned<'a>) -> bool {
+        match (t, e) {
+            (Assigned::Name(t), Assigned::Name(e)) => self.name(t.name, e.name),
+            (Assigned::ArrayAccess(t), Assigned::ArrayAccess(e)) => {
+                self.expr(&t.expr, &e.expr) && self.expr(&t.index, &e.index)
+            }
+            (Assigned::Field(t), Assigned::Field(e)) => {
+                let e_prefix = unsafe { &**e.prefix.as_ptr() };
+                let is_prefix_matched = match (&**t.prefix.borrow(), e_prefix) {
+                    (FieldAccessPrefix::Expr(t), FieldAccessPrefix::Expr(e)) => self.expr(t, e),
+                    (FieldAccessPrefix::Package(t), FieldAccessPrefix::Package(e)) => {
+                        self.package(t, e)
+                    }
+                    _ => false,
+                };
+                is_prefix_matched && self.name(t.name, e.name)
+            }
+            _ => false,
+        }
+    }
+
+    fn package(&mut self, t: &PackagePrefix, e: &PackagePrefix<'a>) -> bool {
+        package_name(t) == package_name(e)
+    }
+
+    fn tpe(&mut self, t: &Type, e: &'f Type<'a>) -> bool {
+        if let Type::Class(class) = t {
+            if is_placeholder(class.name) {
+                let span_opt = self.extents.tpe(e);
+                return self.bind(class.name, Bound::Type(e), span_opt);
+            }
+        }
+
+        match (t, e) {
+            (Type::Class(t), Type::Class(e)) => self.class_type(t, e),
+            // A type parameter is a class type until it is resolved.
+            (Type::Class(t), Type::Parameterized(e)) => {
+                t.prefix_opt.is_none() && t.type_args_opt.is_none() && t.name == e.name
+            }
+            (Type::Primitive(t), Type::Primitive(e)) => t.tpe == e.tpe,
+            (Type::Array(t), Type::Array(e)) => self.array_type(t, e),
+            (Type::Parameterized(t), Type::Parameterized(e)) => t.name == e.name,
+            (Type::Void(_), Type::Void(_)) => true,
+            (Type::Wildcard(t), Type::Wildcard(e)) => {
+                t.extends.len() == e.extends.len()
+                    && t.extends
+                        .iter()
+                        .zip(&e.extends)
+                        .all(|(t, e)| self.reference_type(t, e))
+                    && match (&t.super_opt, &e.super_opt) {
+                        (Some(t), Some(e)) => self.reference_type(t, e),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            _ => false,
+        }
+    }
+
+    fn tpe_opt(&mut self, t: &Option<Type>, e: &'f Option<Type<'a>>) -> bool {
+        match (t, e) {
+            (Some(t), Some(e)) => self.tpe(t, e),
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn array_type(&mut self, t: &ArrayType, e: &'f ArrayType<'a>) -> bool {
+        self.tpe(&t.tpe, &e.tpe)
+            && match (&t.size_opt, &e.size_opt) {
+                (Some(t), Some(e)) => self.expr(t, e),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    // A template without a qualifier (e.g. `Map`) matches a qualified type (e.g. `java.util.Map`).
+    fn class_type(&mut self, t: &ClassType, e: &'f ClassType<'a>) -> bool {
+        if is_placeholder(t.name) && t.prefix_opt.is_none() && t.type_args_opt.is_none() {
+            let span_opt = self.extents.class_type(e);
+            return self.bind(t.name, Bound::ClassType(e), span_opt);
+        }
+
+        t.name == e.name
+            && match (&t.prefix_opt, &e.prefix_opt) {
+                (Some(t), Some(e)) => enclosing_name(t) == enclosing_name(e),
+                (Some(_), None) => false,
+                (None, _) => true,
+            }
+            && self.type_args_opt(&t.type_args_opt, &e.type_args_opt)
+    }
+
+    fn reference_type(&mut self, t: &ReferenceType, e: &'f ReferenceType<'a>) -> bool {
+        match (t, e) {
+            (ReferenceType::Class(t), ReferenceType::Class(e)) => self.class_type(t, e),
+            (ReferenceType::Array(t), ReferenceType::Array(e)) => self.array_type(t, e),
+            (ReferenceType::Parameterized(t), ReferenceType::Parameterized(e)) => t.name == e.name,
+            _ => false,
+        }
+    }
+
+    fn type_args_opt(&mut self, t: &Option<Vec<TypeArg>>, e: &'f Option<Vec<TypeArg<'a>>>) -> bool {
+        match (t, e) {
+            (Some(t), Some(e)) => {
+                t.len() == e.len() && t.iter().zip(e).all(|(t, e)| self.type_arg(t, e))
+            }
+            (None, None) => true,
+            _ => false,
+        }
+    }
+
+    fn type_arg(&mut self, t: &TypeArg, e: &'f TypeArg<'a>) -> bool {
+        if let TypeArg::Class(class) = t {
+            if is_placeholder(class.name) && class.type_args_opt.is_none() {
+                let span_opt = self.extents.tpe(&e.to_type());
+                return self.bind(class.name, Bound::TypeArg(e), span_opt);
+            }
+        }
+
+        match (t, e) {
+            (TypeArg::Class(t), TypeArg::Class(e)) => self.class_type(t, e),
+            (TypeArg::Class(t), TypeArg::Parameterized(e)) => {
+                t.prefix_opt.is_none() && t.type_args_opt.is_none() && t.name == e.name
+            }
+            (TypeArg::Parameterized(t), TypeArg::Parameterized(e)) => t.name == e.name,
+            (TypeArg::Array(t), TypeArg::Array(e)) => self.array_type(t, e),
+            (TypeArg::Primitive(t), TypeArg::Primitive(e)) => t.tpe == e.tpe,
+            (TypeArg::Wildcard(t), TypeArg::Wildcard(e)) => {
+                t.extends.len() == e.extends.len()
+                    && t.extends
+                        .iter()
+                        .zip(&e.extends)
+                        .all(|(t, e)| self.reference_type(t, e))
+                    && match (&t.super_opt, &e.super_opt) {
+                        (Some(t), Some(e)) => self.reference_type(t, e),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            _ => false,
+        }
+    }
+
+    // A template without modifiers matches any modifiers.
+    fn modifiers(&mut self, t: &[Modifier], e: &'f [Modifier<'a>]) -> bool {
+        t.is_empty()
+            || (t.len() == e.len()
+                && t.iter().zip(e).all(|(t, e)| match (t, e) {
+                    (Modifier::Keyword(t), Modifier::Keyword(e)) => {
+                        t.name.fragment() == e.name.fragment()
+                    }
+                    (Modifier::Annotated(t), Modifier::Annotated(e)) => self.annotated(t, e),
+                    _ => false,
+                }))
+    }
+
+    fn annotated(&mut self, t: &Annotated, e: &'f Annotated<'a>) -> bool {
+        match (t, e) {
+            (Annotated::Marker(t), Annotated::Marker(e)) => self.class_type(&t.class, &e.class),
+            (Annotated::Single(t), Annotated::Single(e)) => {
+                self.class_type(&t.class, &e.class) && self.annotated_value(&t.value, &e.value)
+            }
+            (Annotated::Normal(t), Annotated::Normal(e)) => {
+                self.class_type(&t.class, &e.class)
+                    && t.params.len() == e.params.len()
+                    && t.params.iter().zip(&e.params).all(|(t, e)| {
+                        self.name(t.name, e.name) && self.annotated_value(&t.value, &e.value)
+                    })
+            }
+            _ => false,
+        }
+    }
+
+    fn annotated_value(&mut self, t: &AnnotatedValue, e: &'f AnnotatedValue<'a>) -> bool {
+        match (t, e) {
+            (AnnotatedValue::Expr(t), AnnotatedValue::Expr(e)) => self.expr(t, e),
+            (AnnotatedValue::Annotated(t), AnnotatedValue::Annotated(e)) => self.annotated(t, e),
+            (AnnotatedValue::Array(t), AnnotatedValue::Array(e)) => {
+                t.items.len() == e.items.len()
+                    && t.items
+                        .iter()
+                        .zip(&e.items)
+                        .all(|(t, e)| self.annotated_value(t, e))
+            }
+            _ => false,
+        }
+    }
+
+    // An inferred lambda parameter (e.g. `x -> x`) matches any type.
+    fn param(&mut self, t: &Param, e: &'f Param<'a>) -> bool {
+        self.modifiers(&t.modifiers, &e.modifiers)
+            && (t.tpe == Type::UnknownType || self.tpe(&t.tpe, &e.tpe))
+            && t.is_varargs == e.is_varargs
+            && self.name(t.name, e.name)
+    }
+
+    fn statement(&mut self, t: &Statement, e: &'f Statement<'a>) -> bool {
+        if let Statement::Expr(Expr::Name(name)) = t {
+            if is_placeholder(name.name.fragment()) {
+                let span_opt = self.statement_spans.get(&(e as *const _)).cloned();
+                return self.bind(name.name.fragment(), Bound::Statement(e), span_opt);
+            }
+        }
+
+        match (t, e) {
+            (Statement::Assert(t), Statement::Assert(e)) => {
+                self.expr(&t.expr, &e.expr)
+                    && match (&t.error_opt, &e.error_opt) {
+                        (Some(t), Some(e)) => self.expr(t, e),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            (Statement::Block(t), Statement::Block(e)) => self.block(t, e),
+            (Statement::Break(t), Statement::Break(e)) => {
+                self.name_opt(&t.identifier_opt, &e.identifier_opt)
+            }
+            (Statement::Continue(t), Statement::Continue(e)) => {
+                self.name_opt(&t.identifier_opt, &e.identifier_opt)
+            }
+            (Statement::Empty, Statement::Empty) => true,
+            (Statement::DoWhile(t), Statement::DoWhile(e)) => {
+                self.block(&t.block, &e.block) && self.expr(&t.cond, &e.cond)
+            }
+            (Statement::Expr(t), Statement::Expr(e)) => self.expr(t, e),
+            (Statement::ForLoop(t), Statement::ForLoop(e)) => {
+                self.statements(&t.inits, &e.inits)
+                    && match (&t.cond_opt, &e.cond_opt) {
+                        (Some(t), Some(e)) => self.expr(t, e),
+                        (None, None) => true,
+                        _ => false,
+                    }
+                    && self.statements(&t.updates, &e.updates)
+                    && self.block(&t.block, &e.block)
+            }
+            (Statement::Foreach(t), Statement::Foreach(e)) => {
+                self.standalone_declarator(&t.declarator, &e.declarator)
+                    && self.expr(&t.expr, &e.expr)
+                    && self.block(&t.block, &e.block)
+            }
+            (Statement::IfElse(t), Statement::IfElse(e)) => {
+                self.expr(&t.cond, &e.cond)
+                    && self.block(&t.block, &e.block)
+                    && match (&t.else_block_opt, &e.else_block_opt) {
+                        (Some(t), Some(e)) => self.block(t, e),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            (Statement::Labeled(t), Statement::Labeled(e)) => {
+                self.name(t.label, e.label) && self.statement(&t.statement, &e.statement)
+            }
+            (Statement::Return(t), Statement::Return(e)) => match (&t.expr_opt, &e.expr_opt) {
+                (Some(t), Some(e)) => self.expr(t, e),
+                (None, None) => true,
+                _ => false,
+            },
+            (Statement::Switch(t), Statement::Switch(e)) => {
+                self.expr(&t.expr, &e.expr)
+                    && t.cases.len() == e.cases.len()
+                    && t.cases.iter().zip(&e.cases).all(|(t, e)| {
+                        self.expr_opt(&t.label_opt, &e.label_opt)
+                            && self.statements(&t.stmts, &e.stmts)
+                    })
+            }
+            (Statement::Synchronized(t), Statement::Synchronized(e)) => {
+                self.expr(&t.expr, &e.expr) && self.block(&t.block, &e.block)
+            }
+            (Statement::Throw(t), Statement::Throw(e)) => self.expr(&t.expr, &e.expr),
+            (Statement::Try(t), Statement::Try(e)) => {
+                t.resources.len() == e.resources.len()
+                    && t.resources
+                        .iter()
+                        .zip(&e.resources)
+                        .all(|(t, e)| match (t, e) {
+                            (TryResource::Name(t), TryResource::Name(e)) => {
+                                self.name(t.name, e.name)
+                            }
+                            (TryResource::Declarator(t), TryResource::Declarator(e)) => {
+                                self.standalone_declarator(t, e)
+                            }
+                            (TryResource::FieldAccess(t), TryResource::FieldAccess(e)) => {
+                                self.name(t.name, e.name)
+                            }
+                            _ => false,
+                        })
+                    && self.block(&t.try, &e.try)
+                    && t.catches.len() == e.catches.len()
+                    && t.catches
+                        .iter()
+                        .zip(&e.catches)
+                        .all(|(t, e)| self.catch(t, e))
+                    && match (&t.finally_opt, &e.finally_opt) {
+                        (Some(t), Some(e)) => self.block(t, e),
+                        (None, None) => true,
+                        _ => false,
+                    }
+            }
+            (Statement::WhileLoop(t), Statement::WhileLoop(e)) => {
+                self.expr(&t.cond, &e.cond) && self.block(&t.block, &e.block)
+            }
+            (Statement::VariableDeclarators(t), Statement::VariableDeclarators(e)) => {
+                self.modifiers(&t.modifiers, &e.modifiers)
+                    && t.declarators.len() == e.declarators.len()
+                    && t.declarators
+                        .iter()
+                        .zip(&e.declarators)
+                        .all(|(t, e)| self.declarator(t, e))
+            }
+            _ => false,
+        }
+    }
+
+    fn statements(&mut self, t: &[Statement], e: &'f [Statement<'a>]) -> bool {
+        t.len() == e.len() && t.iter().zip(e).all(|(t, e)| self.statement(t, e))
+    }
+
+    // A block with braces matches a single statement, e.g. `if ($c) { $s; }` matches `if (a) b();`.
+    fn block(&mut self, t: &Block, e: &'f Block<'a>) -> bool {
+        !e.is_skipped && self.statements(&t.stmts, &e.stmts)
+    }
+
+    fn declarator(&mut self, t: &VariableDeclarator, e: &'f VariableDeclarator<'a>) -> bool {
+        let e_tpe = unsafe { &*e.tpe.as_ptr() };
+        self.tpe(&t.tpe.borrow(), e_tpe)
+            && self.name(t.name, e.name)
+            && match (&t.expr_opt, &e.expr_opt) {
+                (Some(t), Some(e)) => self.expr(t, e),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    fn standalone_declarator(
+        &mut self,
+        t: &StandaloneVariableDeclarator,
+        e: &'f StandaloneVariableDeclarator<'a>,
+    ) -> bool {
+        let e_tpe = unsafe { &*e.tpe.as_ptr() };
+        self.modifiers(&t.modifiers, &e.modifiers)
+            && self.tpe(&t.tpe.borrow(), e_tpe)
+            && self.name(t.name, e.name)
+            && match (&t.expr_opt, &e.expr_opt) {
+                (Some(t), Some(e)) => self.expr(t, e),
+                (None, None) => true,
+                _ => false,
+            }
+    }
+
+    // `catch ($T $e) {}` matches a catch of one type with an empty block.
+    fn catch(&mut self, t: &Catch, e: &'f Catch<'a>) -> bool {
+        self.modifiers(&t.modifiers, &e.modifiers)
+            && t.class_types.len() == e.class_types.len()
+            && t.class_types
+                .iter()
+                .zip(&e.class_types)
+                .all(|(t, e)| self.class_type(t, e))
+            && self.name(t.param_name, e.param_name)
+            && self.block(&t.block, &e.block)
+    }
+}
+
+// Finds the extent of a node in the tokens, since most nodes don't have spans. The extent of an
+// expression excludes its redundant parentheses.
+#[derive(Clone, Copy)]
+struct Extents<'t, 'a: 't> {
+    tokens: &'t [Token<'a>],
+}
+
+impl<'t, 'a> Extents<'t, 'a> {
+    fn span(&self, first: usize, last: usize) -> Span<'a> {
+        self.tokens[first].span().to(&self.tokens[last].span())
+    }
+
+    fn index(&self, span: Span<'a>) -> Option<usize> {
+        self.tokens
+            .binary_search_by_key(&span.start, |token| token.span().start)
+            .ok()
+    }
+
+    fn is(&self, index: usize, fragment: &str) -> bool {
+        self.tokens
+            .get(index)
+            .is_some_and(|token| token.span().fragment() == fragment)
+    }
+
+    // The index of the bracket that closes the one at `index`.
+    fn close(&self, index: usize) -> Option<usize> {
+        let (open, close) = match self.tokens.get(index)?.span().fragment() {
+            "(" => ("(", ")"),
+            "[" => ("[", "]"),
+            "{" => ("{", "}"),
+            "<" => ("<", ">"),
+            _ => return None,
+        };
+
+        let mut depth = 0;
+        for i in index..self.tokens.len() {
+            if self.is(i, open) {
+                depth += 1;
+            } else if self.is(i, close) {
+                depth -= 1;
+                if depth == 0 {
+                    return Some(i);
+                }
+            }
+        }
+        None
+    }
+
+    // Widens the range until its parentheses are balanced.
+    fn balance(&self, first: usize, last: usize) -> Option<(usize, usize)> {
+        let (mut first, mut last) = (first, last);
+        let mut depth = 0i32;
+        let mut min = 0;
+        for i in first..=last {
+            if self.is(i, "(") {
+                depth += 1;
+            } else if self.is(i, ")") {
+                depth -= 1;
+                min = min.min(depth);
+            }
+        }
+
+        for _ in 0..-min {
+            let mut inner = 0;
+            loop {
+                first = first.checked_sub(1)?;
+                if self.is(first, ")") {
+                    inner += 1;
+                } else if self.is(first, "(") {
+                    if inner == 0 {
+                        break;
+                    }
+                    inner -= 1;
+                }
+            }
+        }
+        for _ in 0..(depth - min) {
+            let mut inner = 0;
+            loop {
+                last += 1;
+                if last >= self.tokens.len() {
+                    return None;
+                } else if self.is(last, "(") {
+                    inner += 1;
+                } else if self.is(last, ")") {
+                    if inner == 0 {
+                        break;
+                    }
+                    inner -= 1;
+                }
+            }
+        }
+
+        Some((first, last))
+    }
+
+    // The index of the last token of the type that starts at `index`, e.g. `java.util.Map<K, V>[]`.
+    fn skip_type(&self, index: usize) -> usize {
+        let mut index = index;
+        if self.is(index, "?") && (self.is(index + 1, "extends") || self.is(index + 1, "super")) {
+            return self.skip_type(index + 2);
+        }
+
+        loop {
+            if self.is(index + 1, "<") {
+                match self.close(index + 1) {
+                    Some(close) => index = close,
+                    None => break,
+                }
+            }
+            if self.is(index + 1, ".")
+                && matches!(self.tokens.get(index + 2), Some(Token::Identifier(_)))
+            {
+                index += 2;
+            } else {
+                break;
+            }
+        }
+        while self.is(index + 1, "[") && self.is(index + 2, "]") {
+            index += 2;
+        }
+
+        index
+    }
+
+    fn expr(&self, expr: &Expr<'a>) -> Option<Span<'a>> {
+        let (first, last) = self.expr_range(expr)?;
+        Some(self.span(first, last))
+    }
+
+    fn expr_range(&self, expr: &Expr<'a>) -> Option<(usize, usize)> {
+        self.balance(self.expr_first(expr)?, self.expr_last(expr)?)
+    }
+
+    fn expr_first(&self, expr: &Expr<'a>) -> Option<usize> {
+        let mut expr = expr;
+        loop {
+            expr = match expr {
+                Expr::ArrayAccess(access) => &access.expr,
+                Expr::ArrayInitializer(initializer) => {
+                    return Some(self.expr_range(initializer.items.first()?)?.0 - 1)
+                }
+                Expr::Assignment(assignment) => match &*assignment.assigned {
+                    Assigned::Name(name) => return self.index(name.name),
+                    Assigned::ArrayAccess(access) => &access.expr,
+                    Assigned::Field(field) => match unsafe { &**field.prefix.as_ptr() } {
+                        FieldAccessPrefix::Expr(prefix) => prefix,
+                        FieldAccessPrefix::Package(package) => return self.package_first(package),
+                    },
+                },
+                Expr::BinaryOperation(binary) => &binary.left,
+                Expr::Boolean(literal) => return self.index(literal.value),
+                Expr::Char(literal) => return self.index(literal.value),
+                Expr::Double(literal) => return self.index(literal.value),
+                Expr::Float(literal) => return self.index(literal.value),
+                Expr::Hex(literal) => return self.index(literal.value),
+                Expr::Int(literal) => return self.index(literal.value),
+                Expr::Long(literal) => return self.index(literal.value),
+                Expr::Null(literal) => return self.index(literal.value),
+                Expr::String(literal) => return self.index(literal.value),
+                Expr::Cast(cast) => return Some(self.type_first(cast.tpes.first()?)? - 1),
+                Expr::ConstructorReference(reference) => {
+                    return self.reference_type_first(&reference.tpe)
+                }
+                Expr::FieldAccess(field) => match unsafe { &**field.prefix.as_ptr() } {
+                    FieldAccessPrefix::Expr(prefix) => prefix,
+                    FieldAccessPrefix::Package(package) => return self.package_first(package),
+                },
+                Expr::InstanceOf(instance_of) => &instance_of.expr,
+                Expr::Lambda(lambda) => {
+                    return match lambda.params.first() {
+                        Some(param) => self.index(param.name),
+                        // `() -> body`
+                        None => Some(self.lambda_body_first(lambda)?.checked_sub(4)?),
+                    };
+                }
+                Expr::MethodCall(call) => match &call.prefix_opt {
+                    Some(prefix) => prefix,
+                    None => return self.index(call.name),
+                },
+                Expr::MethodReference(reference) => match &reference.primary {
+                    MethodReferencePrimary::Class(class) => return self.class_type_first(class),
+                    MethodReferencePrimary::Array(array) => return self.type_first(&array.tpe),
+                    MethodReferencePrimary::Expr(primary) => primary,
+                },
+                Expr::Name(name) => return self.index(name.name),
+                Expr::NewArray(new_array) => {
+                    return self.type_first(&new_array.tpe.tpe)?.checked_sub(1)
+                }
+                Expr::NewObject(new_object) => match &new_object.prefix_opt {
+                    Some(prefix) => prefix,
+                    None => return self.class_type_first(&new_object.tpe)?.checked_sub(1),
+                },
+                Expr::Class(class) => return self.type_first(&class.tpe),
+                Expr::StaticClass(class) => {
+                    return match &class.tpe {
+                        StaticType::Class(class) => self.class_type_first(class),
+                        StaticType::Parameterized(parameterized) => {
+                            self.index(parameterized.span_opt?)
+                        }
+                    }
+                }
+                Expr::Super(keyword) => {
+                    return match &keyword.tpe_opt {
+                        Some(tpe) => self.type_first(tpe),
+                        None => self.index(keyword.span),
+                    }
+                }
+                Expr::This(keyword) => {
+                    return match &keyword.tpe_opt {
+                        Some(tpe) => self.type_first(tpe),
+                        None => self.index(keyword.span),
+                    }
+                }
+                Expr::SuperConstructorCall(call) => match &call.prefix_opt {
+                    Some(prefix) => prefix,
+                    None => return self.index(call.name),
+                },
+                Expr::ThisConstructorCall(call) => return self.index(call.name),
+                Expr::Ternary(ternary) => &ternary.cond,
+                Expr::UnaryOperation(unary) => {
+                    if unary.is_post {
+                        &unary.expr
+                    } else {
+                        return self.index(unary.operator);
+                    }
+                }
+            };
+        }
+    }
+
+    fn expr_last(&self, expr: &Expr<'a>) -> Option<usize> {
+        let mut expr = expr;
+        loop {
+            expr = match expr {
+                Expr::ArrayAccess(access) => return Some(self.expr_range(&access.index)?.1 + 1),
+                Expr::ArrayInitializer(initializer) => {
+                    return self.close(self.expr_range(initializer.items.first()?)?.0 - 1)
+                }
+                Expr::Assignment(assignment) => &assignment.expr,
+                Expr::BinaryOperation(binary) => &binary.right,
+                Expr::Cast(cast) => &cast.expr,
+                Expr::ConstructorReference(reference) => {
+                    let first = self.reference_type_first(&reference.tpe)?;
+                    return (first..self.tokens.len()).find(|&index| self.is(index, "new"));
+                }
+                Expr::FieldAccess(field) => return self.index(field.name),
+                Expr::InstanceOf(instance_of) => {
+                    return Some(self.skip_type(self.type_first(&instance_of.tpe)?))
+                }
+                Expr::Lambda(lambda) => match (&lambda.expr_opt, &lambda.block_opt) {
+                    (Some(body), _) => body,
+                    (None, Some(block)) => {
+                        return self.index(block.span_opt?).and_then(|i| self.close(i))
+                    }
+                    (None, None) => return None,
+                },
+                Expr::MethodCall(call) => return self.close(self.index(call.name)? + 1),
+                Expr::MethodReference(reference) => return self.index(reference.name),
+                Expr::NewArray(new_array) => {
+                    let mut last = self.skip_type(self.type_first(&new_array.tpe.tpe)?);
+                    while self.is(last + 1, "[") {
+                        last = self.close(last + 1)?;
+                    }
+                    if new_array.initializer_opt.is_some() {
+                        last = self.close(last + 1)?;
+                    }
+                    return Some(last);
+                }
+                Expr::NewObject(new_object) => {
+                    let mut last =
+                        self.close(self.skip_type(self.class_type_first(&new_object.tpe)?) + 1)?;
+                    if new_object.body_opt.is_some() {
+                        last = self.close(last + 1)?;
+                    }
+                    return Some(last);
+                }
+                Expr::Class(class) => return self.index(class.span),
+                Expr::StaticClass(class) => {
+                    let first = match &class.tpe {
+                        StaticType::Class(class) => self.class_type_first(class)?,
+                        StaticType::Parameterized(parameterized) => {
+                            self.index(parameterized.span_opt?)?
+                        }
+                    };
+                    return Some(self.skip_type(first));
+                }
+                Expr::Super(keyword) => return self.index(keyword.span),
+                Expr::This(keyword) => return self.index(keyword.span),
+                Expr::SuperConstructorCall(call) => return self.close(self.index(call.name)? + 1),
+                Expr::ThisConstructorCall(call) => return self.close(self.index(call.name)? + 1),
+                Expr::Ternary(ternary) => &ternary.false_expr,
+                Expr::UnaryOperation(unary) => {
+                    if unary.is_post {
+                        // The span of `++` is its first symbol.
+                        return Some(self.index(unary.operator)? + unary.kind.text().len() - 1);
+                    } else {
+                        &unary.expr
+                    }
+                }
+                // The literals and the names are a single token.
+                _ => return self.expr_first(expr),
+            };
+        }
+    }
+
+    fn lambda_body_first(&self, lambda: &::parse::tree::Lambda<'a>) -> Option<usize> {
+        match (&lambda.expr_opt, &lambda.block_opt) {
+            (Some(body), _) => Some(self.expr_range(body)?.0),
+            (None, Some(block)) => self.index(block.span_opt?),
+            (None, None) => None,
+        }
+    }
+
+    fn package_first(&self, package: &PackagePrefix<'a>) -> Option<usize> {
+        match &package.prefix_opt {
+            Some(prefix) => self.package_first(prefix),
+            None => self.index(package.span_opt?),
+        }
+    }
+
+    fn class_type_first(&self, class: &ClassType<'a>) -> Option<usize> {
+        match class.prefix_opt.as_deref() {
+            Some(EnclosingType::Package(package)) => self.package_first(package),
+            Some(EnclosingType::Class(class)) => self.class_type_first(class),
+            Some(EnclosingType::Parameterized(parameterized)) => {
+                self.index(parameterized.span_opt?)
+            }
+            None => self.index(class.span_opt?),
+        }
+    }
+
+    fn reference_type_first(&self, tpe: &ReferenceType<'a>) -> Option<usize> {
+        match tpe {
+            ReferenceType::Class(class) => self.class_type_first(class),
+            ReferenceType::Array(array) => self.type_first(&array.tpe),
+            ReferenceType::Parameterized(parameterized) => self.index(parameterized.span_opt?),
+        }
+    }
+
+    fn type_first(&self, tpe: &Type<'a>) -> Option<usize> {
+        match tpe {
+            Type::Class(class) => self.class_type_first(class),
+            Type::Array(array) => self.type_first(&array.tpe),
+            Type::Primitive(primitive) => self.index(primitive.span_opt?),
+            Type::Parameterized(parameterized) => self.index(parameterized.span_opt?),
+            Type::Void(void) => self.index(void.span_opt?),
+            Type::Wildcard(wildcard) => self.index(wildcard.span_opt?),
+            Type::UnknownType => None,
+        }
+    }
+
+    fn tpe(&self, tpe: &Type<'a>) -> Option<Span<'a>> {
+        let first = self.type_first(tpe)?;
+        Some(self.span(first, self.skip_type(first)))
+    }
+
+    fn class_type(&self, class: &ClassType<'a>) -> Option<Span<'a>> {
+        let first = self.class_type_first(class)?;
+        Some(self.span(first, self.skip_type(first)))
+    }
+
+    // From `catch` to the closing brace.
+    fn catch(&self, catch: &Catch<'a>) -> Option<Span<'a>> {
+        let mut first = self.class_type_first(catch.class_types.first()?)?;
+        while !self.is(first, "(") {
+            first = first.checked_sub(1)?;
+        }
+        let last = self.close(self.index(catch.block.span_opt?)?)?;
+        Some(self.span(first.checked_sub(1)?, last))
+    }
+
+    // The colon after `case ...` or `default` at `index`.
+    fn case_colon(&self, index: usize) -> Option<usize> {
+        let mut index = index;
+        while index < self.tokens.len() && !self.is(index, ":") {
+            if self.is(index, "(") || self.is(index, "[") || self.is(index, "{") {
+                index = self.close(index)?;
+            }
+            index += 1;
+        }
+        Some(index).filter(|&index| index < self.tokens.len())
+    }
+
+    // Whether the spans have the same tokens, ignoring whitespace and comments.
+    fn same_code(&self, a: Span<'a>, b: Span<'a>) -> bool {
+        let tokens = |span: Span<'a>| {
+            self.tokens
+                .iter()
+                .skip_while(move |token| token.span().start < span.start)
+                .take_while(move |token| token.span().end <= span.end)
+                .map(|token| token.span().fragment())
+        };
+        tokens(a).eq(tokens(b))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::search::{search, Bound, Template};
+    use {parse, SourceFile};
+
+    // The matched code and the code of each binding.
+    fn run(template: &str, code: &str) -> Vec<(String, Vec<(String, String)>)> {
+        let template_source = SourceFile::new("Template.java", template);
+        let template = Template::parse(&template_source).unwrap();
+        let source = SourceFile::new("Test.java", code);
+        let file = parse::apply(&source).unwrap();
+
+        search(&file, &template)
+            .iter()
+            .map(|m| {
+                let bindings = m
+                    .bindings
+                    .iter()
+                    .map(|b| (b.placeholder.clone(), b.span.fragment().to_owned()))
+                    .collect();
+                (m.span.fragment().to_owned(), bindings)
+            })
+            .collect()
+    }
+
+    fn matched(template: &str, code: &str) -> Vec<String> {
+        run(template, code).into_iter().map(|(m, _)| m).collect()
+    }
+
+    #[test]
+    fn test_expr() {
+        let code = r#"
+class Test {
+  boolean run(String a) {
+    return a.equals("b") || equals(a) || (a + "c").equals(a.trim()) || a.equals("b", "c");
+  }
+}
+"#;
+
+        assert_eq!(
+            run("$x.equals($y)", code),
+            vec![
+                (
+                    r#"a.equals("b")"#.to_owned(),
+                    vec![
+                        ("$x".to_owned(), "a".to_owned()),
+                        ("$y".to_owned(), r#""b""#.to_owned())
+                    ]
+                ),
+                (
+                    r#"(a + "c").equals(a.trim())"#.to_owned(),
+                    vec![
+                        ("$x".to_owned(), r#"a + "c""#.to_owned()),
+                        ("$y".to_owned(), "a.trim()".to_owned())
+                    ]
+                )
+            ]
+        );
+        assert_eq!(
+            matched(
+                "$x == $x",
+                "class A { boolean b = a.b == a . b || a == b; }"
+            ),
+            vec!["a.b == a . b"]
+        );
+        assert_eq!(
+            matched(
+                "new HashMap<$K, $V>()",
+                "class A { Map<String, List<Integer>> m = new java.util.HashMap<String, List<Integer>>(); Object o = new HashMap<>(); }"
+            ),
+            vec!["new java.util.HashMap<String, List<Integer>>()"]
+        );
+    }
+
+    #[test]
+    fn test_statement() {
+        let code = r#"
+class Test {
+  void run() {
+    try {
+      if (a) b();
+      if (c) { d(); e(); }
+    } catch (final IOException | RuntimeException e) {
+    } catch (Exception e) {
+      // Ignored.
+    } catch (Throwable t) {
+      throw t;
+    }
+  }
+}
+"#;
+
+        assert_eq!(
+            run("catch ($T $e) {}", code),
+            vec![(
+                "catch (Exception e) {\n      // Ignored.\n    }".to_owned(),
+                vec![
+                    ("$T".to_owned(), "Exception".to_owned()),
+                    ("$e".to_owned(), "e".to_owned())
+                ]
+            )]
+        );
+        assert_eq!(
+            run("if ($c) { $s; }", code),
+            vec![(
+                "if (a) b();".to_owned(),
+                vec![
+                    ("$c".to_owned(), "a".to_owned()),
+                    ("$s".to_owned(), "b();".to_owned())
+                ]
+            )]
+        );
+    }
+
+    #[test]
+    fn test_type() {
+        let (files, _root) = apply_semantics!(
+            r#"
+package dev;
+
+class Text {}
+        "#,
+            r#"
+package dev;
+
+class Test {
+  void run() {
+    Text a = null;
+    int b = 1;
+    a.equals(b);
+    b.equals(a);
+  }
+}
+        "#
+        );
+
+        let template_source = SourceFile::new("Template.java", "$x.equals($y)");
+        let template = Template::parse(&template_source)
+            .unwrap()
+            .with_type("$x", "dev.Text");
+        let matches = search(&files[1], &template);
+
+        assert_eq!(matches.len(), 1);
+        assert_eq!(matches[0].span.fragment(), "a.equals(b)");
+        match matches[0].get("$y").unwrap().node {
+            Bound::Expr(expr) => assert_eq!(
+                expr.tpe_opt().unwrap().span_opt().unwrap().fragment(),
+                "int"
+            ),
+            _ => panic!(),
+        }
+    }
+}
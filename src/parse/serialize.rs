@@ -0,0 +1,310 @@
+// JSON serialization of the parse tree. The schema is documented in README.md.
+//
+// Enum nodes are emitted as `{"kind": <variant>, "node": <payload>}`. Pointers into
+// `analyze::definition` are emitted as the stable id of the definition (never its address).
+use analyze::definition::{Class, ConstructorDef, FieldDef, MethodDef, Package, Param, TypeParam};
+use parse::id_gen::Id;
+use parse::tree::{
+    BinaryOperation, BinaryOperator, Expr, ImportDef, ImportPrefixDef, ResolvedName, Try,
+    VariableDeclarator,
+};
+use serde::ser::{SerializeStruct, Serializer};
+use serde::Serialize;
+use std::cell::RefCell;
+use tokenize::span::Span;
+use JavaFile;
+
+pub trait StableId {
+    fn stable_id(&self) -> String;
+}
+
+impl<'a> StableId for Package<'a> {
+    fn stable_id(&self) -> String {
+        self.import_path.to_owned()
+    }
+}
+
+impl<'a> StableId for Class<'a> {
+    fn stable_id(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl<'a> StableId for MethodDef<'a> {
+    fn stable_id(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl<'a> StableId for ConstructorDef<'a> {
+    fn stable_id(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl<'a> StableId for FieldDef<'a> {
+    fn stable_id(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl<'a> StableId for TypeParam<'a> {
+    fn stable_id(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+impl<'a> StableId for VariableDeclarator<'a> {
+    fn stable_id(&self) -> String {
+        self.id.to_string()
+    }
+}
+
+pub fn ptr<T, S>(def: &*const T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: StableId,
+    S: Serializer,
+{
+    if def.is_null() {
+        serializer.serialize_none()
+    } else {
+        serializer.serialize_str(&unsafe { &**def }.stable_id())
+    }
+}
+
+pub fn ptr_opt<T, S>(def_opt: &Option<*const T>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: StableId,
+    S: Serializer,
+{
+    match def_opt {
+        Some(def) => ptr(def, serializer),
+        None => serializer.serialize_none(),
+    }
+}
+
+pub fn ptr_cell<T, S>(def_opt: &RefCell<Option<*const T>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: StableId,
+    S: Serializer,
+{
+    ptr_opt(&*def_opt.borrow(), serializer)
+}
+
+fn serialize_ref<S, T>(kind: &'static str, def: *const T, serializer: S) -> Result<S::Ok, S::Error>
+where
+    T: StableId,
+    S: Serializer,
+{
+    let mut state = serializer.serialize_struct("Ref", 2)?;
+    state.serialize_field("kind", kind)?;
+    state.serialize_field("id", &unsafe { &*def }.stable_id())?;
+    state.end()
+}
+
+impl<'a> Serialize for ImportDef<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ImportDef::Package(p) => serialize_ref("Package", *p, serializer),
+            ImportDef::Class(c) => serialize_ref("Class", *c, serializer),
+        }
+    }
+}
+
+impl<'a> Serialize for ImportPrefixDef<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ImportPrefixDef::Package(p) => serialize_ref("Package", *p, serializer),
+            ImportPrefixDef::Class(c) => serialize_ref("Class", *c, serializer),
+        }
+    }
+}
+
+impl<'a> Serialize for ResolvedName<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        match self {
+            ResolvedName::Package(p) => serialize_ref("Package", *p, serializer),
+            ResolvedName::Class(c) => serialize_ref("Class", *c, serializer),
+            ResolvedName::Variable(v) => serialize_ref("Variable", *v, serializer),
+            ResolvedName::TypeParam(t) => serialize_ref("TypeParam", *t, serializer),
+            ResolvedName::Param(p) => {
+                // Params have no id. They are identified by their name's position instead.
+                let param = unsafe { &**p as &Param };
+                let mut state = serializer.serialize_struct("Ref", 2)?;
+                state.serialize_field("kind", "Param")?;
+                state.serialize_field("name", &param.name)?;
+                state.end()
+            }
+        }
+    }
+}
+
+impl Serialize for Id {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        serializer.collect_str(self)
+    }
+}
+
+// `try` is a keyword in the edition serde_derive is compiled with, so Try is serialized by hand.
+impl<'a> Serialize for Try<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("Try", 4)?;
+        state.serialize_field("try", &self.try)?;
+        state.serialize_field("resources", &self.resources)?;
+        state.serialize_field("catches", &self.catches)?;
+        state.serialize_field("finally_opt", &self.finally_opt)?;
+        state.end()
+    }
+}
+
+// A chain nests on the left, and serde can only emit a nested value by recursing, so the left
+// spine is emitted flat: the innermost left operand, then the operations from the inside out.
+impl<'a> Serialize for BinaryOperation<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (spine, innermost) = self.spine();
+        let operations = spine
+            .into_iter()
+            .rev()
+            .map(|op| Operation {
+                operator: &op.operator,
+                kind: &op.kind,
+                right: &op.right,
+            })
+            .collect::<Vec<_>>();
+
+        let mut state = serializer.serialize_struct("BinaryOperation", 2)?;
+        state.serialize_field("left", innermost)?;
+        state.serialize_field("operations", &operations)?;
+        state.end()
+    }
+}
+
+#[derive(Serialize)]
+struct Operation<'r, 'a: 'r> {
+    operator: &'r Span<'a>,
+    kind: &'r BinaryOperator,
+    right: &'r Expr<'a>,
+}
+
+impl<'a> Serialize for Span<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let (line, col) = self.file().line_col(self.start);
+
+        let mut state = serializer.serialize_struct("Span", 4)?;
+        state.serialize_field("start", &self.start)?;
+        state.serialize_field("end", &self.end)?;
+        state.serialize_field("line", &line)?;
+        state.serialize_field("col", &col)?;
+        state.end()
+    }
+}
+
+impl<'a> Serialize for JavaFile<'a> {
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: Serializer,
+    {
+        let mut state = serializer.serialize_struct("JavaFile", 2)?;
+        state.serialize_field("path", &self.source.path)?;
+        state.serialize_field("unit", &self.unit)?;
+        state.end()
+    }
+}
+
+pub fn to_json(file: &JavaFile) -> serde_json::Result<String> {
+    serde_json::to_string(file)
+}
+
+pub fn to_json_value(file: &JavaFile) -> serde_json::Result<serde_json::Value> {
+    serde_json::to_value(file)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{to_json, to_json_value};
+    use std::ops::Deref;
+    use {parse, SourceFile};
+
+    #[test]
+    fn test_class() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  int a;
+  void method() {}
+}
+        "#
+        );
+
+        let json = to_json_value(files.first().unwrap().deref()).unwrap();
+        let class = &json["unit"]["items"][0];
+
+        assert_eq!(json["path"], "file0.java");
+        assert_eq!(class["kind"], "Class");
+        assert_eq!(class["node"]["name"]["start"], 20);
+        assert_eq!(class["node"]["name"]["end"], 24);
+        assert_eq!(class["node"]["name"]["line"], 3);
+        assert_eq!(class["node"]["name"]["col"], 7);
+        assert_eq!(class["node"]["def_opt"], class["node"]["id"]);
+
+        let method = &class["node"]["body"]["items"][1];
+        assert_eq!(method["kind"], "Method");
+        assert_eq!(method["node"]["return_type"]["kind"], "Void");
+        assert_eq!(method["node"]["def_opt"], method["node"]["id"]);
+    }
+
+    #[test]
+    fn test_binary_operation() {
+        let source = SourceFile::new("Test.java", "class Test { int a = b + c - d; }");
+        let file = parse::apply(&source).unwrap();
+
+        let json = to_json_value(&file).unwrap();
+        let expr = &json["unit"]["items"][0]["node"]["body"]["items"][0]["node"]["declarators"][0]
+            ["expr_opt"];
+        let operations = &expr["node"]["operations"];
+
+        assert_eq!(expr["kind"], "BinaryOperation");
+        assert_eq!(expr["node"]["left"]["node"]["name"]["start"], 21);
+        assert_eq!(operations[0]["kind"], "Add");
+        assert_eq!(operations[0]["right"]["node"]["name"]["start"], 25);
+        assert_eq!(operations[1]["kind"], "Subtract");
+        assert_eq!(operations[1]["right"]["node"]["name"]["start"], 29);
+    }
+
+    // Runs on the default stack of a test thread.
+    #[test]
+    fn test_long_chain() {
+        let content = format!(
+            "class Test {{ int a = {}; }}",
+            vec!["a"; 100_000].join(" + ")
+        );
+        let source = SourceFile::new("Test.java", &content);
+        let file = parse::apply(&source).unwrap();
+
+        let json = to_json(&file).unwrap();
+        assert_eq!(json.matches("\"kind\":\"Add\"").count(), 99_999);
+    }
+}
@@ -1,17 +1,17 @@
 use parse::combinator::{keyword, symbol};
-use parse::id_gen::IdGen;
 use parse::tree::{Assert, Statement};
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
     let (input, _) = keyword("assert")(input)?;
-    let (input, expr) = expr::parse(input, id_gen)?;
+    let (input, expr) = expr::parse(input, context)?;
 
     let (input, error_opt) = if let Ok((input, _)) = symbol(':')(input) {
-        let (input, error) = expr::parse(input, id_gen)?;
+        let (input, error) = expr::parse(input, context)?;
         (input, Some(error))
     } else {
         (input, None)
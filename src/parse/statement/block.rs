@@ -1,20 +1,23 @@
 use parse::combinator::{many0, symbol};
-use parse::id_gen::IdGen;
 use parse::tree::{Block, Statement, Type};
+use parse::Context;
 use parse::{statement, ParseResult, Tokens};
+use tokenize::token::Token;
 
 pub fn parse_block_or_single_statement<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Block<'def>> {
-    if let Ok(ok) = statement::block::parse_block(input, id_gen) {
+    if let Ok(ok) = statement::block::parse_block(input, context) {
         Ok(ok)
     } else {
-        let (input, stmt) = statement::parse(input, id_gen)?;
+        let (input, stmt) = statement::parse(input, context)?;
         Ok((
             input,
             Block {
                 stmts: vec![stmt],
+                span_opt: None,
+                is_skipped: false,
                 return_type: Type::UnknownType,
             },
         ))
@@ -23,26 +26,70 @@ pub fn parse_block_or_single_statement<'def, 'r>(
 
 pub fn parse_block<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Block<'def>> {
-    let (input, _) = symbol('{')(input)?;
-    let (input, stmts) = many0(|i| statement::parse(i, id_gen))(input)?;
-    let (input, _) = symbol('}')(input)?;
+    if let Some(reused) = context.reusable.take_block(input) {
+        return Ok(reused);
+    }
+
+    let (input, open) = symbol('{')(input)?;
+    let (input, stmts) = many0(|i| statement::parse(i, context))(input)?;
+    let (input, close) = symbol('}')(input)?;
 
     Ok((
         input,
         Block {
             stmts,
+            span_opt: Some(open.to(&close)),
+            is_skipped: false,
             return_type: Type::UnknownType,
         },
     ))
 }
 
+// Parses the body of a method, a constructor, or an initializer. When the context skips bodies,
+// only the braces are matched.
+pub fn parse_body<'def, 'r>(
+    input: Tokens<'def, 'r>,
+    context: &mut Context<'def>,
+) -> ParseResult<'def, 'r, Block<'def>> {
+    if !context.skip_bodies {
+        return parse_block(input, context);
+    }
+
+    let (_, open) = symbol('{')(input)?;
+    let mut depth = 0;
+
+    for (index, token) in input.iter().enumerate() {
+        if let Token::Symbol(span) = token {
+            match span.fragment() {
+                "{" => depth += 1,
+                "}" => depth -= 1,
+                _ => continue,
+            }
+
+            if depth == 0 {
+                return Ok((
+                    &input[(index + 1)..],
+                    Block {
+                        stmts: vec![],
+                        span_opt: Some(open.to(span)),
+                        is_skipped: true,
+                        return_type: Type::UnknownType,
+                    },
+                ));
+            }
+        }
+    }
+
+    Err(input)
+}
+
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
-    let (input, block) = parse_block(input, id_gen)?;
+    let (input, block) = parse_block(input, context)?;
     Ok((input, Statement::Block(block)))
 }
 
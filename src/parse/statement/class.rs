@@ -1,16 +1,16 @@
 use parse::combinator::symbol;
 use parse::def::{class, modifiers};
-use parse::id_gen::IdGen;
 use parse::tree::Statement;
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
-    let (input, modifiers) = modifiers::parse(input, id_gen)?;
+    let (input, modifiers) = modifiers::parse(input, context)?;
     let (input, _) = class::parse_prefix(input)?;
-    let (input, class) = class::parse_tail(input, modifiers, id_gen)?;
+    let (input, class) = class::parse_tail(input, modifiers, context)?;
 
     Ok((input, Statement::Class(class)))
 }
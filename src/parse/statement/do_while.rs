@@ -1,19 +1,20 @@
 use parse::combinator::{keyword, symbol};
-use parse::id_gen::IdGen;
 use parse::statement::block;
 use parse::tree::{DoWhile, Statement, WhileLoop};
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
     let (input, _) = keyword("do")(input)?;
-    let (input, block) = block::parse_block_or_single_statement(input, id_gen)?;
+    let (input, block) = block::parse_block_or_single_statement(input, context)?;
     let (input, _) = keyword("while")(input)?;
     let (input, _) = symbol('(')(input)?;
-    let (input, cond) = expr::parse(input, id_gen)?;
+    let (input, cond) = expr::parse(input, context)?;
     let (input, _) = symbol(')')(input)?;
+    let (input, _) = symbol(';')(input)?;
 
     Ok((
         input,
@@ -23,3 +24,22 @@ pub fn parse<'def, 'r>(
         }),
     ))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::parse;
+    use parse::tree::Statement;
+    use parse::Context;
+    use test_common::generate_tokens;
+
+    #[test]
+    fn test_semicolon() {
+        let tokens = generate_tokens("do run(); while (a); b();");
+        let (rest, statement) = parse(&tokens, &mut Context::new("")).ok().unwrap();
+        assert!(matches!(statement, Statement::DoWhile(_)));
+        assert_eq!(rest.len(), 4);
+
+        let tokens = generate_tokens("do run(); while (a) b();");
+        assert!(parse(&tokens, &mut Context::new("")).is_err());
+    }
+}
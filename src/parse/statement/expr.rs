@@ -1,13 +1,13 @@
 use parse::combinator::symbol;
-use parse::id_gen::IdGen;
 use parse::tree::Statement;
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
-    let (input, statement) = parse_without_semicolon(input, id_gen)?;
+    let (input, statement) = parse_without_semicolon(input, context)?;
     let (input, _) = symbol(';')(input)?;
 
     Ok((input, statement))
@@ -15,9 +15,9 @@ pub fn parse<'def, 'r>(
 
 pub fn parse_without_semicolon<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
-    let (input, expr) = expr::parse(input, id_gen)?;
+    let (input, expr) = expr::parse(input, context)?;
     Ok((input, Statement::Expr(expr)))
 }
 
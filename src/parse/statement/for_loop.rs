@@ -1,20 +1,20 @@
 use parse::combinator::{keyword, opt, separated_list, symbol};
-use parse::id_gen::IdGen;
 use parse::statement::block::parse_block_or_single_statement;
 use parse::statement::variable_declarators;
 use parse::tree::{ForLoop, Foreach, Statement};
+use parse::Context;
 use parse::{expr, statement, ParseResult, Tokens};
 
 fn parse_foreach<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
-    let (input, declarator) = variable_declarators::parse_standalone(input, id_gen)?;
+    let (input, declarator) = variable_declarators::parse_standalone(input, context)?;
 
     let (input, _) = symbol(':')(input)?;
-    let (input, expr) = expr::parse(input, id_gen)?;
+    let (input, expr) = expr::parse(input, context)?;
     let (input, _) = symbol(')')(input)?;
-    let (input, block) = parse_block_or_single_statement(input, id_gen)?;
+    let (input, block) = parse_block_or_single_statement(input, context)?;
 
     Ok((
         input,
@@ -28,32 +28,33 @@ fn parse_foreach<'def, 'r>(
 
 fn parse_inits<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Vec<Statement<'def>>> {
-    if let Ok((input, declarators)) = variable_declarators::parse_without_semicolon(input, id_gen) {
+    if let Ok((input, declarators)) = variable_declarators::parse_without_semicolon(input, context)
+    {
         Ok((input, vec![declarators]))
     } else {
         separated_list(symbol(','), |i| {
-            statement::expr::parse_without_semicolon(i, id_gen)
+            statement::expr::parse_without_semicolon(i, context)
         })(input)
     }
 }
 
 fn parse_for_loop<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
-    let (input, inits) = parse_inits(input, id_gen)?;
+    let (input, inits) = parse_inits(input, context)?;
 
     let (input, _) = symbol(';')(input)?;
-    let (input, cond_opt) = opt(|i| expr::parse(i, id_gen))(input)?;
+    let (input, cond_opt) = opt(|i| expr::parse(i, context))(input)?;
     let (input, _) = symbol(';')(input)?;
     let (input, updates) = separated_list(symbol(','), |i| {
-        statement::expr::parse_without_semicolon(i, id_gen)
+        statement::expr::parse_without_semicolon(i, context)
     })(input)?;
 
     let (input, _) = symbol(')')(input)?;
-    let (input, block) = parse_block_or_single_statement(input, id_gen)?;
+    let (input, block) = parse_block_or_single_statement(input, context)?;
 
     Ok((
         input,
@@ -68,14 +69,14 @@ fn parse_for_loop<'def, 'r>(
 
 pub fn parse<'def, 'r>(
     original: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
     let (input, _) = keyword("for")(original)?;
     let (input, _) = symbol('(')(input)?;
 
-    if let Ok(ok) = parse_foreach(input, id_gen) {
+    if let Ok(ok) = parse_foreach(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = parse_for_loop(input, id_gen) {
+    } else if let Ok(ok) = parse_for_loop(input, context) {
         Ok(ok)
     } else {
         Err(original)
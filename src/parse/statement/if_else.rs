@@ -1,25 +1,25 @@
 use parse::combinator::{keyword, symbol};
-use parse::id_gen::IdGen;
 use parse::statement::block;
 use parse::tree::{IfElse, Statement};
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
     let (input, _) = keyword("if")(input)?;
 
     let (input, _) = symbol('(')(input)?;
-    let (input, cond) = expr::parse(input, id_gen)?;
+    let (input, cond) = expr::parse(input, context)?;
 
     let (input, _) = symbol(')')(input)?;
 
-    let (input, block) = block::parse_block_or_single_statement(input, id_gen)?;
+    let (input, block) = block::parse_block_or_single_statement(input, context)?;
 
     let (input, else_block_opt) = match keyword("else")(input) {
         Ok((input, _)) => {
-            let (input, else_block) = block::parse_block_or_single_statement(input, id_gen)?;
+            let (input, else_block) = block::parse_block_or_single_statement(input, context)?;
             (input, Some(else_block))
         }
         Err(_) => (input, None),
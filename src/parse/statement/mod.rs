@@ -1,6 +1,6 @@
 use parse::combinator::{get_and_not_followed_by, identifier, opt, symbol};
-use parse::id_gen::IdGen;
 use parse::tree::{Labeled, Statement};
+use parse::Context;
 use parse::{ParseResult, Tokens};
 use tokenize::span::Span;
 
@@ -24,7 +24,7 @@ pub mod while_loop;
 fn parse_label<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, Span<'def>> {
     let (input, label) = identifier(input)?;
 
-    if label.fragment == "default" {
+    if label.fragment() == "default" {
         Err(input)
     } else {
         let (input, _) = get_and_not_followed_by(symbol(':'), symbol(':'))(input)?;
@@ -35,41 +35,41 @@ fn parse_label<'def, 'r>(input: Tokens<'def, 'r>) -> ParseResult<'def, 'r, Span<
 
 fn parse_statement<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
     if let Ok((input, _)) = symbol(';')(input) {
         Ok((input, Statement::Empty))
-    } else if let Ok(ok) = assert::parse(input, id_gen) {
+    } else if let Ok(ok) = assert::parse(input, context) {
         Ok(ok)
     } else if let Ok(ok) = break_stmt::parse(input) {
         Ok(ok)
-    } else if let Ok(ok) = class::parse(input, id_gen) {
+    } else if let Ok(ok) = class::parse(input, context) {
         Ok(ok)
     } else if let Ok(ok) = continue_stmt::parse(input) {
         Ok(ok)
-    } else if let Ok(ok) = return_stmt::parse(input, id_gen) {
+    } else if let Ok(ok) = return_stmt::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = throw::parse(input, id_gen) {
+    } else if let Ok(ok) = throw::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = try::parse(input, id_gen) {
+    } else if let Ok(ok) = try::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = for_loop::parse(input, id_gen) {
+    } else if let Ok(ok) = for_loop::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = do_while::parse(input, id_gen) {
+    } else if let Ok(ok) = do_while::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = while_loop::parse(input, id_gen) {
+    } else if let Ok(ok) = while_loop::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = switch::parse(input, id_gen) {
+    } else if let Ok(ok) = switch::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = synchronized::parse(input, id_gen) {
+    } else if let Ok(ok) = synchronized::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = if_else::parse(input, id_gen) {
+    } else if let Ok(ok) = if_else::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = block::parse(input, id_gen) {
+    } else if let Ok(ok) = block::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = variable_declarators::parse(input, id_gen) {
+    } else if let Ok(ok) = variable_declarators::parse(input, context) {
         Ok(ok)
-    } else if let Ok(ok) = expr::parse(input, id_gen) {
+    } else if let Ok(ok) = expr::parse(input, context) {
         Ok(ok)
     } else {
         Err(input)
@@ -78,10 +78,10 @@ fn parse_statement<'def, 'r>(
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
     let (input, label_opt) = opt(parse_label)(input)?;
-    let (input, statement) = parse_statement(input, id_gen)?;
+    let (input, statement) = parse_statement(input, context)?;
 
     if let Some(label) = label_opt {
         Ok((
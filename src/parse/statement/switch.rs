@@ -1,16 +1,16 @@
 use parse::combinator::{keyword, many0, symbol};
-use parse::id_gen::IdGen;
 use parse::statement::block;
 use parse::tree::{Case, Statement, Switch, WhileLoop};
+use parse::Context;
 use parse::{expr, statement, ParseResult, Tokens};
 
 fn parse_case<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Case<'def>> {
     let (input, label_opt) = if let Ok((input, _)) = keyword("case")(input) {
         // TODO: The below only allows EnumConstant and ConstantExpression. We could optimize something here.
-        let (input, expr) = expr::parse(input, id_gen)?;
+        let (input, expr) = expr::parse(input, context)?;
         let (input, _) = symbol(':')(input)?;
         (input, Some(Box::new(expr)))
     } else if let Ok((input, _)) = keyword("default")(input) {
@@ -20,22 +20,22 @@ fn parse_case<'def, 'r>(
         return Err(input);
     };
 
-    let (input, stmts) = many0(|i| statement::parse(i, id_gen))(input)?;
+    let (input, stmts) = many0(|i| statement::parse(i, context))(input)?;
 
     Ok((input, Case { label_opt, stmts }))
 }
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
     let (input, _) = keyword("switch")(input)?;
     let (input, _) = symbol('(')(input)?;
-    let (input, expr) = expr::parse(input, id_gen)?;
+    let (input, expr) = expr::parse(input, context)?;
     let (input, _) = symbol(')')(input)?;
 
     let (input, _) = symbol('{')(input)?;
-    let (input, cases) = many0(|i| parse_case(i, id_gen))(input)?;
+    let (input, cases) = many0(|i| parse_case(i, context))(input)?;
     let (input, _) = symbol('}')(input)?;
 
     Ok((
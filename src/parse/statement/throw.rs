@@ -1,15 +1,15 @@
 use parse::combinator::{keyword, symbol};
-use parse::id_gen::IdGen;
 use parse::tree::{Statement, Throw};
+use parse::Context;
 use parse::{expr, ParseResult, Tokens};
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
     let (input, _) = keyword("throw")(input)?;
 
-    let (input, expr) = expr::parse(input, id_gen)?;
+    let (input, expr) = expr::parse(input, context)?;
 
     let (input, _) = symbol(';')(input)?;
 
@@ -1,23 +1,24 @@
 use parse::combinator::{identifier, separated_nonempty_list, symbol};
 use parse::def::modifiers;
-use parse::id_gen::IdGen;
+use parse::id_gen::Id;
 use parse::tree::{
     StandaloneVariableDeclarator, Statement, Type, VariableDeclarator, VariableDeclarators,
 };
+use parse::Context;
 use parse::{expr, tpe, ParseResult, Tokens};
 use std::cell::RefCell;
 
-pub fn parse_single<'def: 'r, 'r, 'id_gen_ref>(
+pub fn parse_single<'def: 'r, 'r, 'context_ref>(
     input: Tokens<'def, 'r>,
     tpe: Type<'def>,
-    id_gen: &'id_gen_ref mut IdGen,
+    context: &'context_ref mut Context<'def>,
 ) -> ParseResult<'def, 'r, VariableDeclarator<'def>> {
     let (input, name) = identifier(input)?;
     let (input, tpe) = tpe::array::parse_tail(input, tpe)?;
 
     let (input, expr_opt) = match symbol('=')(input) {
         Ok((input, _)) => {
-            let (input, expr) = expr::parse(input, id_gen)?;
+            let (input, expr) = expr::parse(input, context)?;
             (input, Some(expr))
         }
         Err(_) => (input, None),
@@ -29,18 +30,18 @@ pub fn parse_single<'def: 'r, 'r, 'id_gen_ref>(
             tpe: RefCell::new(tpe),
             name,
             expr_opt,
-            id: id_gen.get_next("var", name.fragment),
+            id: Id::default(),
         },
     ))
 }
 
 pub fn parse_standalone<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, StandaloneVariableDeclarator<'def>> {
-    let (input, modifiers) = modifiers::parse(input, id_gen)?;
+    let (input, modifiers) = modifiers::parse(input, context)?;
     let (input, tpe) = tpe::parse(input)?;
-    let (input, declarator) = parse_single(input, tpe, id_gen)?;
+    let (input, declarator) = parse_single(input, tpe, context)?;
 
     Ok((
         input,
@@ -55,13 +56,13 @@ pub fn parse_standalone<'def, 'r>(
 
 pub fn parse_without_semicolon<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
-    let (input, modifiers) = modifiers::parse(input, id_gen)?;
+    let (input, modifiers) = modifiers::parse(input, context)?;
     let (input, tpe) = tpe::parse(input)?;
 
     let (input, declarators) =
-        separated_nonempty_list(symbol(','), |i| parse_single(i, tpe.clone(), id_gen))(input)?;
+        separated_nonempty_list(symbol(','), |i| parse_single(i, tpe.clone(), context))(input)?;
 
     Ok((
         input,
@@ -74,9 +75,9 @@ pub fn parse_without_semicolon<'def, 'r>(
 
 pub fn parse<'def, 'r>(
     input: Tokens<'def, 'r>,
-    id_gen: &mut IdGen,
+    context: &mut Context<'def>,
 ) -> ParseResult<'def, 'r, Statement<'def>> {
-    let (input, declarators) = parse_without_semicolon(input, id_gen)?;
+    let (input, declarators) = parse_without_semicolon(input, context)?;
     let (input, _) = symbol(';')(input)?;
 
     Ok((input, declarators))
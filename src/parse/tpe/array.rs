@@ -14,7 +14,7 @@ pub fn parse_tail<'def, 'r>(
                 size_opt: None,
                 underlying: ClassType {
                     prefix_opt: None,
-                    name: NATIVE_ARRAY_CLASS_NAME.to_owned(),
+                    name: NATIVE_ARRAY_CLASS_NAME,
                     span_opt: None,
                     type_args_opt: Some(vec![tpe.clone().to_type_arg()]),
                     def_opt: None,
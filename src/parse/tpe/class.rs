@@ -31,7 +31,7 @@ pub fn parse_tail<'def, 'r>(
             Some(prefix) => Some(Box::new(EnclosingType::Class(prefix))),
             None => None,
         },
-        name: name.fragment.to_owned(),
+        name: name.fragment(),
         span_opt: Some(name),
         type_args_opt,
         def_opt: None,
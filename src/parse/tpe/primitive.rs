@@ -32,7 +32,7 @@ pub fn parse_no_array<'def, 'r>(
     original: Tokens<'def, 'r>,
 ) -> ParseResult<'def, 'r, PrimitiveType<'def>> {
     let (input, name) = any_keyword(original)?;
-    if let Ok(tpe) = build_type_type(name.fragment) {
+    if let Ok(tpe) = build_type_type(name.fragment()) {
         Ok((
             input,
             PrimitiveType {
@@ -1,17 +1,22 @@
 use analyze;
 use analyze::definition::{Field, FieldDef, FieldGroup, MethodDef};
+use parse::id_gen::Id;
+#[cfg(feature = "serde")]
+use parse::serialize;
 use std::borrow::Borrow;
 use std::cell::{Cell, Ref, RefCell};
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
 use std::ops::Deref;
 use tokenize::span::Span;
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Comment<'a> {
     pub content: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct CompilationUnit<'a> {
     pub package_opt: Option<Package<'a>>,
     pub imports: Vec<Import<'a>>,
@@ -19,6 +24,8 @@ pub struct CompilationUnit<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum CompilationUnitItem<'a> {
     Class(Class<'a>),
     Interface(Interface<'a>),
@@ -26,7 +33,28 @@ pub enum CompilationUnitItem<'a> {
     Enum(Enum<'a>),
 }
 
+impl<'a> CompilationUnitItem<'a> {
+    pub fn span_opt(&self) -> Option<Span<'a>> {
+        match self {
+            CompilationUnitItem::Class(class) => class.span_opt,
+            CompilationUnitItem::Interface(interface) => interface.span_opt,
+            CompilationUnitItem::Annotation(annotation) => annotation.span_opt,
+            CompilationUnitItem::Enum(enum_def) => enum_def.span_opt,
+        }
+    }
+
+    pub fn set_span(&mut self, span: Span<'a>) {
+        match self {
+            CompilationUnitItem::Class(class) => class.span_opt = Some(span),
+            CompilationUnitItem::Interface(interface) => interface.span_opt = Some(span),
+            CompilationUnitItem::Annotation(annotation) => annotation.span_opt = Some(span),
+            CompilationUnitItem::Enum(enum_def) => enum_def.span_opt = Some(span),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Import<'a> {
     pub prefix_opt: Option<Box<ImportPrefix<'a>>>,
     pub is_static: bool,
@@ -36,12 +64,33 @@ pub struct Import<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ImportPrefix<'a> {
     pub prefix_opt: Option<Box<ImportPrefix<'a>>>,
     pub name: Span<'a>,
     pub def_opt: RefCell<Option<ImportPrefixDef<'a>>>,
 }
 
+impl<'a> Import<'a> {
+    // The imported name without `.*`, e.g. "java.util.List" or "java.util" for `java.util.*`.
+    pub fn path(&self) -> String {
+        import_path(&self.prefix_opt, &self.name)
+    }
+}
+
+impl<'a> ImportPrefix<'a> {
+    pub fn path(&self) -> String {
+        import_path(&self.prefix_opt, &self.name)
+    }
+}
+
+fn import_path(prefix_opt: &Option<Box<ImportPrefix>>, name: &Span) -> String {
+    match prefix_opt {
+        Some(prefix) => format!("{}.{}", prefix.path(), name.fragment()),
+        None => name.fragment().to_owned(),
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
 pub enum ImportDef<'a> {
     Package(*const analyze::definition::Package<'a>),
@@ -55,14 +104,18 @@ pub enum ImportPrefixDef<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Package<'a> {
     pub prefix_opt: Option<Box<Package<'a>>>,
     pub annotateds: Vec<Annotated<'a>>,
     pub name: Span<'a>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_opt"))]
     pub def_opt: Option<*const analyze::definition::Package<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum Annotated<'a> {
     Normal(NormalAnnotated<'a>),
     Marker(MarkerAnnotated<'a>),
@@ -70,29 +123,41 @@ pub enum Annotated<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct NormalAnnotated<'a> {
     pub class: ClassType<'a>,
     pub params: Vec<AnnotatedParam<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MarkerAnnotated<'a> {
     pub class: ClassType<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SingleAnnotated<'a> {
     pub class: ClassType<'a>,
     pub value: AnnotatedValue<'a>,
+    // The implicit `value` element of the annotation type.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
+    pub def_opt: RefCell<Option<*const analyze::definition::MethodDef<'a>>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct AnnotatedParam<'a> {
     pub name: Span<'a>,
     pub value: AnnotatedValue<'a>,
+    // The element of the annotation type.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
+    pub def_opt: RefCell<Option<*const analyze::definition::MethodDef<'a>>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum AnnotatedValue<'a> {
     Expr(Expr<'a>),
     Annotated(Box<Annotated<'a>>),
@@ -100,11 +165,13 @@ pub enum AnnotatedValue<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct AnnotatedValueArray<'a> {
     pub items: Vec<AnnotatedValue<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Class<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub name: Span<'a>,
@@ -112,49 +179,80 @@ pub struct Class<'a> {
     pub extend_opt: Option<ClassType<'a>>,
     pub implements: Vec<ClassType<'a>>,
     pub body: ClassBody<'a>,
+    // From the first modifier to the closing `}` or `;`. Only set by the parser.
+    pub span_opt: Option<Span<'a>>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
     pub def_opt: RefCell<Option<*const analyze::definition::Class<'a>>>,
-    pub id: String,
+    pub id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Enum<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub name: Span<'a>,
     pub implements: Vec<ClassType<'a>>,
     pub constants: Vec<EnumConstant<'a>>,
     pub body_opt: Option<ClassBody<'a>>,
+    // From the first modifier to the closing `}` or `;`. Only set by the parser.
+    pub span_opt: Option<Span<'a>>,
+    // The class of `analyze::definition::Enum`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
+    pub def_opt: RefCell<Option<*const analyze::definition::Class<'a>>>,
+    pub id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct EnumConstant<'a> {
     pub annotateds: Vec<Annotated<'a>>,
     pub name: Span<'a>,
     pub args_opt: Option<Vec<Expr<'a>>>,
     pub body_opt: Option<ClassBody<'a>>,
+    pub id: Id,
+    // The id of the class in `body_opt`. It is only assigned when there is a body.
+    pub body_id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Interface<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub name: Span<'a>,
     pub type_params: Vec<TypeParam<'a>>,
     pub extends: Vec<ClassType<'a>>,
     pub body: ClassBody<'a>,
+    // From the first modifier to the closing `}` or `;`. Only set by the parser.
+    pub span_opt: Option<Span<'a>>,
+    // The class of `analyze::definition::Interface`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
+    pub def_opt: RefCell<Option<*const analyze::definition::Class<'a>>>,
+    pub id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Annotation<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub name: Span<'a>,
     pub body: AnnotationBody<'a>,
+    // From the first modifier to the closing `}` or `;`. Only set by the parser.
+    pub span_opt: Option<Span<'a>>,
+    // The class of `analyze::definition::AnnotationDef`.
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
+    pub def_opt: RefCell<Option<*const analyze::definition::Class<'a>>>,
+    pub id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct AnnotationBody<'a> {
     pub items: Vec<AnnotationBodyItem<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum AnnotationBodyItem<'a> {
     Param(AnnotationParam<'a>),
     FieldDeclarators(FieldDeclarators<'a>),
@@ -165,19 +263,26 @@ pub enum AnnotationBodyItem<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct AnnotationParam<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub tpe: Type<'a>,
     pub name: Span<'a>,
     pub default_opt: Option<Expr<'a>>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
+    pub def_opt: RefCell<Option<*const analyze::definition::MethodDef<'a>>>,
+    pub id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClassBody<'a> {
     pub items: Vec<ClassBodyItem<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum ClassBodyItem<'a> {
     Method(Method<'a>),
     FieldDeclarators(FieldDeclarators<'a>),
@@ -189,30 +294,73 @@ pub enum ClassBodyItem<'a> {
     Constructor(Constructor<'a>),
 }
 
+impl<'a> ClassBodyItem<'a> {
+    // The extent of the member. A static initializer has none.
+    pub fn span_opt(&self) -> Option<Span<'a>> {
+        match self {
+            ClassBodyItem::Method(method) => method.span_opt,
+            ClassBodyItem::FieldDeclarators(field_declarators) => field_declarators.span_opt,
+            ClassBodyItem::Class(class) => class.span_opt,
+            ClassBodyItem::Interface(interface) => interface.span_opt,
+            ClassBodyItem::Enum(enum_def) => enum_def.span_opt,
+            ClassBodyItem::Annotation(annotation) => annotation.span_opt,
+            ClassBodyItem::StaticInitializer(_) => None,
+            ClassBodyItem::Constructor(constructor) => constructor.span_opt,
+        }
+    }
+
+    pub fn set_span(&mut self, span: Span<'a>) {
+        match self {
+            ClassBodyItem::Method(method) => method.span_opt = Some(span),
+            ClassBodyItem::FieldDeclarators(field_declarators) => {
+                field_declarators.span_opt = Some(span)
+            }
+            ClassBodyItem::Class(class) => class.span_opt = Some(span),
+            ClassBodyItem::Interface(interface) => interface.span_opt = Some(span),
+            ClassBodyItem::Enum(enum_def) => enum_def.span_opt = Some(span),
+            ClassBodyItem::Annotation(annotation) => annotation.span_opt = Some(span),
+            ClassBodyItem::StaticInitializer(_) => (),
+            ClassBodyItem::Constructor(constructor) => constructor.span_opt = Some(span),
+        }
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct FieldDeclarators<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub declarators: Vec<FieldDeclarator<'a>>,
+    // From the first modifier to the closing `}` or `;`. Only set by the parser.
+    pub span_opt: Option<Span<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct FieldDeclarator<'a> {
     pub tpe: RefCell<Type<'a>>,
     pub name: Span<'a>,
     pub expr_opt: Option<Expr<'a>>,
-    pub id: String,
+    pub id: Id,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
     pub def_opt: RefCell<Option<*const analyze::definition::FieldDef<'a>>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum Modifier<'a> {
     Annotated(Annotated<'a>),
     Keyword(Keyword<'a>),
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Block<'a> {
     pub stmts: Vec<Statement<'a>>,
+    // The braces. A single statement in place of a block has no span.
+    pub span_opt: Option<Span<'a>>,
+    // The statements weren't parsed. See `parse::apply_signatures`.
+    pub is_skipped: bool,
     pub return_type: Type<'a>,
 }
 
@@ -222,6 +370,8 @@ pub struct InvocationContext {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum Type<'a> {
     Class(ClassType<'a>),
     Primitive(PrimitiveType<'a>),
@@ -233,6 +383,58 @@ pub enum Type<'a> {
 }
 
 impl<'a> Type<'a> {
+    // The erasure named by import paths, e.g. `java.util.List` for `List<String>` and
+    // `java.lang.Number` for `T extends Number`. A class that isn't resolved is named by its
+    // simple name. It is used to compare method signatures.
+    pub fn erasure(&self) -> String {
+        match self {
+            Type::Class(class) => class.erased_name(),
+            Type::Primitive(primitive) => primitive.tpe.name().to_owned(),
+            Type::Array(array) => format!("{}[]", array.tpe.erasure()),
+            Type::Parameterized(parameterized) => {
+                let type_param = unsafe { &*parameterized.def };
+                match type_param.extends.borrow().first() {
+                    Some(TypeParamExtend::Class(class)) => class.erased_name(),
+                    Some(TypeParamExtend::Parameterized(p)) => {
+                        Type::Parameterized(p.clone()).erasure()
+                    }
+                    None => analyze::build::class::OBJECT_CLASS_NAME.to_owned(),
+                }
+            }
+            Type::Void(_) => "void".to_owned(),
+            Type::Wildcard(_) | Type::UnknownType => "?".to_owned(),
+        }
+    }
+
+    // The primitive type of a value of this type after unboxing (JLS 5.1.8), e.g. `int` for
+    // `java.lang.Integer`. A class has to be resolved to be unboxed.
+    pub fn unboxed_opt(&self) -> Option<PrimitiveTypeType> {
+        let class = match self {
+            Type::Primitive(primitive) => return Some(primitive.tpe.clone()),
+            Type::Class(class) => class,
+            _ => return None,
+        };
+        let def = unsafe { &*class.def_opt? };
+        match def.import_path.as_str() {
+            "java.lang.Boolean" => Some(PrimitiveTypeType::Boolean),
+            "java.lang.Byte" => Some(PrimitiveTypeType::Byte),
+            "java.lang.Character" => Some(PrimitiveTypeType::Char),
+            "java.lang.Double" => Some(PrimitiveTypeType::Double),
+            "java.lang.Float" => Some(PrimitiveTypeType::Float),
+            "java.lang.Integer" => Some(PrimitiveTypeType::Int),
+            "java.lang.Long" => Some(PrimitiveTypeType::Long),
+            "java.lang.Short" => Some(PrimitiveTypeType::Short),
+            _ => None,
+        }
+    }
+
+    pub fn is_string(&self) -> bool {
+        match self {
+            Type::Class(class) => class.erased_name() == "java.lang.String",
+            _ => false,
+        }
+    }
+
     pub fn span_opt(&self) -> Option<&Span<'a>> {
         match self {
             Type::Array(arr) => None,
@@ -306,11 +508,14 @@ impl<'a> Type<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Void<'a> {
     pub span_opt: Option<Span<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum ReferenceType<'a> {
     Class(ClassType<'a>),
     Array(ArrayType<'a>),
@@ -318,10 +523,12 @@ pub enum ReferenceType<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct PackagePrefix<'a> {
     pub prefix_opt: Option<Box<PackagePrefix<'a>>>,
-    pub name: String,
+    pub name: &'a str,
     pub span_opt: Option<Span<'a>>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr"))]
     pub def: *const analyze::definition::Package<'a>,
 }
 
@@ -336,6 +543,8 @@ impl<'a> PackagePrefix<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum StaticType<'a> {
     Class(ClassType<'a>),
     Parameterized(ParameterizedType<'a>),
@@ -357,6 +566,8 @@ impl<'a> StaticType<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum EnclosingType<'a> {
     Package(PackagePrefix<'a>),
     Class(ClassType<'a>),
@@ -402,13 +613,13 @@ impl<'a> EnclosingType<'a> {
                 } else {
                     None
                 },
-                name: package.name.to_owned(),
+                name: package.name,
                 span_opt: package.span_opt,
                 def: package.def,
             }),
             EnclosingType::Class(class) => EnclosingType::Class(ClassType {
                 prefix_opt: prefix_opt.map(Box::new),
-                name: class.name.to_owned(),
+                name: class.name,
                 span_opt: class.span_opt,
                 type_args_opt: class.type_args_opt.clone(),
                 def_opt: class.def_opt.clone(),
@@ -427,6 +638,8 @@ impl<'a> EnclosingType<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum TypeArg<'a> {
     Class(ClassType<'a>),
     Parameterized(ParameterizedType<'a>),
@@ -448,6 +661,7 @@ impl<'a> TypeArg<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct WildcardType<'a> {
     pub span_opt: Option<Span<'a>>,
     pub extends: Vec<ReferenceType<'a>>,
@@ -455,12 +669,14 @@ pub struct WildcardType<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct PrimitiveType<'a> {
     pub span_opt: Option<Span<'a>>,
     pub tpe: PrimitiveTypeType,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub enum PrimitiveTypeType {
     Boolean,
     Byte,
@@ -472,12 +688,145 @@ pub enum PrimitiveTypeType {
     Short,
 }
 
+impl PrimitiveTypeType {
+    pub fn name(&self) -> &'static str {
+        match self {
+            PrimitiveTypeType::Boolean => "boolean",
+            PrimitiveTypeType::Byte => "byte",
+            PrimitiveTypeType::Char => "char",
+            PrimitiveTypeType::Double => "double",
+            PrimitiveTypeType::Float => "float",
+            PrimitiveTypeType::Int => "int",
+            PrimitiveTypeType::Long => "long",
+            PrimitiveTypeType::Short => "short",
+        }
+    }
+
+    // The type of a primitive value after unary numeric promotion (JLS 5.6.1), or None if it
+    // isn't numeric.
+    pub fn promote(&self) -> Option<PrimitiveTypeType> {
+        match self {
+            PrimitiveTypeType::Boolean => None,
+            PrimitiveTypeType::Byte | PrimitiveTypeType::Char | PrimitiveTypeType::Short => {
+                Some(PrimitiveTypeType::Int)
+            }
+            other => Some(other.clone()),
+        }
+    }
+
+    // The type that both primitive values are converted to by binary numeric promotion (JLS
+    // 5.6.2), or None if one of them isn't numeric.
+    pub fn promote_with(&self, other: &PrimitiveTypeType) -> Option<PrimitiveTypeType> {
+        let (left, right) = (self.promote()?, other.promote()?);
+        for wider in &[
+            PrimitiveTypeType::Double,
+            PrimitiveTypeType::Float,
+            PrimitiveTypeType::Long,
+        ] {
+            if left == *wider || right == *wider {
+                return Some(wider.clone());
+            }
+        }
+        Some(PrimitiveTypeType::Int)
+    }
+
+    pub fn is_integral(&self) -> bool {
+        matches!(
+            self,
+            PrimitiveTypeType::Byte
+                | PrimitiveTypeType::Char
+                | PrimitiveTypeType::Short
+                | PrimitiveTypeType::Int
+                | PrimitiveTypeType::Long
+        )
+    }
+}
+
+// The methods that a class inherits from its supertypes, cached in the class. A method of a
+// supertype is left out when a method declared or inherited earlier has the same signature (i.e.
+// it is overridden or hidden), when it is private, or when it is package-private and declared in
+// another package. The superclass is searched before the interfaces, so an inherited
+// implementation is kept over an interface's method. A cyclic hierarchy doesn't compile, so a
+// supertype that is already being collected is skipped.
+fn inherited_methods<'r, 'a>(
+    def: &'r analyze::definition::Class<'a>,
+    visiting: &mut Vec<*const analyze::definition::Class<'a>>,
+) -> &'r [analyze::definition::InheritedMethod<'a>] {
+    def.inherited_methods.get_or_init(|| {
+        visiting.push(def);
+
+        let mut signatures = def
+            .methods
+            .iter()
+            .map(|method| method.erased_signature())
+            .collect::<HashSet<_>>();
+        let supertypes = def
+            .extend_opt
+            .borrow()
+            .iter()
+            .chain(def.implements.borrow().iter())
+            .cloned()
+            .collect::<Vec<_>>();
+
+        let mut inherited = vec![];
+        for supertype in &supertypes {
+            let super_def = match supertype.def_opt {
+                Some(super_def) if !visiting.contains(&super_def) => unsafe { &*super_def },
+                _ => continue,
+            };
+
+            let mut candidates = super_def
+                .methods
+                .iter()
+                .map(|method_def| analyze::definition::InheritedMethod {
+                    method: supertype.realize_method(method_def, 1),
+                    declaring: super_def,
+                })
+                .collect::<Vec<_>>();
+            for further in inherited_methods(super_def, visiting) {
+                let mut further = further.clone();
+                further.method.depth += 1;
+                candidates.push(further);
+            }
+
+            for candidate in candidates {
+                if is_inherited(&candidate, def)
+                    && signatures.insert(candidate.method.erased_signature())
+                {
+                    inherited.push(candidate);
+                }
+            }
+        }
+
+        visiting.pop();
+        inherited
+    })
+}
+
+fn is_inherited<'a>(
+    method: &analyze::definition::InheritedMethod<'a>,
+    heir: &analyze::definition::Class<'a>,
+) -> bool {
+    let modifiers = &unsafe { &*method.method.def }.modifiers;
+    if modifiers.contains(&analyze::definition::Modifier::Private) {
+        false
+    } else if modifiers.contains(&analyze::definition::Modifier::Public)
+        || modifiers.contains(&analyze::definition::Modifier::Protected)
+    {
+        true
+    } else {
+        unsafe { &*method.declaring }.package_path == heir.package_path
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClassType<'a> {
     pub prefix_opt: Option<Box<EnclosingType<'a>>>,
-    pub name: String,
+    pub name: &'a str,
     pub span_opt: Option<Span<'a>>,
     pub type_args_opt: Option<Vec<TypeArg<'a>>>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_opt"))]
     pub def_opt: Option<*const analyze::definition::Class<'a>>,
 }
 
@@ -486,17 +835,31 @@ impl<'a> ClassType<'a> {
         self.span_opt = span_opt.map(|s| s.clone());
     }
 
+    fn erased_name(&self) -> String {
+        match self.def_opt {
+            Some(def) => unsafe { &*def }.import_path.clone(),
+            None => self.name.to_owned(),
+        }
+    }
+
+    // The single abstract method of a functional interface, which may be inherited. The public
+    // methods of `Object` (e.g. `equals(java.lang.Object)`) don't count.
     pub fn lambda_method(&self) -> Option<analyze::definition::Method<'a>> {
-        let def = if let Some(def) = self.def_opt {
-            unsafe { &*def }
-        } else {
-            return None;
-        };
+        let context = InvocationContext { only_static: false };
+        let mut abstract_methods = self
+            .collect_methods(&|_| true, &context, 0)
+            .into_iter()
+            .filter(|method| {
+                let def = unsafe { &*method.def };
+                def.modifiers
+                    .contains(&analyze::definition::Modifier::Abstract)
+                    && !OBJECT_METHOD_SIGNATURES.contains(&def.erased_signature().as_str())
+            })
+            .collect::<Vec<_>>();
 
-        if let Some(method_def) = def.methods.first() {
-            Some(self.realize_method(method_def, 0))
+        if abstract_methods.len() == 1 {
+            abstract_methods.pop()
         } else {
-            // TODO: Go to the super class
             None
         }
     }
@@ -551,6 +914,12 @@ impl<'a> ClassType<'a> {
             }
         };
 
+        for implement in class.implements.borrow().iter() {
+            if let Some(found) = implement.find_inner_class(name) {
+                return Some(found);
+            }
+        }
+
         None
     }
 
@@ -582,7 +951,7 @@ impl<'a> ClassType<'a> {
                 extends.push(self.realize_type_param_extend(extend));
             }
             type_params.push(analyze::definition::TypeParam {
-                name: t.name.to_owned(),
+                name: t.name,
                 span_opt: t.span_opt,
                 extends: RefCell::new(extends),
                 id: t.id.to_owned(),
@@ -595,7 +964,8 @@ impl<'a> ClassType<'a> {
                 // Realize should get type params from method
                 tpe: RefCell::new(self.realize(param.tpe.borrow().deref())),
                 is_varargs: param.is_varargs,
-                name: param.name.clone(),
+                name: param.name,
+                span_opt: param.span_opt,
             })
         }
 
@@ -608,36 +978,93 @@ impl<'a> ClassType<'a> {
         }
     }
 
+    // Constructors aren't inherited, so only the class's own constructors are returned.
+    pub fn find_constructors(&self) -> Vec<analyze::definition::Constructor<'a>> {
+        let def = if let Some(def) = self.def_opt {
+            unsafe { &*def }
+        } else {
+            return vec![];
+        };
+
+        def.constructors
+            .iter()
+            .map(|constructor| analyze::definition::Constructor {
+                type_params: constructor.type_params.clone(),
+                params: constructor
+                    .params
+                    .iter()
+                    .map(|param| analyze::definition::Param {
+                        tpe: RefCell::new(self.realize(param.tpe.borrow().deref())),
+                        is_varargs: param.is_varargs,
+                        name: param.name,
+                        span_opt: param.span_opt,
+                    })
+                    .collect(),
+                def: constructor,
+            })
+            .collect()
+    }
+
+    // The overloads named `name`, including the inherited ones.
     pub fn find_methods(
         &self,
         name: &str,
         context: &InvocationContext,
         depth: usize,
     ) -> Vec<analyze::definition::Method<'a>> {
+        self.collect_methods(&|method_def| method_def.name == name, context, depth)
+    }
+
+    // The method with the erased signature as declared, e.g.
+    // `put(java.lang.Object,java.lang.Object)` for `Map<String, Integer>`. It may be inherited.
+    pub fn find_method_by_signature(
+        &self,
+        signature: &str,
+        context: &InvocationContext,
+    ) -> Option<analyze::definition::Method<'a>> {
+        let name = signature.split('(').next().unwrap();
+        self.find_methods(name, context, 0)
+            .into_iter()
+            .find(|method| unsafe { &*method.def }.erased_signature() == signature)
+    }
+
+    // The methods declared in the class, and then the inherited ones (see `inherited_methods`).
+    fn collect_methods<F>(
+        &self,
+        filter: &F,
+        context: &InvocationContext,
+        depth: usize,
+    ) -> Vec<analyze::definition::Method<'a>>
+    where
+        F: Fn(&MethodDef<'a>) -> bool,
+    {
         let def = if let Some(def) = self.def_opt {
             unsafe { &*def }
         } else {
             return vec![];
         };
+        let is_included = |method_def: &MethodDef<'a>| {
+            (!context.only_static
+                || method_def
+                    .modifiers
+                    .contains(&analyze::definition::Modifier::Static))
+                && filter(method_def)
+        };
 
         let mut methods = vec![];
 
         for method_def in &def.methods {
-            if context.only_static
-                && !method_def
-                    .modifiers
-                    .contains(&analyze::definition::Modifier::Static)
-            {
-                continue;
-            }
-
-            if &method_def.name == name {
+            if is_included(method_def) {
                 methods.push(self.realize_method(method_def, depth));
             }
         }
 
-        if let Some(extend) = def.extend_opt.borrow().as_ref() {
-            methods.append(&mut extend.find_methods(name, context, depth + 1));
+        for inherited in inherited_methods(def, &mut vec![]) {
+            if is_included(unsafe { &*inherited.method.def }) {
+                let mut method = inherited.method.clone();
+                method.depth += depth;
+                methods.push(method);
+            }
         }
 
         methods
@@ -660,7 +1087,7 @@ impl<'a> ClassType<'a> {
             }
 
             for item in &group.items {
-                if &item.name == name {
+                if item.name == name {
                     return Some(Field {
                         tpe: self.realize(item.tpe.borrow().deref()),
                         def: item,
@@ -675,6 +1102,12 @@ impl<'a> ClassType<'a> {
             }
         }
 
+        for implement in def.implements.borrow().iter() {
+            if let Some(field) = implement.find_field(name, context) {
+                return Some(field);
+            }
+        }
+
         None
     }
 
@@ -778,7 +1211,7 @@ impl<'a> ClassType<'a> {
                 .prefix_opt
                 .as_ref()
                 .map(|p| Box::new(self.realize_enclosing(&p))),
-            name: class.name.to_owned(),
+            name: class.name,
             span_opt: class.span_opt,
             type_args_opt: class.type_args_opt.as_ref().map(|type_args| {
                 let mut realizeds = vec![];
@@ -814,7 +1247,11 @@ impl<'a> ClassType<'a> {
 
 pub static NATIVE_ARRAY_CLASS_NAME: &str = "NATIVE:Array";
 
+static OBJECT_METHOD_SIGNATURES: [&str; 3] =
+    ["equals(java.lang.Object)", "hashCode()", "toString()"];
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ArrayType<'a> {
     pub tpe: Box<Type<'a>>,
     pub size_opt: Option<Box<Expr<'a>>>,
@@ -837,9 +1274,11 @@ impl<'a> ArrayType<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ParameterizedType<'a> {
-    pub name: String,
+    pub name: &'a str,
     pub span_opt: Option<Span<'a>>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr"))]
     pub def: *const analyze::definition::TypeParam<'a>,
 }
 
@@ -890,14 +1329,18 @@ impl<'a> ParameterizedType<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct TypeParam<'a> {
     pub name: Span<'a>,
     pub extends: Vec<TypeParamExtend<'a>>,
-    pub id: String,
+    pub id: Id,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
     pub def_opt: RefCell<Option<*const analyze::definition::TypeParam<'a>>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum TypeParamExtend<'a> {
     Class(ClassType<'a>),
     Parameterized(ParameterizedType<'a>),
@@ -936,6 +1379,7 @@ impl<'a> TypeParamExtend<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Param<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub tpe: Type<'a>,
@@ -944,6 +1388,7 @@ pub struct Param<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Constructor<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub type_params: Vec<TypeParam<'a>>,
@@ -951,9 +1396,15 @@ pub struct Constructor<'a> {
     pub params: Vec<Param<'a>>,
     pub throws: Vec<ClassType<'a>>,
     pub block: Block<'a>,
+    // From the first modifier to the closing `}` or `;`. Only set by the parser.
+    pub span_opt: Option<Span<'a>>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
+    pub def_opt: RefCell<Option<*const analyze::definition::ConstructorDef<'a>>>,
+    pub id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Method<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub type_params: Vec<TypeParam<'a>>,
@@ -962,11 +1413,16 @@ pub struct Method<'a> {
     pub params: Vec<Param<'a>>,
     pub throws: Vec<ClassType<'a>>,
     pub block_opt: Option<Block<'a>>,
+    // From the first modifier to the closing `}` or `;`. Only set by the parser.
+    pub span_opt: Option<Span<'a>>,
+    #[cfg_attr(feature = "serde", serde(serialize_with = "serialize::ptr_cell"))]
     pub def_opt: RefCell<Option<*const analyze::definition::MethodDef<'a>>>,
-    pub id: String,
+    pub id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum Statement<'a> {
     Assert(Assert<'a>),
     Block(Block<'a>),
@@ -990,46 +1446,54 @@ pub enum Statement<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Assert<'a> {
     pub expr: Expr<'a>,
     pub error_opt: Option<Expr<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Labeled<'a> {
     pub label: Span<'a>,
     pub statement: Box<Statement<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Continue<'a> {
     pub identifier_opt: Option<Span<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Break<'a> {
     pub identifier_opt: Option<Span<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Switch<'a> {
     pub expr: Box<Expr<'a>>,
     pub cases: Vec<Case<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Case<'a> {
     pub label_opt: Option<Box<Expr<'a>>>,
     pub stmts: Vec<Statement<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct DoWhile<'a> {
     pub block: Block<'a>,
     pub cond: Box<Expr<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct WhileLoop<'a> {
     pub cond: Box<Expr<'a>>,
     pub block: Block<'a>,
@@ -1044,6 +1508,8 @@ pub struct Try<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum TryResource<'a> {
     Name(Name<'a>),
     Declarator(StandaloneVariableDeclarator<'a>),
@@ -1051,6 +1517,7 @@ pub enum TryResource<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Catch<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub param_name: Span<'a>,
@@ -1059,17 +1526,20 @@ pub struct Catch<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Synchronized<'a> {
     pub expr: Box<Expr<'a>>,
     pub block: Block<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Throw<'a> {
     pub expr: Expr<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Foreach<'a> {
     pub declarator: StandaloneVariableDeclarator<'a>,
     pub expr: Expr<'a>,
@@ -1077,6 +1547,7 @@ pub struct Foreach<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ForLoop<'a> {
     pub inits: Vec<Statement<'a>>,
     pub cond_opt: Option<Expr<'a>>,
@@ -1085,6 +1556,7 @@ pub struct ForLoop<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct IfElse<'a> {
     pub cond: Expr<'a>,
     pub block: Block<'a>,
@@ -1092,17 +1564,20 @@ pub struct IfElse<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ReturnStmt<'a> {
     pub expr_opt: Option<Expr<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct VariableDeclarators<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub declarators: Vec<VariableDeclarator<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct StandaloneVariableDeclarator<'a> {
     pub modifiers: Vec<Modifier<'a>>,
     pub tpe: RefCell<Type<'a>>,
@@ -1111,14 +1586,17 @@ pub struct StandaloneVariableDeclarator<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct VariableDeclarator<'a> {
     pub tpe: RefCell<Type<'a>>,
     pub name: Span<'a>,
     pub expr_opt: Option<Expr<'a>>,
-    pub id: String,
+    pub id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum Expr<'a> {
     ArrayAccess(ArrayAccess<'a>),
     ArrayInitializer(ArrayInitializer<'a>),
@@ -1165,44 +1643,78 @@ impl<'a> Expr<'a> {
                 }
             }
             Expr::StaticClass(s) => None,
-            Expr::Int(i) => Some(Type::Primitive(PrimitiveType {
+            Expr::NewObject(n) => {
+                if n.tpe.def_opt.is_some() {
+                    Some(Type::Class(n.tpe.clone()))
+                } else {
+                    None
+                }
+            }
+            Expr::Int(_) => Some(Type::Primitive(PrimitiveType {
                 span_opt: None,
                 tpe: PrimitiveTypeType::Int,
             })),
+            Expr::Long(_) => Some(Type::Primitive(PrimitiveType {
+                span_opt: None,
+                tpe: PrimitiveTypeType::Long,
+            })),
+            Expr::Float(_) => Some(Type::Primitive(PrimitiveType {
+                span_opt: None,
+                tpe: PrimitiveTypeType::Float,
+            })),
+            Expr::Double(_) => Some(Type::Primitive(PrimitiveType {
+                span_opt: None,
+                tpe: PrimitiveTypeType::Double,
+            })),
+            Expr::Boolean(_) => Some(Type::Primitive(PrimitiveType {
+                span_opt: None,
+                tpe: PrimitiveTypeType::Boolean,
+            })),
+            Expr::Char(_) => Some(Type::Primitive(PrimitiveType {
+                span_opt: None,
+                tpe: PrimitiveTypeType::Char,
+            })),
+            Expr::BinaryOperation(b) => b.tpe_opt(),
             _ => None,
         }
     }
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct StaticClass<'a> {
     pub tpe: StaticType<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct This<'a> {
     pub tpe_opt: Option<Type<'a>>,
     pub span: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Super<'a> {
     pub tpe_opt: Option<Type<'a>>,
     pub span: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Char<'a> {
     pub value: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ConstructorReference<'a> {
     pub tpe: ReferenceType<'a>,
     pub type_args_opt: Option<Vec<TypeArg<'a>>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MethodReference<'a> {
     pub primary: MethodReferencePrimary<'a>,
     pub type_args_opt: Option<Vec<TypeArg<'a>>>,
@@ -1210,6 +1722,8 @@ pub struct MethodReference<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum MethodReferencePrimary<'a> {
     Class(ClassType<'a>),
     Array(ArrayType<'a>),
@@ -1217,6 +1731,7 @@ pub enum MethodReferencePrimary<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Ternary<'a> {
     pub cond: Box<Expr<'a>>,
     pub true_expr: Box<Expr<'a>>,
@@ -1224,17 +1739,20 @@ pub struct Ternary<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ArrayAccess<'a> {
     pub expr: Box<Expr<'a>>,
     pub index: Box<Expr<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Keyword<'a> {
     pub name: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Name<'a> {
     pub name: Span<'a>,
     pub resolved_opt: Cell<Option<ResolvedName<'a>>>,
@@ -1259,21 +1777,26 @@ impl<'def> ResolvedName<'def> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct SuperConstructorCall<'a> {
     pub prefix_opt: Option<Box<Expr<'a>>>,
     pub type_args_opt: Option<Vec<TypeArg<'a>>>,
     pub name: Span<'a>,
     pub args: Vec<Expr<'a>>,
+    pub def_opt: RefCell<Option<analyze::definition::Constructor<'a>>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ThisConstructorCall<'a> {
     pub type_args_opt: Option<Vec<TypeArg<'a>>>,
     pub name: Span<'a>,
     pub args: Vec<Expr<'a>>,
+    pub def_opt: RefCell<Option<analyze::definition::Constructor<'a>>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct MethodCall<'a> {
     pub prefix_opt: Option<Box<Expr<'a>>>,
     pub name: Span<'a>,
@@ -1283,6 +1806,7 @@ pub struct MethodCall<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Lambda<'a> {
     pub inferred_method_opt: Option<analyze::definition::Method<'a>>,
     pub inferred_params: Vec<analyze::definition::Param<'a>>,
@@ -1293,66 +1817,86 @@ pub struct Lambda<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct NewArray<'a> {
     pub tpe: ArrayType<'a>,
     pub initializer_opt: Option<ArrayInitializer<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ArrayInitializer<'a> {
     pub items: Vec<Expr<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct NewObject<'a> {
     pub prefix_opt: Option<Box<Expr<'a>>>,
     pub tpe: ClassType<'a>,
     pub constructor_type_args_opt: Option<Vec<TypeArg<'a>>>,
     pub args: Vec<Expr<'a>>,
     pub body_opt: Option<ClassBody<'a>>,
+    pub def_opt: RefCell<Option<analyze::definition::Constructor<'a>>>,
+    // The definition of the anonymous class in `body_opt`. It is built by the semantics because
+    // the analysis doesn't look into method bodies.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub anonymous_class_opt: Option<Box<analyze::definition::Class<'a>>>,
+    // The id of the anonymous class. It is only assigned when there is a body.
+    pub id: Id,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Hex<'a> {
     pub value: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Double<'a> {
     pub value: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Float<'a> {
     pub value: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Long<'a> {
     pub value: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Int<'a> {
     pub value: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Boolean<'a> {
     pub value: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Null<'a> {
     pub value: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct LiteralString<'a> {
     pub value: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum Assigned<'a> {
     Name(Name<'a>),
     ArrayAccess(ArrayAccess<'a>),
@@ -1360,12 +1904,14 @@ pub enum Assigned<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct ClassExpr<'a> {
     pub tpe: Type<'a>,
     pub span: Span<'a>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct FieldAccess<'a> {
     pub prefix: RefCell<Box<FieldAccessPrefix<'a>>>,
     pub name: Span<'a>,
@@ -1373,40 +1919,412 @@ pub struct FieldAccess<'a> {
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+#[cfg_attr(feature = "serde", serde(tag = "kind", content = "node"))]
 pub enum FieldAccessPrefix<'a> {
     Package(PackagePrefix<'a>),
     Expr(Expr<'a>),
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Assignment<'a> {
     pub assigned: Box<Assigned<'a>>,
     pub operator: Span<'a>,
+    pub kind: AssignOperator,
     pub expr: Box<Expr<'a>>,
 }
 
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct InstanceOf<'a> {
     pub expr: Box<Expr<'a>>,
     pub operator: Span<'a>,
     pub tpe: Type<'a>,
 }
 
-#[derive(Debug, PartialEq, Clone)]
+// A long chain (e.g. `a + b + ... + z`) nests on the left, so `Drop`, `Clone`, `PartialEq`,
+// `Debug`, and `Serialize` (see `parse::serialize`) go through the left spine in a loop instead of
+// recursing. They destructure every field, so a new field doesn't compile until it's handled.
 pub struct BinaryOperation<'a> {
     pub left: Box<Expr<'a>>,
     pub operator: Span<'a>,
+    pub kind: BinaryOperator,
     pub right: Box<Expr<'a>>,
 }
 
+impl<'a> Drop for BinaryOperation<'a> {
+    fn drop(&mut self) {
+        let placeholder = Expr::Null(Null {
+            value: self.operator,
+        });
+        let mut left = std::mem::replace(&mut *self.left, placeholder);
+
+        loop {
+            let next = match left {
+                Expr::BinaryOperation(ref mut op) => {
+                    let placeholder = Expr::Null(Null { value: op.operator });
+                    std::mem::replace(&mut *op.left, placeholder)
+                }
+                _ => break,
+            };
+            left = next;
+        }
+    }
+}
+
+impl<'a> BinaryOperation<'a> {
+    // The operations on the left spine from `self` inward, and the innermost left operand.
+    pub fn spine(&self) -> (Vec<&BinaryOperation<'a>>, &Expr<'a>) {
+        let mut spine = vec![self];
+        let mut left = &*self.left;
+        while let Expr::BinaryOperation(op) = left {
+            spine.push(op);
+            left = &*op.left;
+        }
+        (spine, left)
+    }
+
+    // The type of the result, or None if it depends on an operand whose type isn't known.
+    pub fn tpe_opt(&self) -> Option<Type<'a>> {
+        let (spine, innermost) = self.spine();
+        let mut tpe_opt = innermost.tpe_opt();
+        for op in spine.into_iter().rev() {
+            tpe_opt = op
+                .kind
+                .result_type(tpe_opt.as_ref(), op.right.tpe_opt().as_ref());
+        }
+        tpe_opt
+    }
+}
+
+impl<'a> Clone for BinaryOperation<'a> {
+    fn clone(&self) -> BinaryOperation<'a> {
+        let (spine, innermost) = self.spine();
+        let mut left = innermost.clone();
+        for op in spine.into_iter().rev() {
+            let BinaryOperation {
+                left: _,
+                operator,
+                kind,
+                right,
+            } = op;
+            left = Expr::BinaryOperation(BinaryOperation {
+                left: Box::new(left),
+                operator: *operator,
+                kind: *kind,
+                right: right.clone(),
+            });
+        }
+
+        match left {
+            Expr::BinaryOperation(op) => op,
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<'a> PartialEq for BinaryOperation<'a> {
+    fn eq(&self, other: &BinaryOperation<'a>) -> bool {
+        let (mut a, mut b) = (self, other);
+        loop {
+            let BinaryOperation {
+                left: left_a,
+                operator,
+                kind,
+                right,
+            } = a;
+            if *operator != b.operator || *kind != b.kind || *right != b.right {
+                return false;
+            }
+            match (&**left_a, &*b.left) {
+                (Expr::BinaryOperation(left_a), Expr::BinaryOperation(left_b)) => {
+                    a = left_a;
+                    b = left_b;
+                }
+                (left_a, left_b) => return left_a == left_b,
+            }
+        }
+    }
+}
+
+// Prints the chain flat: the innermost left operand, then the operations from the inside out,
+// e.g. `a + b - c` is `BinaryOperation { left: a, operations: [(+, Add, b), (-, Subtract, c)] }`.
+impl<'a> std::fmt::Debug for BinaryOperation<'a> {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        let (spine, innermost) = self.spine();
+        let operations = spine
+            .into_iter()
+            .rev()
+            .map(|op| {
+                let BinaryOperation {
+                    left: _,
+                    operator,
+                    kind,
+                    right,
+                } = op;
+                (operator, kind, right)
+            })
+            .collect::<Vec<_>>();
+        f.debug_struct("BinaryOperation")
+            .field("left", innermost)
+            .field("operations", &operations)
+            .finish()
+    }
+}
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct UnaryOperation<'a> {
     pub expr: Box<Expr<'a>>,
     pub operator: Span<'a>,
+    pub kind: UnaryOperator,
     pub is_post: bool,
 }
 
+// The precedence of the operators, where a higher one binds tighter: 1 is an assignment, 2 is a
+// ternary, 3 (`||`) to 12 (`*`) are the binary operators, 13 is a prefix operator or a cast, and 14
+// is a postfix operator.
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum BinaryOperator {
+    Multiply,
+    Divide,
+    Remainder,
+    Add,
+    Subtract,
+    LeftShift,
+    RightShift,
+    UnsignedRightShift,
+    Less,
+    Greater,
+    LessOrEqual,
+    GreaterOrEqual,
+    Equal,
+    NotEqual,
+    BitwiseAnd,
+    BitwiseXor,
+    BitwiseOr,
+    And,
+    Or,
+}
+
+impl BinaryOperator {
+    pub fn precedence(self) -> u8 {
+        match self {
+            BinaryOperator::Multiply | BinaryOperator::Divide | BinaryOperator::Remainder => 12,
+            BinaryOperator::Add | BinaryOperator::Subtract => 11,
+            BinaryOperator::LeftShift
+            | BinaryOperator::RightShift
+            | BinaryOperator::UnsignedRightShift => 10,
+            BinaryOperator::Less
+            | BinaryOperator::Greater
+            | BinaryOperator::LessOrEqual
+            | BinaryOperator::GreaterOrEqual => 9,
+            BinaryOperator::Equal | BinaryOperator::NotEqual => 8,
+            BinaryOperator::BitwiseAnd => 7,
+            BinaryOperator::BitwiseXor => 6,
+            BinaryOperator::BitwiseOr => 5,
+            BinaryOperator::And => 4,
+            BinaryOperator::Or => 3,
+        }
+    }
+
+    pub fn associativity(self) -> Associativity {
+        Associativity::Left
+    }
+
+    // The type of the result for operands of the given types (JLS 15.17 to 15.24). The operands
+    // of an arithmetic, shift or bitwise operator are unboxed and promoted; `+` with a `String`
+    // operand is a string concatenation.
+    pub fn result_type<'a>(
+        self,
+        left_opt: Option<&Type<'a>>,
+        right_opt: Option<&Type<'a>>,
+    ) -> Option<Type<'a>> {
+        let unboxed = |tpe_opt: Option<&Type<'a>>| tpe_opt.and_then(|t| t.unboxed_opt());
+        let tpe = match self {
+            BinaryOperator::Less
+            | BinaryOperator::Greater
+            | BinaryOperator::LessOrEqual
+            | BinaryOperator::GreaterOrEqual
+            | BinaryOperator::Equal
+            | BinaryOperator::NotEqual
+            | BinaryOperator::And
+            | BinaryOperator::Or => PrimitiveTypeType::Boolean,
+            BinaryOperator::Add if left_opt.is_some_and(Type::is_string) => {
+                return left_opt.cloned()
+            }
+            BinaryOperator::Add if right_opt.is_some_and(Type::is_string) => {
+                return right_opt.cloned()
+            }
+            BinaryOperator::LeftShift
+            | BinaryOperator::RightShift
+            | BinaryOperator::UnsignedRightShift => {
+                let left = unboxed(left_opt)?;
+                if !left.is_integral() {
+                    return None;
+                }
+                left.promote()?
+            }
+            BinaryOperator::BitwiseAnd | BinaryOperator::BitwiseXor | BinaryOperator::BitwiseOr => {
+                let (left, right) = (unboxed(left_opt)?, unboxed(right_opt)?);
+                if left == PrimitiveTypeType::Boolean && right == PrimitiveTypeType::Boolean {
+                    PrimitiveTypeType::Boolean
+                } else if left.is_integral() && right.is_integral() {
+                    left.promote_with(&right)?
+                } else {
+                    return None;
+                }
+            }
+            BinaryOperator::Multiply
+            | BinaryOperator::Divide
+            | BinaryOperator::Remainder
+            | BinaryOperator::Add
+            | BinaryOperator::Subtract => unboxed(left_opt)?.promote_with(&unboxed(right_opt)?)?,
+        };
+        Some(Type::Primitive(PrimitiveType {
+            span_opt: None,
+            tpe,
+        }))
+    }
+
+    pub fn text(self) -> &'static str {
+        match self {
+            BinaryOperator::Multiply => "*",
+            BinaryOperator::Divide => "/",
+            BinaryOperator::Remainder => "%",
+            BinaryOperator::Add => "+",
+            BinaryOperator::Subtract => "-",
+            BinaryOperator::LeftShift => "<<",
+            BinaryOperator::RightShift => ">>",
+            BinaryOperator::UnsignedRightShift => ">>>",
+            BinaryOperator::Less => "<",
+            BinaryOperator::Greater => ">",
+            BinaryOperator::LessOrEqual => "<=",
+            BinaryOperator::GreaterOrEqual => ">=",
+            BinaryOperator::Equal => "==",
+            BinaryOperator::NotEqual => "!=",
+            BinaryOperator::BitwiseAnd => "&",
+            BinaryOperator::BitwiseXor => "^",
+            BinaryOperator::BitwiseOr => "|",
+            BinaryOperator::And => "&&",
+            BinaryOperator::Or => "||",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum UnaryOperator {
+    Plus,
+    Minus,
+    Not,
+    BitwiseNot,
+    PreIncrement,
+    PreDecrement,
+    PostIncrement,
+    PostDecrement,
+}
+
+impl UnaryOperator {
+    pub fn precedence(self) -> u8 {
+        match self {
+            UnaryOperator::PostIncrement | UnaryOperator::PostDecrement => 14,
+            _ => 13,
+        }
+    }
+
+    pub fn associativity(self) -> Associativity {
+        match self {
+            UnaryOperator::PostIncrement | UnaryOperator::PostDecrement => Associativity::Left,
+            _ => Associativity::Right,
+        }
+    }
+
+    pub fn text(self) -> &'static str {
+        match self {
+            UnaryOperator::Plus => "+",
+            UnaryOperator::Minus => "-",
+            UnaryOperator::Not => "!",
+            UnaryOperator::BitwiseNot => "~",
+            UnaryOperator::PreIncrement | UnaryOperator::PostIncrement => "++",
+            UnaryOperator::PreDecrement | UnaryOperator::PostDecrement => "--",
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum AssignOperator {
+    Assign,
+    Multiply,
+    Divide,
+    Remainder,
+    Add,
+    Subtract,
+    LeftShift,
+    RightShift,
+    UnsignedRightShift,
+    BitwiseAnd,
+    BitwiseXor,
+    BitwiseOr,
+}
+
+impl AssignOperator {
+    pub fn precedence(self) -> u8 {
+        1
+    }
+
+    pub fn associativity(self) -> Associativity {
+        Associativity::Right
+    }
+
+    pub fn text(self) -> &'static str {
+        match self {
+            AssignOperator::Assign => "=",
+            AssignOperator::Multiply => "*=",
+            AssignOperator::Divide => "/=",
+            AssignOperator::Remainder => "%=",
+            AssignOperator::Add => "+=",
+            AssignOperator::Subtract => "-=",
+            AssignOperator::LeftShift => "<<=",
+            AssignOperator::RightShift => ">>=",
+            AssignOperator::UnsignedRightShift => ">>>=",
+            AssignOperator::BitwiseAnd => "&=",
+            AssignOperator::BitwiseXor => "^=",
+            AssignOperator::BitwiseOr => "|=",
+        }
+    }
+
+    // The operation of a compound assignment, e.g. `+` for `+=`.
+    pub fn binary_opt(self) -> Option<BinaryOperator> {
+        match self {
+            AssignOperator::Assign => None,
+            AssignOperator::Multiply => Some(BinaryOperator::Multiply),
+            AssignOperator::Divide => Some(BinaryOperator::Divide),
+            AssignOperator::Remainder => Some(BinaryOperator::Remainder),
+            AssignOperator::Add => Some(BinaryOperator::Add),
+            AssignOperator::Subtract => Some(BinaryOperator::Subtract),
+            AssignOperator::LeftShift => Some(BinaryOperator::LeftShift),
+            AssignOperator::RightShift => Some(BinaryOperator::RightShift),
+            AssignOperator::UnsignedRightShift => Some(BinaryOperator::UnsignedRightShift),
+            AssignOperator::BitwiseAnd => Some(BinaryOperator::BitwiseAnd),
+            AssignOperator::BitwiseXor => Some(BinaryOperator::BitwiseXor),
+            AssignOperator::BitwiseOr => Some(BinaryOperator::BitwiseOr),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
+pub enum Associativity {
+    Left,
+    Right,
+}
+
 #[derive(Debug, PartialEq, Clone)]
+#[cfg_attr(feature = "serde", derive(Serialize))]
 pub struct Cast<'a> {
     pub tpes: Vec<Type<'a>>,
     pub expr: Box<Expr<'a>>,
@@ -0,0 +1,642 @@
+use parse::id_gen::{Id, IdGen};
+use parse::tree::*;
+use std::cell::{Cell, RefCell};
+use std::{mem, ptr};
+use tokenize::span::Span;
+
+// Visits the syntax of a tree: every span, every name borrowed from the source, and every id.
+// Definitions filled in by the analysis and the semantics (e.g. `def_opt`) are not visited.
+pub trait Visitor<'a> {
+    fn span(&mut self, _span: &mut Span<'a>) {}
+    fn name(&mut self, _name: &mut &'a str) {}
+    // Called after the node's children are visited. The children of a node with an id are visited
+    // between `enter` and `leave`.
+    fn id(&mut self, _kind: &'static str, _name: Span<'a>, _id: &mut Id) {}
+    fn enter(&mut self, _kind: &'static str, _name: Span<'a>) {}
+    fn leave(&mut self) {}
+}
+
+// A visitor of a tree that isn't changed (see `Walk::visit`) gets the nodes too, borrowed for as
+// long as the tree (`'f`), so it can keep them.
+pub trait NodeVisitor<'f, 'a>: Visitor<'a> {
+    // Called before the node's children are visited. In a chain of binary operations, each
+    // operation is an expression too.
+    fn expr(&mut self, _expr: &'f Expr<'a>) {}
+    fn statement(&mut self, _statement: &'f Statement<'a>) {}
+    fn block(&mut self, _block: &'f Block<'a>) {}
+    fn catch(&mut self, _catch: &'f Catch<'a>) {}
+}
+
+pub trait Walk<'a> {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V);
+    // Like `walk` without changing the tree. The visitor gets copies of the spans, the names, and
+    // the ids, and the nodes themselves.
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V);
+}
+
+// Assigns the ids in the order of the tree, so the ids don't depend on how the parser backtracks
+// and a reparsed tree gets the same ids as a full parse.
+pub fn assign_ids<'a, T: Walk<'a>>(node: &mut T, id_gen: &mut IdGen) {
+    struct AssignIds<'g> {
+        id_gen: &'g mut IdGen,
+    }
+
+    impl<'a, 'g> Visitor<'a> for AssignIds<'g> {
+        fn id(&mut self, kind: &'static str, name: Span<'a>, id: &mut Id) {
+            *id = self.id_gen.get_next(kind, name.fragment());
+        }
+
+        fn enter(&mut self, kind: &'static str, name: Span<'a>) {
+            self.id_gen.enter(kind, name.fragment());
+        }
+
+        fn leave(&mut self) {
+            self.id_gen.leave();
+        }
+    }
+
+    node.walk(&mut AssignIds { id_gen });
+}
+
+impl<'a> Walk<'a> for Span<'a> {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+        visitor.span(self)
+    }
+
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+        visitor.span(&mut { *self })
+    }
+}
+
+impl<'a> Walk<'a> for &'a str {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+        visitor.name(self)
+    }
+
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+        visitor.name(&mut { *self })
+    }
+}
+
+impl<'a, T: Walk<'a>> Walk<'a> for Option<T> {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+        if let Some(node) = self {
+            node.walk(visitor)
+        }
+    }
+
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+        if let Some(node) = self {
+            node.visit(visitor)
+        }
+    }
+}
+
+impl<'a, T: Walk<'a>> Walk<'a> for Vec<T> {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+        for node in self {
+            node.walk(visitor)
+        }
+    }
+
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+        for node in self {
+            node.visit(visitor)
+        }
+    }
+}
+
+impl<'a, T: Walk<'a>> Walk<'a> for Box<T> {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+        (**self).walk(visitor)
+    }
+
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+        (**self).visit(visitor)
+    }
+}
+
+impl<'a, T: Walk<'a>> Walk<'a> for RefCell<T> {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+        self.get_mut().walk(visitor)
+    }
+
+    // The nodes are kept for as long as the tree is borrowed, which outlives a `borrow()`. The
+    // syntax in a `RefCell` (e.g. a resolved type) is only replaced by the analysis, which takes
+    // the tree before it is visited.
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+        unsafe { &*self.as_ptr() }.visit(visitor)
+    }
+}
+
+// Maps the spans of a tree from one source to another (see `Move`).
+pub trait Mover<'a, 'b> {
+    fn span(&mut self, span: Span<'a>) -> Option<Span<'b>>;
+}
+
+// Copies a tree to another source, e.g. to reuse the subtrees of a previous parse. The spans are
+// mapped, the names are taken from the mapped spans, and the rest of the syntax is kept. What the
+// analysis and the semantics fill in (e.g. `def_opt`) is left out, so only a tree straight from the
+// parser is copied whole. It is None if a span can't be mapped.
+pub trait Move<'a, 'b> {
+    type Moved;
+    fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<Self::Moved>;
+}
+
+impl<'a, 'b> Move<'a, 'b> for Span<'a> {
+    type Moved = Span<'b>;
+    fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<Span<'b>> {
+        mover.span(*self)
+    }
+}
+
+// A name is the text of its span, or a built-in name without a span (the `T` of an array).
+fn move_name<'b>(name: &str, span_opt: Option<Span<'b>>) -> Option<&'b str> {
+    match span_opt {
+        Some(span) => Some(span.fragment()),
+        None if name == NATIVE_ARRAY_CLASS_NAME => Some(NATIVE_ARRAY_CLASS_NAME),
+        None => None,
+    }
+}
+
+impl<'a, 'b, T: Move<'a, 'b>> Move<'a, 'b> for Option<T> {
+    type Moved = Option<T::Moved>;
+    fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<Option<T::Moved>> {
+        match self {
+            Some(node) => Some(Some(node.move_to(mover)?)),
+            None => Some(None),
+        }
+    }
+}
+
+impl<'a, 'b, T: Move<'a, 'b>> Move<'a, 'b> for Vec<T> {
+    type Moved = Vec<T::Moved>;
+    fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<Vec<T::Moved>> {
+        self.iter().map(|node| node.move_to(mover)).collect()
+    }
+}
+
+impl<'a, 'b, T: Move<'a, 'b>> Move<'a, 'b> for Box<T> {
+    type Moved = Box<T::Moved>;
+    fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<Box<T::Moved>> {
+        Some(Box::new((**self).move_to(mover)?))
+    }
+}
+
+impl<'a, 'b, T: Move<'a, 'b>> Move<'a, 'b> for RefCell<T> {
+    type Moved = RefCell<T::Moved>;
+    fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<RefCell<T::Moved>> {
+        Some(RefCell::new(self.borrow().move_to(mover)?))
+    }
+}
+
+macro_rules! or_default {
+    () => {
+        Default::default()
+    };
+    ($value:expr) => {
+        $value
+    };
+}
+
+// Every field of a node is listed, so a field added later doesn't compile until it is listed too:
+// first the syntax that is visited and moved, then what is kept as it is (`keep`, e.g. a flag), and
+// what the analysis and the semantics fill in (`reset`, with the value that the parser gives).
+macro_rules! move_struct {
+    ($name:ident { $($field:ident),* } { $($keep:ident),* } { $($reset:ident $(= $value:expr)?),* }) => {
+        impl<'a, 'b> Move<'a, 'b> for $name<'a> {
+            type Moved = $name<'b>;
+            #[allow(unused_variables)]
+            fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<$name<'b>> {
+                let $name { $($field,)* $($keep,)* $($reset,)* } = self;
+                Some($name {
+                    $($field: $field.move_to(mover)?,)*
+                    $($keep: $keep.clone(),)*
+                    $($reset: or_default!($($value)?),)*
+                })
+            }
+        }
+    };
+}
+
+// A node with a hook (e.g. `Block => block`) is passed to the `NodeVisitor` of `visit` before its
+// children.
+macro_rules! walk_structs {
+    ($($name:ident $(=> $hook:ident)? {
+        $($field:ident),*
+        $(; keep $($keep:ident),*)?
+        $(; reset $($reset:ident $(= $value:expr)?),*)?
+    })*) => {
+        $(
+            impl<'a> Walk<'a> for $name<'a> {
+                #[allow(unused_variables)]
+                fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+                    let $name { $($field,)* $($($keep,)*)? $($($reset,)*)? } = self;
+                    $($field.walk(visitor);)*
+                }
+
+                #[allow(unused_variables)]
+                fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+                    $(visitor.$hook(self);)?
+                    let $name { $($field,)* $($($keep,)*)? $($($reset,)*)? } = self;
+                    $($field.visit(visitor);)*
+                }
+            }
+
+            move_struct!($name { $($field),* } { $($($keep),*)? } { $($($reset $(= $value)?),*)? });
+        )*
+    };
+}
+
+// The children are visited between `enter` and `leave`, then the id, which is kept when the node is
+// moved.
+macro_rules! walk_structs_with_id {
+    ($($name:ident $kind:expr => {
+        $($field:ident),*
+        $(; reset $($reset:ident $(= $value:expr)?),*)?
+    })*) => {
+        $(
+            impl<'a> Walk<'a> for $name<'a> {
+                #[allow(unused_variables)]
+                fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+                    visitor.enter($kind, self.name);
+                    let $name { $($field,)* id, $($($reset,)*)? } = self;
+                    $($field.walk(visitor);)*
+                    visitor.leave();
+                    visitor.id($kind, self.name, &mut self.id);
+                }
+
+                #[allow(unused_variables)]
+                fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+                    visitor.enter($kind, self.name);
+                    let $name { $($field,)* id, $($($reset,)*)? } = self;
+                    $($field.visit(visitor);)*
+                    visitor.leave();
+                    visitor.id($kind, self.name, &mut { self.id });
+                }
+            }
+
+            move_struct!($name { $($field),* } { id } { $($($reset $(= $value)?),*)? });
+        )*
+    };
+}
+
+// The name of these nodes (in brackets) is the text of `span_opt`, so a moved node takes it from the
+// moved span.
+macro_rules! walk_named_structs {
+    ($($name:ident {
+        $($before:ident,)* [name, span_opt] $(, $after:ident)*; reset $reset:ident $(= $value:expr)?
+    })*) => {
+        $(
+            impl<'a> Walk<'a> for $name<'a> {
+                #[allow(unused_variables)]
+                fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+                    let $name { $($before,)* name, span_opt, $($after,)* $reset } = self;
+                    $($before.walk(visitor);)*
+                    name.walk(visitor);
+                    span_opt.walk(visitor);
+                    $($after.walk(visitor);)*
+                }
+
+                #[allow(unused_variables)]
+                fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+                    let $name { $($before,)* name, span_opt, $($after,)* $reset } = self;
+                    $($before.visit(visitor);)*
+                    name.visit(visitor);
+                    span_opt.visit(visitor);
+                    $($after.visit(visitor);)*
+                }
+            }
+
+            impl<'a, 'b> Move<'a, 'b> for $name<'a> {
+                type Moved = $name<'b>;
+                #[allow(unused_variables)]
+                fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<$name<'b>> {
+                    let $name { $($before,)* name, span_opt, $($after,)* $reset } = self;
+                    let span_opt = span_opt.move_to(mover)?;
+                    Some($name {
+                        $($before: $before.move_to(mover)?,)*
+                        name: move_name(name, span_opt)?,
+                        span_opt,
+                        $($after: $after.move_to(mover)?,)*
+                        $reset: or_default!($($value)?),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+// The variants without a node (e.g. `Statement::Empty`) come after `;`.
+macro_rules! walk_enums {
+    ($($name:ident $(=> $hook:ident)? { $($variant:ident),* $(; $($unit:ident),*)? })*) => {
+        $(
+            impl<'a> Walk<'a> for $name<'a> {
+                fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+                    match self {
+                        $($name::$variant(node) => node.walk(visitor),)*
+                        $($($name::$unit => {})*)?
+                    }
+                }
+
+                fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+                    $(visitor.$hook(self);)?
+                    match self {
+                        $($name::$variant(node) => node.visit(visitor),)*
+                        $($($name::$unit => {})*)?
+                    }
+                }
+            }
+
+            impl<'a, 'b> Move<'a, 'b> for $name<'a> {
+                type Moved = $name<'b>;
+                fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<$name<'b>> {
+                    Some(match self {
+                        $($name::$variant(node) => $name::$variant(node.move_to(mover)?),)*
+                        $($($name::$unit => $name::$unit,)*)?
+                    })
+                }
+            }
+        )*
+    };
+}
+
+walk_structs! {
+    CompilationUnit { package_opt, imports, items }
+    Import { prefix_opt, name; keep is_static, is_wildcard; reset def_opt }
+    ImportPrefix { prefix_opt, name; reset def_opt }
+    Package { prefix_opt, annotateds, name; reset def_opt }
+    NormalAnnotated { class, params }
+    MarkerAnnotated { class }
+    SingleAnnotated { class, value; reset def_opt }
+    AnnotatedParam { name, value; reset def_opt }
+    AnnotatedValueArray { items }
+    AnnotationBody { items }
+    ClassBody { items }
+    FieldDeclarators { modifiers, declarators, span_opt }
+    Block => block { stmts, span_opt; keep is_skipped; reset return_type = Type::UnknownType }
+    Void { span_opt }
+    WildcardType { span_opt, extends, super_opt }
+    PrimitiveType { span_opt; keep tpe }
+    ArrayType { tpe, size_opt, underlying }
+    Param { modifiers, tpe, name; keep is_varargs }
+    Assert { expr, error_opt }
+    Labeled { label, statement }
+    Continue { identifier_opt }
+    Break { identifier_opt }
+    Switch { expr, cases }
+    Case { label_opt, stmts }
+    DoWhile { block, cond }
+    WhileLoop { cond, block }
+    Try { try, resources, catches, finally_opt }
+    Catch => catch { modifiers, param_name, class_types, block }
+    Synchronized { expr, block }
+    Throw { expr }
+    Foreach { declarator, expr, block }
+    ForLoop { inits, cond_opt, updates, block }
+    IfElse { cond, block, else_block_opt }
+    ReturnStmt { expr_opt }
+    VariableDeclarators { modifiers, declarators }
+    StandaloneVariableDeclarator { modifiers, tpe, name, expr_opt }
+    StaticClass { tpe }
+    This { tpe_opt, span }
+    Super { tpe_opt, span }
+    Char { value }
+    ConstructorReference { tpe, type_args_opt }
+    MethodReference { primary, type_args_opt, name }
+    Ternary { cond, true_expr, false_expr }
+    ArrayAccess { expr, index }
+    Keyword { name }
+    Name { name; reset resolved_opt }
+    SuperConstructorCall { prefix_opt, type_args_opt, name, args; reset def_opt }
+    ThisConstructorCall { type_args_opt, name, args; reset def_opt }
+    MethodCall { prefix_opt, name, type_args_opt, args; reset def_opt }
+    Lambda {
+        params, expr_opt, block_opt;
+        reset inferred_method_opt, inferred_params, inferred_return_type = Type::UnknownType
+    }
+    NewArray { tpe, initializer_opt }
+    ArrayInitializer { items }
+    Hex { value }
+    Double { value }
+    Float { value }
+    Long { value }
+    Int { value }
+    Boolean { value }
+    Null { value }
+    LiteralString { value }
+    ClassExpr { tpe, span }
+    FieldAccess { prefix, name; reset def_opt }
+    Assignment { assigned, operator, expr; keep kind }
+    InstanceOf { expr, operator, tpe }
+    UnaryOperation { expr, operator; keep kind, is_post }
+    Cast { tpes, expr }
+}
+
+// A long chain (e.g. `a + b + ... + z`) nests on the left, so the left spine is collected in a loop
+// instead of recursively. The order is the same: left, operator, then right.
+impl<'a> Walk<'a> for BinaryOperation<'a> {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+        // The operator and the right operand of each operation on the left spine, from the
+        // outermost inward.
+        let mut rest = vec![];
+        let mut current = self;
+        let innermost = loop {
+            let BinaryOperation {
+                left,
+                operator,
+                right,
+                ..
+            } = current;
+            rest.push((operator, right));
+            match **left {
+                Expr::BinaryOperation(ref mut op) => current = op,
+                ref mut innermost => break innermost,
+            }
+        };
+
+        innermost.walk(visitor);
+        for (operator, right) in rest.into_iter().rev() {
+            operator.walk(visitor);
+            right.walk(visitor);
+        }
+    }
+
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+        let mut spine = vec![self];
+
+        loop {
+            let last = *spine.last().unwrap();
+            match &*last.left {
+                Expr::BinaryOperation(left) => {
+                    visitor.expr(&last.left);
+                    spine.push(left);
+                }
+                _ => break,
+            }
+        }
+
+        for (index, op) in spine.into_iter().rev().enumerate() {
+            if index == 0 {
+                op.left.visit(visitor);
+            }
+            op.operator.visit(visitor);
+            op.right.visit(visitor);
+        }
+    }
+}
+
+impl<'a, 'b> Move<'a, 'b> for BinaryOperation<'a> {
+    type Moved = BinaryOperation<'b>;
+    fn move_to<M: Mover<'a, 'b>>(&self, mover: &mut M) -> Option<BinaryOperation<'b>> {
+        let mut spine = vec![self];
+        while let Expr::BinaryOperation(left) = &*spine.last().unwrap().left {
+            spine.push(left);
+        }
+
+        let mut left = spine.last().unwrap().left.move_to(mover)?;
+        for op in spine.into_iter().rev() {
+            left = Box::new(Expr::BinaryOperation(BinaryOperation {
+                left,
+                operator: op.operator.move_to(mover)?,
+                kind: op.kind,
+                right: op.right.move_to(mover)?,
+            }));
+        }
+
+        match *left {
+            Expr::BinaryOperation(op) => Some(op),
+            _ => unreachable!(),
+        }
+    }
+}
+
+// An anonymous class is named after the type it instantiates. Without a body, there is no class and
+// no id.
+impl<'a> Walk<'a> for NewObject<'a> {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+        self.prefix_opt.walk(visitor);
+        self.tpe.walk(visitor);
+        self.constructor_type_args_opt.walk(visitor);
+        self.args.walk(visitor);
+        match (&self.body_opt, self.tpe.span_opt) {
+            (Some(_), Some(span)) => {
+                visitor.enter("anonymous", span);
+                self.body_opt.walk(visitor);
+                visitor.leave();
+                visitor.id("anonymous", span, &mut self.id);
+            }
+            _ => self.body_opt.walk(visitor),
+        }
+    }
+
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+        self.prefix_opt.visit(visitor);
+        self.tpe.visit(visitor);
+        self.constructor_type_args_opt.visit(visitor);
+        self.args.visit(visitor);
+        match (&self.body_opt, self.tpe.span_opt) {
+            (Some(_), Some(span)) => {
+                visitor.enter("anonymous", span);
+                self.body_opt.visit(visitor);
+                visitor.leave();
+                visitor.id("anonymous", span, &mut { self.id });
+            }
+            _ => self.body_opt.visit(visitor),
+        }
+    }
+}
+
+// The body of a constant is an anonymous class, so it gets its own id.
+impl<'a> Walk<'a> for EnumConstant<'a> {
+    fn walk<V: Visitor<'a>>(&mut self, visitor: &mut V) {
+        self.annotateds.walk(visitor);
+        self.name.walk(visitor);
+        self.args_opt.walk(visitor);
+        if self.body_opt.is_some() {
+            visitor.enter("anonymous", self.name);
+            self.body_opt.walk(visitor);
+            visitor.leave();
+        }
+        visitor.id("var", self.name, &mut self.id);
+        if self.body_opt.is_some() {
+            visitor.id("anonymous", self.name, &mut self.body_id);
+        }
+    }
+
+    fn visit<'f, V: NodeVisitor<'f, 'a>>(&'f self, visitor: &mut V) {
+        self.annotateds.visit(visitor);
+        self.name.visit(visitor);
+        self.args_opt.visit(visitor);
+        if self.body_opt.is_some() {
+            visitor.enter("anonymous", self.name);
+            self.body_opt.visit(visitor);
+            visitor.leave();
+        }
+        visitor.id("var", self.name, &mut { self.id });
+        if self.body_opt.is_some() {
+            visitor.id("anonymous", self.name, &mut { self.body_id });
+        }
+    }
+}
+
+move_struct!(EnumConstant { annotateds, name, args_opt, body_opt } { id, body_id } {});
+
+move_struct!(NewObject {
+    prefix_opt, tpe, constructor_type_args_opt, args, body_opt
+} { id } { def_opt, anonymous_class_opt });
+
+walk_structs_with_id! {
+    Class "class" => {
+        modifiers, name, type_params, extend_opt, implements, body, span_opt; reset def_opt
+    }
+    Interface "class" => { modifiers, name, type_params, extends, body, span_opt; reset def_opt }
+    Enum "class" => { modifiers, name, implements, constants, body_opt, span_opt; reset def_opt }
+    Annotation "class" => { modifiers, name, body, span_opt; reset def_opt }
+    AnnotationParam "method" => { modifiers, tpe, name, default_opt; reset def_opt }
+    FieldDeclarator "var" => { tpe, name, expr_opt; reset def_opt }
+    TypeParam "TypeParam" => { name, extends; reset def_opt }
+    Constructor "constructor" => {
+        modifiers, type_params, name, params, throws, block, span_opt; reset def_opt
+    }
+    Method "method" => {
+        modifiers, type_params, return_type, name, params, throws, block_opt, span_opt;
+        reset def_opt
+    }
+    VariableDeclarator "var" => { tpe, name, expr_opt }
+}
+
+walk_named_structs! {
+    PackagePrefix { prefix_opt, [name, span_opt]; reset def = ptr::null() }
+    ClassType { prefix_opt, [name, span_opt], type_args_opt; reset def_opt }
+    ParameterizedType { [name, span_opt]; reset def = ptr::null() }
+}
+
+walk_enums! {
+    CompilationUnitItem { Class, Interface, Annotation, Enum }
+    Annotated { Normal, Marker, Single }
+    AnnotatedValue { Expr, Annotated, Array }
+    AnnotationBodyItem { Param, FieldDeclarators, Class, Interface, Enum, Annotation }
+    ClassBodyItem { Method, FieldDeclarators, Class, Interface, Enum, Annotation, StaticInitializer, Constructor }
+    Modifier { Annotated, Keyword }
+    Type { Class, Primitive, Array, Parameterized, Void, Wildcard; UnknownType }
+    ReferenceType { Class, Array, Parameterized }
+    StaticType { Class, Parameterized }
+    EnclosingType { Package, Class, Parameterized }
+    TypeArg { Class, Parameterized, Array, Wildcard, Primitive }
+    TypeParamExtend { Class, Parameterized }
+    Statement => statement {
+        Assert, Block, Break, Class, Continue, DoWhile, Expr, ForLoop, Foreach, IfElse, Labeled,
+        Return, Switch, Synchronized, Throw, Try, WhileLoop, VariableDeclarators; Empty
+    }
+    TryResource { Name, Declarator, FieldAccess }
+    MethodReferencePrimary { Class, Array, Expr }
+    Expr => expr {
+        ArrayAccess, ArrayInitializer, Assignment, BinaryOperation, Boolean, Cast, Char,
+        ConstructorReference, Double, FieldAccess, Float, Hex, InstanceOf, Int, Lambda, Long,
+        MethodCall, MethodReference, Name, NewArray, NewObject, Null, Class, StaticClass, String,
+        Super, SuperConstructorCall, This, ThisConstructorCall, Ternary, UnaryOperation
+    }
+    Assigned { Name, ArrayAccess, Field }
+    FieldAccessPrefix { Package, Expr }
+}
@@ -1,6 +1,6 @@
 use analyze::resolve::scope::{EnclosingTypeDef, Scope};
 use parse::tree::CompilationUnitItem;
-use semantics::def::class;
+use semantics::def::{annotation, class, enum_def, interface};
 use semantics::{import, Context};
 use {analyze, parse};
 
@@ -33,9 +33,9 @@ pub fn apply<'def>(
 fn apply_item<'def>(item: &mut CompilationUnitItem<'def>, context: &mut Context<'def, '_, '_>) {
     match item {
         CompilationUnitItem::Class(c) => class::apply(c, context),
-        CompilationUnitItem::Interface(_) => panic!(),
-        CompilationUnitItem::Annotation(_) => panic!(),
-        CompilationUnitItem::Enum(_) => panic!(),
+        CompilationUnitItem::Interface(i) => interface::apply(i, context),
+        CompilationUnitItem::Annotation(a) => annotation::apply(a, context),
+        CompilationUnitItem::Enum(e) => enum_def::apply(e, context),
     };
 }
 
@@ -52,9 +52,11 @@ fn enter_package<'def>(package: &parse::tree::Package<'def>, context: &mut Conte
             .unwrap()
         {
             &EnclosingTypeDef::Package(p) => {
-                context
-                    .scope
-                    .enter_package(unsafe { &*p }.find_package(package.name.fragment).unwrap());
+                context.scope.enter_package(
+                    unsafe { &*p }
+                        .find_package(package.name.fragment())
+                        .unwrap(),
+                );
             }
             _ => panic!(),
         }
@@ -62,7 +64,7 @@ fn enter_package<'def>(package: &parse::tree::Package<'def>, context: &mut Conte
         context.scope.enter_package(unsafe {
             &*context
                 .scope
-                .resolve_package(package.name.fragment)
+                .resolve_package(package.name.fragment())
                 .unwrap()
         });
     }
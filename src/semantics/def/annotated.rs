@@ -0,0 +1,267 @@
+use analyze::definition::MethodDef;
+use analyze::resolve::assign_type;
+use parse::tree::{Annotated, AnnotatedValue, ClassType, Modifier, Type};
+use semantics::{expr, Context};
+use std::ops::Deref;
+
+pub fn apply_modifiers<'def>(
+    modifiers: &mut [Modifier<'def>],
+    context: &mut Context<'def, '_, '_>,
+) {
+    for modifier in modifiers {
+        if let Modifier::Annotated(annotated) = modifier {
+            apply(annotated, context);
+        }
+    }
+}
+
+pub fn apply<'def>(annotated: &mut Annotated<'def>, context: &mut Context<'def, '_, '_>) {
+    match annotated {
+        Annotated::Marker(m) => resolve_class(&mut m.class, context),
+        Annotated::Single(s) => {
+            resolve_class(&mut s.class, context);
+
+            let element_opt = find_element(&s.class, "value");
+            s.def_opt
+                .replace(element_opt.map(|e| e as *const MethodDef));
+            apply_value(&mut s.value, &element_type(element_opt), context);
+        }
+        Annotated::Normal(n) => {
+            resolve_class(&mut n.class, context);
+
+            for param in &mut n.params {
+                let element_opt = find_element(&n.class, param.name.fragment());
+                param
+                    .def_opt
+                    .replace(element_opt.map(|e| e as *const MethodDef));
+                apply_value(&mut param.value, &element_type(element_opt), context);
+            }
+        }
+    }
+}
+
+fn find_element<'def, 'r>(class: &'r ClassType<'def>, name: &str) -> Option<&'r MethodDef<'def>> {
+    class
+        .def_opt
+        .and_then(|def| unsafe { &*def }.find_method(name))
+}
+
+fn element_type<'def>(element_opt: Option<&MethodDef<'def>>) -> Type<'def> {
+    match element_opt {
+        Some(element) => element.return_type.borrow().clone(),
+        None => Type::UnknownType,
+    }
+}
+
+fn resolve_class<'def>(class: &mut ClassType<'def>, context: &mut Context<'def, '_, '_>) {
+    if let Some(Type::Class(resolved)) =
+        assign_type::resolve_class_or_parameterized_type(class, &context.scope)
+    {
+        *class = resolved;
+    }
+}
+
+// The value has the element's type. An array element takes a single item too, e.g. `@Target(FIELD)`.
+fn apply_value<'def>(
+    value: &mut AnnotatedValue<'def>,
+    tpe: &Type<'def>,
+    context: &mut Context<'def, '_, '_>,
+) {
+    let item_type = match tpe {
+        Type::Array(array) => array.tpe.deref().clone(),
+        tpe => tpe.clone(),
+    };
+    match value {
+        AnnotatedValue::Expr(e) => expr::apply(e, &item_type, context),
+        AnnotatedValue::Annotated(a) => apply(a, context),
+        AnnotatedValue::Array(array) => {
+            for item in &mut array.items {
+                apply_value(item, &item_type, context);
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use analyze::definition::{AnnotationDef, Decl, Root};
+    use analyze::test_common::{find_class, find_package};
+    use parse::tree::{Annotated, ClassBodyItem, CompilationUnitItem, Expr, Modifier};
+    use {analyze, semantics};
+
+    #[test]
+    fn test_element() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  @JsonProperty(value = "x") int a;
+  @JsonProperty("y") int b;
+}
+        "#,
+            r#"
+package dev;
+
+@Retention(RetentionPolicy.RUNTIME)
+@Target({ElementType.FIELD, ElementType.METHOD})
+@interface JsonProperty {
+  String value() default "";
+  int index() default -1;
+}
+        "#
+        );
+
+        let test = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let field = unwrap!(
+            ClassBodyItem::FieldDeclarators,
+            &test.body.items.get(0).unwrap()
+        );
+        let annotated = unwrap!(
+            Annotated::Normal,
+            unwrap!(Modifier::Annotated, field.modifiers.get(0).unwrap())
+        );
+
+        let json_property = find_class(&root, "dev.JsonProperty");
+        assert_eq!(
+            annotated.class.def_opt,
+            Some(json_property as *const analyze::definition::Class)
+        );
+        assert_eq!(
+            *annotated.params.get(0).unwrap().def_opt.borrow(),
+            Some(json_property.find_method("value").unwrap() as *const _)
+        );
+
+        let field = unwrap!(
+            ClassBodyItem::FieldDeclarators,
+            &test.body.items.get(1).unwrap()
+        );
+        let single = unwrap!(
+            Annotated::Single,
+            unwrap!(Modifier::Annotated, field.modifiers.get(0).unwrap())
+        );
+        assert_eq!(
+            *single.def_opt.borrow(),
+            Some(json_property.find_method("value").unwrap() as *const _)
+        );
+
+        let def = find_package(&root, "dev")
+            .units()
+            .iter()
+            .find_map(|unit| match &unit.main {
+                Decl::Annotation(annotation) => Some(annotation),
+                _ => None,
+            })
+            .unwrap();
+        assert_eq!(def.retention_opt, Some("RUNTIME"));
+        assert_eq!(def.targets, vec!["FIELD", "METHOD"]);
+        assert_eq!(
+            def.elements
+                .iter()
+                .map(|e| (e.name, e.default_opt.is_some()))
+                .collect::<Vec<_>>(),
+            vec![("value", true), ("index", true)]
+        );
+    }
+
+    #[test]
+    fn test_element_default() {
+        let (_files, root) = apply_semantics!(
+            r#"
+package dev;
+
+@interface Range {
+  int min() default 0;
+  int max();
+  String label() default "range";
+}
+        "#
+        );
+
+        let def = find_annotation(&root);
+        assert_eq!(
+            def.elements.iter().map(|e| e.name).collect::<Vec<_>>(),
+            vec!["min", "max", "label"]
+        );
+        assert_eq!(
+            unwrap!(Expr::Int, def.elements[0].default_opt.as_ref().unwrap())
+                .value
+                .fragment(),
+            "0"
+        );
+        assert_eq!(def.elements[1].default_opt, None);
+        assert_eq!(
+            unwrap!(Expr::String, def.elements[2].default_opt.as_ref().unwrap())
+                .value
+                .fragment(),
+            "\"range\""
+        );
+    }
+
+    #[test]
+    fn test_meta_annotation() {
+        let (_files, root) = apply_semantics!(
+            r#"
+package dev;
+
+@Retention(value = RetentionPolicy.SOURCE)
+@Target(ElementType.TYPE)
+@interface Marker {}
+        "#
+        );
+
+        let def = find_annotation(&root);
+        assert_eq!(def.retention_opt, Some("SOURCE"));
+        assert_eq!(def.targets, vec!["TYPE"]);
+
+        let (_files, root) = apply_semantics!(
+            r#"
+package dev;
+
+@interface Marker {}
+        "#
+        );
+
+        let def = find_annotation(&root);
+        assert_eq!(def.retention_opt, None);
+        assert!(def.targets.is_empty());
+    }
+
+    #[test]
+    fn test_implements_annotation() {
+        let (_files, root) = apply_semantics!(
+            r#"
+package dev;
+
+@interface Marker {}
+        "#,
+            r#"
+package java.lang.annotation;
+
+public interface Annotation {}
+        "#
+        );
+
+        let marker = find_class(&root, "dev.Marker");
+        let implements = marker.implements.borrow();
+        assert_eq!(implements.len(), 1);
+        assert_eq!(
+            implements[0].def_opt,
+            Some(find_class(&root, "java.lang.annotation.Annotation") as *const _)
+        );
+    }
+
+    fn find_annotation<'r, 'def>(root: &'r Root<'def>) -> &'r AnnotationDef<'def> {
+        find_package(root, "dev")
+            .units()
+            .iter()
+            .find_map(|unit| match &unit.main {
+                Decl::Annotation(annotation) => Some(annotation),
+                _ => None,
+            })
+            .unwrap()
+    }
+}
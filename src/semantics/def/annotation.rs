@@ -0,0 +1,43 @@
+use parse::tree::{AnnotationBodyItem, Type};
+use semantics::def::{annotated, class, enum_def, field, interface};
+use semantics::{expr, Context};
+use {analyze, parse};
+
+pub fn apply<'def>(
+    annotation: &mut parse::tree::Annotation<'def>,
+    context: &mut Context<'def, '_, '_>,
+) {
+    let def = context
+        .id_hash
+        .get_by_id::<analyze::definition::AnnotationDef>(annotation.id)
+        .unwrap() as *const analyze::definition::AnnotationDef<'def>;
+    let def = unsafe { &*def };
+    annotation.def_opt.replace(Some(&def.class));
+
+    annotated::apply_modifiers(&mut annotation.modifiers, context);
+
+    context.scope.enter_class(&def.class);
+
+    for item in &mut annotation.body.items {
+        match item {
+            AnnotationBodyItem::Param(p) => {
+                p.def_opt.replace(Some(
+                    context
+                        .id_hash
+                        .get_by_id::<analyze::definition::MethodDef>(p.id)
+                        .unwrap(),
+                ));
+                if let Some(default) = &mut p.default_opt {
+                    expr::apply(default, &Type::UnknownType, context);
+                }
+            }
+            AnnotationBodyItem::FieldDeclarators(f) => field::apply(f, context),
+            AnnotationBodyItem::Class(c) => class::apply(c, context),
+            AnnotationBodyItem::Enum(e) => enum_def::apply(e, context),
+            AnnotationBodyItem::Annotation(a) => apply(a, context),
+            AnnotationBodyItem::Interface(i) => interface::apply(i, context),
+        };
+    }
+
+    context.scope.leave();
+}
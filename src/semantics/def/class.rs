@@ -1,7 +1,9 @@
 use analyze::resolve::scope::Scope;
 use parse::tree::{Class, ClassBodyItem, EnclosingType};
-use semantics::def::{field, method, type_param};
-use semantics::Context;
+use semantics::def::{
+    annotated, annotation, constructor, enum_def, field, interface, method, type_param,
+};
+use semantics::{block, Context};
 use std::borrow::Borrow;
 use {analyze, parse};
 
@@ -9,10 +11,12 @@ pub fn apply<'def>(class: &mut parse::tree::Class<'def>, context: &mut Context<'
     class.def_opt.replace(Some(
         context
             .id_hash
-            .get_by_id::<analyze::definition::Class>(&class.id)
+            .get_by_id::<analyze::definition::Class>(class.id)
             .unwrap(),
     ));
 
+    annotated::apply_modifiers(&mut class.modifiers, context);
+
     context.scope.enter();
 
     for t in &mut class.type_params {
@@ -31,16 +35,20 @@ pub fn apply<'def>(class: &mut parse::tree::Class<'def>, context: &mut Context<'
     context.scope.leave();
 }
 
-fn apply_class_body<'def>(
+pub fn apply_class_body<'def>(
     body: &mut parse::tree::ClassBody<'def>,
     context: &mut Context<'def, '_, '_>,
 ) {
     for item in &mut body.items {
         match item {
+            ClassBodyItem::Constructor(c) => constructor::apply(c, context),
             ClassBodyItem::Method(m) => method::apply(m, context),
             ClassBodyItem::FieldDeclarators(f) => field::apply(f, context),
             ClassBodyItem::Class(c) => apply(c, context),
-            _ => (),
+            ClassBodyItem::Enum(e) => enum_def::apply(e, context),
+            ClassBodyItem::Interface(i) => interface::apply(i, context),
+            ClassBodyItem::Annotation(a) => annotation::apply(a, context),
+            ClassBodyItem::StaticInitializer(b) => block::apply(b, context),
         };
     }
 }
@@ -0,0 +1,27 @@
+use semantics::def::{annotated, type_param};
+use semantics::{block, Context};
+use {analyze, parse};
+
+pub fn apply<'def>(
+    constructor: &mut parse::tree::Constructor<'def>,
+    context: &mut Context<'def, '_, '_>,
+) {
+    constructor.def_opt.replace(Some(
+        context
+            .id_hash
+            .get_by_id::<analyze::definition::ConstructorDef>(constructor.id)
+            .unwrap(),
+    ));
+
+    annotated::apply_modifiers(&mut constructor.modifiers, context);
+
+    context.scope.enter();
+
+    for t in &mut constructor.type_params {
+        type_param::apply(t, context);
+    }
+
+    block::apply(&mut constructor.block, context);
+
+    context.scope.leave();
+}
@@ -0,0 +1,41 @@
+use parse::tree::Type;
+use semantics::def::{annotated, class};
+use semantics::{expr, Context};
+use {analyze, parse};
+
+pub fn apply<'def>(enum_def: &mut parse::tree::Enum<'def>, context: &mut Context<'def, '_, '_>) {
+    let def = context
+        .id_hash
+        .get_by_id::<analyze::definition::Enum>(enum_def.id)
+        .unwrap() as *const analyze::definition::Enum<'def>;
+    let def = unsafe { &*def };
+    enum_def.def_opt.replace(Some(&def.class));
+
+    annotated::apply_modifiers(&mut enum_def.modifiers, context);
+
+    context.scope.enter_class(&def.class);
+
+    for (constant, constant_def) in enum_def.constants.iter_mut().zip(def.constants.iter()) {
+        for a in &mut constant.annotateds {
+            annotated::apply(a, context);
+        }
+
+        if let Some(args) = &mut constant.args_opt {
+            for arg in args {
+                expr::apply(arg, &Type::UnknownType, context);
+            }
+        }
+
+        if let (Some(body), Some(body_def)) = (&mut constant.body_opt, &constant_def.body_opt) {
+            context.scope.enter_class(body_def);
+            class::apply_class_body(body, context);
+            context.scope.leave();
+        }
+    }
+
+    if let Some(body) = &mut enum_def.body_opt {
+        class::apply_class_body(body, context);
+    }
+
+    context.scope.leave();
+}
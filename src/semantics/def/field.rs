@@ -1,4 +1,5 @@
 use analyze::resolve::scope::Scope;
+use semantics::def::annotated;
 use semantics::{block, Context};
 use {analyze, parse};
 
@@ -6,6 +7,8 @@ pub fn apply<'def>(
     field_declarators: &mut parse::tree::FieldDeclarators<'def>,
     context: &mut Context<'def, '_, '_>,
 ) {
+    annotated::apply_modifiers(&mut field_declarators.modifiers, context);
+
     for decl in &mut field_declarators.declarators {
         apply_decl(decl, context);
     }
@@ -18,7 +21,7 @@ pub fn apply_decl<'def>(
     decl.def_opt.replace(Some(
         context
             .id_hash
-            .get_by_id::<analyze::definition::FieldDef>(&decl.id)
+            .get_by_id::<analyze::definition::FieldDef>(decl.id)
             .unwrap(),
     ));
 }
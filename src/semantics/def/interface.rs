@@ -0,0 +1,30 @@
+use semantics::def::{annotated, class, type_param};
+use semantics::Context;
+use {analyze, parse};
+
+pub fn apply<'def>(
+    interface: &mut parse::tree::Interface<'def>,
+    context: &mut Context<'def, '_, '_>,
+) {
+    let def = context
+        .id_hash
+        .get_by_id::<analyze::definition::Interface>(interface.id)
+        .unwrap() as *const analyze::definition::Interface<'def>;
+    let def = unsafe { &*def };
+    interface.def_opt.replace(Some(&def.class));
+
+    annotated::apply_modifiers(&mut interface.modifiers, context);
+
+    context.scope.enter();
+
+    for t in &mut interface.type_params {
+        type_param::apply(t, context);
+    }
+
+    context.scope.enter_class(&def.class);
+
+    class::apply_class_body(&mut interface.body, context);
+
+    context.scope.leave();
+    context.scope.leave();
+}
@@ -1,5 +1,5 @@
 use analyze::resolve::scope::Scope;
-use semantics::def::type_param;
+use semantics::def::{annotated, type_param};
 use semantics::{block, Context};
 use {analyze, parse};
 
@@ -7,10 +7,12 @@ pub fn apply<'def>(method: &mut parse::tree::Method<'def>, context: &mut Context
     method.def_opt.replace(Some(
         context
             .id_hash
-            .get_by_id::<analyze::definition::MethodDef>(&method.id)
+            .get_by_id::<analyze::definition::MethodDef>(method.id)
             .unwrap(),
     ));
 
+    annotated::apply_modifiers(&mut method.modifiers, context);
+
     context.scope.enter();
 
     for t in &mut method.type_params {
@@ -1,4 +1,9 @@
+pub mod annotated;
+pub mod annotation;
 pub mod class;
+pub mod constructor;
+pub mod enum_def;
 pub mod field;
+pub mod interface;
 pub mod method;
 pub mod type_param;
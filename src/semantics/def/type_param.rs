@@ -8,7 +8,7 @@ pub fn apply<'def>(
 ) {
     let def = context
         .id_hash
-        .get_by_id::<analyze::definition::TypeParam>(&type_param.id)
+        .get_by_id::<analyze::definition::TypeParam>(type_param.id)
         .unwrap();
     type_param.def_opt.replace(Some(def));
 
@@ -0,0 +1,233 @@
+use analyze::definition::{Class, Param};
+use parse::builder;
+use parse::tree::{ClassType, Expr, PrimitiveType, PrimitiveTypeType, Type};
+use semantics::expr::method_call::{coerce_from_primitive_to_class, make_java_lang_class_type};
+use semantics::Context;
+use std::collections::HashSet;
+use std::ops::Deref;
+
+// Overload resolution in the phases of JLS 15.12.2: the first phase that finds an applicable
+// member wins, and then the most specific member among the applicable ones is picked.
+//
+// A type that isn't resolved (e.g. the type of a lambda or of a class that isn't loaded) is
+// assumed to fit, so a member isn't ruled out by what the analysis doesn't know.
+
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub enum Phase {
+    // Subtyping and primitive widening.
+    Strict,
+    // Also boxing and unboxing.
+    Loose,
+    // Also a variable number of args for the last param.
+    Varargs,
+}
+
+pub const PHASES: [Phase; 3] = [Phase::Strict, Phase::Loose, Phase::Varargs];
+
+#[derive(Debug, PartialEq, Clone)]
+pub enum ArgType<'def> {
+    Known(Type<'def>),
+    Null,
+    Unknown,
+}
+
+pub fn arg_type<'def>(arg: &Expr<'def>, context: &Context<'def, '_, '_>) -> ArgType<'def> {
+    let primitive = |tpe| {
+        ArgType::Known(Type::Primitive(PrimitiveType {
+            span_opt: None,
+            tpe,
+        }))
+    };
+    match arg {
+        Expr::Null(_) => ArgType::Null,
+        Expr::Boolean(_) => primitive(PrimitiveTypeType::Boolean),
+        Expr::Char(_) => primitive(PrimitiveTypeType::Char),
+        Expr::Double(_) => primitive(PrimitiveTypeType::Double),
+        Expr::Float(_) => primitive(PrimitiveTypeType::Float),
+        Expr::Hex(_) | Expr::Int(_) => primitive(PrimitiveTypeType::Int),
+        Expr::Long(_) => primitive(PrimitiveTypeType::Long),
+        Expr::String(_) => {
+            ArgType::Known(Type::Class(make_java_lang_class_type("String", context)))
+        }
+        _ => match arg.tpe_opt() {
+            Some(Type::UnknownType) | None => ArgType::Unknown,
+            Some(tpe) => ArgType::Known(tpe),
+        },
+    }
+}
+
+// Whether the params can take the args in the phase. A varargs param takes an array in the first
+// two phases, and any number of its elements in the last one.
+pub fn is_applicable<'def>(
+    params: &[Param<'def>],
+    args: &[ArgType<'def>],
+    phase: Phase,
+    context: &Context<'def, '_, '_>,
+) -> bool {
+    let is_varargs = params.last().is_some_and(|param| param.is_varargs);
+    let fits = |param: &Type<'def>, arg: &ArgType<'def>| match arg {
+        ArgType::Known(arg) => {
+            is_subtype(arg, param)
+                || (phase != Phase::Strict && is_convertible(arg, param, context))
+        }
+        ArgType::Null => !matches!(param, Type::Primitive(_)),
+        ArgType::Unknown => true,
+    };
+
+    if phase == Phase::Varargs {
+        if !is_varargs || args.len() + 1 < params.len() {
+            return false;
+        }
+        let (last, fixed) = params.split_last().unwrap();
+        let element = last.tpe.borrow();
+        fixed
+            .iter()
+            .zip(args)
+            .all(|(param, arg)| fits(param.tpe.borrow().deref(), arg))
+            && args[fixed.len()..]
+                .iter()
+                .all(|arg| fits(element.deref(), arg))
+    } else {
+        params.len() == args.len()
+            && params
+                .iter()
+                .zip(args)
+                .all(|(param, arg)| fits(&declared_type(param), arg))
+    }
+}
+
+// Whether `a` is more specific than `b` for `arg_count` args: each param of `a` is a subtype of the
+// param of `b` that takes the same arg.
+pub fn is_more_specific<'def>(
+    a: &[Param<'def>],
+    b: &[Param<'def>],
+    arg_count: usize,
+    phase: Phase,
+) -> bool {
+    (0..arg_count.max(a.len()).max(b.len())).all(|index| {
+        match (param_type(a, index, phase), param_type(b, index, phase)) {
+            (Some(a), Some(b)) => is_subtype(&a, &b),
+            _ => true,
+        }
+    })
+}
+
+// The type of the param that takes the arg at `index`. In the last phase, the varargs param takes
+// the rest of the args.
+fn param_type<'def>(params: &[Param<'def>], index: usize, phase: Phase) -> Option<Type<'def>> {
+    match params.get(index) {
+        Some(param) if phase != Phase::Varargs => Some(declared_type(param)),
+        Some(param) => Some(param.tpe.borrow().clone()),
+        None => match params.last() {
+            Some(last) if last.is_varargs && phase == Phase::Varargs => {
+                Some(last.tpe.borrow().clone())
+            }
+            _ => None,
+        },
+    }
+}
+
+// The type of the param as a fixed arity param, which is an array for a varargs param.
+fn declared_type<'def>(param: &Param<'def>) -> Type<'def> {
+    let tpe = param.tpe.borrow().clone();
+    if param.is_varargs {
+        builder::array(tpe)
+    } else {
+        tpe
+    }
+}
+
+// Identity, primitive widening (JLS 5.1.2), and reference widening (JLS 5.1.5).
+fn is_subtype<'def>(sub: &Type<'def>, sup: &Type<'def>) -> bool {
+    match (sub, sup) {
+        (Type::Primitive(sub), Type::Primitive(sup)) => is_widening(&sub.tpe, &sup.tpe),
+        (Type::Primitive(_), _) | (_, Type::Primitive(_)) => false,
+        (Type::Array(sub), Type::Array(sup)) => match (sub.tpe.deref(), sup.tpe.deref()) {
+            (Type::Primitive(sub), Type::Primitive(sup)) => sub.tpe == sup.tpe,
+            (sub, sup) => is_subtype(sub, sup),
+        },
+        (Type::Array(_), Type::Class(sup)) => match sup.def_opt {
+            Some(def) => matches!(
+                unsafe { &*def }.import_path.as_str(),
+                "java.lang.Object" | "java.lang.Cloneable" | "java.io.Serializable"
+            ),
+            None => true,
+        },
+        (Type::Class(sub), Type::Class(sup)) => match (sub.def_opt, sup.def_opt) {
+            (Some(sub), Some(sup)) => is_subclass(sub, sup),
+            _ => true,
+        },
+        (Type::Class(_), Type::Array(_)) => false,
+        // A type variable is bounded by `Object` unless it says otherwise, which isn't checked.
+        _ => true,
+    }
+}
+
+fn is_subclass<'def>(sub: *const Class<'def>, sup: *const Class<'def>) -> bool {
+    let mut visited = HashSet::new();
+    let mut stack = vec![sub];
+    while let Some(class) = stack.pop() {
+        if class == sup {
+            return true;
+        }
+        if !visited.insert(class) {
+            continue;
+        }
+
+        let class = unsafe { &*class };
+        stack.extend(class.extend_opt.borrow().iter().filter_map(|c| c.def_opt));
+        stack.extend(class.implements.borrow().iter().filter_map(|c| c.def_opt));
+    }
+    false
+}
+
+fn is_widening(from: &PrimitiveTypeType, to: &PrimitiveTypeType) -> bool {
+    use parse::tree::PrimitiveTypeType::*;
+    from == to
+        || match from {
+            Byte => matches!(to, Short | Int | Long | Float | Double),
+            Short | Char => matches!(to, Int | Long | Float | Double),
+            Int => matches!(to, Long | Float | Double),
+            Long => matches!(to, Float | Double),
+            Float => matches!(to, Double),
+            Boolean | Double => false,
+        }
+}
+
+// Boxing followed by reference widening, or unboxing followed by primitive widening (JLS 5.3).
+fn is_convertible<'def>(
+    arg: &Type<'def>,
+    param: &Type<'def>,
+    context: &Context<'def, '_, '_>,
+) -> bool {
+    match (arg, param) {
+        (Type::Primitive(arg), Type::Primitive(_)) => false,
+        (Type::Primitive(arg), param) => is_subtype(
+            &Type::Class(coerce_from_primitive_to_class(arg, context)),
+            param,
+        ),
+        (Type::Class(arg), Type::Primitive(param)) => match unbox(arg) {
+            Some(arg) => is_widening(&arg, &param.tpe),
+            None => false,
+        },
+        _ => false,
+    }
+}
+
+fn unbox(class: &ClassType) -> Option<PrimitiveTypeType> {
+    let import_path = match class.def_opt {
+        Some(def) => unsafe { &*def }.import_path.as_str(),
+        None => return None,
+    };
+    Some(match import_path {
+        "java.lang.Boolean" => PrimitiveTypeType::Boolean,
+        "java.lang.Byte" => PrimitiveTypeType::Byte,
+        "java.lang.Character" => PrimitiveTypeType::Char,
+        "java.lang.Double" => PrimitiveTypeType::Double,
+        "java.lang.Float" => PrimitiveTypeType::Float,
+        "java.lang.Integer" => PrimitiveTypeType::Int,
+        "java.lang.Long" => PrimitiveTypeType::Long,
+        "java.lang.Short" => PrimitiveTypeType::Short,
+        _ => return None,
+    })
+}
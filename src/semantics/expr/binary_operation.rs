@@ -0,0 +1,137 @@
+use parse::tree::{BinaryOperation, Expr, Type};
+use semantics::{expr, Context};
+
+// The operands are applied from left to right. A long chain nests on the left, so its left spine
+// is gone through in a loop. The type of the result is computed by `BinaryOperation::tpe_opt`.
+pub fn apply<'def>(
+    binary_operation: &mut BinaryOperation<'def>,
+    context: &mut Context<'def, '_, '_>,
+) {
+    let mut rights = vec![];
+    let mut current = binary_operation;
+    let innermost = loop {
+        let BinaryOperation { left, right, .. } = current;
+        rights.push(right);
+        match **left {
+            Expr::BinaryOperation(ref mut op) => current = op,
+            ref mut innermost => break innermost,
+        }
+    };
+
+    expr::apply(innermost, &Type::UnknownType, context);
+    for right in rights.into_iter().rev() {
+        expr::apply(right, &Type::UnknownType, context);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use parse::tree::{
+        ClassBodyItem, CompilationUnitItem, Expr, PrimitiveType, PrimitiveTypeType, Statement, Type,
+    };
+    use std::ops::Deref;
+
+    #[test]
+    fn test_promotion() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  void method() {
+    byte b;
+    char c;
+    long l;
+    float f;
+    boolean z;
+    Integer boxed;
+    String s;
+    int a1 = b + c;
+    long a2 = b * l;
+    float a3 = l - f;
+    int a4 = b << l;
+    long a5 = l & c;
+    boolean a6 = z | z;
+    boolean a7 = f < l;
+    int a8 = boxed % c;
+    String a9 = 1 + s;
+    double a10 = b + c + l + f + 1.0;
+    int a11 = 'a' + 1;
+  }
+}
+        "#,
+            r#"
+package java.lang;
+
+public final class Integer {}
+        "#,
+            r#"
+package java.lang;
+
+public final class String {}
+        "#
+        );
+
+        let class = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &class.body.items.get(0).unwrap());
+        let stmts = &method.block_opt.as_ref().unwrap().stmts;
+        let tpe = |index: usize| {
+            let var = unwrap!(Statement::VariableDeclarators, stmts.get(index).unwrap());
+            var.declarators
+                .first()
+                .unwrap()
+                .expr_opt
+                .as_ref()
+                .unwrap()
+                .tpe_opt()
+        };
+        let primitive = |tpe| {
+            Some(Type::Primitive(PrimitiveType {
+                span_opt: None,
+                tpe,
+            }))
+        };
+
+        assert_eq!(tpe(7), primitive(PrimitiveTypeType::Int));
+        assert_eq!(tpe(8), primitive(PrimitiveTypeType::Long));
+        assert_eq!(tpe(9), primitive(PrimitiveTypeType::Float));
+        assert_eq!(tpe(10), primitive(PrimitiveTypeType::Int));
+        assert_eq!(tpe(11), primitive(PrimitiveTypeType::Long));
+        assert_eq!(tpe(12), primitive(PrimitiveTypeType::Boolean));
+        assert_eq!(tpe(13), primitive(PrimitiveTypeType::Boolean));
+        assert_eq!(tpe(14), primitive(PrimitiveTypeType::Int));
+        assert!(tpe(15).unwrap().is_string());
+        assert_eq!(tpe(16), primitive(PrimitiveTypeType::Double));
+        assert_eq!(tpe(17), primitive(PrimitiveTypeType::Int));
+    }
+
+    #[test]
+    fn test_long_chain() {
+        let content = format!(
+            "package dev; class Test {{ void method() {{ int a; long b = {}; }} }}",
+            vec!["a"; 100_000].join(" + ")
+        );
+        let (files, root) = apply_semantics!(&content);
+
+        let class = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &class.body.items.get(0).unwrap());
+        let var = unwrap!(
+            Statement::VariableDeclarators,
+            &method.block_opt.as_ref().unwrap().stmts.get(1).unwrap()
+        );
+        let expr = var.declarators.first().unwrap().expr_opt.as_ref().unwrap();
+        assert_eq!(
+            expr.tpe_opt(),
+            Some(Type::Primitive(PrimitiveType {
+                span_opt: None,
+                tpe: PrimitiveTypeType::Int
+            }))
+        );
+    }
+}
@@ -0,0 +1,205 @@
+use analyze::definition::Constructor;
+use parse::tree::{Expr, SuperConstructorCall, ThisConstructorCall, Type};
+use semantics::expr::applicability::{self, ArgType, PHASES};
+use semantics::{expr, Context};
+use std::ops::Deref;
+
+pub fn apply_this<'def>(call: &mut ThisConstructorCall<'def>, context: &mut Context<'def, '_, '_>) {
+    let constructors = match context.scope.enclosing_class_opt() {
+        Some(class) => unsafe { &*class }.to_type().find_constructors(),
+        None => vec![],
+    };
+
+    call.def_opt
+        .replace(select(constructors, &mut call.args, context));
+}
+
+pub fn apply_super<'def>(
+    call: &mut SuperConstructorCall<'def>,
+    context: &mut Context<'def, '_, '_>,
+) {
+    if let Some(prefix) = &mut call.prefix_opt {
+        expr::apply(prefix, &Type::UnknownType, context);
+    }
+
+    let constructors = match context.scope.enclosing_class_opt() {
+        Some(class) => match unsafe { &*class }.extend_opt.borrow().as_ref() {
+            Some(extend) => extend.find_constructors(),
+            None => vec![],
+        },
+        None => vec![],
+    };
+
+    call.def_opt
+        .replace(select(constructors, &mut call.args, context));
+}
+
+// Picks the constructor like javac (see `applicability`). When the applicable constructors have no
+// most specific one (i.e. the call is ambiguous), the first one wins. When none is applicable, the
+// first one with the same number of params wins, so a call with an error still links somewhere.
+//
+// A lambda needs its param's type as the target type, so lambdas are applied after the pick.
+pub fn select<'def>(
+    constructors: Vec<Constructor<'def>>,
+    args: &mut Vec<Expr<'def>>,
+    context: &mut Context<'def, '_, '_>,
+) -> Option<Constructor<'def>> {
+    for arg in args.iter_mut() {
+        if !is_lambda(arg) {
+            expr::apply(arg, &Type::UnknownType, context);
+        }
+    }
+
+    let arg_types = args
+        .iter()
+        .map(|arg| applicability::arg_type(arg, context))
+        .collect::<Vec<_>>();
+    let selected_opt = pick(constructors, &arg_types, context);
+
+    for (index, arg) in args.iter_mut().enumerate() {
+        if !is_lambda(arg) {
+            continue;
+        }
+
+        let param_opt = selected_opt.as_ref().and_then(|selected| {
+            selected
+                .params
+                .get(index)
+                .or_else(|| selected.params.last().filter(|param| param.is_varargs))
+        });
+        match param_opt {
+            Some(param) => expr::apply(arg, param.tpe.borrow().deref(), context),
+            None => expr::apply(arg, &Type::UnknownType, context),
+        }
+    }
+
+    selected_opt
+}
+
+fn pick<'def>(
+    mut constructors: Vec<Constructor<'def>>,
+    arg_types: &[ArgType<'def>],
+    context: &Context<'def, '_, '_>,
+) -> Option<Constructor<'def>> {
+    for &phase in PHASES.iter() {
+        let applicable = constructors
+            .iter()
+            .enumerate()
+            .filter(|(_, c)| applicability::is_applicable(&c.params, arg_types, phase, context))
+            .map(|(index, _)| index)
+            .collect::<Vec<_>>();
+        if applicable.is_empty() {
+            continue;
+        }
+
+        let most_specific_opt = applicable.iter().cloned().find(|&index| {
+            applicable.iter().all(|&other| {
+                other == index
+                    || applicability::is_more_specific(
+                        &constructors[index].params,
+                        &constructors[other].params,
+                        arg_types.len(),
+                        phase,
+                    )
+            })
+        });
+        let index = most_specific_opt.unwrap_or(applicable[0]);
+        return Some(constructors.swap_remove(index));
+    }
+
+    let index = constructors
+        .iter()
+        .position(|c| c.params.len() == arg_types.len())
+        .unwrap_or(0);
+    if constructors.is_empty() {
+        None
+    } else {
+        Some(constructors.swap_remove(index))
+    }
+}
+
+fn is_lambda(expr: &Expr) -> bool {
+    matches!(expr, Expr::Lambda(_))
+}
+
+#[cfg(test)]
+mod tests {
+    use analyze::definition::ConstructorDef;
+    use analyze::test_common::find_class;
+    use parse::tree::{ClassBodyItem, CompilationUnitItem, Expr, Statement};
+
+    #[test]
+    fn test_phases() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  void method() {
+    new Widen(1);
+    new Specific("a");
+    new Boxing(1);
+    new Varargs(1, 2);
+    new Varargs(1);
+  }
+}
+        "#,
+            r#"
+package dev;
+
+class Widen {
+  Widen(Integer value) {}
+  Widen(long value) {}
+}
+        "#,
+            r#"
+package dev;
+
+class Specific {
+  Specific(Object value) {}
+  Specific(String value) {}
+}
+        "#,
+            r#"
+package dev;
+
+class Boxing {
+  Boxing(String value) {}
+  Boxing(Object value) {}
+}
+        "#,
+            r#"
+package dev;
+
+class Varargs {
+  Varargs(int... values) {}
+  Varargs(int value) {}
+}
+        "#
+        );
+
+        let test = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &test.body.items.get(0).unwrap());
+        let stmts = &method.block_opt.as_ref().unwrap().stmts;
+        let selected = |index: usize| {
+            let new_object = unwrap!(Expr::NewObject, unwrap!(Statement::Expr, &stmts[index]));
+            new_object.def_opt.borrow().as_ref().unwrap().def
+        };
+        let constructor = |class: &str, index: usize| {
+            find_class(&root, class).constructors.get(index).unwrap() as *const ConstructorDef
+        };
+
+        // Widening comes before boxing.
+        assert_eq!(selected(0), constructor("dev.Widen", 1));
+        // `String` is more specific than `Object`.
+        assert_eq!(selected(1), constructor("dev.Specific", 1));
+        // An `int` is boxed to `Integer`, which is an `Object`.
+        assert_eq!(selected(2), constructor("dev.Boxing", 1));
+        // A variable number of args only comes last.
+        assert_eq!(selected(3), constructor("dev.Varargs", 0));
+        assert_eq!(selected(4), constructor("dev.Varargs", 1));
+    }
+}
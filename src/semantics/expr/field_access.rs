@@ -22,7 +22,7 @@ pub fn apply_field_access<'def>(
 
     match field_access.prefix.borrow().as_ref() {
         FieldAccessPrefix::Package(prefix) => {
-            if let Some(mut tpe) = prefix.find(field_access.name.fragment) {
+            if let Some(mut tpe) = prefix.find(field_access.name.fragment()) {
                 tpe.set_span_opt(Some(&field_access.name));
                 match tpe {
                     EnclosingType::Package(mut p) => {
@@ -46,7 +46,7 @@ pub fn apply_field_access<'def>(
         FieldAccessPrefix::Expr(Expr::StaticClass(static_class)) => {
             if let Some(mut class) = static_class
                 .tpe
-                .find_inner_class(field_access.name.fragment)
+                .find_inner_class(field_access.name.fragment())
             {
                 class.prefix_opt = Some(Box::new(match &static_class.tpe {
                     StaticType::Class(c) => EnclosingType::Class(c.clone()),
@@ -58,7 +58,7 @@ pub fn apply_field_access<'def>(
                 })));
             }
             if let Some(field) = static_class.tpe.find_field(
-                field_access.name.fragment,
+                field_access.name.fragment(),
                 &InvocationContext { only_static: true },
             ) {
                 field_access.def_opt.replace(Some(field));
@@ -67,7 +67,7 @@ pub fn apply_field_access<'def>(
         FieldAccessPrefix::Expr(e) => {
             if let Some(tpe) = e.tpe_opt() {
                 if let Some(field) = tpe.find_field(
-                    field_access.name.fragment,
+                    field_access.name.fragment(),
                     &InvocationContext { only_static: false },
                 ) {
                     field_access.def_opt.replace(Some(field));
@@ -96,12 +96,12 @@ fn apply_prefix<'def>(
             }
         }
         Expr::Name(n) => {
-            if let Some(resolved) = context.scope.resolve_name(n.name.fragment) {
+            if let Some(resolved) = context.scope.resolve_name(n.name.fragment()) {
                 match resolved {
                     ResolvedName::Package(p) => {
                         return Some(FieldAccessPrefix::Package(PackagePrefix {
                             prefix_opt: None,
-                            name: n.name.fragment.to_owned(),
+                            name: n.name.fragment(),
                             span_opt: Some(n.name.clone()),
                             def: p,
                         }))
@@ -118,7 +118,7 @@ fn apply_prefix<'def>(
                     ResolvedName::TypeParam(p) => {
                         return Some(FieldAccessPrefix::Expr(Expr::StaticClass(StaticClass {
                             tpe: StaticType::Parameterized(ParameterizedType {
-                                name: n.name.fragment.to_owned(),
+                                name: n.name.fragment(),
                                 span_opt: Some(n.name.clone()),
                                 def: p,
                             }),
@@ -24,7 +24,8 @@ pub fn apply<'def>(
                     target_param.tpe.borrow().deref(),
                     context,
                 )),
-                name: param.name.clone(),
+                name: param.name.fragment(),
+                span_opt: Some(param.name),
                 is_varargs: param.is_varargs,
             });
         }
@@ -95,7 +96,7 @@ fn infer_class<'def>(declared: &ClassType<'def>, target: &Type<'def>) -> ClassTy
                 // TODO: we need to recursively handle prefix_opt and type_args_opt
                 return ClassType {
                     prefix_opt: None,
-                    name: declared.name.clone(),
+                    name: declared.name,
                     span_opt: declared.span_opt,
                     // TODO: what do we do if `target` has `type_args` but `declared` doesn't? And vice versa
                     // TODO: what do we do if they have different numbers of `type_args`?
@@ -79,14 +79,14 @@ pub fn apply<'def>(method_call: &mut MethodCall<'def>, context: &mut Context<'de
         expr::apply(prefix, &Type::UnknownType, context);
 
         if let Some(tpe) = prefix.tpe_opt() {
-            tpe.find_methods(method_call.name.fragment, &invocation_context, 0)
+            tpe.find_methods(method_call.name.fragment(), &invocation_context, 0)
         } else {
             vec![]
         }
     } else {
         context
             .scope
-            .resolve_methods(method_call.name.fragment, &invocation_context)
+            .resolve_methods(method_call.name.fragment(), &invocation_context)
     };
 
     let mut scores = vec![];
@@ -142,7 +142,7 @@ fn realize_type<'def>(tpe: &Type<'def>, map: &HashMap<String, Type<'def>>) -> Ty
         return tpe.clone();
     };
 
-    if let Some(new_type) = map.get(&parameterized.name) {
+    if let Some(new_type) = map.get(parameterized.name) {
         new_type.clone()
     } else {
         tpe.clone()
@@ -168,7 +168,7 @@ fn compute<'def>(
 
         if let Type::Parameterized(t) = param.tpe.borrow().deref() {
             if let Some(tpe) = arg.tpe_opt() {
-                if should_replace(inferred.get(&t.name)) {
+                if should_replace(inferred.get(t.name)) {
                     inferred.insert(t.name.to_owned(), tpe);
                 }
             }
@@ -218,6 +218,7 @@ fn realize_method_with_type_mapping<'def>(
         params.push(Param {
             tpe: RefCell::new(realize_type(param.tpe.borrow().deref(), &type_mapping)),
             name: param.name,
+            span_opt: param.span_opt,
             is_varargs: param.is_varargs,
         })
     }
@@ -237,7 +238,7 @@ fn compute_param_score<'def>(
     context: &mut Context<'def, '_, '_>,
 ) -> ParamScore {
     if let Type::Parameterized(p) = param.tpe.borrow().deref() {
-        if let Some(found) = inferred.get(&p.name) {
+        if let Some(found) = inferred.get(p.name) {
             param.tpe.replace(found.clone());
         }
     }
@@ -350,10 +351,13 @@ pub fn coerce_from_primitive_to_class<'def>(
     }
 }
 
-fn make_java_lang_class_type<'def>(name: &str, context: &Context<'def, '_, '_>) -> ClassType<'def> {
+pub fn make_java_lang_class_type<'def>(
+    name: &'def str,
+    context: &Context<'def, '_, '_>,
+) -> ClassType<'def> {
     ClassType {
         prefix_opt: None,
-        name: name.to_owned(),
+        name,
         span_opt: None,
         type_args_opt: None,
         def_opt: context
@@ -370,7 +374,7 @@ fn is_valid_primitive_class<'def>(class: &ClassType<'def>, name: &str) -> bool {
     let def = if let Some(def) = class.def_opt {
         unsafe { &*def }
     } else {
-        return &class.name == name && is_java_lang(class);
+        return class.name == name && is_java_lang(class);
     };
 
     def.import_path == format!("java.lang.{}", name)
@@ -389,8 +393,8 @@ fn is_java_lang(class: &ClassType) -> bool {
 
     if let Some(prefix_of_prefix) = &prefix.prefix_opt {
         prefix_of_prefix.prefix_opt.is_none()
-            && &(*prefix_of_prefix).name == "java"
-            && &prefix.name == "lang"
+            && prefix_of_prefix.name == "java"
+            && prefix.name == "lang"
     } else {
         false
     }
@@ -398,11 +402,12 @@ fn is_java_lang(class: &ClassType) -> bool {
 
 #[cfg(test)]
 mod tests {
-    use analyze::definition::MethodDef;
-    use analyze::test_common::find_class;
+    use analyze::definition::{Decl, MethodDef, Modifier};
+    use analyze::test_common::{find_class, find_package};
+    use parse::id_gen::Id;
     use parse::tree::{
-        ClassBodyItem, ClassType, CompilationUnitItem, Expr, PrimitiveType, PrimitiveTypeType,
-        Statement, Type, TypeParam,
+        ClassBodyItem, ClassType, CompilationUnitItem, Expr, InvocationContext, MethodCall,
+        PrimitiveType, PrimitiveTypeType, ResolvedName, Statement, Type, TypeParam,
     };
     use std::ops::Deref;
     use test_common::{span, span2};
@@ -495,6 +500,64 @@ class Another {
         );
     }
 
+    #[test]
+    fn test_object_method() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+import lib.*;
+
+class Test {
+  void method() {
+    Named named;
+    toString();
+    named.hashCode();
+    named.getClass();
+    String.valueOf(1);
+  }
+}
+        "#,
+            r#"
+package lib;
+
+public interface Named {}
+        "#
+        );
+
+        let test = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &test.body.items.get(0).unwrap());
+        let stmts = &method.block_opt.as_ref().unwrap().stmts;
+        let call =
+            |index: usize| unwrap!(Expr::MethodCall, unwrap!(Statement::Expr, &stmts[index]));
+        let object = find_class(&root, "java.lang.Object");
+        fn def<'a>(method_call: &MethodCall<'a>) -> *const MethodDef<'a> {
+            method_call.def_opt.borrow().as_ref().unwrap().def
+        }
+
+        assert_eq!(
+            def(call(1)),
+            object.find_method("toString").unwrap() as *const MethodDef
+        );
+        assert_eq!(
+            def(call(2)),
+            object.find_method("hashCode").unwrap() as *const MethodDef
+        );
+        assert_eq!(
+            def(call(3)),
+            object.find_method("getClass").unwrap() as *const MethodDef
+        );
+        assert_eq!(
+            unwrap!(Expr::Name, call(4).prefix_opt.as_ref().unwrap().deref())
+                .resolved_opt
+                .get(),
+            Some(ResolvedName::Class(find_class(&root, "java.lang.String")))
+        );
+    }
+
     #[test]
     fn test_infer() {
         let (files, root) = apply_semantics!(
@@ -528,7 +591,7 @@ class Test {
         assert_eq!(
             &Type::Class(ClassType {
                 prefix_opt: None,
-                name: "Test".to_string(),
+                name: "Test",
                 span_opt: Some(span2(5, 5, "Test", files.first().unwrap().deref())),
                 type_args_opt: None,
                 def_opt: Some(find_class(&root, "dev.Test"))
@@ -536,4 +599,370 @@ class Test {
             &def.as_ref().unwrap().return_type
         )
     }
+
+    #[test]
+    fn test_enum() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  void method() {
+    Color.RED.name();
+    Color.GREEN.code();
+  }
+}
+        "#,
+            r#"
+package dev;
+
+enum Color {
+  RED, GREEN { int code() { return 2; } };
+  int code() { return 1; }
+}
+        "#
+        );
+
+        let test = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &test.body.items.get(0).unwrap());
+        let stmts = &method.block_opt.as_ref().unwrap().stmts;
+        let call = |index: usize| {
+            let method_call = unwrap!(Expr::MethodCall, unwrap!(Statement::Expr, &stmts[index]));
+            method_call.def_opt.borrow().as_ref().unwrap().clone()
+        };
+
+        let color = find_class(&root, "dev.Color");
+        let name = call(0);
+        assert_eq!(
+            name.def,
+            color.find_method("name").unwrap() as *const MethodDef
+        );
+
+        let values = color.find_method("values").unwrap().return_type.borrow();
+        let elem = unwrap!(Type::Array, values.deref()).tpe.deref();
+        assert_eq!(
+            unwrap!(Type::Class, elem).def_opt,
+            Some(color as *const analyze::definition::Class)
+        );
+
+        let enum_def = unwrap!(
+            CompilationUnitItem::Enum,
+            &files.get(1).unwrap().unit.items.get(0).unwrap()
+        );
+        let code = unwrap!(
+            ClassBodyItem::Method,
+            &enum_def.body_opt.as_ref().unwrap().items.get(0).unwrap()
+        );
+        assert_eq!(call(1).def, code.def_opt.borrow().unwrap());
+        assert_eq!(
+            *enum_def.def_opt.borrow(),
+            Some(color as *const analyze::definition::Class)
+        );
+
+        // The body of a constant is a class with an id of its own.
+        let green = enum_def.constants.get(1).unwrap();
+        let green_def = find_package(&root, "dev")
+            .units()
+            .iter()
+            .find_map(|unit| match &unit.main {
+                Decl::Enum(enum_def) => enum_def.constants.get(1),
+                _ => None,
+            })
+            .unwrap();
+        let green_body = green_def.body_opt.as_ref().unwrap();
+        assert_ne!(green.body_id, Id::default());
+        assert_ne!(green.body_id, green.id);
+        assert_eq!(green_body.id, green.body_id);
+        assert_eq!(color.find_field("GREEN").unwrap().id, green.id);
+    }
+
+    #[test]
+    fn test_interface() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  void method() {
+    List<String> list;
+    list.get(0);
+    list.isEmpty();
+  }
+}
+        "#,
+            r#"
+package dev;
+
+interface Collection<E> {
+  int size();
+  default boolean isEmpty() { return size() == 0; }
+}
+        "#,
+            r#"
+package dev;
+
+interface List<E> extends Collection<E> {
+  E get(int index);
+}
+        "#
+        );
+
+        let test = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &test.body.items.get(0).unwrap());
+        let stmts = &method.block_opt.as_ref().unwrap().stmts;
+        let call = |index: usize| {
+            let method_call = unwrap!(Expr::MethodCall, unwrap!(Statement::Expr, &stmts[index]));
+            method_call.def_opt.borrow().as_ref().unwrap().clone()
+        };
+
+        let collection = find_class(&root, "dev.Collection");
+        let list = find_class(&root, "dev.List");
+        assert_eq!(
+            call(1).def,
+            list.find_method("get").unwrap() as *const MethodDef
+        );
+        assert_eq!(
+            call(2).def,
+            collection.find_method("isEmpty").unwrap() as *const MethodDef
+        );
+        assert!(collection
+            .find_method("size")
+            .unwrap()
+            .modifiers
+            .contains(&Modifier::Abstract));
+
+        let interface = unwrap!(
+            CompilationUnitItem::Interface,
+            &files.get(2).unwrap().unit.items.get(0).unwrap()
+        );
+        assert_eq!(
+            *interface.def_opt.borrow(),
+            Some(list as *const analyze::definition::Class)
+        );
+    }
+
+    #[test]
+    fn test_overload() {
+        let (_files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Parent<T> {
+  void set(T t) {}
+  void set(int a, int b) {}
+  int size() { return 0; }
+}
+        "#,
+            r#"
+package dev;
+
+class Child extends Parent<String> {
+  void set(String s) {}
+  int size() { return 1; }
+}
+        "#,
+            r#"
+package dev;
+
+interface Callback {
+  boolean equals(Object other);
+  void call(int a);
+}
+        "#,
+            r#"
+package dev;
+
+interface Named extends Callback {
+  default void call(int a) {}
+  void name();
+}
+        "#
+        );
+
+        let context = InvocationContext { only_static: false };
+        let parent = find_class(&root, "dev.Parent");
+        let child = find_class(&root, "dev.Child");
+
+        assert_eq!(parent.find_methods("set").len(), 2);
+        assert_eq!(
+            parent
+                .find_method_by_signature("set(java.lang.Object)")
+                .unwrap() as *const MethodDef,
+            parent.methods.get(0).unwrap() as *const MethodDef
+        );
+
+        let sets = child
+            .to_type()
+            .find_methods("set", &context, 0)
+            .into_iter()
+            .map(|method| method.def)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            sets,
+            vec![
+                child.methods.get(0).unwrap() as *const MethodDef,
+                parent.methods.get(1).unwrap() as *const MethodDef
+            ]
+        );
+
+        let sizes = child.to_type().find_methods("size", &context, 0);
+        assert_eq!(sizes.len(), 1);
+        assert_eq!(
+            sizes.get(0).unwrap().def,
+            child.methods.get(1).unwrap() as *const MethodDef
+        );
+        assert_eq!(
+            child
+                .to_type()
+                .find_method_by_signature("set(int,int)", &context)
+                .unwrap()
+                .def,
+            parent.methods.get(1).unwrap() as *const MethodDef
+        );
+
+        let callback = find_class(&root, "dev.Callback");
+        assert_eq!(
+            callback.to_type().lambda_method().unwrap().def,
+            callback.find_method("call").unwrap() as *const MethodDef
+        );
+        let named = find_class(&root, "dev.Named");
+        assert_eq!(
+            named.to_type().lambda_method().unwrap().def,
+            named.find_method("name").unwrap() as *const MethodDef
+        );
+    }
+
+    #[test]
+    fn test_overload_same_simple_name() {
+        let (_files, root) = apply_semantics!(
+            r#"
+package other;
+
+public class Base {
+  public void put(Item item) {}
+}
+        "#,
+            r#"
+package dev;
+
+import other.Base;
+
+class Derived extends Base {
+  void put(Item item) {}
+}
+        "#,
+            r#"
+package dev;
+
+class Item {}
+        "#,
+            r#"
+package other;
+
+public class Item {}
+        "#
+        );
+
+        let context = InvocationContext { only_static: false };
+        let base = find_class(&root, "other.Base");
+        let derived = find_class(&root, "dev.Derived");
+
+        assert_eq!(
+            base.methods.get(0).unwrap().erased_signature(),
+            "put(other.Item)"
+        );
+        // `put(dev.Item)` overloads `put(other.Item)` instead of overriding it.
+        let puts = derived
+            .to_type()
+            .find_methods("put", &context, 0)
+            .into_iter()
+            .map(|method| method.def)
+            .collect::<Vec<_>>();
+        assert_eq!(
+            puts,
+            vec![
+                derived.methods.get(0).unwrap() as *const MethodDef,
+                base.methods.get(0).unwrap() as *const MethodDef
+            ]
+        );
+    }
+
+    #[test]
+    fn test_inherited_access() {
+        let (files, root) = apply_semantics!(
+            r#"
+package other;
+
+public class Base {
+  public void a() {}
+  protected void b() {}
+  void c() {}
+  private void d() {}
+}
+        "#,
+            r#"
+package other;
+
+public class Same extends Base {}
+        "#,
+            r#"
+package dev;
+
+import other.Same;
+
+class Derived extends Same {}
+        "#
+        );
+
+        let context = InvocationContext { only_static: false };
+        let names = |import_path: &str| {
+            let tpe = find_class(&root, import_path).to_type();
+            vec!["a", "b", "c", "d"]
+                .into_iter()
+                .filter(|name| !tpe.find_methods(name, &context, 0).is_empty())
+                .collect::<Vec<_>>()
+        };
+
+        // A private method isn't inherited, and a package-private one only within its package.
+        assert_eq!(names("other.Same"), vec!["a", "b", "c"]);
+        assert_eq!(names("dev.Derived"), vec!["a", "b"]);
+
+        // The inherited methods are collected once per class.
+        let derived = find_class(&root, "dev.Derived");
+        let first = derived.to_type().find_methods("a", &context, 0);
+        let second = derived.to_type().find_methods("a", &context, 0);
+        assert_eq!(first, second);
+        assert_eq!(first.get(0).unwrap().depth, 2);
+    }
+
+    #[test]
+    fn test_cyclic_hierarchy() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class A extends B {
+  void a() {}
+}
+        "#,
+            r#"
+package dev;
+
+class B extends A {
+  void b() {}
+}
+        "#
+        );
+
+        let context = InvocationContext { only_static: false };
+        let a = find_class(&root, "dev.A");
+        assert_eq!(a.to_type().find_methods("b", &context, 0).len(), 1);
+        assert!(a.to_type().find_methods("c", &context, 0).is_empty());
+    }
 }
@@ -2,10 +2,14 @@ use analyze::resolve::scope::Scope;
 use parse::tree::{Expr, Type};
 use semantics::Context;
 
+pub mod applicability;
+pub mod binary_operation;
+pub mod constructor_call;
 pub mod field_access;
 pub mod lambda;
 pub mod method_call;
 pub mod name;
+pub mod new_object;
 
 pub fn apply<'def>(
     expr: &mut Expr<'def>,
@@ -17,7 +21,7 @@ pub fn apply<'def>(
         Expr::ArrayAccess(_) => {}
         Expr::ArrayInitializer(_) => {}
         Expr::Assignment(_) => {}
-        Expr::BinaryOperation(_) => {}
+        Expr::BinaryOperation(b) => binary_operation::apply(b, context),
         Expr::Boolean(_) => {}
         Expr::Cast(_) => {}
         Expr::Char(_) => {}
@@ -33,14 +37,14 @@ pub fn apply<'def>(
         Expr::MethodReference(_) => {}
         Expr::Name(n) => name::apply(n, context),
         Expr::NewArray(_) => {}
-        Expr::NewObject(_) => {}
+        Expr::NewObject(n) => new_object::apply(n, target_type, context),
         Expr::Null(_) => {}
         Expr::Class(_) => {}
         Expr::String(_) => {}
         Expr::Super(_) => {}
-        Expr::SuperConstructorCall(_) => {}
+        Expr::SuperConstructorCall(s) => constructor_call::apply_super(s, context),
         Expr::This(_) => {}
-        Expr::ThisConstructorCall(_) => {}
+        Expr::ThisConstructorCall(t) => constructor_call::apply_this(t, context),
         Expr::Ternary(_) => {}
         Expr::UnaryOperation(_) => {}
         Expr::StaticClass(_) => {}
@@ -0,0 +1,554 @@
+use analyze::build;
+use analyze::definition::{Class, InheritedMethods, TypeParam};
+use analyze::resolve::{assign_parameterized_type, assign_type};
+use parse::id_gen::Id;
+use parse::tree::{
+    ClassBody, ClassType, NewObject, ReferenceType, Type, TypeArg, TypeParamExtend, WildcardType,
+};
+use semantics::def::class;
+use semantics::expr::constructor_call;
+use semantics::{expr, Context};
+use std::cell::RefCell;
+use std::collections::{HashMap, HashSet};
+
+pub fn apply<'def>(
+    new_object: &mut NewObject<'def>,
+    target_type: &Type<'def>,
+    context: &mut Context<'def, '_, '_>,
+) {
+    if let Some(prefix) = &mut new_object.prefix_opt {
+        expr::apply(prefix, &Type::UnknownType, context);
+    }
+
+    let is_diamond = new_object.tpe.type_args_opt == Some(vec![]);
+    new_object.tpe = resolve(new_object, context);
+
+    // The type args of a diamond that can't be inferred stay empty.
+    if is_diamond {
+        new_object.tpe.type_args_opt =
+            infer_type_args(&new_object.tpe, target_type).or_else(|| Some(vec![]));
+    }
+
+    let selected_opt = constructor_call::select(
+        new_object.tpe.find_constructors(),
+        &mut new_object.args,
+        context,
+    );
+    new_object.def_opt.replace(selected_opt);
+
+    if let Some(body) = &mut new_object.body_opt {
+        let package_path = context
+            .scope
+            .current_package_opt()
+            .map_or(String::new(), |package| {
+                unsafe { &*package }.import_path.clone()
+            });
+        let class = Box::new(build_anonymous_class(
+            new_object.id,
+            &new_object.tpe,
+            body,
+            package_path,
+        ));
+        let class = new_object.anonymous_class_opt.get_or_insert(class).as_ref() as *const Class;
+        apply_anonymous_class(body, unsafe { &*class }, context);
+    }
+}
+
+fn resolve<'def>(new_object: &NewObject<'def>, context: &Context<'def, '_, '_>) -> ClassType<'def> {
+    // `outer.new Inner()` instantiates an inner class of the prefix's type.
+    if let Some(prefix) = &new_object.prefix_opt {
+        if let Some(Type::Class(prefix_type)) = prefix.tpe_opt() {
+            if let Some(mut inner) = prefix_type.find_inner_class(new_object.tpe.name) {
+                inner.span_opt = new_object.tpe.span_opt;
+                inner.type_args_opt = new_object.tpe.type_args_opt.clone();
+                return inner;
+            }
+        }
+
+        return new_object.tpe.clone();
+    }
+
+    match assign_type::resolve_class_or_parameterized_type(&new_object.tpe, &context.scope) {
+        Some(Type::Class(resolved)) => resolved,
+        _ => new_object.tpe.clone(),
+    }
+}
+
+// Infers the type args of a diamond (e.g. `new ArrayList<>()`) from the target type, which is the
+// class itself or one of its supertypes. For example, `List<String>` gives `ArrayList<String>`.
+fn infer_type_args<'def>(
+    tpe: &ClassType<'def>,
+    target_type: &Type<'def>,
+) -> Option<Vec<TypeArg<'def>>> {
+    let target = if let Type::Class(target) = target_type {
+        target
+    } else {
+        return None;
+    };
+    let class = unsafe { &*tpe.def_opt? };
+    let mapping = map_type_params(class, target)?;
+
+    class
+        .type_params
+        .iter()
+        .map(|type_param| capture(mapping.get(type_param.name)?.clone(), type_param))
+        .collect()
+}
+
+// A wildcard can't be the type arg of a class instance creation, so it's replaced with its bound,
+// e.g. `List<? extends Number>` gives `ArrayList<Number>`. An unbounded wildcard gives the bound of
+// the type param, and it can't be inferred when the type param has no bound (i.e. `Object`).
+fn capture<'def>(type_arg: TypeArg<'def>, type_param: &TypeParam<'def>) -> Option<TypeArg<'def>> {
+    let wildcard = match type_arg {
+        TypeArg::Wildcard(wildcard) => wildcard,
+        other => return Some(other),
+    };
+
+    let WildcardType {
+        extends, super_opt, ..
+    } = wildcard;
+    if let Some(bound) = super_opt
+        .map(|bound| *bound)
+        .or_else(|| extends.into_iter().next())
+    {
+        return Some(match bound {
+            ReferenceType::Class(class) => TypeArg::Class(class),
+            ReferenceType::Array(array) => TypeArg::Array(array),
+            ReferenceType::Parameterized(parameterized) => TypeArg::Parameterized(parameterized),
+        });
+    }
+
+    match type_param.extends.borrow().first()? {
+        TypeParamExtend::Class(class) => Some(TypeArg::Class(class.clone())),
+        TypeParamExtend::Parameterized(parameterized) => {
+            Some(TypeArg::Parameterized(parameterized.clone()))
+        }
+    }
+}
+
+// Maps the type params of `class` to the type args of `target` by going up the supertypes of
+// `class` until `target` is reached.
+fn map_type_params<'def>(
+    class: &Class<'def>,
+    target: &ClassType<'def>,
+) -> Option<HashMap<&'def str, TypeArg<'def>>> {
+    if target.def_opt == Some(class as *const Class) {
+        let type_args = target.type_args_opt.as_ref()?;
+        return Some(
+            class
+                .type_params
+                .iter()
+                .map(|type_param| type_param.name)
+                .zip(type_args.iter().cloned())
+                .collect(),
+        );
+    }
+
+    let mut supertypes = class
+        .extend_opt
+        .borrow()
+        .iter()
+        .cloned()
+        .collect::<Vec<_>>();
+    supertypes.extend(class.implements.borrow().iter().cloned());
+
+    for supertype in supertypes {
+        let super_class = if let Some(def) = supertype.def_opt {
+            unsafe { &*def }
+        } else {
+            continue;
+        };
+        let super_mapping = if let Some(mapping) = map_type_params(super_class, target) {
+            mapping
+        } else {
+            continue;
+        };
+
+        let mut mapping = HashMap::new();
+        if let Some(type_args) = &supertype.type_args_opt {
+            for (type_param, type_arg) in super_class.type_params.iter().zip(type_args.iter()) {
+                if let (TypeArg::Parameterized(p), Some(found)) =
+                    (type_arg, super_mapping.get(type_param.name))
+                {
+                    mapping.insert(p.name, found.clone());
+                }
+            }
+        }
+        return Some(mapping);
+    }
+
+    None
+}
+
+// The supertype of an anonymous class is either a class or an interface. Members are found the
+// same way in both, so it is kept in `extend_opt`.
+fn build_anonymous_class<'def>(
+    id: Id,
+    tpe: &ClassType<'def>,
+    body: &ClassBody<'def>,
+    package_path: String,
+) -> Class<'def> {
+    let mut scope = build::scope::Scope {
+        paths: vec![],
+        package_path: package_path.clone(),
+    };
+    let mut class = Class {
+        id,
+        name: "",
+        import_path: String::new(),
+        package_path,
+        span_opt: None,
+        modifiers: HashSet::new(),
+        type_params: vec![],
+        extend_opt: RefCell::new(Some(tpe.clone())),
+        implements: RefCell::new(vec![]),
+        constructors: vec![],
+        methods: vec![],
+        field_groups: vec![],
+        decls: vec![],
+        inherited_methods: InheritedMethods::default(),
+    };
+    build::class::build_body(&mut class, body, &mut scope);
+    class
+}
+
+// The members of an anonymous class are added to the id hash, so its body is applied like the body
+// of any other class.
+fn apply_anonymous_class<'def>(
+    body: &mut ClassBody<'def>,
+    class: &'def Class<'def>,
+    context: &mut Context<'def, '_, '_>,
+) {
+    assign_type::apply_class(class, &mut context.scope);
+    assign_parameterized_type::apply_class(class, &mut context.scope);
+    context.id_hash.add_class(class);
+
+    context.scope.enter_class(class);
+    class::apply_class_body(body, context);
+    context.scope.leave();
+}
+
+#[cfg(test)]
+mod tests {
+    use analyze::definition::{Class, ConstructorDef, Decl, FieldDef, MethodDef};
+    use analyze::test_common::find_class;
+    use parse::id_gen::Id;
+    use parse::tree::{ClassBodyItem, CompilationUnitItem, Expr, NewObject, Statement, TypeArg};
+    use {analyze, semantics};
+
+    fn constructor<'a>(new_object: &NewObject<'a>) -> *const ConstructorDef<'a> {
+        new_object.def_opt.borrow().as_ref().unwrap().def
+    }
+
+    fn name<'a>(type_arg: &TypeArg<'a>) -> &'a str {
+        unwrap!(TypeArg::Class, type_arg).name
+    }
+
+    #[test]
+    fn test_new_object() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  void method() {
+    new Box();
+    new Box(1);
+    new Box(1, 2);
+    new Plain();
+    List<String> list = new ArrayList<>();
+    new Box(2) { int get() { return 3; } };
+  }
+}
+        "#,
+            r#"
+package dev;
+
+class Box {
+  Box() { this(0); }
+  Box(int value) {}
+  Box(int value, int other) {}
+}
+        "#,
+            r#"
+package dev;
+
+class Sub extends Box {
+  Sub() { super(1); }
+}
+        "#,
+            r#"
+package dev;
+
+class Plain {}
+        "#,
+            r#"
+package dev;
+
+interface List<E> {}
+        "#,
+            r#"
+package dev;
+
+class ArrayList<E> implements List<E> {}
+        "#
+        );
+
+        let test = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &test.body.items.get(0).unwrap());
+        let stmts = &method.block_opt.as_ref().unwrap().stmts;
+        let new_object =
+            |index: usize| unwrap!(Expr::NewObject, unwrap!(Statement::Expr, &stmts[index]));
+
+        let boxed = find_class(&root, "dev.Box");
+        for index in 0..3 {
+            assert_eq!(
+                constructor(new_object(index)),
+                boxed.constructors.get(index).unwrap() as *const ConstructorDef
+            );
+        }
+
+        let plain = find_class(&root, "dev.Plain");
+        assert_eq!(plain.constructors.len(), 1);
+        assert_eq!(plain.constructors.get(0).unwrap().span_opt, None);
+        assert_eq!(
+            constructor(new_object(3)),
+            plain.constructors.get(0).unwrap() as *const ConstructorDef
+        );
+
+        let list = unwrap!(Statement::VariableDeclarators, &stmts[4]);
+        let array_list = unwrap!(
+            Expr::NewObject,
+            list.declarators.get(0).unwrap().expr_opt.as_ref().unwrap()
+        );
+        let type_args = array_list.tpe.type_args_opt.as_ref().unwrap();
+        assert_eq!(type_args.len(), 1);
+        assert_eq!(
+            unwrap!(TypeArg::Class, type_args.get(0).unwrap()).name,
+            "String"
+        );
+
+        let anonymous = new_object(5);
+        assert_eq!(
+            constructor(anonymous),
+            boxed.constructors.get(1).unwrap() as *const ConstructorDef
+        );
+        let anonymous_class = anonymous.anonymous_class_opt.as_ref().unwrap();
+        assert_eq!(
+            anonymous_class
+                .extend_opt
+                .borrow()
+                .as_ref()
+                .unwrap()
+                .def_opt,
+            Some(boxed as *const analyze::definition::Class)
+        );
+        let get = unwrap!(
+            ClassBodyItem::Method,
+            anonymous.body_opt.as_ref().unwrap().items.get(0).unwrap()
+        );
+        assert_eq!(
+            get.def_opt.borrow().unwrap(),
+            anonymous_class.methods.get(0).unwrap() as *const analyze::definition::MethodDef
+        );
+
+        let box_def = unwrap!(
+            CompilationUnitItem::Class,
+            &files.get(1).unwrap().unit.items.get(0).unwrap()
+        );
+        let this_call = unwrap!(
+            ClassBodyItem::Constructor,
+            box_def.body.items.get(0).unwrap()
+        );
+        let this_call = unwrap!(
+            Expr::ThisConstructorCall,
+            unwrap!(Statement::Expr, this_call.block.stmts.get(0).unwrap())
+        );
+        assert_eq!(
+            this_call.def_opt.borrow().as_ref().unwrap().def,
+            boxed.constructors.get(1).unwrap() as *const ConstructorDef
+        );
+
+        let sub = unwrap!(
+            CompilationUnitItem::Class,
+            &files.get(2).unwrap().unit.items.get(0).unwrap()
+        );
+        let sub_constructor = unwrap!(ClassBodyItem::Constructor, sub.body.items.get(0).unwrap());
+        let super_call = unwrap!(
+            Expr::SuperConstructorCall,
+            unwrap!(Statement::Expr, sub_constructor.block.stmts.get(0).unwrap())
+        );
+        assert_eq!(
+            super_call.def_opt.borrow().as_ref().unwrap().def,
+            boxed.constructors.get(1).unwrap() as *const ConstructorDef
+        );
+        assert_eq!(
+            *sub_constructor.def_opt.borrow(),
+            Some(find_class(&root, "dev.Sub").constructors.get(0).unwrap() as *const ConstructorDef)
+        );
+    }
+
+    #[test]
+    fn test_anonymous_class() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  void method() {
+    new Box() {
+      int count;
+      { set(count); }
+      class Inner {}
+    };
+  }
+}
+        "#,
+            r#"
+package dev;
+
+class Box {
+  void set(int value) {}
+}
+        "#
+        );
+
+        let test = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &test.body.items.get(0).unwrap());
+        let anonymous = unwrap!(
+            Expr::NewObject,
+            unwrap!(
+                Statement::Expr,
+                &method.block_opt.as_ref().unwrap().stmts[0]
+            )
+        );
+        let anonymous_class = anonymous.anonymous_class_opt.as_ref().unwrap();
+        assert_ne!(anonymous.id, Id::default());
+        assert_eq!(anonymous_class.id, anonymous.id);
+
+        let boxed = find_class(&root, "dev.Box");
+        let items = &anonymous.body_opt.as_ref().unwrap().items;
+        let field = unwrap!(ClassBodyItem::FieldDeclarators, items.get(0).unwrap());
+        let count = field.declarators.get(0).unwrap();
+        assert_eq!(
+            count.def_opt.borrow().unwrap(),
+            anonymous_class.field_groups[0].items.get(0).unwrap() as *const FieldDef
+        );
+
+        // Double brace initialization.
+        let initializer = unwrap!(ClassBodyItem::StaticInitializer, items.get(1).unwrap());
+        let set = unwrap!(
+            Expr::MethodCall,
+            unwrap!(Statement::Expr, initializer.stmts.get(0).unwrap())
+        );
+        assert_eq!(
+            set.def_opt.borrow().as_ref().unwrap().def,
+            boxed.methods.get(0).unwrap() as *const MethodDef
+        );
+
+        let inner = unwrap!(ClassBodyItem::Class, items.get(2).unwrap());
+        assert_eq!(
+            inner.def_opt.borrow().unwrap(),
+            unwrap!(Decl::Class, anonymous_class.decls.get(0).unwrap()) as *const Class
+        );
+    }
+
+    #[test]
+    fn test_diamond() {
+        let (files, _root) = apply_semantics!(
+            r#"
+package dev;
+
+class Test {
+  void method() {
+    Map<String, List<Integer>> nested = new HashMap<>();
+    List<? extends Number> extended = new ArrayList<>();
+    List<? super Integer> supered = new ArrayList<>();
+    Box<Integer> exact = new NumberBox<>();
+    Box<?> unbounded = new NumberBox<>();
+    List<?> object = new ArrayList<>();
+  }
+}
+        "#,
+            r#"
+package dev;
+
+interface Map<K, V> {}
+        "#,
+            r#"
+package dev;
+
+class HashMap<K, V> implements Map<K, V> {}
+        "#,
+            r#"
+package dev;
+
+interface List<E> {}
+        "#,
+            r#"
+package dev;
+
+class ArrayList<E> implements List<E> {}
+        "#,
+            r#"
+package dev;
+
+interface Box<T> {}
+        "#,
+            r#"
+package dev;
+
+class NumberBox<N extends Number> implements Box<N> {}
+        "#
+        );
+
+        let test = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &test.body.items.get(0).unwrap());
+        let stmts = &method.block_opt.as_ref().unwrap().stmts;
+        let type_args = |index: usize| {
+            let declarators = unwrap!(Statement::VariableDeclarators, &stmts[index]);
+            let new_object = unwrap!(
+                Expr::NewObject,
+                declarators
+                    .declarators
+                    .get(0)
+                    .unwrap()
+                    .expr_opt
+                    .as_ref()
+                    .unwrap()
+            );
+            new_object.tpe.type_args_opt.clone().unwrap()
+        };
+
+        let nested = type_args(0);
+        assert_eq!(
+            nested.iter().map(name).collect::<Vec<_>>(),
+            vec!["String", "List"]
+        );
+        let list = unwrap!(TypeArg::Class, nested.get(1).unwrap());
+        assert_eq!(
+            list.type_args_opt
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(name)
+                .collect::<Vec<_>>(),
+            vec!["Integer"]
+        );
+
+        assert_eq!(name(type_args(1).get(0).unwrap()), "Number");
+        assert_eq!(name(type_args(2).get(0).unwrap()), "Integer");
+        assert_eq!(name(type_args(3).get(0).unwrap()), "Integer");
+        assert_eq!(name(type_args(4).get(0).unwrap()), "Number");
+        assert_eq!(type_args(5), vec![]);
+    }
+}
@@ -1,18 +1,25 @@
 use analyze::definition::{
-    Class, CompilationUnit, Decl, FieldDef, MethodDef, Package, Root, TypeParam,
+    AnnotationDef, Class, CompilationUnit, ConstructorDef, Decl, Enum, FieldDef, Interface,
+    MethodDef, Package, Root, TypeParam,
 };
+use parse::id_gen::Id;
 use std::collections::HashMap;
 
 pub struct IdHash {
-    pub underlying: HashMap<String, usize>,
+    pub underlying: HashMap<Id, usize>,
 }
 
 impl IdHash {
-    pub fn get_by_id<T>(&self, id: &str) -> Option<&T> {
+    pub fn get_by_id<T>(&self, id: Id) -> Option<&T> {
         self.underlying
-            .get(id)
+            .get(&id)
             .map(|p| unsafe { &*((*p) as *const T) })
     }
+
+    // Adds a class that isn't in the root, e.g. an anonymous class, with its members.
+    pub fn add_class(&mut self, class: &Class) {
+        build_class(class, self);
+    }
 }
 
 pub fn apply(root: &Root) -> IdHash {
@@ -26,21 +33,25 @@ pub fn apply(root: &Root) -> IdHash {
 }
 
 fn build_root(root: &Root, id_hash: &mut IdHash) {
-    for package in &root.subpackages {
+    for package in root.subpackages() {
         build_package(package, id_hash);
     }
 
-    for unit in &root.units {
+    for unit in root.units() {
         build_unit(unit, id_hash);
     }
+
+    for external in &root.externals {
+        build_class(external, id_hash);
+    }
 }
 
 fn build_package(package: &Package, id_hash: &mut IdHash) {
-    for subpackage in &package.subpackages {
+    for subpackage in package.subpackages() {
         build_package(subpackage, id_hash);
     }
 
-    for unit in &package.units {
+    for unit in package.units() {
         build_unit(unit, id_hash);
     }
 }
@@ -56,19 +67,59 @@ fn build_unit(unit: &CompilationUnit, id_hash: &mut IdHash) {
 fn build_decl(decl: &Decl, id_hash: &mut IdHash) {
     match decl {
         Decl::Class(class) => build_class(class, id_hash),
-        Decl::Interface(interface) => (),
+        Decl::Interface(interface) => build_interface(interface, id_hash),
+        Decl::Enum(enum_def) => build_enum(enum_def, id_hash),
+        Decl::Annotation(annotation) => build_annotation(annotation, id_hash),
     };
 }
 
+fn build_interface(interface: &Interface, id_hash: &mut IdHash) {
+    id_hash
+        .underlying
+        .insert(interface.class.id, interface as *const Interface as usize);
+    build_members(&interface.class, id_hash);
+}
+
+// The id of an annotation type maps to the `AnnotationDef`. Its elements are methods.
+fn build_annotation(annotation: &AnnotationDef, id_hash: &mut IdHash) {
+    id_hash.underlying.insert(
+        annotation.class.id,
+        annotation as *const AnnotationDef as usize,
+    );
+    build_members(&annotation.class, id_hash);
+}
+
+// The id of an enum maps to the `Enum`, not to its class. The body of a constant is a class of its
+// own.
+fn build_enum(enum_def: &Enum, id_hash: &mut IdHash) {
+    id_hash
+        .underlying
+        .insert(enum_def.class.id, enum_def as *const Enum as usize);
+    build_members(&enum_def.class, id_hash);
+
+    for constant in &enum_def.constants {
+        if let Some(body) = &constant.body_opt {
+            build_class(body, id_hash);
+        }
+    }
+}
+
 fn build_class(class: &Class, id_hash: &mut IdHash) {
     id_hash
         .underlying
-        .insert(class.id.to_owned(), class as *const Class as usize);
+        .insert(class.id, class as *const Class as usize);
+    build_members(class, id_hash);
+}
 
+fn build_members(class: &Class, id_hash: &mut IdHash) {
     for type_param in &class.type_params {
         build_type_param(type_param, id_hash);
     }
 
+    for constructor in &class.constructors {
+        build_constructor(constructor, id_hash);
+    }
+
     for method in &class.methods {
         build_method(method, id_hash);
     }
@@ -84,10 +135,21 @@ fn build_class(class: &Class, id_hash: &mut IdHash) {
     }
 }
 
+fn build_constructor(constructor: &ConstructorDef, id_hash: &mut IdHash) {
+    id_hash.underlying.insert(
+        constructor.id,
+        constructor as *const ConstructorDef as usize,
+    );
+
+    for type_param in &constructor.type_params {
+        build_type_param(type_param, id_hash);
+    }
+}
+
 fn build_method(method: &MethodDef, id_hash: &mut IdHash) {
     id_hash
         .underlying
-        .insert(method.id.to_owned(), method as *const MethodDef as usize);
+        .insert(method.id, method as *const MethodDef as usize);
 
     for type_param in &method.type_params {
         build_type_param(type_param, id_hash);
@@ -95,14 +157,13 @@ fn build_method(method: &MethodDef, id_hash: &mut IdHash) {
 }
 
 fn build_type_param(type_param: &TypeParam, id_hash: &mut IdHash) {
-    id_hash.underlying.insert(
-        type_param.id.to_owned(),
-        type_param as *const TypeParam as usize,
-    );
+    id_hash
+        .underlying
+        .insert(type_param.id, type_param as *const TypeParam as usize);
 }
 
 fn build_field(field: &FieldDef, id_hash: &mut IdHash) {
     id_hash
         .underlying
-        .insert(field.id.to_owned(), field as *const FieldDef as usize);
+        .insert(field.id, field as *const FieldDef as usize);
 }
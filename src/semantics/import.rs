@@ -9,11 +9,18 @@ pub fn apply<'def>(import: &mut parse::tree::Import<'def>, context: &mut Context
         None => (),
     };
 
-    import.def_opt.replace(get_def(
+    let def_opt = get_def(
         &mut import.prefix_opt,
-        import.name.fragment,
+        import.name.fragment(),
         &mut context.scope,
-    ));
+    )
+    .or_else(|| {
+        context
+            .scope
+            .find_external(&import.path())
+            .map(ImportDef::Class)
+    });
+    import.def_opt.replace(def_opt);
 }
 
 pub fn apply_prefix<'def>(
@@ -25,11 +32,18 @@ pub fn apply_prefix<'def>(
         None => (),
     };
 
-    import.def_opt.replace(get_prefix_def(
+    let def_opt = get_prefix_def(
         &import.prefix_opt,
-        import.name.fragment,
+        import.name.fragment(),
         &mut context.scope,
-    ));
+    )
+    .or_else(|| {
+        context
+            .scope
+            .find_external(&import.path())
+            .map(ImportPrefixDef::Class)
+    });
+    import.def_opt.replace(def_opt);
 }
 
 fn get_def<'def>(
@@ -69,7 +83,14 @@ fn get_enclosing_type_def<'def>(
         Some(prefix) => match prefix.def_opt.borrow().as_ref() {
             Some(ImportPrefixDef::Package(package)) => {
                 let package = unsafe { &(**package) };
-                package.find(name)
+                match package.find(name) {
+                    Some(EnclosingTypeDef::Class(class))
+                        if !scope.is_accessible(unsafe { &*class }) =>
+                    {
+                        None
+                    }
+                    found => found,
+                }
             }
             Some(ImportPrefixDef::Class(class)) => {
                 let class = unsafe { &(**class) };
@@ -104,7 +125,7 @@ class Test {}
             r#"
 package dev2;
 
-class Super {}
+public class Super {}
         "#
         ];
 
@@ -15,13 +15,14 @@ pub mod statement;
 
 pub struct Context<'def, 'def_ref, 'id_hash_ref> {
     pub scope: Scope<'def, 'def_ref>,
-    pub id_hash: &'id_hash_ref IdHash,
+    // The semantics add the definitions they build, e.g. anonymous classes.
+    pub id_hash: &'id_hash_ref mut IdHash,
 }
 
 pub fn apply<'def>(
     target: &mut parse::tree::CompilationUnit<'def>,
     root: &analyze::definition::Root<'def>,
-    id_hash: &IdHash,
+    id_hash: &mut IdHash,
 ) {
     let mut context = Context {
         scope: Scope {
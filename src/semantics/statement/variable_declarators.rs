@@ -22,6 +22,7 @@ pub fn apply<'def>(
 
 #[cfg(test)]
 mod tests {
+    use analyze::definition::Class;
     use analyze::test_common::find_class;
     use parse::tree::{
         ArrayType, ClassBodyItem, ClassType, CompilationUnitItem, ParameterizedType, Statement,
@@ -107,17 +108,17 @@ class Test<T> {
             tpe,
             ArrayType {
                 tpe: Box::new(Type::Parameterized(ParameterizedType {
-                    name: "T".to_owned(),
+                    name: "T",
                     span_opt: Some(span2(5, 5, "T", files.get(0).unwrap().deref())),
                     def: find_class(&root, "dev.Test").type_params.first().unwrap(),
                 })),
                 size_opt: None,
                 underlying: ClassType {
                     prefix_opt: None,
-                    name: NATIVE_ARRAY_CLASS_NAME.to_owned(),
+                    name: NATIVE_ARRAY_CLASS_NAME,
                     span_opt: None,
                     type_args_opt: Some(vec![TypeArg::Parameterized(ParameterizedType {
-                        name: "T".to_owned(),
+                        name: "T",
                         span_opt: Some(span2(5, 5, "T", files.get(0).unwrap().deref())),
                         def: find_class(&root, "dev.Test").type_params.first().unwrap(),
                     })]),
@@ -126,4 +127,66 @@ class Test<T> {
             }
         );
     }
+
+    #[test]
+    fn test_top_level_visibility() {
+        let (files, root) = apply_semantics!(
+            r#"
+package dev;
+
+import dev2.*;
+
+class Test {
+  void method() {
+    Helper helper;
+    Shared shared;
+    Hidden hidden;
+    dev2.Hidden qualified;
+  }
+}
+        "#,
+            r#"
+package dev;
+
+public class Other {}
+class Helper {}
+        "#,
+            r#"
+package dev2;
+
+class Hidden {}
+public class Shared {}
+        "#
+        );
+
+        let class = unwrap!(
+            CompilationUnitItem::Class,
+            &files.first().unwrap().unit.items.get(0).unwrap()
+        );
+        let method = unwrap!(ClassBodyItem::Method, &class.body.items.get(0).unwrap());
+        let def_opts = method
+            .block_opt
+            .as_ref()
+            .unwrap()
+            .stmts
+            .iter()
+            .map(|stmt| {
+                let var = unwrap!(Statement::VariableDeclarators, stmt);
+                match var.declarators.first().unwrap().tpe.borrow().deref() {
+                    Type::Class(class) => class.def_opt,
+                    _ => panic!(),
+                }
+            })
+            .collect::<Vec<_>>();
+
+        assert_eq!(
+            def_opts,
+            vec![
+                Some(find_class(&root, "dev.Helper") as *const Class),
+                Some(find_class(&root, "dev2.Shared") as *const Class),
+                None,
+                None,
+            ]
+        );
+    }
 }
@@ -1,33 +1,46 @@
-use parse::id_gen::IdGen;
 use parse::tpe::primitive::build_type_type;
 use parse::tree::{CompilationUnit, PrimitiveType, Type};
-use parse::{apply_tokens, Tokens};
+use parse::{apply_tokens, Context, Tokens};
+use std::cell::Cell;
 use tokenize::span::Span;
 use tokenize::token::Token;
-use {tokenize, JavaFile};
+use {tokenize, JavaFile, SourceFile};
 
-pub fn span(line: usize, col: usize, fragment: &str) -> Span {
-    span2(line, col, fragment, std::ptr::null())
+thread_local! {
+    static CURRENT_SOURCE: Cell<Option<&'static SourceFile>> = Cell::new(None);
 }
 
-pub fn span2<'def>(
-    line: usize,
-    col: usize,
-    fragment: &'def str,
-    file: *const JavaFile<'def>,
-) -> Span<'def> {
-    Span {
-        line,
-        col,
-        fragment,
-        file,
-    }
+// Creates a source file that lives until the end of the test. `span(..)` refers to the latest one.
+pub fn source(content: &str) -> &'static SourceFile {
+    leak_source("", content)
 }
 
-pub fn generate_tokens(fragment: &str) -> Vec<Token> {
-    tokenize::apply(fragment.trim(), std::ptr::null())
-        .ok()
-        .unwrap()
+pub fn leak_source(path: &str, content: &str) -> &'static SourceFile {
+    let source: &'static SourceFile = Box::leak(Box::new(SourceFile::new(path, content)));
+    CURRENT_SOURCE.with(|current| current.set(Some(source)));
+    source
+}
+
+pub fn span(line: usize, col: usize, fragment: &str) -> Span<'static> {
+    let source = CURRENT_SOURCE
+        .with(|current| current.get())
+        .expect("No source file. Call generate_tokens(..) or source(..) first.");
+    span_in(source, line, col, fragment)
+}
+
+pub fn span2<'def>(line: usize, col: usize, fragment: &str, file: &JavaFile<'def>) -> Span<'def> {
+    span_in(file.source, line, col, fragment)
+}
+
+fn span_in<'def>(source: &'def SourceFile, line: usize, col: usize, fragment: &str) -> Span<'def> {
+    let start = source.offset(line, col) as usize;
+    let span = Span::new(source, start, start + fragment.len());
+    assert_eq!(span.fragment(), fragment, "at line {} col {}", line, col);
+    span
+}
+
+pub fn generate_tokens(fragment: &str) -> Vec<Token<'static>> {
+    tokenize::apply(source(fragment.trim())).ok().unwrap()
 }
 
 pub fn primitive(line: usize, col: usize, name: &str) -> Type {
@@ -44,9 +57,12 @@ macro_rules! apply_parse {
 
         for (index, source) in $sources.iter().enumerate() {
             files.push(
-                ::parse::apply(source.trim(), &format!("file{}.java", index))
-                    .ok()
-                    .unwrap(),
+                ::parse::apply(::test_common::leak_source(
+                    &format!("file{}.java", index),
+                    source.trim(),
+                ))
+                .ok()
+                .unwrap(),
             );
         }
 
@@ -120,10 +136,6 @@ macro_rules! unwrap {
 
 pub fn apply_analyze_build(source: &str) -> CompilationUnit {
     let tokens = generate_tokens(source);
-    let mut id_gen = IdGen {
-        uuid: 0,
-        path: "".to_string(),
-        runner: 0,
-    };
-    apply_tokens(&tokens, &mut id_gen).ok().unwrap()
+    let mut context = Context::new("");
+    apply_tokens(&tokens, &mut context).ok().unwrap()
 }
@@ -7,42 +7,52 @@ use tokenize::combinator::{
 use tokenize::span::CharAt;
 use tokenize::span::Span;
 use tokenize::token::Token;
-use JavaFile;
+use SourceFile;
 
 pub mod combinator;
 pub mod span;
 pub mod token;
 
-pub fn apply<'def>(
-    content: &'def str,
-    file: *const JavaFile<'def>,
-) -> Result<Vec<Token<'def>>, Span<'def>> {
-    let mut input = Span {
-        line: 1,
-        col: 1,
-        fragment: content,
-        file,
-    };
+pub fn apply(file: &SourceFile) -> Result<Vec<Token<'_>>, Span<'_>> {
+    apply_from(file, 0, |_| false).map(|(tokens, _)| tokens)
+}
+
+// Tokenizes from `start`, which must not be inside a token, a string, or a comment. It stops at the
+// first token for which `stop` returns true and returns the tokens before it with its start.
+pub fn apply_from<'def, F>(
+    file: &'def SourceFile,
+    start: usize,
+    mut stop: F,
+) -> Result<(Vec<Token<'def>>, Option<u32>), Span<'def>>
+where
+    F: FnMut(&Token<'def>) -> bool,
+{
+    let mut input = Span::new(file, start, file.content.len());
     let mut tokens = vec![];
 
-    while input.fragment.len() > 0 {
+    while !input.is_empty() {
         let (next_input, token_opt) = tokenize(input)?;
         input = next_input;
 
         match token_opt {
             Some(Token::Comment(_)) => (),
-            Some(token) => tokens.push(token),
+            Some(token) => {
+                if stop(&token) {
+                    return Ok((tokens, Some(token.span().start)));
+                }
+                tokens.push(token)
+            }
             None => (),
         };
     }
 
-    Ok(tokens)
+    Ok((tokens, None))
 }
 
 fn tokenize<'def>(input: Span<'def>) -> Result<(Span<'def>, Option<Token<'def>>), Span<'def>> {
     let input = skip_space(input);
 
-    if input.fragment.is_empty() {
+    if input.is_empty() {
         return Ok((input, None));
     }
 
@@ -80,7 +90,8 @@ fn skip_space(input: Span) -> Span {
 }
 
 fn string(input: Span) -> Result<(Span, Token), Span> {
-    if input.fragment.char_at(0) == '"' {
+    let fragment = input.fragment();
+    if fragment.char_at(0) == '"' {
     } else {
         return Err(input);
     }
@@ -88,8 +99,8 @@ fn string(input: Span) -> Result<(Span, Token), Span> {
     let mut escaped = false;
     let mut size = 1;
 
-    for index in 1..input.fragment.len() {
-        let c = input.fragment.char_at(index);
+    for index in 1..fragment.len() {
+        let c = fragment.char_at(index);
 
         size += 1;
 
@@ -113,7 +124,7 @@ fn string(input: Span) -> Result<(Span, Token), Span> {
 }
 
 fn literal_char(input: Span) -> Result<(Span, Token), Span> {
-    if input.fragment.char_at(0) == '\'' {
+    if input.fragment().char_at(0) == '\'' {
     } else {
         return Err(input);
     }
@@ -138,14 +149,14 @@ fn literal_char(input: Span) -> Result<(Span, Token), Span> {
 fn hex_p<'a>(num: Span<'a>, original: Span<'a>) -> Result<(Span<'a>, Token<'a>), Span<'a>> {
     let (must_be_p, input) = take_one_if_case_insensitive("P", original);
 
-    if must_be_p.fragment.is_empty() {
+    if must_be_p.is_empty() {
         return Err(original);
     }
 
     let (maybe_sign, input) = take_one_if_case_insensitive("-+", input);
     let (exponent, input) = take_number(input);
 
-    if exponent.fragment.is_empty() {
+    if exponent.is_empty() {
         return Err(input);
     }
 
@@ -153,7 +164,7 @@ fn hex_p<'a>(num: Span<'a>, original: Span<'a>) -> Result<(Span<'a>, Token<'a>),
 
     let num = concat(&[num, must_be_p, maybe_sign, exponent, ending]);
 
-    if ending.fragment.char_at(0).to_ascii_uppercase() == 'F' {
+    if ending.fragment().char_at(0).eq_ignore_ascii_case(&'F') {
         Ok((input, Token::Float(num)))
     } else {
         Ok((input, Token::Double(num)))
@@ -163,20 +174,20 @@ fn hex_p<'a>(num: Span<'a>, original: Span<'a>) -> Result<(Span<'a>, Token<'a>),
 fn hex_decimal<'a>(num: Span<'a>, original: Span<'a>) -> Result<(Span<'a>, Token<'a>), Span<'a>> {
     let (maybe_p_or_dot, input) = take_one_if_case_insensitive("P.", original);
 
-    if maybe_p_or_dot.fragment.is_empty() {
+    if maybe_p_or_dot.is_empty() {
         return Ok((input, Token::Int(num)));
     }
 
-    if maybe_p_or_dot.fragment.char_at(0) != '.' {
+    if maybe_p_or_dot.fragment().char_at(0) != '.' {
         return hex_p(num, original);
     }
 
     let must_be_dot = maybe_p_or_dot;
-    assert_eq!(must_be_dot.fragment, ".");
+    assert_eq!(must_be_dot.fragment(), ".");
 
     let (decimal, input) = take_hex_number(input);
 
-    if num.fragment.is_empty() && decimal.fragment.is_empty() {
+    if num.is_empty() && decimal.is_empty() {
         return Err(original);
     }
 
@@ -186,9 +197,9 @@ fn hex_decimal<'a>(num: Span<'a>, original: Span<'a>) -> Result<(Span<'a>, Token
 }
 
 fn hex(original: Span) -> Result<(Span, Token), Span> {
-    if original.fragment.len() >= 2
-        && original.fragment.char_at(0) == '0'
-        && original.fragment.char_at(1).to_ascii_uppercase() == 'X'
+    if original.len() >= 2
+        && original.fragment().char_at(0) == '0'
+        && original.fragment().char_at(1).eq_ignore_ascii_case(&'X')
     {
     } else {
         return Err(original);
@@ -200,10 +211,10 @@ fn hex(original: Span) -> Result<(Span, Token), Span> {
     let (maybe_l, input) = take_one_if_case_insensitive("L", input);
     let num = concat(&[prefix, hex, maybe_l]);
 
-    if maybe_l.fragment.is_empty() {
+    if maybe_l.is_empty() {
         hex_decimal(num, input)
     } else {
-        if hex.fragment.is_empty() {
+        if hex.is_empty() {
             return Err(original);
         }
 
@@ -212,9 +223,9 @@ fn hex(original: Span) -> Result<(Span, Token), Span> {
 }
 
 fn bit(original: Span) -> Result<(Span, Token), Span> {
-    if original.fragment.len() >= 2
-        && original.fragment.char_at(0) == '0'
-        && original.fragment.char_at(1).to_ascii_uppercase() == 'B'
+    if original.len() >= 2
+        && original.fragment().char_at(0) == '0'
+        && original.fragment().char_at(1).eq_ignore_ascii_case(&'B')
     {
     } else {
         return Err(original);
@@ -226,7 +237,7 @@ fn bit(original: Span) -> Result<(Span, Token), Span> {
     let (maybe_l, input) = take_one_if_case_insensitive("L", input);
     let num = concat(&[prefix, bits, maybe_l]);
 
-    if maybe_l.fragment.is_empty() {
+    if maybe_l.is_empty() {
         Ok((input, Token::Int(num)))
     } else {
         Ok((input, Token::Long(num)))
@@ -238,7 +249,7 @@ fn is_identifier(index: usize, s: &str) -> bool {
     c >= '0' && c <= '9' || c >= 'a' && c <= 'z' || c >= 'A' && c <= 'Z' || c == '_' || c == '$'
 }
 
-fn is_keyword(s: &str) -> bool {
+pub fn is_keyword(s: &str) -> bool {
     match s {
         "abstract" | "assert" | "boolean" | "break" | "byte" | "case" | "catch" | "char"
         | "class" | "const" | "continue" | "default" | "do" | "double" | "else" | "enum"
@@ -254,11 +265,11 @@ fn is_keyword(s: &str) -> bool {
 fn keyword_or_identifier(original: Span) -> Result<(Span, Token), Span> {
     let (ident, input) = take_while(is_identifier, original);
 
-    if ident.fragment.is_empty() {
+    if ident.is_empty() {
         return Err(original);
     }
 
-    if is_keyword(ident.fragment) {
+    if is_keyword(ident.fragment()) {
         Ok((input, Token::Keyword(ident)))
     } else {
         Ok((input, Token::Identifier(ident)))
@@ -278,43 +289,17 @@ fn float_or_double_end<'a>(
     original: Span<'a>,
     include_dot_or_e: bool,
 ) -> Result<(Span<'a>, Token<'a>), Span<'a>> {
-    if number.fragment.is_empty() {
+    if number.is_empty() {
         return Err(original);
     }
 
     let (symbol, input) = take(1, original);
 
-    let last_char = symbol.fragment.char_at(0).to_ascii_uppercase();
+    let last_char = symbol.fragment().char_at(0).to_ascii_uppercase();
     if last_char == 'D' {
-        Ok((
-            input,
-            Token::Double(Span {
-                line: number.line,
-                col: number.col,
-                fragment: unsafe {
-                    std::str::from_utf8_unchecked(slice::from_raw_parts(
-                        number.fragment.as_ptr(),
-                        number.fragment.len() + symbol.fragment.len(),
-                    ))
-                },
-                file: number.file,
-            }),
-        ))
+        Ok((input, Token::Double(concat(&[number, symbol]))))
     } else if last_char == 'F' {
-        Ok((
-            input,
-            Token::Float(Span {
-                line: number.line,
-                col: number.col,
-                fragment: unsafe {
-                    std::str::from_utf8_unchecked(slice::from_raw_parts(
-                        number.fragment.as_ptr(),
-                        number.fragment.len() + symbol.fragment.len(),
-                    ))
-                },
-                file: number.file,
-            }),
-        ))
+        Ok((input, Token::Float(concat(&[number, symbol]))))
     } else if include_dot_or_e {
         Ok((original, Token::Double(number)))
     } else {
@@ -329,11 +314,11 @@ fn float_or_double_e<'a>(
 ) -> Result<(Span<'a>, Token<'a>), Span<'a>> {
     let (maybe_e, input) = take_one_if_case_insensitive("E", original);
 
-    if maybe_e.fragment.is_empty() {
+    if maybe_e.is_empty() {
         return float_or_double_end(num, original, include_dot);
     }
 
-    if num.fragment.is_empty() {
+    if num.is_empty() {
         return Err(input);
     }
 
@@ -350,8 +335,8 @@ fn float_or_double_dot<'a>(
     original: Span<'a>,
 ) -> Result<(Span<'a>, Token<'a>), Span<'a>> {
     let (symbol, input) = take(1, original);
-    let last_char = if !symbol.fragment.is_empty() {
-        symbol.fragment.char_at(0).to_ascii_uppercase()
+    let last_char = if !symbol.is_empty() {
+        symbol.fragment().char_at(0).to_ascii_uppercase()
     } else {
         ' ' // anything that is not a dot.
     };
@@ -362,21 +347,11 @@ fn float_or_double_dot<'a>(
 
     let (second_number, input) = take_number(input);
 
-    if first_number.fragment.is_empty() && second_number.fragment.is_empty() {
+    if first_number.is_empty() && second_number.is_empty() {
         return Err(original);
     }
 
-    let num = Span {
-        line: first_number.line,
-        col: first_number.col,
-        fragment: unsafe {
-            std::str::from_utf8_unchecked(slice::from_raw_parts(
-                first_number.fragment.as_ptr(),
-                first_number.fragment.len() + symbol.fragment.len() + second_number.fragment.len(),
-            ))
-        },
-        file: first_number.file,
-    };
+    let num = concat(&[first_number, symbol, second_number]);
 
     float_or_double_e(num, input, true)
 }
@@ -388,7 +363,7 @@ fn int_or_long_or_double_or_float(original: Span) -> Result<(Span, Token), Span>
         return Ok(ok);
     }
 
-    if number.fragment.is_empty() {
+    if number.is_empty() {
         return Err(original);
     }
 
@@ -396,7 +371,7 @@ fn int_or_long_or_double_or_float(original: Span) -> Result<(Span, Token), Span>
 
     let num = concat(&[number, maybe_l]);
 
-    if maybe_l.fragment.is_empty() {
+    if maybe_l.is_empty() {
         Ok((input, Token::Int(num)))
     } else {
         Ok((input, Token::Long(num)))
@@ -404,9 +379,7 @@ fn int_or_long_or_double_or_float(original: Span) -> Result<(Span, Token), Span>
 }
 
 fn oneline_comment(input: Span) -> Result<(Span, Token), Span> {
-    if input.fragment.len() >= 2
-        && input.fragment.char_at(0) == '/'
-        && input.fragment.char_at(1) == '/'
+    if input.len() >= 2 && input.fragment().char_at(0) == '/' && input.fragment().char_at(1) == '/'
     {
     } else {
         return Err(input);
@@ -418,9 +391,7 @@ fn oneline_comment(input: Span) -> Result<(Span, Token), Span> {
 }
 
 fn multiline_comment(input: Span) -> Result<(Span, Token), Span> {
-    if input.fragment.len() >= 2
-        && input.fragment.char_at(0) == '/'
-        && input.fragment.char_at(1) == '*'
+    if input.len() >= 2 && input.fragment().char_at(0) == '/' && input.fragment().char_at(1) == '*'
     {
     } else {
         return Err(input);
@@ -436,13 +407,35 @@ fn multiline_comment(input: Span) -> Result<(Span, Token), Span> {
 
 #[cfg(test)]
 mod tests {
-    use test_common::{generate_tokens, span};
+    use test_common::{source, span};
     use tokenize::span::Span;
     use tokenize::token::Token;
-    use JavaFile;
+    use SourceFile;
 
-    fn apply(content: &str) -> Result<Vec<Token>, Span> {
-        super::apply(content, std::ptr::null())
+    fn apply(content: &str) -> Result<Vec<Token<'static>>, Span<'static>> {
+        super::apply(source(content))
+    }
+
+    #[test]
+    fn test_size() {
+        assert_eq!(std::mem::size_of::<Span>(), 16);
+        assert_eq!(std::mem::size_of::<Token>(), 24);
+    }
+
+    #[test]
+    fn test_moved_file() {
+        let files = vec![SourceFile::new("A.java", "class A {}")];
+        let tokens = super::apply(&files[0]).unwrap();
+        assert_eq!(tokens[1].span().fragment(), "A");
+        drop(tokens);
+
+        // The file is at another address now.
+        let mut moved = files;
+        moved.reserve(100);
+        let tokens = super::apply(&moved[0]).unwrap();
+        assert_eq!(tokens[1].span().fragment(), "A");
+        assert_eq!(tokens[1].span().line(), 1);
+        assert_eq!(tokens[1].span().col(), 7);
     }
 
     #[test]
@@ -1,11 +1,87 @@
-use JavaFile;
+use std::fmt;
+use std::ptr;
+use SourceFile;
 
-#[derive(Debug, PartialEq, Copy, Clone)]
+// A span is a byte range of a source file. Line and column are computed on demand from the file's
+// line table, so a span is only 16 bytes.
+#[derive(Copy, Clone)]
 pub struct Span<'a> {
-    pub line: usize,
-    pub col: usize,
-    pub fragment: &'a str,
-    pub file: *const JavaFile<'a>,
+    pub start: u32,
+    pub end: u32,
+    file: &'a SourceFile,
+}
+
+impl<'a> Span<'a> {
+    pub fn new(file: &'a SourceFile, start: usize, end: usize) -> Span<'a> {
+        Span {
+            start: start as u32,
+            end: end as u32,
+            file,
+        }
+    }
+
+    // The span from `start` to `end` of the same file.
+    pub fn slice(&self, start: u32, end: u32) -> Span<'a> {
+        Span {
+            start,
+            end,
+            file: self.file,
+        }
+    }
+
+    pub fn file(&self) -> &'a SourceFile {
+        self.file
+    }
+
+    pub fn file_id(&self) -> u64 {
+        self.file.id()
+    }
+
+    pub fn fragment(&self) -> &'a str {
+        &self.file.content[(self.start as usize)..(self.end as usize)]
+    }
+
+    pub fn len(&self) -> usize {
+        (self.end - self.start) as usize
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.start == self.end
+    }
+
+    // 1-based line
+    pub fn line(&self) -> usize {
+        self.file.line_col(self.start).0
+    }
+
+    // 1-based column in bytes
+    pub fn col(&self) -> usize {
+        self.file.line_col(self.start).1
+    }
+
+    // The span that covers both `self` and `other`.
+    pub fn to(&self, other: &Span<'a>) -> Span<'a> {
+        self.slice(
+            std::cmp::min(self.start, other.start),
+            std::cmp::max(self.end, other.end),
+        )
+    }
+}
+
+impl<'a> PartialEq for Span<'a> {
+    fn eq(&self, other: &Span<'a>) -> bool {
+        ptr::eq(self.file, other.file) && self.start == other.start && self.end == other.end
+    }
+}
+
+impl<'a> fmt::Debug for Span<'a> {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.debug_struct("Span")
+            .field("line", &self.line())
+            .field("col", &self.col())
+            .field("fragment", &self.fragment())
+            .finish()
+    }
 }
 
 pub trait CharAt {
@@ -18,6 +94,10 @@ impl CharAt for str {
     }
 }
 
+// Returns '\0' beyond the end of the string.
 fn raw_char_at(index: usize, s: &str) -> char {
-    unsafe { *s.as_ptr().add(index) as char }
+    match s.as_bytes().get(index) {
+        Some(b) => *b as char,
+        None => '\0',
+    }
 }
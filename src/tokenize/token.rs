@@ -32,4 +32,20 @@ impl<'a> Token<'a> {
 
         *s
     }
+
+    // The same kind of token at `span`.
+    pub fn with_span<'b>(&self, span: Span<'b>) -> Token<'b> {
+        match self {
+            Token::Symbol(_) => Token::Symbol(span),
+            Token::Identifier(_) => Token::Identifier(span),
+            Token::Keyword(_) => Token::Keyword(span),
+            Token::Int(_) => Token::Int(span),
+            Token::Double(_) => Token::Double(span),
+            Token::Float(_) => Token::Float(span),
+            Token::Char(_) => Token::Char(span),
+            Token::Long(_) => Token::Long(span),
+            Token::String(_) => Token::String(span),
+            Token::Comment(_) => Token::Comment(span),
+        }
+    }
 }
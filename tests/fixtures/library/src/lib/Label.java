@@ -0,0 +1,36 @@
+package lib;
+
+import java.lang.annotation.ElementType;
+import java.lang.annotation.Retention;
+import java.lang.annotation.RetentionPolicy;
+import java.lang.annotation.Target;
+
+@Retention(RetentionPolicy.RUNTIME)
+@Target({ElementType.TYPE, ElementType.METHOD})
+public @interface Label {
+    String value();
+
+    String[] tags() default {};
+
+    Kind kind() default Kind.LOOSE;
+
+    char mark() default '\'';
+
+    double weight() default 0.5;
+
+    Class<?> type() default int[].class;
+
+    enum Kind {
+        FIXED,
+        LOOSE {
+            @Override
+            public boolean isLoose() {
+                return true;
+            }
+        };
+
+        public boolean isLoose() {
+            return false;
+        }
+    }
+}
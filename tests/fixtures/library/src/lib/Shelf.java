@@ -0,0 +1,55 @@
+package lib;
+
+import java.io.IOException;
+import java.util.List;
+import java.util.Map;
+
+@Label(value = "shelf", tags = {"a", "b"}, kind = Label.Kind.FIXED)
+public abstract class Shelf<T extends Comparable<? super T>> implements Iterable<T> {
+    public static final int SIZE = 10;
+    protected List<? extends T> items;
+    Map.Entry<String, T> last;
+
+    public Shelf(List<? extends T> items) {
+        this.items = items;
+    }
+
+    public abstract T first();
+
+    public <R> R fold(R init, java.util.function.BiFunction<R, T, R> f) throws IOException {
+        Runnable r = () -> {};
+        return init;
+    }
+
+    public static int sum(int... values) {
+        return new Object() {
+            int total() {
+                return 0;
+            }
+        }.total();
+    }
+
+    public static class Slot {
+        public String name;
+    }
+
+    public class Row {
+        public Row(int index) {}
+
+        public Slot slot() {
+            return null;
+        }
+    }
+
+    public interface Visitor<V> {
+        V visit(Shelf<?> shelf);
+
+        default boolean accepts(Object o) {
+            return true;
+        }
+
+        static <V> Visitor<V> empty() {
+            return null;
+        }
+    }
+}
@@ -1,4 +1,4 @@
-use javaparser::parse;
+use javaparser::{parse, SourceFile};
 use std::fs;
 use std::time::Instant;
 
@@ -17,15 +17,16 @@ fn all() {
         let content = fs::read_to_string(entry.path()).unwrap();
 
         let start = Instant::now();
-        let file = parse::apply(&content, entry.path().to_str().unwrap());
+        let source = SourceFile::new(entry.path().to_str().unwrap(), &content);
+        let file = parse::apply(&source);
         println!(" ({:?})", start.elapsed());
         assert!(file.is_ok(), {
             let remainder = file.err().unwrap();
             format!(
                 "Parsed {} failed at line {} and column {}",
                 entry.path().file_name().unwrap().to_str().unwrap(),
-                remainder.line,
-                remainder.col,
+                remainder.line(),
+                remainder.col(),
             )
         });
     }
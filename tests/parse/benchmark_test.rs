@@ -1,4 +1,4 @@
-use javaparser::{parse, tokenize};
+use javaparser::{parse, tokenize, SourceFile};
 use std::time::{Duration, Instant};
 use std::{fs, thread};
 
@@ -7,15 +7,41 @@ use std::{fs, thread};
 fn benchmark() {
     let content = fs::read_to_string("./tests/fixtures/LocalCache.java").unwrap();
 
+    let sources: Vec<SourceFile> = (0..10)
+        .map(|_| SourceFile::new("test.java", &content))
+        .collect();
+
     let mut results = vec![];
-    for i in 0..10 {
+    for (i, source) in sources.iter().enumerate() {
         let start = Instant::now();
-        let result = parse::apply(&content, "test.java").unwrap();
+        let result = parse::apply(source).unwrap();
         let elapsed = start.elapsed().as_nanos();
 
-        println!("{}. Parsing took {:?}", i, elapsed,);
+        println!("{}. Parsing took {:?}", i, elapsed);
         results.push(result)
     }
 
     println!("size: {}", results.len());
 }
+
+#[test]
+#[ignore]
+fn benchmark_signatures() {
+    let content = fs::read_to_string("./tests/fixtures/LocalCache.java").unwrap();
+    let source = SourceFile::new("test.java", &content);
+
+    for i in 0..10 {
+        let start = Instant::now();
+        parse::apply(&source).unwrap();
+        let full = start.elapsed().as_nanos();
+
+        let start = Instant::now();
+        parse::apply_signatures(&source).unwrap();
+        let signatures = start.elapsed().as_nanos();
+
+        println!(
+            "{}. Parsing took {:?} (signatures only: {:?})",
+            i, full, signatures
+        );
+    }
+}
@@ -1,4 +1,5 @@
 pub mod acceptance_test;
 pub mod benchmark_test;
+pub mod print_test;
 pub mod real_world_test;
 pub mod simple_test;
@@ -0,0 +1,30 @@
+use javaparser::parse::print;
+use javaparser::{parse, SourceFile};
+use std::fs;
+
+// Printing a parsed file and parsing the printed code gives the same tree, so it prints the same.
+#[test]
+fn round_trip() {
+    for entry in fs::read_dir("./tests/fixtures").unwrap() {
+        let path = entry.unwrap().path();
+        if path.is_dir() {
+            continue;
+        }
+
+        let content = fs::read_to_string(&path).unwrap();
+        let source = SourceFile::new(path.to_str().unwrap(), &content);
+        let printed = print::unit(&parse::apply(&source).unwrap().unit);
+
+        let reprinted_source = SourceFile::new(path.to_str().unwrap(), &printed);
+        let reparsed = match parse::apply(&reprinted_source) {
+            Ok(file) => file,
+            Err(span) => panic!(
+                "The printed {} doesn't parse at line {} and column {}",
+                path.display(),
+                span.line(),
+                span.col()
+            ),
+        };
+        assert_eq!(print::unit(&reparsed.unit), printed, "{}", path.display());
+    }
+}
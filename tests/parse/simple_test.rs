@@ -1,8 +1,9 @@
-use javaparser::parse;
+use javaparser::{parse, SourceFile};
 
 #[test]
 fn parse_minimal() {
-    let result = parse::apply(
+    let source = SourceFile::new(
+        "test.java",
         r#"
  /* This file
  */
@@ -20,15 +21,16 @@ class Test {
     }
 }
         "#,
-        "test.java",
     );
+    let result = parse::apply(&source);
 
     assert!(result.is_ok(), format!("{:#?}", result));
 }
 
 #[test]
 fn parse_minimal2() {
-    let result = parse::apply(
+    let source = SourceFile::new(
+        "test.java",
         r#"
  /* This file
  */
@@ -43,7 +45,7 @@ class Test {
     }
 }
         "#,
-        "test.java",
     );
+    let result = parse::apply(&source);
     assert!(result.is_ok(), format!("{:#?}", result));
 }